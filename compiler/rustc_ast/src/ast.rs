@@ -1087,7 +1087,9 @@ pub fn returns(&self) -> bool {
                 // Implicit return
                 Some(StmtKind::Expr(_)) => true,
                 // Last statement is an explicit return?
-                Some(StmtKind::Semi(expr)) => matches!(expr.kind, ExprKind::Ret(_)),
+                Some(StmtKind::Semi(expr)) => {
+                    matches!(expr.kind, ExprKind::Ret(_) | ExprKind::Become(_))
+                }
                 // This is a block that doesn't end in either an implicit or explicit return.
                 _ => false,
             }
@@ -1215,6 +1217,7 @@ pub fn precedence(&self) -> ExprPrecedence {
             ExprKind::Break(..) => ExprPrecedence::Break,
             ExprKind::Continue(..) => ExprPrecedence::Continue,
             ExprKind::Ret(..) => ExprPrecedence::Ret,
+            ExprKind::Become(..) => ExprPrecedence::Become,
             ExprKind::InlineAsm(..) | ExprKind::LlvmInlineAsm(..) => ExprPrecedence::InlineAsm,
             ExprKind::MacCall(..) => ExprPrecedence::Mac,
             ExprKind::Struct(..) => ExprPrecedence::Struct,
@@ -1367,6 +1370,8 @@ pub enum ExprKind {
     Continue(Option<Label>),
     /// A `return`, with an optional value to be returned.
     Ret(Option<P<Expr>>),
+    /// A `become`, with the call whose result is returned as a guaranteed tail call.
+    Become(P<Expr>),
 
     /// Output of the `asm!()` macro.
     InlineAsm(P<InlineAsm>),
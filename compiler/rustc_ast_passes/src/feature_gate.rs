@@ -712,6 +712,7 @@ macro_rules! gate_all {
     gate_all!(const_trait_impl, "const trait impls are experimental");
     gate_all!(half_open_range_patterns, "half-open range patterns are unstable");
     gate_all!(inline_const, "inline-const is experimental");
+    gate_all!(explicit_tail_calls, "`become` expression is experimental");
     gate_all!(
         const_generics_defaults,
         "default values for const generic parameters are experimental"
@@ -113,8 +113,6 @@ pub fn register_builtin_macros(resolver: &mut dyn ResolverExpand) {
     }
 
     let client = proc_macro::bridge::client::Client::expand1(proc_macro::quote);
-    register(
-        sym::quote,
-        SyntaxExtensionKind::Bang(Box::new(BangProcMacro { client, krate: LOCAL_CRATE })),
-    );
+    let quote = BangProcMacro { client, krate: LOCAL_CRATE, external: None, crate_hash: None };
+    register(sym::quote, SyntaxExtensionKind::Bang(Box::new(quote)));
 }
@@ -494,6 +494,14 @@ pub(crate) fn codegen_terminator_call<'tcx>(
     }
 }
 
+/// Cranelift has no guaranteed tail calls. Lowering `become` to a regular call would silently
+/// grow the stack on every call, so it is rejected instead.
+pub(crate) fn codegen_tail_call(fx: &mut FunctionCx<'_, '_, '_>, span: Span) {
+    fx.tcx.sess.span_err(span, "tail calls are not yet supported by cg_clif");
+    // Prevent verifier error
+    trap_unreachable(fx, "compilation should not have succeeded");
+}
+
 pub(crate) fn codegen_drop<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     span: Span,
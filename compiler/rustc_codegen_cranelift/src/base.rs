@@ -358,6 +358,9 @@ fn codegen_fn_content(fx: &mut FunctionCx<'_, '_, '_>) {
                     crate::abi::codegen_terminator_call(fx, *fn_span, func, args, *destination)
                 });
             }
+            TerminatorKind::TailCall { fn_span, .. } => {
+                crate::abi::codegen_tail_call(fx, *fn_span);
+            }
             TerminatorKind::InlineAsm {
                 template,
                 operands,
@@ -7,8 +7,7 @@
 use rustc_errors::ErrorReported;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::interpret::{
-    alloc_range, read_target_uint, AllocId, Allocation, ConstValue, ErrorHandled, GlobalAlloc,
-    Scalar,
+    alloc_range, AllocId, Allocation, ConstValue, ErrorHandled, GlobalAlloc, Scalar,
 };
 use rustc_middle::ty::ConstKind;
 
@@ -395,15 +394,7 @@ fn define_all_allocs(tcx: TyCtxt<'_>, module: &mut dyn Module, cx: &mut Constant
         data_ctx.define(bytes.into_boxed_slice());
 
         for &(offset, (_tag, reloc)) in alloc.relocations().iter() {
-            let addend = {
-                let endianness = tcx.data_layout.endian;
-                let offset = offset.bytes() as usize;
-                let ptr_size = tcx.data_layout.pointer_size;
-                let bytes = &alloc.inspect_with_uninit_and_ptr_outside_interpreter(
-                    offset..offset + ptr_size.bytes() as usize,
-                );
-                read_target_uint(endianness, bytes).unwrap()
-            };
+            let addend = alloc.read_relocation_offset(&tcx, offset).bytes();
 
             let reloc_target_alloc = tcx.get_global_alloc(reloc).unwrap();
             let data_id = match reloc_target_alloc {
@@ -529,7 +520,7 @@ pub(crate) fn mir_operand_get_const_val<'tcx>(
                     {
                         return None;
                     }
-                    TerminatorKind::Call { .. } => {}
+                    TerminatorKind::Call { .. } | TerminatorKind::TailCall { .. } => {}
                 }
             }
             computed_const_val
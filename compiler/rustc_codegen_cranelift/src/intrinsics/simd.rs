@@ -84,21 +84,16 @@ pub(super) fn codegen_simd_intrinsic_call<'tcx>(
                 use rustc_middle::mir::interpret::*;
                 let idx_const = crate::constant::mir_operand_get_const_val(fx, idx).expect("simd_shuffle* idx not const");
 
-                let idx_bytes = match idx_const {
-                    ConstValue::ByRef { alloc, offset } => {
-                        let size = Size::from_bytes(4 * ret_lane_count /* size_of([u32; ret_lane_count]) */);
-                        alloc.get_bytes(fx, alloc_range(offset, size)).unwrap()
-                    }
+                let (alloc, offset) = match idx_const {
+                    ConstValue::ByRef { alloc, offset } => (alloc, offset),
                     _ => unreachable!("{:?}", idx_const),
                 };
 
                 (0..ret_lane_count).map(|i| {
-                    let i = usize::try_from(i).unwrap();
-                    let idx = rustc_middle::mir::interpret::read_target_uint(
-                        fx.tcx.data_layout.endian,
-                        &idx_bytes[4*i.. 4*i + 4],
-                    ).expect("read_target_uint");
-                    u16::try_from(idx).expect("try_from u32")
+                    let size = Size::from_bytes(4);
+                    let range = alloc_range(offset + size * i, size);
+                    let idx = alloc.read_scalar_int(fx, range).expect("read_scalar_int");
+                    u16::try_from(idx.assert_bits(size)).expect("try_from u32")
                 }).collect::<Vec<u16>>()
             };
 
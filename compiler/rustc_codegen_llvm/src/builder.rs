@@ -1152,6 +1152,10 @@ fn zext(&mut self, val: &'ll Value, dest_ty: &'ll Type) -> &'ll Value {
     fn do_not_inline(&mut self, llret: &'ll Value) {
         llvm::Attribute::NoInline.apply_callsite(llvm::AttributePlace::Function, llret);
     }
+
+    fn set_must_tail(&mut self, llret: &'ll Value) {
+        unsafe { llvm::LLVMRustSetMustTail(llret) }
+    }
 }
 
 impl StaticBuilderMethods for Builder<'a, 'll, 'tcx> {
@@ -891,8 +891,10 @@ macro_rules! require_simd {
                     }
                     Some(idx) if idx >= total_len => {
                         emit_error!(
-                            "shuffle index #{} is out of bounds (limit {})",
+                            "shuffle index #{} is out of bounds: \
+                             the index is {} but the limit is {}",
                             arg_idx,
+                            idx,
                             total_len
                         );
                         None
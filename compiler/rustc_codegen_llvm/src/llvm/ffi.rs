@@ -1066,6 +1066,7 @@ pub fn LLVMRustGetNamedValue(
         NameLen: size_t,
     ) -> Option<&Value>;
     pub fn LLVMSetTailCall(CallInst: &Value, IsTailCall: Bool);
+    pub fn LLVMRustSetMustTail(CallInst: &Value);
 
     // Operations on functions
     pub fn LLVMRustGetOrInsertFunction(
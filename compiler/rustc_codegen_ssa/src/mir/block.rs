@@ -815,6 +815,103 @@ fn codegen_call_terminator(
         );
     }
 
+    fn codegen_tail_call_terminator(
+        &mut self,
+        mut bx: Bx,
+        terminator: &mir::Terminator<'tcx>,
+        func: &mir::Operand<'tcx>,
+        args: &[mir::Operand<'tcx>],
+    ) {
+        let span = terminator.source_info.span;
+
+        let callee = self.codegen_operand(&mut bx, func);
+        let instance = match *callee.layout.ty.kind() {
+            ty::FnDef(def_id, substs) => Some(
+                ty::Instance::resolve(bx.tcx(), ty::ParamEnv::reveal_all(), def_id, substs)
+                    .unwrap()
+                    .unwrap()
+                    .polymorphize(bx.tcx()),
+            ),
+            ty::FnPtr(_) => None,
+            _ => bug!("{} is not callable", callee.layout.ty),
+        };
+        let sig = callee.layout.ty.fn_sig(bx.tcx());
+        let fn_abi = match instance {
+            Some(instance) => FnAbi::of_instance(&bx, instance, &[]),
+            None => FnAbi::of_fn_ptr(&bx, sig, &[]),
+        };
+
+        // `musttail` requires the callee to take its arguments and return its result exactly
+        // like the current function does. MIR building only accepts tail calls between matching
+        // signatures that are not virtual calls, "rust-call" functions or unsized arguments, so
+        // this holds for every instantiation.
+        if fn_abi.conv != self.fn_abi.conv
+            || fn_abi.c_variadic
+            || fn_abi.ret.mode != self.fn_abi.ret.mode
+            || fn_abi.args.len() != self.fn_abi.args.len()
+            || fn_abi.args.iter().zip(&self.fn_abi.args).any(|(a, b)| a.mode != b.mode)
+            || fn_abi.args.iter().any(|arg| arg.is_unsized_indirect())
+            || matches!(instance.map(|i| i.def), Some(ty::InstanceDef::Virtual(..)))
+        {
+            span_bug!(span, "tail call with a mismatched ABI: {:?}", terminator.kind);
+        }
+
+        let arg_count = fn_abi.args.len() + fn_abi.ret.is_indirect() as usize;
+        let mut llargs = Vec::with_capacity(arg_count);
+
+        // The callee writes its result to wherever our own caller expects ours.
+        if fn_abi.ret.is_indirect() {
+            match self.locals[mir::RETURN_PLACE] {
+                LocalRef::Place(dest) => llargs.push(dest.llval),
+                _ => bug!("indirect return place is not a place"),
+            }
+        }
+
+        let mut ops: Vec<_> = args.iter().map(|arg| self.codegen_operand(&mut bx, arg)).collect();
+
+        // Arguments passed by reference must not point into the current stack frame, which is
+        // replaced by the callee. Instead they are moved into the memory that our own arguments
+        // were passed in, which belongs to our caller. All of them are copied out first, since
+        // they might still refer to that memory.
+        let temps: Vec<_> = ops
+            .iter()
+            .zip(&fn_abi.args)
+            .map(|(op, arg)| {
+                arg.is_indirect().then(|| {
+                    let tmp = PlaceRef::alloca(&mut bx, op.layout);
+                    op.val.store(&mut bx, tmp);
+                    tmp
+                })
+            })
+            .collect();
+        for ((op, tmp), local) in ops.iter_mut().zip(temps).zip(self.mir.args_iter()) {
+            if let Some(tmp) = tmp {
+                let dest = match self.locals[local] {
+                    LocalRef::Place(dest) => dest,
+                    _ => bug!("indirect argument {:?} is not a place", local),
+                };
+                Ref(tmp.llval, None, tmp.align).store(&mut bx, dest);
+                op.val = Ref(dest.llval, None, dest.align);
+            }
+        }
+
+        for (op, arg) in ops.into_iter().zip(&fn_abi.args) {
+            self.codegen_argument(&mut bx, op, &mut llargs, arg);
+        }
+
+        let fn_ptr = match instance {
+            Some(instance) => bx.get_fn_addr(instance),
+            None => callee.immediate(),
+        };
+        let llret = bx.call(fn_ptr, &llargs, None);
+        bx.apply_attrs_callsite(&fn_abi, llret);
+        bx.set_must_tail(llret);
+        match fn_abi.ret.mode {
+            PassMode::Ignore | PassMode::Indirect { .. } => bx.ret_void(),
+            PassMode::Direct(_) | PassMode::Pair(..) | PassMode::Cast(_) => bx.ret(llret),
+        }
+    }
+
     fn codegen_asm_terminator(
         &mut self,
         helper: TerminatorCodegenHelper<'tcx>,
@@ -986,6 +1083,9 @@ fn codegen_terminator(
                     fn_span,
                 );
             }
+            mir::TerminatorKind::TailCall { ref func, ref args, fn_span: _ } => {
+                self.codegen_tail_call_terminator(bx, terminator, func, args);
+            }
             mir::TerminatorKind::GeneratorDrop | mir::TerminatorKind::Yield { .. } => {
                 bug!("generator ops in codegen")
             }
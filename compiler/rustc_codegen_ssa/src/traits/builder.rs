@@ -299,4 +299,8 @@ fn call(
     fn zext(&mut self, val: Self::Value, dest_ty: Self::Type) -> Self::Value;
 
     fn do_not_inline(&mut self, llret: Self::Value);
+
+    /// Requires the call `llret` to be a tail call, which reuses the stack frame of the current
+    /// function. The call must be followed by a return of its result.
+    fn set_must_tail(&mut self, llret: Self::Value);
 }
@@ -18,7 +18,7 @@
 use rustc_codegen_ssa::{traits::CodegenBackend, CodegenResults};
 use rustc_data_structures::profiling::{get_resident_set_size, print_time_passes_entry};
 use rustc_data_structures::sync::SeqCst;
-use rustc_errors::registry::{InvalidErrorCode, Registry};
+use rustc_errors::registry::{self, InvalidErrorCode, Registry};
 use rustc_errors::{ErrorReported, PResult};
 use rustc_feature::find_gated_cfg;
 use rustc_interface::util::{self, collect_crate_types, get_codegen_backend};
@@ -56,6 +56,8 @@
 pub mod args;
 pub mod pretty;
 
+pub use rustc_span::source_map::VirtualFileLoader;
+
 /// Exit status code used for successful compilation and help output.
 pub const EXIT_SUCCESS: i32 = 0;
 
@@ -129,7 +131,7 @@ fn config(&mut self, config: &mut interface::Config) {
 }
 
 pub fn diagnostics_registry() -> Registry {
-    Registry::new(&rustc_error_codes::DIAGNOSTICS)
+    Registry::new(&rustc_error_codes::DIAGNOSTICS).with_templates(&rustc_error_codes::TEMPLATES)
 }
 
 /// This is the primary entry point for rustc.
@@ -170,7 +172,7 @@ pub fn set_emitter(&mut self, emitter: Option<Box<dyn Write + Send>>) -> &mut Se
 
     /// Load files from sources other than the file system.
     ///
-    /// Used by RLS.
+    /// Used by RLS. A [`VirtualFileLoader`] compiles a crate from files in memory.
     pub fn set_file_loader(
         &mut self,
         file_loader: Option<Box<dyn FileLoader + Send + Sync>>,
@@ -209,7 +211,8 @@ fn run_compiler(
 
     let sopts = config::build_session_options(&matches);
 
-    if let Some(ref code) = matches.opt_str("explain") {
+    // With a crate to compile, the explanation is printed after its errors instead.
+    if let (Some(code), true) = (&sopts.explain, matches.free.is_empty()) {
         handle_explain(diagnostics_registry(), code, sopts.error_format);
         return Ok(());
     }
@@ -417,6 +420,16 @@ fn run_compiler(
                 sess.code_stats.print_type_sizes();
             }
 
+            if let Some(path) = &sess.opts.debugging_opts.profile_const_eval {
+                if let Err(err) = sess.code_stats.write_const_eval_profile(path) {
+                    sess.err(&format!(
+                        "failed to write const evaluation profile to `{}`: {}",
+                        path.display(),
+                        err
+                    ));
+                }
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -528,27 +541,9 @@ fn stderr_isatty() -> bool {
 }
 
 fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
-    let normalised =
-        if code.starts_with('E') { code.to_string() } else { format!("E{0:0>4}", code) };
-    match registry.try_find_description(&normalised) {
+    match registry.try_find_description(code) {
         Ok(Some(description)) => {
-            let mut is_in_code_block = false;
-            let mut text = String::new();
-            // Slice off the leading newline and print.
-            for line in description.lines() {
-                let indent_level =
-                    line.find(|c: char| !c.is_whitespace()).unwrap_or_else(|| line.len());
-                let dedented_line = &line[indent_level..];
-                if dedented_line.starts_with("```") {
-                    is_in_code_block = !is_in_code_block;
-                    text.push_str(&line[..(indent_level + 3)]);
-                } else if is_in_code_block && dedented_line.starts_with("# ") {
-                    continue;
-                } else {
-                    text.push_str(line);
-                }
-                text.push('\n');
-            }
+            let text = registry::strip_hidden_lines(description);
             if stdout_isatty() {
                 show_content_with_pager(&text);
             } else {
@@ -1297,6 +1292,11 @@ pub fn init_env_logger(env: &str) {
 }
 
 pub fn main() -> ! {
+    rustc_metadata::proc_macro_server::run_if_requested();
+    // The server is unstable, so stable compilers ignore the environment variable starting it.
+    if rustc_feature::UnstableFeatures::from_environment(None).is_nightly_build() {
+        rustc_errors::dedup_server::run_if_requested();
+    }
     let start_time = Instant::now();
     let start_rss = get_resident_set_size();
     init_rustc_env_logger();
@@ -12,4 +12,6 @@ macro_rules! register_diagnostics {
 }
 
 mod error_codes;
+mod templates;
 pub use error_codes::DIAGNOSTICS;
+pub use templates::TEMPLATES;
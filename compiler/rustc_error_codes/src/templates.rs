@@ -0,0 +1,10 @@
+//! The templates of the explanations of error codes that can be instantiated for a diagnostic. A
+//! template starts the explanation, with `{name}` replaced by the argument `name` that the
+//! diagnostic was reported with, see `Diagnostic::set_arg`.
+
+pub static TEMPLATES: &[(&str, &str)] = &[(
+    "E0277",
+    "The type `{self_ty}` doesn't implement the trait `{trait}`, but it is used in a place that \
+     requires it: the trait bound `{bound}` is not satisfied. Implement `{trait}` for \
+     `{self_ty}`, or use a type that already implements it.\n",
+)];
@@ -0,0 +1,145 @@
+//! Deduplicating warnings across the compilers of a build, see `-Z diagnostic-dedup-server`.
+//!
+//! The server is a copy of the compiler started with [`LISTEN_ENV`] set to the path of a Unix
+//! socket, which runs until it is killed. Before emitting a warning, a compiler connected to it
+//! sends the stable hash of the warning, and leaves the warning out if the server has seen that
+//! hash before. The hash covers the level, code and message of the warning and of its notes, and
+//! the file, line and column of their primary spans, so a warning in code that several crates of
+//! the build share, like the expansion of a macro of a common dependency, is only shown once.
+
+use crate::Diagnostic;
+
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::SourceMap;
+
+use std::env;
+use std::hash::Hash;
+use std::io;
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::process;
+
+#[cfg(unix)]
+use rustc_data_structures::fx::FxHashSet;
+#[cfg(unix)]
+use std::io::{Read, Write};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::sync::{Arc, Mutex};
+#[cfg(unix)]
+use std::thread;
+
+/// Set to the path of the socket to listen on in the environment of the server.
+pub const LISTEN_ENV: &str = "RUSTC_DIAGNOSTIC_DEDUP_LISTEN";
+/// Set to the path of the socket of the server in the environment of the compilers, unless they
+/// are passed `-Z diagnostic-dedup-server`.
+pub const SERVER_ENV: &str = "RUSTC_DIAGNOSTIC_DEDUP_SERVER";
+
+// The answers of the server.
+#[cfg(unix)]
+const NEW: u8 = 1;
+#[cfg(unix)]
+const SEEN: u8 = 0;
+
+/// The connection of a compiler to the server.
+pub struct DedupClient {
+    #[cfg(unix)]
+    stream: UnixStream,
+}
+
+impl DedupClient {
+    #[cfg(unix)]
+    pub fn connect(path: &Path) -> io::Result<DedupClient> {
+        Ok(DedupClient { stream: UnixStream::connect(path)? })
+    }
+
+    #[cfg(not(unix))]
+    pub fn connect(_path: &Path) -> io::Result<DedupClient> {
+        Err(unsupported())
+    }
+
+    /// Sends the hash of a warning to the server, returns whether it is the first one with it.
+    #[cfg(unix)]
+    crate fn report(&mut self, hash: u128) -> io::Result<bool> {
+        self.stream.write_all(&hash.to_le_bytes())?;
+        let mut answer = [0];
+        self.stream.read_exact(&mut answer)?;
+        Ok(answer[0] == NEW)
+    }
+
+    #[cfg(not(unix))]
+    crate fn report(&mut self, _hash: u128) -> io::Result<bool> {
+        Err(unsupported())
+    }
+}
+
+/// The hash of `diagnostic` that is the same in every compiler of the build.
+crate fn stable_hash(diagnostic: &Diagnostic, source_map: Option<&Lrc<SourceMap>>) -> u128 {
+    let mut hasher = StableHasher::new();
+    diagnostic.code.hash(&mut hasher);
+    let messages = iter::once((diagnostic.level, diagnostic.message(), &diagnostic.span)).chain(
+        diagnostic.children.iter().map(|child| (child.level, child.message(), &child.span)),
+    );
+    for (level, message, span) in messages {
+        level.hash(&mut hasher);
+        message.hash(&mut hasher);
+        if let Some(source_map) = source_map {
+            for &span in span.primary_spans() {
+                source_map.span_to_embeddable_string(span).hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Runs the server and exits if this process was started to be one, returns otherwise. Drivers
+/// call this before anything else, and only if unstable features are allowed.
+pub fn run_if_requested() {
+    let path = match env::var_os(LISTEN_ENV) {
+        Some(path) => PathBuf::from(path),
+        None => return,
+    };
+    if let Err(err) = serve(&path) {
+        eprintln!("error: failed to run the diagnostic deduplication server: {}", err);
+        process::exit(1);
+    }
+    process::exit(0)
+}
+
+#[cfg(unix)]
+fn serve(path: &Path) -> io::Result<()> {
+    let listener = UnixListener::bind(path)?;
+    let seen = Arc::new(Mutex::new(FxHashSet::default()));
+    for stream in listener.incoming() {
+        let (stream, seen) = (stream?, seen.clone());
+        // A compiler that goes away only ends its own connection.
+        thread::spawn(move || serve_client(stream, &seen));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve(_path: &Path) -> io::Result<()> {
+    Err(unsupported())
+}
+
+/// Answers the hashes a compiler sends until it closes the connection.
+#[cfg(unix)]
+fn serve_client(mut stream: UnixStream, seen: &Mutex<FxHashSet<u128>>) -> io::Result<()> {
+    let mut hash = [0; 16];
+    loop {
+        match stream.read_exact(&mut hash) {
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            result => result?,
+        }
+        let new = seen.lock().unwrap().insert(u128::from_le_bytes(hash));
+        stream.write_all(&[if new { NEW } else { SEEN }])?;
+    }
+}
+
+#[cfg(not(unix))]
+fn unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "only supported on Unix")
+}
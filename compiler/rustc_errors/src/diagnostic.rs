@@ -19,6 +19,12 @@ pub struct Diagnostic {
     pub span: MultiSpan,
     pub children: Vec<SubDiagnostic>,
     pub suggestions: Vec<CodeSuggestion>,
+    /// The values the diagnostic was reported with, like the types it is about, by name. The
+    /// explanation of its error code can be instantiated with them, see
+    /// `Registry::instantiate_description`.
+    pub args: Vec<(String, String)>,
+    /// What the diagnostic is about, see `DiagnosticAnchor`.
+    pub anchor: Option<DiagnosticAnchor>,
 
     /// This is not used for highlighting or rendering any error message.  Rather, it can be used
     /// as a sort key to sort a buffer of diagnostics.  By default, it is the primary span of
@@ -26,6 +32,24 @@ pub struct Diagnostic {
     pub sort_span: Span,
 }
 
+/// The code a diagnostic is about, identified in a way that unlike its spans doesn't change when
+/// unrelated code is edited. Only set with `-Z diagnostic-anchors`, for the JSON output.
+#[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub struct DiagnosticAnchor {
+    /// The path of the definition, like `krate::module::{impl#0}::method`.
+    pub def_path: String,
+    /// The local id of the HIR node in the definition.
+    pub hir_local_id: Option<u32>,
+    /// The location in the MIR body of the definition.
+    pub mir_location: Option<DiagnosticMirLocation>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub struct DiagnosticMirLocation {
+    pub basic_block: u32,
+    pub statement_index: u32,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
 pub enum DiagnosticId {
     Error(String),
@@ -69,6 +93,11 @@ pub fn normal<S: Into<String>>(t: S) -> DiagnosticStyledString {
     pub fn highlighted<S: Into<String>>(t: S) -> DiagnosticStyledString {
         DiagnosticStyledString(vec![StringPart::Highlighted(t.into())])
     }
+
+    /// The text of all parts, without their highlighting.
+    pub fn content(&self) -> String {
+        self.0.iter().map(|part| part.content()).collect()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -77,6 +106,21 @@ pub enum StringPart {
     Highlighted(String),
 }
 
+impl StringPart {
+    pub fn content(&self) -> &str {
+        match self {
+            StringPart::Normal(s) | StringPart::Highlighted(s) => s,
+        }
+    }
+
+    fn into_styled(self) -> (String, Style) {
+        match self {
+            StringPart::Normal(s) => (s, Style::NoStyle),
+            StringPart::Highlighted(s) => (s, Style::Highlight),
+        }
+    }
+}
+
 impl Diagnostic {
     pub fn new(level: Level, message: &str) -> Self {
         Diagnostic::new_with_code(level, None, message)
@@ -90,6 +134,8 @@ pub fn new_with_code(level: Level, code: Option<DiagnosticId>, message: &str) ->
             span: MultiSpan::new(),
             children: vec![],
             suggestions: vec![],
+            args: vec![],
+            anchor: None,
             sort_span: DUMMY_SP,
         }
     }
@@ -242,6 +288,11 @@ pub fn highlighted_note(&mut self, msg: Vec<(String, Style)>) -> &mut Self {
         self
     }
 
+    /// Add a note with the highlighted parts of `msg` highlighted, which may span several lines.
+    pub fn styled_note(&mut self, msg: DiagnosticStyledString) -> &mut Self {
+        self.highlighted_note(msg.0.into_iter().map(StringPart::into_styled).collect())
+    }
+
     /// Prints the span with a note above it.
     /// This is like [`Diagnostic::note()`], but it gets its own span.
     crate fn span_note<S: Into<MultiSpan>>(&mut self, sp: S, msg: &str) -> &mut Self {
@@ -532,6 +583,18 @@ pub fn code(&mut self, s: DiagnosticId) -> &mut Self {
         self
     }
 
+    /// Sets the argument `name` of the diagnostic, which is `{name}` in the template of the
+    /// explanation of its error code.
+    pub fn set_arg<A: fmt::Display>(&mut self, name: &str, arg: A) -> &mut Self {
+        self.args.push((name.to_owned(), arg.to_string()));
+        self
+    }
+
+    pub fn set_anchor(&mut self, anchor: Option<DiagnosticAnchor>) -> &mut Self {
+        self.anchor = anchor;
+        self
+    }
+
     pub fn clear_code(&mut self) -> &mut Self {
         self.code = None;
         self
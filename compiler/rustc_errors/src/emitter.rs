@@ -11,7 +11,7 @@
 
 use rustc_lint_defs::FutureBreakage;
 use rustc_span::source_map::SourceMap;
-use rustc_span::{MultiSpan, SourceFile, Span};
+use rustc_span::{FileName, MultiSpan, SourceFile, Span};
 
 use crate::snippet::{Annotation, AnnotationType, Line, MultilineAnnotation, Style, StyledString};
 use crate::styled_buffer::StyledBuffer;
@@ -19,11 +19,12 @@
 
 use rustc_lint_defs::pluralize;
 
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_data_structures::sync::Lrc;
 use rustc_span::hygiene::{ExpnKind, MacroKind};
 use std::borrow::Cow;
 use std::cmp::{max, min, Reverse};
+use std::env;
 use std::io;
 use std::io::prelude::*;
 use std::iter;
@@ -35,6 +36,9 @@
 /// Default column width, used in tests and when terminal dimensions cannot be determined.
 const DEFAULT_COLUMN_WIDTH: usize = 140;
 
+const ERROR_INDEX: &str = "https://doc.rust-lang.org/error-index.html";
+const STD_DOCS: &str = "https://doc.rust-lang.org";
+
 /// Describes the way the content of the `rendered` field of the json output is generated
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HumanReadableErrorType {
@@ -208,6 +212,12 @@ fn supports_color(&self) -> bool {
         false
     }
 
+    /// Checks if the repeated frames of macro backtraces should be grouped, see
+    /// `-Z macro-backtrace=full`.
+    fn should_fold_macro_backtrace(&self) -> bool {
+        true
+    }
+
     fn source_map(&self) -> Option<&Lrc<SourceMap>>;
 
     /// Formats the substitutions of the primary_span
@@ -352,7 +362,8 @@ fn render_multispans_macro_backtrace(
     }
 
     fn render_multispan_macro_backtrace(&self, span: &mut MultiSpan, always_backtrace: bool) {
-        let mut new_labels: Vec<(Span, String)> = vec![];
+        // The labels, with the number of the frame of the backtrace they belong to if it is shown.
+        let mut new_labels: Vec<(Span, String, Option<usize>)> = vec![];
 
         for &sp in span.primary_spans() {
             if sp.is_dummy() {
@@ -369,8 +380,11 @@ fn render_multispan_macro_backtrace(&self, span: &mut MultiSpan, always_backtrac
                 }
 
                 if matches!(trace.kind, ExpnKind::Inlined) {
-                    new_labels
-                        .push((trace.call_site, "in the inlined copy of this code".to_string()));
+                    new_labels.push((
+                        trace.call_site,
+                        "in the inlined copy of this code".to_string(),
+                        None,
+                    ));
                 } else if always_backtrace {
                     let proc_macro = if let ExpnKind::Macro { kind: _, name: _, proc_macro: true } =
                         trace.kind
@@ -382,18 +396,10 @@ fn render_multispan_macro_backtrace(&self, span: &mut MultiSpan, always_backtrac
 
                     new_labels.push((
                         trace.def_site,
-                        format!(
-                            "in this expansion of {}`{}`{}",
-                            proc_macro,
-                            trace.kind.descr(),
-                            if macro_backtrace.len() > 1 {
-                                // if macro_backtrace.len() == 1 it'll be
-                                // pointed at by "in this macro invocation"
-                                format!(" (#{})", i + 1)
-                            } else {
-                                String::new()
-                            },
-                        ),
+                        format!("in this expansion of {}`{}`", proc_macro, trace.kind.descr()),
+                        // if macro_backtrace.len() == 1 it'll be
+                        // pointed at by "in this macro invocation"
+                        (macro_backtrace.len() > 1).then(|| i + 1),
                     ));
                 }
 
@@ -419,16 +425,10 @@ fn render_multispan_macro_backtrace(&self, span: &mut MultiSpan, always_backtrac
                 {
                     new_labels.push((
                         trace.call_site,
-                        format!(
-                            "in this macro invocation{}",
-                            if macro_backtrace.len() > 1 && always_backtrace {
-                                // only specify order when the macro
-                                // backtrace is multiple levels deep
-                                format!(" (#{})", i + 1)
-                            } else {
-                                String::new()
-                            },
-                        ),
+                        "in this macro invocation".to_string(),
+                        // only specify order when the macro
+                        // backtrace is multiple levels deep
+                        (macro_backtrace.len() > 1 && always_backtrace).then(|| i + 1),
                     ));
                 }
                 if !always_backtrace {
@@ -437,6 +437,17 @@ fn render_multispan_macro_backtrace(&self, span: &mut MultiSpan, always_backtrac
             }
         }
 
+        let new_labels = if always_backtrace && self.should_fold_macro_backtrace() {
+            fold_macro_backtrace_labels(new_labels)
+        } else {
+            new_labels
+                .into_iter()
+                .map(|(span, label, frame)| match frame {
+                    Some(frame) => (span, format!("{} (#{})", label, frame)),
+                    None => (span, label),
+                })
+                .collect()
+        };
         for (label_span, label_text) in new_labels {
             span.push_span_label(label_span, label_text);
         }
@@ -527,6 +538,10 @@ fn should_show_explain(&self) -> bool {
     fn supports_color(&self) -> bool {
         self.dst.supports_color()
     }
+
+    fn should_fold_macro_backtrace(&self) -> bool {
+        self.fold_macro_backtrace
+    }
 }
 
 /// An emitter that does nothing when emitting a diagnostic.
@@ -579,6 +594,17 @@ fn suggests_using_colors(self) -> bool {
     }
 }
 
+/// Whether the human readable output contains OSC 8 hyperlinks, which terminals that support them
+/// show as clickable text: error codes link to the error index, the locations of spans to their
+/// files, and paths into the standard library in notes to its documentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminalUrl {
+    No,
+    Yes,
+    /// Only if the output goes to a terminal that is known to support them.
+    Auto,
+}
+
 /// Handles the writing of `HumanReadableErrorType::Default` and `HumanReadableErrorType::Short`
 pub struct EmitterWriter {
     dst: Destination,
@@ -587,8 +613,11 @@ pub struct EmitterWriter {
     teach: bool,
     ui_testing: bool,
     terminal_width: Option<usize>,
+    /// The template of the links to the locations of spans, if the output contains hyperlinks.
+    file_url_template: Option<String>,
 
     macro_backtrace: bool,
+    fold_macro_backtrace: bool,
 }
 
 #[derive(Debug)]
@@ -615,7 +644,9 @@ pub fn stderr(
             teach,
             ui_testing: false,
             terminal_width,
+            file_url_template: None,
             macro_backtrace,
+            fold_macro_backtrace: true,
         }
     }
 
@@ -635,7 +666,9 @@ pub fn new(
             teach,
             ui_testing: false,
             terminal_width,
+            file_url_template: None,
             macro_backtrace,
+            fold_macro_backtrace: true,
         }
     }
 
@@ -644,6 +677,83 @@ pub fn ui_testing(mut self, ui_testing: bool) -> Self {
         self
     }
 
+    /// Whether the repeated frames of macro backtraces are grouped, which they are by default.
+    pub fn fold_macro_backtrace(mut self, fold_macro_backtrace: bool) -> Self {
+        self.fold_macro_backtrace = fold_macro_backtrace;
+        self
+    }
+
+    /// Adds hyperlinks to the output, see [`TerminalUrl`]. Locations link to `file_url_template`
+    /// with `{path}`, `{line}` and `{column}` replaced, or to their `file://` URL without one.
+    pub fn terminal_urls(
+        mut self,
+        terminal_urls: TerminalUrl,
+        file_url_template: Option<String>,
+    ) -> Self {
+        let enabled = match terminal_urls {
+            TerminalUrl::No => false,
+            TerminalUrl::Yes => true,
+            TerminalUrl::Auto => matches!(self.dst, Terminal(_)) && stderr_supports_hyperlinks(),
+        };
+        self.file_url_template =
+            enabled.then(|| file_url_template.unwrap_or_else(|| "file://{path}".to_owned()));
+        self
+    }
+
+    /// Links `code` to its entry in the error index, if hyperlinks are enabled.
+    fn link_error_code<'a>(&self, code: &'a str) -> Cow<'a, str> {
+        match self.file_url_template {
+            Some(_) => Cow::Owned(hyperlink(&format!("{}#{}", ERROR_INDEX, code), code)),
+            None => Cow::Borrowed(code),
+        }
+    }
+
+    /// Links `text`, which describes a location in `file`, to the URL of the location, if
+    /// hyperlinks are enabled and the file is on the local disk.
+    fn link_location(&self, text: String, file: &SourceFile, line: usize, col: usize) -> String {
+        let (template, path) = match (&self.file_url_template, &file.name) {
+            (Some(template), FileName::Real(name)) => match name.local_path() {
+                Some(path) => (template, path),
+                None => return text,
+            },
+            _ => return text,
+        };
+        let path = match env::current_dir() {
+            Ok(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_owned(),
+        };
+        let url = template
+            .replace("{path}", &url_encode_path(&path))
+            .replace("{line}", &line.to_string())
+            .replace("{column}", &col.to_string());
+        hyperlink(&url, &text)
+    }
+
+    /// Links the paths into the standard library crates that are quoted in `text` to a search for
+    /// them in their documentation, if hyperlinks are enabled. Paths into other crates aren't
+    /// linked, a path in a diagnostic doesn't tell which of its segments is a crate.
+    fn link_std_paths<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.file_url_template.is_none() || !text.contains('`') {
+            return Cow::Borrowed(text);
+        }
+        let mut linked = String::with_capacity(text.len());
+        // Every other part is quoted.
+        for (i, part) in text.split('`').enumerate() {
+            if i > 0 {
+                linked.push('`');
+            }
+            match std_item_path(part).filter(|_| i % 2 == 1) {
+                Some((krate, path)) => {
+                    let url = format!("{}/{}/?search={}", STD_DOCS, krate, path);
+                    linked.push_str(&hyperlink(&url, path));
+                    linked.push_str(&part[path.len()..]);
+                }
+                None => linked.push_str(part),
+            }
+        }
+        Cow::Owned(linked)
+    }
+
     fn maybe_anonymized(&self, line_num: usize) -> String {
         if self.ui_testing { ANONYMIZED_LINE_NUM.to_string() } else { line_num.to_string() }
     }
@@ -1221,6 +1331,7 @@ fn style_or_override(style: Style, override_: Option<Style>) -> Style {
         //                very *weird* formats
         //                see?
         for &(ref text, ref style) in msg.iter() {
+            let text = self.link_std_paths(text);
             let lines = text.split('\n').collect::<Vec<_>>();
             if lines.len() > 1 {
                 for (i, line) in lines.iter().enumerate() {
@@ -1231,7 +1342,7 @@ fn style_or_override(style: Style, override_: Option<Style>) -> Style {
                     buffer.append(line_number, line, style_or_override(*style, override_style));
                 }
             } else {
-                buffer.append(line_number, text, style_or_override(*style, override_style));
+                buffer.append(line_number, &text, style_or_override(*style, override_style));
             }
         }
     }
@@ -1267,7 +1378,7 @@ fn emit_message_default(
             // only render error codes, not lint codes
             if let Some(DiagnosticId::Error(ref code)) = *code {
                 buffer.append(0, "[", Style::Level(*level));
-                buffer.append(0, &code, Style::Level(*level));
+                buffer.append(0, &self.link_error_code(code), Style::Level(*level));
                 buffer.append(0, "]", Style::Level(*level));
             }
             let header_style = if is_secondary { Style::HeaderMsg } else { Style::MainHeaderMsg };
@@ -1275,7 +1386,8 @@ fn emit_message_default(
                 buffer.append(0, ": ", header_style);
             }
             for &(ref text, _) in msg.iter() {
-                buffer.append(0, &replace_tabs(text), header_style);
+                let text = if is_secondary { self.link_std_paths(text) } else { Cow::from(text) };
+                buffer.append(0, &replace_tabs(&text), header_style);
             }
         }
 
@@ -1319,28 +1431,25 @@ fn emit_message_default(
                     let buffer_msg_line_offset = buffer.num_lines();
 
                     buffer.prepend(buffer_msg_line_offset, "--> ", Style::LineNumber);
+                    let line = sm.doctest_offset_line(&loc.file.name, loc.line);
+                    let col = loc.col.0 + 1;
+                    let text = format!("{}:{}:{}", loc.file.name.prefer_local(), line, col);
                     buffer.append(
                         buffer_msg_line_offset,
-                        &format!(
-                            "{}:{}:{}",
-                            loc.file.name.prefer_local(),
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
+                        &self.link_location(text, &loc.file, loc.line, col),
                         Style::LineAndColumn,
                     );
                     for _ in 0..max_line_num_len {
                         buffer.prepend(buffer_msg_line_offset, " ", Style::NoStyle);
                     }
                 } else {
+                    let line = sm.doctest_offset_line(&loc.file.name, loc.line);
+                    let col = loc.col.0 + 1;
+                    let text = format!("{}:{}:{}", loc.file.name.prefer_local(), line, col);
+                    buffer.prepend(0, ": ", Style::LineAndColumn);
                     buffer.prepend(
                         0,
-                        &format!(
-                            "{}:{}:{}: ",
-                            loc.file.name.prefer_local(),
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
+                        &self.link_location(text, &loc.file, loc.line, col),
                         Style::LineAndColumn,
                     );
                 }
@@ -1354,19 +1463,23 @@ fn emit_message_default(
                 // Then, the secondary file indicator
                 buffer.prepend(buffer_msg_line_offset + 1, "::: ", Style::LineNumber);
                 let loc = if let Some(first_line) = annotated_file.lines.first() {
-                    let col = if let Some(first_annotation) = first_line.annotations.first() {
-                        format!(":{}", first_annotation.start_col + 1)
+                    let first_col = first_line.annotations.first().map(|a| a.start_col + 1);
+                    let col = if let Some(first_col) = first_col {
+                        format!(":{}", first_col)
                     } else {
                         String::new()
                     };
-                    format!(
+                    let loc = format!(
                         "{}:{}{}",
                         annotated_file.file.name.prefer_local(),
                         sm.doctest_offset_line(&annotated_file.file.name, first_line.line_index),
                         col
-                    )
+                    );
+                    let (line, col) = (first_line.line_index, first_col.unwrap_or(1));
+                    self.link_location(loc, &annotated_file.file, line, col)
                 } else {
-                    format!("{}", annotated_file.file.name.prefer_local())
+                    let loc = format!("{}", annotated_file.file.name.prefer_local());
+                    self.link_location(loc, &annotated_file.file, 1, 1)
                 };
                 buffer.append(buffer_msg_line_offset + 1, &loc, Style::LineAndColumn);
                 for _ in 0..max_line_num_len {
@@ -2039,6 +2152,89 @@ fn overlaps(a1: &Annotation, a2: &Annotation, padding: usize) -> bool {
     num_overlap(a1.start_col, a1.end_col + padding, a2.start_col, a2.end_col, false)
 }
 
+/// Merges the labels of a macro backtrace that say the same about the same code, like the ones of
+/// every expansion of a recursive macro, into one label with the range and number of the frames
+/// they belong to.
+fn fold_macro_backtrace_labels(labels: Vec<(Span, String, Option<usize>)>) -> Vec<(Span, String)> {
+    let mut folded: FxIndexMap<_, (Span, Vec<usize>)> = FxIndexMap::default();
+    for (span, label, frame) in labels {
+        let (_, frames) = folded.entry((span.lo(), span.hi(), label)).or_insert((span, vec![]));
+        frames.extend(frame);
+    }
+    folded
+        .into_iter()
+        .map(|((_, _, label), (span, mut frames))| {
+            frames.sort_unstable();
+            frames.dedup();
+            let label = match frames[..] {
+                [] => label,
+                [frame] => format!("{} (#{})", label, frame),
+                [first, .., last] => {
+                    format!("{} (#{} to #{}, {} times)", label, first, last, frames.len())
+                }
+            };
+            (span, label)
+        })
+        .collect()
+}
+
+/// Makes `text` a hyperlink to `url`, with the OSC 8 escape sequence.
+fn hyperlink(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
+
+/// The path of the `file://` URL of the absolute `path`, with the bytes that can't appear in a URL
+/// percent-encoded.
+fn url_encode_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let path = if cfg!(windows) { path.replace('\\', "/") } else { path.into_owned() };
+    let mut encoded = String::with_capacity(path.len() + 1);
+    // Windows paths start with a drive letter instead.
+    if !path.starts_with('/') {
+        encoded.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Returns the crate and the path at the start of `text` if it is a path into one of the crates
+/// of the standard library, like `std::vec::Vec` at the start of `std::vec::Vec<T>`.
+fn std_item_path(text: &str) -> Option<(&str, &str)> {
+    let len = text.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'));
+    let path = text[..len.unwrap_or(text.len())].trim_end_matches(':');
+    let mut segments = path.split("::");
+    let krate = segments.next()?;
+    let is_std = matches!(krate, "std" | "core" | "alloc" | "proc_macro" | "test");
+    let is_path = path.len() > krate.len()
+        && segments.all(|segment| !segment.is_empty() && !segment.contains(':'));
+    (is_std && is_path).then(|| (krate, path))
+}
+
+/// Guesses whether the terminal behind the standard error supports hyperlinks, from the variables
+/// that the terminals known to support them set. `FORCE_HYPERLINK` overrides the guess.
+fn stderr_supports_hyperlinks() -> bool {
+    if let Ok(force) = env::var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if !atty::is(atty::Stream::Stderr) {
+        return false;
+    }
+    let var = |name: &str| env::var(name).unwrap_or_default();
+    matches!(&*var("TERM_PROGRAM"), "iTerm.app" | "WezTerm" | "vscode" | "Hyper")
+        || var("TERM") == "xterm-kitty"
+        || var("VTE_VERSION").parse::<u32>().map_or(false, |version| version >= 5000)
+        || env::var_os("DOMTERM").is_some()
+        || env::var_os("WT_SESSION").is_some()
+        || env::var_os("KONSOLE_VERSION").is_some()
+}
+
 fn emit_to_destination(
     rendered_buffer: &[Vec<StyledString>],
     lvl: &Level,
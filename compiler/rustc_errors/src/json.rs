@@ -13,13 +13,13 @@
 
 use crate::emitter::{Emitter, HumanReadableErrorType};
 use crate::registry::Registry;
-use crate::DiagnosticId;
+use crate::{DiagnosticAnchor, DiagnosticId};
 use crate::ToolMetadata;
 use crate::{CodeSuggestion, SubDiagnostic};
 use rustc_lint_defs::{Applicability, FutureBreakage};
 
 use rustc_data_structures::sync::Lrc;
-use rustc_span::hygiene::ExpnData;
+use rustc_span::hygiene::{ExpnData, ExpnKind, MacroKind};
 use rustc_span::{MultiSpan, Span, SpanLabel};
 use std::io::{self, Write};
 use std::path::Path;
@@ -41,6 +41,7 @@ pub struct JsonEmitter {
     json_rendered: HumanReadableErrorType,
     terminal_width: Option<usize>,
     macro_backtrace: bool,
+    fold_macro_backtrace: bool,
 }
 
 impl JsonEmitter {
@@ -61,6 +62,7 @@ pub fn stderr(
             json_rendered,
             terminal_width,
             macro_backtrace,
+            fold_macro_backtrace: true,
         }
     }
 
@@ -99,12 +101,17 @@ pub fn new(
             json_rendered,
             terminal_width,
             macro_backtrace,
+            fold_macro_backtrace: true,
         }
     }
 
     pub fn ui_testing(self, ui_testing: bool) -> Self {
         Self { ui_testing, ..self }
     }
+
+    pub fn fold_macro_backtrace(self, fold_macro_backtrace: bool) -> Self {
+        Self { fold_macro_backtrace, ..self }
+    }
 }
 
 impl Emitter for JsonEmitter {
@@ -198,6 +205,8 @@ struct Diagnostic {
     rendered: Option<String>,
     /// Extra tool metadata
     tool_metadata: ToolMetadata,
+    /// What the diagnostic is about, with `-Z diagnostic-anchors`.
+    anchor: Option<DiagnosticAnchor>,
 }
 
 macro_rules! encode_fields {
@@ -226,7 +235,7 @@ macro_rules! encode_fields {
     };
 }
 
-// Special-case encoder to skip tool_metadata if not set
+// Special-case encoder to skip tool_metadata and anchor if not set
 impl<E: Encoder> Encodable<E> for Diagnostic {
     fn encode(&self, s: &mut E) -> Result<(), E::Error> {
         s.emit_struct(false, |s| {
@@ -238,7 +247,7 @@ fn encode(&self, s: &mut E) -> Result<(), E::Error> {
                 self,
                 Self,
                 [message, code, level, spans, children, rendered],
-                [tool_metadata]
+                [tool_metadata, anchor]
             );
             if self.tool_metadata.is_set() {
                 idx = encode_fields!(
@@ -247,7 +256,17 @@ fn encode(&self, s: &mut E) -> Result<(), E::Error> {
                     self,
                     Self,
                     [tool_metadata],
-                    [message, code, level, spans, children, rendered]
+                    [message, code, level, spans, children, rendered, anchor]
+                );
+            }
+            if self.anchor.is_some() {
+                idx = encode_fields!(
+                    s,
+                    idx,
+                    self,
+                    Self,
+                    [anchor],
+                    [message, code, level, spans, children, rendered, tool_metadata]
                 );
             }
 
@@ -304,6 +323,10 @@ struct DiagnosticSpanMacroExpansion {
     /// name of macro that was applied (e.g., "foo!" or "#[derive(Eq)]")
     macro_decl_name: String,
 
+    /// what produced this code: "bang_macro", "attribute_macro", "derive_macro", "desugaring",
+    /// "ast_pass" or "inlined"
+    kind: &'static str,
+
     /// span where macro was defined (if known)
     def_site_span: DiagnosticSpan,
 }
@@ -312,8 +335,9 @@ struct DiagnosticSpanMacroExpansion {
 struct DiagnosticCode {
     /// The code itself.
     code: String,
-    /// An explanation for the code.
-    explanation: Option<&'static str>,
+    /// An explanation for the code, instantiated with the arguments of the diagnostic if the code
+    /// has a template for them.
+    explanation: Option<String>,
 }
 
 #[derive(Encodable)]
@@ -357,6 +381,7 @@ fn from_errors_diagnostic(diag: &crate::Diagnostic, je: &JsonEmitter) -> Diagnos
             children: vec![],
             rendered: None,
             tool_metadata: sugg.tool_metadata.clone(),
+            anchor: None,
         });
 
         // generate regular command line output and store it in the json
@@ -383,6 +408,7 @@ fn flush(&mut self) -> io::Result<()> {
                 je.terminal_width,
                 je.macro_backtrace,
             )
+            .fold_macro_backtrace(je.fold_macro_backtrace)
             .ui_testing(je.ui_testing)
             .emit_diagnostic(diag);
         let output = Arc::try_unwrap(output.0).unwrap().into_inner().unwrap();
@@ -390,7 +416,7 @@ fn flush(&mut self) -> io::Result<()> {
 
         Diagnostic {
             message: diag.message(),
-            code: DiagnosticCode::map_opt_string(diag.code.clone(), je),
+            code: DiagnosticCode::map_opt_string(diag.code.clone(), &diag.args, je),
             level: diag.level.to_str(),
             spans: DiagnosticSpan::from_multispan(&diag.span, je),
             children: diag
@@ -401,6 +427,7 @@ fn flush(&mut self) -> io::Result<()> {
                 .collect(),
             rendered: Some(output),
             tool_metadata: ToolMetadata::default(),
+            anchor: diag.anchor.clone(),
         }
     }
 
@@ -417,6 +444,7 @@ fn from_sub_diagnostic(diag: &SubDiagnostic, je: &JsonEmitter) -> Diagnostic {
             children: vec![],
             rendered: None,
             tool_metadata: ToolMetadata::default(),
+            anchor: None,
         }
     }
 }
@@ -463,6 +491,7 @@ fn from_span_full(
             Box::new(DiagnosticSpanMacroExpansion {
                 span: call_site,
                 macro_decl_name: bt.kind.descr(),
+                kind: expansion_kind(&bt.kind),
                 def_site_span,
             })
         });
@@ -510,6 +539,18 @@ fn from_suggestion(suggestion: &CodeSuggestion, je: &JsonEmitter) -> Vec<Diagnos
     }
 }
 
+fn expansion_kind(kind: &ExpnKind) -> &'static str {
+    match kind {
+        ExpnKind::Root => "root",
+        ExpnKind::Macro { kind: MacroKind::Bang, .. } => "bang_macro",
+        ExpnKind::Macro { kind: MacroKind::Attr, .. } => "attribute_macro",
+        ExpnKind::Macro { kind: MacroKind::Derive, .. } => "derive_macro",
+        ExpnKind::AstPass(_) => "ast_pass",
+        ExpnKind::Desugaring(_) => "desugaring",
+        ExpnKind::Inlined => "inlined",
+    }
+}
+
 impl DiagnosticSpanLine {
     fn line_from_source_file(
         sf: &rustc_span::SourceFile,
@@ -555,16 +596,22 @@ fn from_span(span: Span, je: &JsonEmitter) -> Vec<DiagnosticSpanLine> {
 }
 
 impl DiagnosticCode {
-    fn map_opt_string(s: Option<DiagnosticId>, je: &JsonEmitter) -> Option<DiagnosticCode> {
+    fn map_opt_string(
+        s: Option<DiagnosticId>,
+        args: &[(String, String)],
+        je: &JsonEmitter,
+    ) -> Option<DiagnosticCode> {
         s.map(|s| {
             let s = match s {
                 DiagnosticId::Error(s) => s,
                 DiagnosticId::Lint { name, has_future_breakage: _ } => name,
             };
-            let je_result =
-                je.registry.as_ref().map(|registry| registry.try_find_description(&s)).unwrap();
+            let registry = je.registry.as_ref().unwrap();
+            let explanation = registry.instantiate_description(&s, args).or_else(|| {
+                registry.try_find_description(&s).unwrap_or(None).map(str::to_owned)
+            });
 
-            DiagnosticCode { code: s, explanation: je_result.unwrap_or(None) }
+            DiagnosticCode { code: s, explanation }
         })
     }
 }
@@ -13,11 +13,12 @@
 #[macro_use]
 extern crate rustc_macros;
 
-pub use emitter::ColorConfig;
+pub use emitter::{ColorConfig, TerminalUrl};
 
 use tracing::debug;
 use Level::*;
 
+use dedup_server::DedupClient;
 use emitter::{is_case_difference, Emitter, EmitterWriter};
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
@@ -33,6 +34,7 @@
 
 use std::borrow::Cow;
 use std::hash::{Hash, Hasher};
+use std::io;
 use std::num::NonZeroUsize;
 use std::panic;
 use std::path::Path;
@@ -43,8 +45,10 @@
 pub mod annotate_snippet_emitter_writer;
 mod diagnostic;
 mod diagnostic_builder;
+pub mod dedup_server;
 pub mod emitter;
 pub mod json;
+pub mod sarif;
 mod lock;
 pub mod registry;
 mod snippet;
@@ -293,7 +297,10 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 
 impl error::Error for ExplicitBug {}
 
-pub use diagnostic::{Diagnostic, DiagnosticId, DiagnosticStyledString, SubDiagnostic};
+pub use diagnostic::{
+    Diagnostic, DiagnosticAnchor, DiagnosticId, DiagnosticMirLocation, DiagnosticStyledString,
+    SubDiagnostic,
+};
 pub use diagnostic_builder::DiagnosticBuilder;
 use std::backtrace::Backtrace;
 
@@ -329,6 +336,10 @@ struct HandlerInner {
     /// Used to suggest rustc --explain <error code>
     emitted_diagnostic_codes: FxHashSet<DiagnosticId>,
 
+    /// The error code given to `--explain` along with the crate, and the arguments of the first
+    /// diagnostic with that code, whose explanation is printed at the end.
+    explained_code: Option<(String, Option<Vec<(String, String)>>)>,
+
     /// This set contains a hash of every diagnostic that has been emitted by
     /// this handler. These hashes is used to avoid emitting the same error
     /// twice.
@@ -343,6 +354,11 @@ struct HandlerInner {
     /// The warning count, used for a recap upon finishing
     deduplicated_warn_count: usize,
 
+    /// The connection to the server that deduplicates warnings across the compilers of a build,
+    /// and the number of warnings left out because it had seen them before.
+    dedup_client: Option<DedupClient>,
+    dedup_suppressed_count: usize,
+
     future_breakage_diagnostics: Vec<Diagnostic>,
 }
 
@@ -455,9 +471,12 @@ pub fn with_emitter_and_flags(
                 delayed_good_path_bugs: Vec::new(),
                 taught_diagnostics: Default::default(),
                 emitted_diagnostic_codes: Default::default(),
+                explained_code: None,
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
                 future_breakage_diagnostics: Vec::new(),
+                dedup_client: None,
+                dedup_suppressed_count: 0,
             }),
         }
     }
@@ -727,6 +746,19 @@ pub fn has_errors_or_delayed_span_bugs(&self) -> bool {
         self.inner.borrow().has_errors_or_delayed_span_bugs()
     }
 
+    /// Leaves out the warnings the deduplication server at `path` has seen before, see
+    /// `dedup_server`.
+    pub fn connect_dedup_server(&self, path: &Path) -> io::Result<()> {
+        self.inner.borrow_mut().dedup_client = Some(DedupClient::connect(path)?);
+        Ok(())
+    }
+
+    /// Prints the explanation of the error `code` at the end, instantiated with the arguments of
+    /// the first diagnostic with that code, instead of suggesting `rustc --explain`.
+    pub fn explain_with_args(&self, code: &str) {
+        self.inner.borrow_mut().explained_code = Some((code.to_owned(), None));
+    }
+
     pub fn print_error_count(&self, registry: &Registry) {
         self.inner.borrow_mut().print_error_count(registry)
     }
@@ -817,6 +849,13 @@ fn emit_diagnostic(&mut self, diagnostic: &Diagnostic) {
 
         if let Some(ref code) = diagnostic.code {
             self.emitted_diagnostic_codes.insert(code.clone());
+            if let (DiagnosticId::Error(code), Some((explained, args @ None))) =
+                (code, &mut self.explained_code)
+            {
+                if code == explained {
+                    *args = Some(diagnostic.args.clone());
+                }
+            }
         }
 
         let already_emitted = |this: &mut Self| {
@@ -829,11 +868,15 @@ fn emit_diagnostic(&mut self, diagnostic: &Diagnostic) {
         // Only emit the diagnostic if we've been asked to deduplicate and
         // haven't already emitted an equivalent diagnostic.
         if !(self.flags.deduplicate_diagnostics && already_emitted(self)) {
-            self.emitter.emit_diagnostic(diagnostic);
-            if diagnostic.is_error() {
-                self.deduplicated_err_count += 1;
-            } else if diagnostic.level == Warning {
-                self.deduplicated_warn_count += 1;
+            if diagnostic.level == Warning && self.reported_by_other_compiler(diagnostic) {
+                self.dedup_suppressed_count += 1;
+            } else {
+                self.emitter.emit_diagnostic(diagnostic);
+                if diagnostic.is_error() {
+                    self.deduplicated_err_count += 1;
+                } else if diagnostic.level == Warning {
+                    self.deduplicated_warn_count += 1;
+                }
             }
         }
         if diagnostic.is_error() {
@@ -843,6 +886,23 @@ fn emit_diagnostic(&mut self, diagnostic: &Diagnostic) {
         }
     }
 
+    /// Whether the deduplication server has seen `diagnostic` before. If the connection to it
+    /// fails, every warning is shown from then on.
+    fn reported_by_other_compiler(&mut self, diagnostic: &Diagnostic) -> bool {
+        let client = match &mut self.dedup_client {
+            Some(client) => client,
+            None => return false,
+        };
+        let hash = dedup_server::stable_hash(diagnostic, self.emitter.source_map());
+        match client.report(hash) {
+            Ok(new) => !new,
+            Err(_) => {
+                self.dedup_client = None;
+                false
+            }
+        }
+    }
+
     fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
         self.emitter.emit_artifact_notification(path, artifact_type);
     }
@@ -872,6 +932,16 @@ fn print_error_count(&mut self, registry: &Registry) {
             return;
         }
 
+        if self.dedup_suppressed_count > 0 {
+            let msg = format!(
+                "{} {} not shown, another compiler of this build already emitted {}",
+                self.dedup_suppressed_count,
+                if self.dedup_suppressed_count == 1 { "warning was" } else { "warnings were" },
+                if self.dedup_suppressed_count == 1 { "it" } else { "them" },
+            );
+            self.emit_diagnostic(&Diagnostic::new(Note, &msg));
+        }
+
         match (errors.len(), warnings.len()) {
             (0, 0) => return,
             (0, _) => self.emit_diagnostic(&Diagnostic::new(Level::Warning, &warnings)),
@@ -884,6 +954,15 @@ fn print_error_count(&mut self, registry: &Registry) {
         }
 
         let can_show_explain = self.emitter.should_show_explain();
+        let explanation = self.explained_code.as_ref().and_then(|(code, args)| {
+            registry.instantiate_description(code, args.as_ref()?)
+        });
+        if let Some(explanation) = explanation {
+            if can_show_explain {
+                self.failure(registry::strip_hidden_lines(&explanation).trim_end());
+            }
+            return;
+        }
         let are_there_diagnostics = !self.emitted_diagnostic_codes.is_empty();
         if can_show_explain && are_there_diagnostics {
             let mut error_codes = self
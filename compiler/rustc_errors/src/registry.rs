@@ -6,11 +6,22 @@
 #[derive(Clone)]
 pub struct Registry {
     long_descriptions: FxHashMap<&'static str, Option<&'static str>>,
+    templates: FxHashMap<&'static str, &'static str>,
 }
 
 impl Registry {
     pub fn new(long_descriptions: &[(&'static str, Option<&'static str>)]) -> Registry {
-        Registry { long_descriptions: long_descriptions.iter().copied().collect() }
+        Registry {
+            long_descriptions: long_descriptions.iter().copied().collect(),
+            templates: FxHashMap::default(),
+        }
+    }
+
+    /// Adds the templates of explanations that refer to the arguments of a diagnostic, see
+    /// [`Registry::instantiate_description`].
+    pub fn with_templates(mut self, templates: &[(&'static str, &'static str)]) -> Registry {
+        self.templates.extend(templates.iter().copied());
+        self
     }
 
     /// Returns `InvalidErrorCode` if the code requested does not exist in the
@@ -22,4 +33,47 @@ pub fn try_find_description(
     ) -> Result<Option<&'static str>, InvalidErrorCode> {
         self.long_descriptions.get(code).copied().ok_or(InvalidErrorCode)
     }
+
+    /// Returns the explanation of `code` for a diagnostic with `args`: the template of the code,
+    /// with every `{name}` replaced by the argument `name`, followed by the generic explanation.
+    /// Returns `None` if the code has no template, or the diagnostic lacks one of its arguments.
+    pub fn instantiate_description(&self, code: &str, args: &[(String, String)]) -> Option<String> {
+        let template = self.templates.get(code)?;
+        let description = self.try_find_description(code).ok()??;
+        let mut text = String::new();
+        let mut rest = *template;
+        while let Some(start) = rest.find('{') {
+            let end = start + rest[start..].find('}')?;
+            let name = &rest[start + 1..end];
+            let (_, arg) = args.iter().find(|(arg_name, _)| arg_name == name)?;
+            text.push_str(&rest[..start]);
+            text.push_str(arg);
+            rest = &rest[end + 1..];
+        }
+        text.push_str(rest);
+        text.push('\n');
+        text.push_str(description);
+        Some(text)
+    }
+}
+
+/// Prepares an explanation for the terminal: removes the lines of its code blocks that rustdoc
+/// hides, the ones starting with `# `, and the attributes after the ``` of the code blocks.
+pub fn strip_hidden_lines(description: &str) -> String {
+    let mut is_in_code_block = false;
+    let mut text = String::new();
+    for line in description.lines() {
+        let indent_level = line.find(|c: char| !c.is_whitespace()).unwrap_or_else(|| line.len());
+        let dedented_line = &line[indent_level..];
+        if dedented_line.starts_with("```") {
+            is_in_code_block = !is_in_code_block;
+            text.push_str(&line[..(indent_level + 3)]);
+        } else if is_in_code_block && dedented_line.starts_with("# ") {
+            continue;
+        } else {
+            text.push_str(line);
+        }
+        text.push('\n');
+    }
+    text
 }
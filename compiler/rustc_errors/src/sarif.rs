@@ -0,0 +1,253 @@
+//! An emitter for SARIF 2.1.0, the Static Analysis Results Interchange Format, see
+//! <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>.
+//!
+//! A SARIF log is a single JSON document, so the emitter collects the diagnostics and writes the
+//! log when it is dropped. Every diagnostic becomes a result, with the error code or lint name as
+//! its rule, its primary spans as locations, and its other spans and the spans of its notes as
+//! related locations. Machine-applicable suggestions become fixes.
+
+use crate::emitter::Emitter;
+use crate::{CodeSuggestion, Diagnostic, DiagnosticId, Level, SubDiagnostic};
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::sync::Lrc;
+use rustc_lint_defs::Applicability;
+use rustc_serialize::json::{Json, Object};
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::Span;
+
+use std::io::{self, Write};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://www.rust-lang.org/";
+const ERROR_INDEX: &str = "https://doc.rust-lang.org/error-index.html";
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    sm: Lrc<SourceMap>,
+    rules: Vec<Json>,
+    rule_ids: FxHashSet<String>,
+    results: Vec<Json>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(source_map: Lrc<SourceMap>) -> SarifEmitter {
+        SarifEmitter::new(Box::new(io::BufWriter::new(io::stderr())), source_map)
+    }
+
+    pub fn basic() -> SarifEmitter {
+        SarifEmitter::stderr(Lrc::new(SourceMap::new(FilePathMapping::empty())))
+    }
+
+    pub fn new(dst: Box<dyn Write + Send>, source_map: Lrc<SourceMap>) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            sm: source_map,
+            rules: Vec::new(),
+            rule_ids: FxHashSet::default(),
+            results: Vec::new(),
+        }
+    }
+
+    fn result(&mut self, diag: &Diagnostic) -> Json {
+        let mut message = diag.message();
+        let mut locations = Vec::new();
+        let mut related_locations = Vec::new();
+        for span_label in diag.span.span_labels() {
+            if span_label.span.is_dummy() {
+                continue;
+            }
+            if span_label.is_primary {
+                locations.push(self.location(span_label.span, span_label.label));
+            } else if let Some(label) = span_label.label {
+                related_locations.push(self.location(span_label.span, Some(label)));
+            }
+        }
+        for child in &diag.children {
+            if child.span.primary_spans().is_empty() {
+                message.push_str(&format!("\n{}: {}", child.level, child.message()));
+            } else {
+                related_locations.extend(self.child_locations(child));
+            }
+        }
+
+        let mut result = Object::new();
+        if let Some(rule_id) = self.rule_id(&diag.code) {
+            result.insert("ruleId".to_owned(), Json::String(rule_id));
+        }
+        result.insert("level".to_owned(), Json::String(level(diag.level).to_owned()));
+        result.insert("message".to_owned(), text(message));
+        insert_array(&mut result, "locations", locations);
+        insert_array(&mut result, "relatedLocations", related_locations);
+        let fixes = diag.suggestions.iter().flat_map(|suggestion| self.fixes(suggestion));
+        insert_array(&mut result, "fixes", fixes.collect());
+        Json::Object(result)
+    }
+
+    /// Returns the rule of a diagnostic with `code`, adding it to the rules of the log the first
+    /// time.
+    fn rule_id(&mut self, code: &Option<DiagnosticId>) -> Option<String> {
+        let (id, help_uri) = match code.as_ref()? {
+            DiagnosticId::Error(code) => (code, Some(format!("{}#{}", ERROR_INDEX, code))),
+            DiagnosticId::Lint { name, .. } => (name, None),
+        };
+        if self.rule_ids.insert(id.clone()) {
+            let mut rule = Object::new();
+            rule.insert("id".to_owned(), Json::String(id.clone()));
+            if let Some(help_uri) = help_uri {
+                rule.insert("helpUri".to_owned(), Json::String(help_uri));
+            }
+            self.rules.push(Json::Object(rule));
+        }
+        Some(id.clone())
+    }
+
+    /// The locations of the spans of a note or help message, which describes its primary spans.
+    fn child_locations<'a>(&'a self, child: &'a SubDiagnostic) -> impl Iterator<Item = Json> + 'a {
+        child
+            .span
+            .span_labels()
+            .into_iter()
+            .filter(|span_label| !span_label.span.is_dummy())
+            .map(move |span_label| {
+                let label =
+                    if span_label.is_primary { Some(child.message()) } else { span_label.label };
+                self.location(span_label.span, label)
+            })
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> Json {
+        let mut location = Object::new();
+        location.insert("physicalLocation".to_owned(), self.physical_location(span));
+        if let Some(message) = message {
+            location.insert("message".to_owned(), text(message));
+        }
+        Json::Object(location)
+    }
+
+    fn physical_location(&self, span: Span) -> Json {
+        let mut physical_location = Object::new();
+        physical_location.insert("artifactLocation".to_owned(), self.artifact_location(span));
+        physical_location.insert("region".to_owned(), self.region(span));
+        Json::Object(physical_location)
+    }
+
+    fn artifact_location(&self, span: Span) -> Json {
+        let file = self.sm.lookup_source_file(span.lo());
+        let uri = file.name.prefer_local().to_string().replace('\\', "/");
+        let mut artifact_location = Object::new();
+        artifact_location.insert("uri".to_owned(), Json::String(uri));
+        Json::Object(artifact_location)
+    }
+
+    fn region(&self, span: Span) -> Json {
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        let mut region = Object::new();
+        region.insert("startLine".to_owned(), Json::U64(start.line as u64));
+        region.insert("startColumn".to_owned(), Json::U64(start.col.0 as u64 + 1));
+        region.insert("endLine".to_owned(), Json::U64(end.line as u64));
+        region.insert("endColumn".to_owned(), Json::U64(end.col.0 as u64 + 1));
+        region.insert("byteOffset".to_owned(), Json::U64(byte_start.into()));
+        region.insert("byteLength".to_owned(), Json::U64((byte_end - byte_start).into()));
+        Json::Object(region)
+    }
+
+    /// The fixes of a machine-applicable suggestion, one for every alternative it suggests.
+    fn fixes<'a>(&'a self, suggestion: &'a CodeSuggestion) -> impl Iterator<Item = Json> + 'a {
+        let substitutions = match suggestion.applicability {
+            Applicability::MachineApplicable => &suggestion.substitutions[..],
+            _ => &[],
+        };
+        substitutions.iter().map(move |substitution| {
+            let changes = substitution
+                .parts
+                .iter()
+                .map(|part| {
+                    let mut replacement = Object::new();
+                    replacement.insert("deletedRegion".to_owned(), self.region(part.span));
+                    replacement.insert("insertedContent".to_owned(), text(part.snippet.clone()));
+                    let mut change = Object::new();
+                    let artifact_location = self.artifact_location(part.span);
+                    change.insert("artifactLocation".to_owned(), artifact_location);
+                    let replacements = vec![Json::Object(replacement)];
+                    change.insert("replacements".to_owned(), Json::Array(replacements));
+                    Json::Object(change)
+                })
+                .collect();
+            let mut fix = Object::new();
+            fix.insert("description".to_owned(), text(suggestion.msg.clone()));
+            fix.insert("artifactChanges".to_owned(), Json::Array(changes));
+            Json::Object(fix)
+        })
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        if matches!(diag.level, Level::Cancelled | Level::FailureNote | Level::Allow) {
+            return;
+        }
+        let result = self.result(diag);
+        self.results.push(result);
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let mut driver = Object::new();
+        driver.insert("name".to_owned(), Json::String("rustc".to_owned()));
+        driver.insert("informationUri".to_owned(), Json::String(INFORMATION_URI.to_owned()));
+        driver.insert("rules".to_owned(), Json::Array(std::mem::take(&mut self.rules)));
+        let mut tool = Object::new();
+        tool.insert("driver".to_owned(), Json::Object(driver));
+
+        let mut run = Object::new();
+        run.insert("tool".to_owned(), Json::Object(tool));
+        run.insert("columnKind".to_owned(), Json::String("unicodeCodePoints".to_owned()));
+        run.insert("results".to_owned(), Json::Array(std::mem::take(&mut self.results)));
+
+        let mut log = Object::new();
+        log.insert("$schema".to_owned(), Json::String(SCHEMA.to_owned()));
+        log.insert("version".to_owned(), Json::String("2.1.0".to_owned()));
+        log.insert("runs".to_owned(), Json::Array(vec![Json::Object(run)]));
+
+        let result =
+            writeln!(&mut self.dst, "{}", Json::Object(log)).and_then(|_| self.dst.flush());
+        if let Err(e) = result {
+            panic!("failed to print diagnostics: {:?}", e);
+        }
+    }
+}
+
+fn level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::Fatal | Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Note | Level::Help | Level::Cancelled | Level::FailureNote | Level::Allow => "note",
+    }
+}
+
+/// A SARIF message with plain text.
+fn text(text: String) -> Json {
+    let mut message = Object::new();
+    message.insert("text".to_owned(), Json::String(text));
+    Json::Object(message)
+}
+
+/// Inserts the optional array `key` into `object`, unless it is empty.
+fn insert_array(object: &mut Object, key: &str, array: Vec<Json>) {
+    if !array.is_empty() {
+        object.insert(key.to_owned(), Json::Array(array));
+    }
+}
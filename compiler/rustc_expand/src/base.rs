@@ -1,5 +1,6 @@
 use crate::expand::{self, AstFragment, Invocation};
 use crate::module::DirOwnership;
+use crate::proc_macro_cache::ProcMacroCache;
 
 use rustc_ast::ptr::P;
 use rustc_ast::token::{self, Nonterminal};
@@ -22,6 +23,7 @@
 use rustc_span::{FileName, MultiSpan, Span, DUMMY_SP};
 use smallvec::{smallvec, SmallVec};
 
+use std::cell::RefCell;
 use std::default::Default;
 use std::iter;
 use std::path::PathBuf;
@@ -939,6 +941,8 @@ pub struct ExtCtxt<'a> {
     ///
     /// `Ident` is the module name.
     pub(super) extern_mod_loaded: OnExternModLoaded<'a>,
+    /// The outputs of proc macros reused between compilations, see `-Z proc-macro-cache`.
+    pub proc_macro_cache: Option<RefCell<ProcMacroCache>>,
 }
 
 impl<'a> ExtCtxt<'a> {
@@ -964,6 +968,7 @@ pub fn new(
             },
             force_mode: false,
             expansions: FxHashMap::default(),
+            proc_macro_cache: None,
         }
     }
 
@@ -25,6 +25,8 @@
 pub mod expand;
 pub mod module;
 pub mod proc_macro;
+pub mod proc_macro_cache;
+pub mod proc_macro_process;
 
 crate mod mbe;
 
@@ -1,10 +1,13 @@
 use crate::base::{self, *};
+use crate::proc_macro_cache::{self, Observed};
+use crate::proc_macro_process::ExternalProcMacro;
 use crate::proc_macro_server;
 
 use rustc_ast as ast;
 use rustc_ast::ptr::P;
 use rustc_ast::token;
 use rustc_ast::tokenstream::{CanSynthesizeMissingTokens, TokenStream, TokenTree};
+use rustc_data_structures::svh::Svh;
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::ErrorReported;
 use rustc_parse::nt_to_tokenstream;
@@ -14,9 +17,35 @@
 
 const EXEC_STRATEGY: pm::bridge::server::SameThread = pm::bridge::server::SameThread;
 
+/// Runs a proc macro of `krate` on `inputs` with `run`, unless `-Z proc-macro-cache` has its
+/// output cached. `crate_hash` is `None` for macros whose output is never cached.
+fn run_cached(
+    ecx: &ExtCtxt<'_>,
+    krate: CrateNum,
+    crate_hash: Option<Svh>,
+    inputs: &[&TokenStream],
+    run: impl FnOnce(proc_macro_server::Rustc<'_>) -> Result<TokenStream, pm::bridge::PanicMessage>,
+) -> Result<TokenStream, pm::bridge::PanicMessage> {
+    let pending = match crate_hash.map(|hash| proc_macro_cache::lookup(ecx, hash, inputs)) {
+        Some(Ok(output)) => return Ok(output),
+        Some(Err(pending)) => pending,
+        None => None,
+    };
+    let observed = Observed::default();
+    let result = run(proc_macro_server::Rustc::new(ecx, krate, &observed));
+    if let (Some(pending), Ok(output)) = (pending, &result) {
+        proc_macro_cache::insert(ecx, pending, output, observed);
+    }
+    result
+}
+
 pub struct BangProcMacro {
     pub client: pm::bridge::client::Client<fn(pm::TokenStream) -> pm::TokenStream>,
     pub krate: CrateNum,
+    /// Set if the macro runs in a separate process.
+    pub external: Option<ExternalProcMacro>,
+    /// The hash of the proc macro crate, `None` if the output of the macro is never cached.
+    pub crate_hash: Option<Svh>,
 }
 
 impl base::ProcMacro for BangProcMacro {
@@ -26,8 +55,17 @@ fn expand<'cx>(
         span: Span,
         input: TokenStream,
     ) -> Result<TokenStream, ErrorReported> {
-        let server = proc_macro_server::Rustc::new(ecx, self.krate);
-        self.client.run(&EXEC_STRATEGY, server, input, ecx.ecfg.proc_macro_backtrace).map_err(|e| {
+        let proc_macro_backtrace = ecx.ecfg.proc_macro_backtrace;
+        let result = run_cached(ecx, self.krate, self.crate_hash, &[&input], |server| {
+            let input = input.clone();
+            match &self.external {
+                Some(external) => {
+                    self.client.run(&external.strategy(), server, input, proc_macro_backtrace)
+                }
+                None => self.client.run(&EXEC_STRATEGY, server, input, proc_macro_backtrace),
+            }
+        });
+        result.map_err(|e| {
             let mut err = ecx.struct_span_err(span, "proc macro panicked");
             if let Some(s) = e.as_str() {
                 err.help(&format!("message: {}", s));
@@ -41,6 +79,10 @@ fn expand<'cx>(
 pub struct AttrProcMacro {
     pub client: pm::bridge::client::Client<fn(pm::TokenStream, pm::TokenStream) -> pm::TokenStream>,
     pub krate: CrateNum,
+    /// Set if the macro runs in a separate process.
+    pub external: Option<ExternalProcMacro>,
+    /// The hash of the proc macro crate, `None` if the output of the macro is never cached.
+    pub crate_hash: Option<Svh>,
 }
 
 impl base::AttrProcMacro for AttrProcMacro {
@@ -51,23 +93,45 @@ fn expand<'cx>(
         annotation: TokenStream,
         annotated: TokenStream,
     ) -> Result<TokenStream, ErrorReported> {
-        let server = proc_macro_server::Rustc::new(ecx, self.krate);
-        self.client
-            .run(&EXEC_STRATEGY, server, annotation, annotated, ecx.ecfg.proc_macro_backtrace)
-            .map_err(|e| {
-                let mut err = ecx.struct_span_err(span, "custom attribute panicked");
-                if let Some(s) = e.as_str() {
-                    err.help(&format!("message: {}", s));
-                }
-                err.emit();
-                ErrorReported
-            })
+        let proc_macro_backtrace = ecx.ecfg.proc_macro_backtrace;
+        let inputs = [&annotation, &annotated];
+        let result = run_cached(ecx, self.krate, self.crate_hash, &inputs, |server| {
+            let (annotation, annotated) = (annotation.clone(), annotated.clone());
+            match &self.external {
+                Some(external) => self.client.run(
+                    &external.strategy(),
+                    server,
+                    annotation,
+                    annotated,
+                    proc_macro_backtrace,
+                ),
+                None => self.client.run(
+                    &EXEC_STRATEGY,
+                    server,
+                    annotation,
+                    annotated,
+                    proc_macro_backtrace,
+                ),
+            }
+        });
+        result.map_err(|e| {
+            let mut err = ecx.struct_span_err(span, "custom attribute panicked");
+            if let Some(s) = e.as_str() {
+                err.help(&format!("message: {}", s));
+            }
+            err.emit();
+            ErrorReported
+        })
     }
 }
 
 pub struct ProcMacroDerive {
     pub client: pm::bridge::client::Client<fn(pm::TokenStream) -> pm::TokenStream>,
     pub krate: CrateNum,
+    /// Set if the macro runs in a separate process.
+    pub external: Option<ExternalProcMacro>,
+    /// The hash of the proc macro crate, `None` if the output of the macro is never cached.
+    pub crate_hash: Option<Svh>,
 }
 
 impl MultiItemModifier for ProcMacroDerive {
@@ -101,19 +165,27 @@ fn expand(
             nt_to_tokenstream(&item, &ecx.sess.parse_sess, CanSynthesizeMissingTokens::No)
         };
 
-        let server = proc_macro_server::Rustc::new(ecx, self.krate);
-        let stream =
-            match self.client.run(&EXEC_STRATEGY, server, input, ecx.ecfg.proc_macro_backtrace) {
-                Ok(stream) => stream,
-                Err(e) => {
-                    let mut err = ecx.struct_span_err(span, "proc-macro derive panicked");
-                    if let Some(s) = e.as_str() {
-                        err.help(&format!("message: {}", s));
-                    }
-                    err.emit();
-                    return ExpandResult::Ready(vec![]);
+        let proc_macro_backtrace = ecx.ecfg.proc_macro_backtrace;
+        let result = run_cached(ecx, self.krate, self.crate_hash, &[&input], |server| {
+            let input = input.clone();
+            match &self.external {
+                Some(external) => {
+                    self.client.run(&external.strategy(), server, input, proc_macro_backtrace)
+                }
+                None => self.client.run(&EXEC_STRATEGY, server, input, proc_macro_backtrace),
+            }
+        });
+        let stream = match result {
+            Ok(stream) => stream,
+            Err(e) => {
+                let mut err = ecx.struct_span_err(span, "proc-macro derive panicked");
+                if let Some(s) = e.as_str() {
+                    err.help(&format!("message: {}", s));
                 }
-            };
+                err.emit();
+                return ExpandResult::Ready(vec![]);
+            }
+        };
 
         let error_count_before = ecx.sess.parse_sess.span_diagnostic.err_count();
         let mut parser =
@@ -0,0 +1,270 @@
+//! Reusing the output of proc macros between incremental compilations, see `-Z proc-macro-cache`.
+//!
+//! An expansion is identified by the hash of the proc macro crate, the kind and name of the macro,
+//! and its input tokens without their spans. Its output is cached with every span replaced by a
+//! reference to the input token it was taken from, or to the call site, definition site or mixed
+//! site span of the expansion, so a cached output gets the spans of the new input and expansion.
+//!
+//! Expansions with other spans in their output aren't cached. Neither are those of macros that
+//! looked at the source code behind their spans, emitted diagnostics, or are marked with
+//! `#[proc_macro_impure]`, which macros that read files or untracked environment variables need.
+//! Environment variables read through `proc_macro::tracked_env` are cached with the output, which
+//! is only reused while they keep their values.
+
+use crate::base::ExtCtxt;
+use crate::proc_macro_server::Sites;
+
+use rustc_ast::token::{DelimToken, Token, TokenKind};
+use rustc_ast::tokenstream::{DelimSpan, Spacing, TokenStream, TokenTree};
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_data_structures::svh::Svh;
+use rustc_serialize::{opaque, Decodable, Encodable};
+use rustc_session::Session;
+use rustc_span::hygiene::ExpnKind;
+use rustc_span::{Span, Symbol};
+
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::fs;
+use std::hash::Hash;
+use std::io;
+
+const FILE_NAME: &str = "proc-macro-cache.bin";
+const FILE_MAGIC: &[u8] = b"RSPC";
+
+/// The expansions of the previous and the current compilation.
+pub struct ProcMacroCache {
+    previous: FxHashMap<Fingerprint, Entry>,
+    /// The expansions this compilation found or added, which are saved for the next one.
+    current: FxHashMap<Fingerprint, Entry>,
+}
+
+#[derive(Encodable, Decodable)]
+struct Entry {
+    env: Vec<(String, Option<String>)>,
+    output: Vec<CachedTree>,
+}
+
+#[derive(Encodable, Decodable)]
+enum CachedTree {
+    Token(TokenKind, SpanRef, Spacing),
+    Delimited(SpanRef, SpanRef, DelimToken, Spacing, Vec<CachedTree>),
+}
+
+#[derive(Clone, Copy, Encodable, Decodable)]
+enum SpanRef {
+    CallSite,
+    DefSite,
+    MixedSite,
+    /// The span of the input token with this index, counting the open and close delimiters of
+    /// groups as separate tokens.
+    Input(u32),
+}
+
+/// What a proc macro did during an expansion, besides turning its input into its output.
+#[derive(Default)]
+crate struct Observed {
+    uncacheable: Cell<bool>,
+    env: RefCell<Vec<(String, Option<String>)>>,
+}
+
+impl Observed {
+    /// Keeps the expansion from being cached, its output depends on more than its input.
+    crate fn mark_uncacheable(&self) {
+        self.uncacheable.set(true);
+    }
+
+    crate fn track_env_var(&self, var: &str, value: Option<&str>) {
+        self.env.borrow_mut().push((var.to_owned(), value.map(str::to_owned)));
+    }
+}
+
+/// An expansion that isn't cached yet. Its output can be cached with [`insert`].
+crate struct Pending {
+    key: Fingerprint,
+    input_spans: Vec<Span>,
+}
+
+impl ProcMacroCache {
+    /// Loads the expansions cached by the previous compilation, if `-Z proc-macro-cache` and
+    /// incremental compilation are enabled.
+    pub fn load(sess: &Session) -> Option<ProcMacroCache> {
+        if !sess.opts.debugging_opts.proc_macro_cache {
+            return None;
+        }
+        let path = sess.incr_comp_session_dir_opt()?.join(FILE_NAME);
+        let previous = match fs::read(&path) {
+            Ok(data) => decode_file(&data).unwrap_or_default(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => FxHashMap::default(),
+            Err(err) => {
+                sess.warn(&format!("failed to read the proc macro cache: {}", err));
+                FxHashMap::default()
+            }
+        };
+        Some(ProcMacroCache { previous, current: FxHashMap::default() })
+    }
+
+    /// Saves the expansions of this compilation for the next one.
+    pub fn save(&self, sess: &Session) {
+        let path = sess.incr_comp_session_dir().join(FILE_NAME);
+        let mut encoder = opaque::Encoder::new(FILE_MAGIC.to_vec());
+        rustc_version().encode(&mut encoder).unwrap();
+        self.current.encode(&mut encoder).unwrap();
+
+        // The file may be a hard link to the one of the previous compilation, which has to stay
+        // as it is.
+        let result = match fs::remove_file(&path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => fs::write(&path, encoder.into_inner()),
+        };
+        if let Err(err) = result {
+            sess.warn(&format!("failed to write the proc macro cache: {}", err));
+        }
+    }
+}
+
+fn decode_file(data: &[u8]) -> Option<FxHashMap<Fingerprint, Entry>> {
+    if !data.starts_with(FILE_MAGIC) {
+        return None;
+    }
+    let mut decoder = opaque::Decoder::new(data, FILE_MAGIC.len());
+    if String::decode(&mut decoder).ok()? != rustc_version() {
+        return None;
+    }
+    Decodable::decode(&mut decoder).ok()
+}
+
+fn rustc_version() -> &'static str {
+    option_env!("CFG_VERSION").unwrap_or("unknown version")
+}
+
+/// Returns the cached output of the current expansion of `ecx`, which runs a macro of the crate
+/// with `crate_hash` on `inputs`. If there is none, returns what [`insert`] needs to cache the
+/// output of the macro, unless the expansion can't be cached.
+crate fn lookup(
+    ecx: &ExtCtxt<'_>,
+    crate_hash: Svh,
+    inputs: &[&TokenStream],
+) -> Result<TokenStream, Option<Pending>> {
+    let cache = match &ecx.proc_macro_cache {
+        Some(cache) => cache,
+        None => return Err(None),
+    };
+    let (kind, name) = match ecx.current_expansion.id.expn_data().kind {
+        ExpnKind::Macro { kind, name, .. } => (kind, name),
+        _ => return Err(None),
+    };
+
+    let mut input_spans = Vec::new();
+    let mut span_ref = |span| {
+        input_spans.push(span);
+        Some(SpanRef::Input(input_spans.len() as u32 - 1))
+    };
+    let inputs = match inputs.iter().map(|input| to_cached(input, &mut span_ref)).collect() {
+        Some(inputs) => inputs,
+        None => return Err(None),
+    };
+    let mut encoder = opaque::Encoder::new(Vec::new());
+    Vec::<Vec<CachedTree>>::encode(&inputs, &mut encoder).unwrap();
+
+    let mut hasher = StableHasher::new();
+    crate_hash.as_u64().hash(&mut hasher);
+    kind.descr().hash(&mut hasher);
+    name.as_str().hash(&mut hasher);
+    encoder.into_inner().hash(&mut hasher);
+    let key: Fingerprint = hasher.finish();
+
+    let mut cache = cache.borrow_mut();
+    if let Some(entry) = cache.previous.remove(&key) {
+        cache.current.insert(key, entry);
+    }
+    let output = cache.current.get(&key).and_then(|entry| {
+        if entry.env.iter().any(|(var, value)| env::var(var).ok() != *value) {
+            return None;
+        }
+        let sites = Sites::new(ecx);
+        let output = from_cached(&entry.output, &|span_ref| match span_ref {
+            SpanRef::CallSite => Some(sites.call_site),
+            SpanRef::DefSite => Some(sites.def_site),
+            SpanRef::MixedSite => Some(sites.mixed_site),
+            SpanRef::Input(index) => input_spans.get(index as usize).copied(),
+        })?;
+        let mut env_depinfo = ecx.sess.parse_sess.env_depinfo.borrow_mut();
+        for (var, value) in &entry.env {
+            env_depinfo.insert((Symbol::intern(var), value.as_deref().map(Symbol::intern)));
+        }
+        Some(output)
+    });
+    output.ok_or(Some(Pending { key, input_spans }))
+}
+
+/// Caches `output` as the output of `pending`, unless the macro did something `observed` that its
+/// output could depend on, or the output has spans that can't be cached.
+crate fn insert(ecx: &ExtCtxt<'_>, pending: Pending, output: &TokenStream, observed: Observed) {
+    if observed.uncacheable.get() {
+        return;
+    }
+    let mut input_indices = FxHashMap::default();
+    for (index, &span) in pending.input_spans.iter().enumerate() {
+        input_indices.entry(span).or_insert(index as u32);
+    }
+    let sites = Sites::new(ecx);
+    let mut span_ref = |span| {
+        if span == sites.call_site {
+            Some(SpanRef::CallSite)
+        } else if span == sites.def_site {
+            Some(SpanRef::DefSite)
+        } else if span == sites.mixed_site {
+            Some(SpanRef::MixedSite)
+        } else {
+            input_indices.get(&span).map(|&index| SpanRef::Input(index))
+        }
+    };
+    if let Some(output) = to_cached(output, &mut span_ref) {
+        let entry = Entry { env: observed.env.into_inner(), output };
+        ecx.proc_macro_cache.as_ref().unwrap().borrow_mut().current.insert(pending.key, entry);
+    }
+}
+
+fn to_cached(
+    stream: &TokenStream,
+    span_ref: &mut impl FnMut(Span) -> Option<SpanRef>,
+) -> Option<Vec<CachedTree>> {
+    let mut trees = stream.trees();
+    let mut cached = Vec::with_capacity(stream.len());
+    while let Some((tree, spacing)) = trees.next_with_spacing() {
+        cached.push(match tree {
+            TokenTree::Token(Token { kind: TokenKind::Interpolated(_), .. }) => return None,
+            TokenTree::Token(token) => {
+                CachedTree::Token(token.kind, span_ref(token.span)?, spacing)
+            }
+            TokenTree::Delimited(span, delim, stream) => {
+                let (open, close) = (span_ref(span.open)?, span_ref(span.close)?);
+                CachedTree::Delimited(open, close, delim, spacing, to_cached(&stream, span_ref)?)
+            }
+        });
+    }
+    Some(cached)
+}
+
+fn from_cached(
+    trees: &[CachedTree],
+    span: &impl Fn(SpanRef) -> Option<Span>,
+) -> Option<TokenStream> {
+    let trees = trees
+        .iter()
+        .map(|tree| match tree {
+            CachedTree::Token(kind, span_ref, spacing) => {
+                Some((TokenTree::token(kind.clone(), span(*span_ref)?), *spacing))
+            }
+            CachedTree::Delimited(open, close, delim, spacing, trees) => {
+                let delim_span = DelimSpan::from_pair(span(*open)?, span(*close)?);
+                let stream = from_cached(trees, span)?;
+                Some((TokenTree::Delimited(delim_span, *delim, stream), *spacing))
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(TokenStream::new(trees))
+}
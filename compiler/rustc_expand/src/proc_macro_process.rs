@@ -0,0 +1,237 @@
+//! Running proc macros in a separate process, see `-Z proc-macro-execution=external`.
+//!
+//! The compiler still loads proc macro crates to find their macros, but expands the macros by
+//! asking a copy of itself that was started with [`SERVER_ENV`] set. That process loads the crate
+//! again and runs its macros with `ProcMacro::run_remote`, sending their requests to the compiler
+//! and reading the answers over its standard input and output. If it crashes or runs out of
+//! memory, the macro being expanded fails like one that panicked, and the next expansion starts a
+//! new process.
+
+use pm::bridge::client::ProcMacro;
+use pm::bridge::server::{client_panicked, Remote};
+use pm::bridge::Buffer;
+use rustc_data_structures::sync::{Lock, Lrc};
+
+use std::convert::TryFrom;
+use std::env;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// Set to the path of the proc macro crate in the environment of the proc macro process.
+pub const SERVER_ENV: &str = "RUSTC_PROC_MACRO_SERVER";
+/// Set to the symbol of the proc macro declarations in that crate.
+const DECLS_ENV: &str = "RUSTC_PROC_MACRO_DECLS";
+/// Set to the memory limit of the process in MiB, if there is one.
+const MEMORY_LIMIT_ENV: &str = "RUSTC_PROC_MACRO_MEMORY_LIMIT";
+
+// The tags of the messages the compiler sends.
+const EXPAND: u8 = 0;
+const RESPONSE: u8 = 1;
+
+// The tags of the messages the proc macro process sends.
+const REQUEST: u8 = 0;
+const DONE: u8 = 1;
+
+/// The proc macro crate a proc macro process runs the macros of.
+pub struct ServerConfig {
+    pub dylib: PathBuf,
+    pub decls_symbol: String,
+    pub memory_limit: Option<usize>,
+}
+
+impl ServerConfig {
+    /// Returns the configuration of this process if it was started as a proc macro process. The
+    /// variables are removed from the environment, so that processes started by the proc macros
+    /// don't inherit them.
+    pub fn from_env() -> Option<ServerConfig> {
+        let dylib = env::var_os(SERVER_ENV)?.into();
+        let decls_symbol = env::var(DECLS_ENV).ok()?;
+        let memory_limit = env::var(MEMORY_LIMIT_ENV).ok().and_then(|limit| limit.parse().ok());
+        for var in &[SERVER_ENV, DECLS_ENV, MEMORY_LIMIT_ENV] {
+            env::remove_var(var);
+        }
+        Some(ServerConfig { dylib, decls_symbol, memory_limit })
+    }
+}
+
+/// A separate process that runs the macros of one proc macro crate. It is started by the first
+/// expansion.
+pub struct ProcMacroProcess {
+    config: ServerConfig,
+    connection: Lock<Option<Connection>>,
+}
+
+struct Connection {
+    child: Child,
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl ProcMacroProcess {
+    pub fn new(config: ServerConfig) -> ProcMacroProcess {
+        ProcMacroProcess { config, connection: Lock::new(None) }
+    }
+
+    /// Expands the macro at `index` in the declarations of the crate, passing the requests of the
+    /// macro to `dispatch`. If the process fails, the macro is reported as having panicked.
+    fn expand(
+        &self,
+        index: usize,
+        input: Buffer<u8>,
+        force_show_panics: bool,
+        dispatch: &mut dyn FnMut(Buffer<u8>) -> Buffer<u8>,
+    ) -> Buffer<u8> {
+        let mut connection = self.connection.lock();
+        let output: io::Result<_> = try {
+            if connection.is_none() {
+                *connection = Some(self.spawn()?);
+            }
+            connection.as_mut().unwrap().expand(index, input, force_show_panics, dispatch)?
+        };
+        output.unwrap_or_else(|err| {
+            // The process is in an unknown state, the next expansion starts a new one.
+            let message = match connection.take() {
+                Some(connection) => connection.shut_down(err),
+                None => format!("failed to start the proc macro process: {}", err),
+            };
+            client_panicked(message)
+        })
+    }
+
+    fn spawn(&self) -> io::Result<Connection> {
+        let mut command = Command::new(env::current_exe()?);
+        command
+            .env(SERVER_ENV, &self.config.dylib)
+            .env(DECLS_ENV, &self.config.decls_symbol)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped());
+        if let Some(limit) = self.config.memory_limit {
+            command.env(MEMORY_LIMIT_ENV, limit.to_string());
+        }
+        let mut child = command.spawn()?;
+        let stdin = BufWriter::new(child.stdin.take().unwrap());
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Ok(Connection { child, stdin, stdout })
+    }
+}
+
+impl Connection {
+    fn expand(
+        &mut self,
+        index: usize,
+        input: Buffer<u8>,
+        force_show_panics: bool,
+        dispatch: &mut dyn FnMut(Buffer<u8>) -> Buffer<u8>,
+    ) -> io::Result<Buffer<u8>> {
+        let mut message = Vec::with_capacity(5 + input.len());
+        message.extend_from_slice(&(index as u32).to_le_bytes());
+        message.push(force_show_panics as u8);
+        message.extend_from_slice(&input);
+        write_message(&mut self.stdin, EXPAND, &message)?;
+        loop {
+            match read_message(&mut self.stdout)? {
+                (REQUEST, request) => {
+                    let response = dispatch(request.into());
+                    write_message(&mut self.stdin, RESPONSE, &response)?;
+                }
+                (DONE, output) => return Ok(output.into()),
+                (tag, _) => return Err(unexpected_message(tag)),
+            }
+        }
+    }
+
+    /// Stops the process after `err` and describes what went wrong.
+    fn shut_down(mut self, err: io::Error) -> String {
+        // A closed pipe means the process exited, otherwise it still runs and has to be stopped.
+        let exited = matches!(err.kind(), io::ErrorKind::UnexpectedEof | io::ErrorKind::BrokenPipe);
+        if !exited {
+            let _ = self.child.kill();
+        }
+        match self.child.wait() {
+            Ok(status) if exited => {
+                format!("the proc macro process exited unexpectedly: {}", status)
+            }
+            _ => format!("lost the connection to the proc macro process: {}", err),
+        }
+    }
+}
+
+/// A proc macro that runs in a [`ProcMacroProcess`], with its index in the declarations of the
+/// crate.
+pub struct ExternalProcMacro {
+    pub process: Lrc<ProcMacroProcess>,
+    pub index: usize,
+}
+
+impl ExternalProcMacro {
+    /// The execution strategy that expands this macro in the process.
+    crate fn strategy(
+        &self,
+    ) -> Remote<
+        impl Fn(Buffer<u8>, bool, &mut dyn FnMut(Buffer<u8>) -> Buffer<u8>) -> Buffer<u8> + '_,
+    > {
+        Remote(
+            move |input: Buffer<u8>,
+                  force_show_panics: bool,
+                  dispatch: &mut dyn FnMut(Buffer<u8>) -> Buffer<u8>| {
+                self.process.expand(self.index, input, force_show_panics, dispatch)
+            },
+        )
+    }
+}
+
+/// The main loop of the proc macro process: expands the macros in `macros` that the compiler asks
+/// for on `input` until the compiler closes it.
+pub fn serve(macros: &[ProcMacro], input: impl Read, output: impl Write) -> io::Result<()> {
+    let (mut input, mut output) = (BufReader::new(input), BufWriter::new(output));
+    loop {
+        let message = match read_message(&mut input) {
+            Ok((EXPAND, message)) if message.len() >= 5 => message,
+            Ok((tag, _)) => return Err(unexpected_message(tag)),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        let index = u32::from_le_bytes([message[0], message[1], message[2], message[3]]);
+        let proc_macro = macros.get(index as usize).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("no proc macro at index {}", index))
+        })?;
+        let force_show_panics = message[4] != 0;
+
+        let dispatch = |request: Buffer<u8>| -> Buffer<u8> {
+            let response = write_message(&mut output, REQUEST, &request)
+                .and_then(|()| read_message(&mut input));
+            match response {
+                Ok((RESPONSE, response)) => response.into(),
+                // Without the compiler, there is nobody left to expand the macro for.
+                _ => process::exit(1),
+            }
+        };
+        let macro_input = message[5..].to_vec().into();
+        let result = proc_macro.run_remote(macro_input, dispatch, force_show_panics);
+        write_message(&mut output, DONE, &result)?;
+    }
+}
+
+/// Writes a message: the length of `data` as a little-endian `u32`, `tag`, and `data`.
+fn write_message(w: &mut impl Write, tag: u8, data: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(data.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "message too large"))?;
+    w.write_all(&len.to_le_bytes())?;
+    w.write_all(&[tag])?;
+    w.write_all(data)?;
+    w.flush()
+}
+
+fn read_message(r: &mut impl Read) -> io::Result<(u8, Vec<u8>)> {
+    let mut header = [0; 5];
+    r.read_exact(&mut header)?;
+    let len = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let mut data = vec![0; len as usize];
+    r.read_exact(&mut data)?;
+    Ok((header[4], data))
+}
+
+fn unexpected_message(tag: u8) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("unexpected message with tag {}", tag))
+}
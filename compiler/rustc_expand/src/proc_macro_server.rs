@@ -1,4 +1,5 @@
 use crate::base::{ExtCtxt, ResolverExpand};
+use crate::proc_macro_cache::Observed;
 
 use rustc_ast as ast;
 use rustc_ast::token::{self, Nonterminal, NtIdent, TokenKind};
@@ -355,9 +356,28 @@ pub struct Literal {
     span: Span,
 }
 
+/// The spans `proc_macro::Span::{def_site, call_site, mixed_site}` return in an expansion.
+crate struct Sites {
+    crate def_site: Span,
+    crate call_site: Span,
+    crate mixed_site: Span,
+}
+
+impl Sites {
+    crate fn new(cx: &ExtCtxt<'_>) -> Sites {
+        let expn_data = cx.current_expansion.id.expn_data();
+        Sites {
+            def_site: cx.with_def_site_ctxt(expn_data.def_site),
+            call_site: cx.with_call_site_ctxt(expn_data.call_site),
+            mixed_site: cx.with_mixed_site_ctxt(expn_data.call_site),
+        }
+    }
+}
+
 pub(crate) struct Rustc<'a> {
     resolver: &'a dyn ResolverExpand,
     sess: &'a ParseSess,
+    observed: &'a Observed,
     def_site: Span,
     call_site: Span,
     mixed_site: Span,
@@ -368,15 +388,13 @@ pub(crate) struct Rustc<'a> {
 }
 
 impl<'a> Rustc<'a> {
-    pub fn new(cx: &'a ExtCtxt<'_>, krate: CrateNum) -> Self {
-        let expn_data = cx.current_expansion.id.expn_data();
-        let def_site = cx.with_def_site_ctxt(expn_data.def_site);
-        let call_site = cx.with_call_site_ctxt(expn_data.call_site);
-        let mixed_site = cx.with_mixed_site_ctxt(expn_data.call_site);
+    pub fn new(cx: &'a ExtCtxt<'_>, krate: CrateNum, observed: &'a Observed) -> Self {
+        let Sites { def_site, call_site, mixed_site } = Sites::new(cx);
         let sess = cx.parse_sess();
         Rustc {
             resolver: cx.resolver,
             sess,
+            observed,
             def_site,
             call_site,
             mixed_site,
@@ -409,6 +427,7 @@ impl server::Types for Rustc<'_> {
 
 impl server::FreeFunctions for Rustc<'_> {
     fn track_env_var(&mut self, var: &str, value: Option<&str>) {
+        self.observed.track_env_var(var, value);
         self.sess.env_depinfo.borrow_mut().insert((Symbol::intern(var), value.map(Symbol::intern)));
     }
 }
@@ -623,6 +642,7 @@ fn subspan(
         start: Bound<usize>,
         end: Bound<usize>,
     ) -> Option<Self::Span> {
+        self.observed.mark_uncacheable();
         let span = literal.span;
         let length = span.hi().to_usize() - span.lo().to_usize();
 
@@ -700,12 +720,14 @@ fn sub(
         diag.sub(level.to_internal(), msg, MultiSpan::from_spans(spans), None);
     }
     fn emit(&mut self, diag: Self::Diagnostic) {
+        self.observed.mark_uncacheable();
         self.sess.span_diagnostic.emit_diagnostic(&diag);
     }
 }
 
 impl server::Span for Rustc<'_> {
     fn debug(&mut self, span: Self::Span) -> String {
+        self.observed.mark_uncacheable();
         if self.span_debug {
             format!("{:?}", span)
         } else {
@@ -722,23 +744,29 @@ fn mixed_site(&mut self) -> Self::Span {
         self.mixed_site
     }
     fn source_file(&mut self, span: Self::Span) -> Self::SourceFile {
+        self.observed.mark_uncacheable();
         self.sess.source_map().lookup_char_pos(span.lo()).file
     }
     fn parent(&mut self, span: Self::Span) -> Option<Self::Span> {
+        self.observed.mark_uncacheable();
         span.parent()
     }
     fn source(&mut self, span: Self::Span) -> Self::Span {
+        self.observed.mark_uncacheable();
         span.source_callsite()
     }
     fn start(&mut self, span: Self::Span) -> LineColumn {
+        self.observed.mark_uncacheable();
         let loc = self.sess.source_map().lookup_char_pos(span.lo());
         LineColumn { line: loc.line, column: loc.col.to_usize() }
     }
     fn end(&mut self, span: Self::Span) -> LineColumn {
+        self.observed.mark_uncacheable();
         let loc = self.sess.source_map().lookup_char_pos(span.hi());
         LineColumn { line: loc.line, column: loc.col.to_usize() }
     }
     fn join(&mut self, first: Self::Span, second: Self::Span) -> Option<Self::Span> {
+        self.observed.mark_uncacheable();
         let self_loc = self.sess.source_map().lookup_char_pos(first.lo());
         let other_loc = self.sess.source_map().lookup_char_pos(second.lo());
 
@@ -752,6 +780,7 @@ fn resolved_at(&mut self, span: Self::Span, at: Self::Span) -> Self::Span {
         span.with_ctxt(at.ctxt())
     }
     fn source_text(&mut self, span: Self::Span) -> Option<String> {
+        self.observed.mark_uncacheable();
         self.sess.source_map().span_to_snippet(span).ok()
     }
     /// Saves the provided span into the metadata of
@@ -779,6 +808,7 @@ fn source_text(&mut self, span: Self::Span) -> Option<String> {
     /// since we've loaded `my_proc_macro` from disk in order to execute it).
     /// In this way, we have obtained a span pointing into `my_proc_macro`
     fn save_span(&mut self, mut span: Self::Span) -> usize {
+        self.observed.mark_uncacheable();
         // Throw away the `SyntaxContext`, since we currently
         // skip serializing `SyntaxContext`s for proc-macro crates
         span = span.with_ctxt(rustc_span::SyntaxContext::root());
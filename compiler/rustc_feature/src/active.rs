@@ -666,6 +666,12 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows qualified paths in struct expressions, struct patterns and tuple struct patterns.
     (active, more_qualified_paths, "1.54.0", Some(80080), None),
 
+    /// Allows guaranteed tail calls with `become`.
+    (active, explicit_tail_calls, "1.54.0", None, None),
+
+    /// Allows `#[proc_macro_impure]`, which keeps the expansions of a proc macro from being cached.
+    (active, proc_macro_impure, "1.54.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -694,6 +700,7 @@ pub fn set(&self, features: &mut Features, span: Span) {
     sym::type_alias_impl_trait,
     sym::rustc_insignificant_dtor,
     sym::unnamed_fields,
+    sym::explicit_tail_calls,
 ];
 
 /// Some features are not allowed to be used together at the same time, if
@@ -238,7 +238,7 @@ macro_rules! experimental {
     ungated!(recursion_limit, CrateLevel, template!(NameValueStr: "N")),
     ungated!(type_length_limit, CrateLevel, template!(NameValueStr: "N")),
     gated!(
-        const_eval_limit, CrateLevel, template!(NameValueStr: "N"), const_eval_limit,
+        const_eval_limit, AssumedUsed, template!(NameValueStr: "N"), const_eval_limit,
         experimental!(const_eval_limit)
     ),
     gated!(
@@ -315,6 +315,9 @@ macro_rules! experimental {
         )
     ),
 
+    // Proc macros:
+    gated!(proc_macro_impure, AssumedUsed, template!(Word), experimental!(proc_macro_impure)),
+
     // Testing:
     gated!(allow_fail, Normal, template!(Word), experimental!(allow_fail)),
     gated!(
@@ -1207,6 +1207,7 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr<'v>)
         ExprKind::Ret(ref optional_expression) => {
             walk_list!(visitor, visit_expr, optional_expression);
         }
+        ExprKind::Become(ref expression) => visitor.visit_expr(expression),
         ExprKind::InlineAsm(ref asm) => {
             walk_inline_asm(visitor, asm);
         }
@@ -1181,7 +1181,10 @@ pub fn print_expr_as_cond(&mut self, expr: &hir::Expr<'_>) {
         let needs_par = match expr.kind {
             // These cases need parens due to the parse error observed in #26461: `if return {}`
             // parses as the erroneous construct `if (return {})`, not `if (return) {}`.
-            hir::ExprKind::Closure(..) | hir::ExprKind::Ret(..) | hir::ExprKind::Break(..) => true,
+            hir::ExprKind::Closure(..)
+            | hir::ExprKind::Ret(..)
+            | hir::ExprKind::Become(..)
+            | hir::ExprKind::Break(..) => true,
 
             _ => contains_exterior_struct_lit(expr),
         };
@@ -1631,6 +1634,11 @@ pub fn print_expr(&mut self, expr: &hir::Expr<'_>) {
                     self.print_expr_maybe_paren(&expr, parser::PREC_JUMP);
                 }
             }
+            hir::ExprKind::Become(ref result) => {
+                self.s.word("become");
+                self.s.word(" ");
+                self.print_expr_maybe_paren(&result, parser::PREC_JUMP);
+            }
             hir::ExprKind::InlineAsm(ref asm) => {
                 self.word("asm!");
                 self.print_inline_asm(asm);
@@ -77,6 +77,7 @@
 use std::{cmp, fmt, iter};
 
 mod note;
+mod type_diff;
 
 mod need_type_info;
 pub use need_type_info::TypeAnnotationNeeded;
@@ -1636,14 +1637,26 @@ enum Mismatch<'a> {
                             }
                         }))
                     {
-                        diag.note_expected_found_extra(
-                            &expected_label,
-                            expected,
-                            &found_label,
-                            found,
-                            &sort_string(values.expected),
-                            &sort_string(values.found),
-                        );
+                        // Long types are easier to compare as a tree of their differences.
+                        let is_long = expected.content().len().max(found.content().len())
+                            > type_diff::TYPE_DIFF_MIN_LEN;
+                        let noted_diff = match exp_found {
+                            Some(ef) if is_long && !extra => {
+                                let ef = self.resolve_vars_if_possible(ef);
+                                self.note_type_diff(diag, ef.expected, ef.found)
+                            }
+                            _ => false,
+                        };
+                        if !noted_diff {
+                            diag.note_expected_found_extra(
+                                &expected_label,
+                                expected,
+                                &found_label,
+                                found,
+                                &sort_string(values.expected),
+                                &sort_string(values.found),
+                            );
+                        }
                     }
                 }
                 (TypeError::ObjectUnsafeCoercion(_), _) => {
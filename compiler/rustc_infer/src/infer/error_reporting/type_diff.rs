@@ -0,0 +1,133 @@
+//! Tree-shaped diffs of the expected and found types of a type mismatch. When the types are too
+//! long to compare on two lines, `note_type_err` shows what they have in common once, with every
+//! generic argument on its own line, and both versions of only the parts that differ:
+//!
+//! ```text
+//! = note: the expected and found types differ here:
+//!         Wrapper<
+//!             Vec<(
+//!                 _,
+//!                 Option<Box<
+//!                     expected `dyn Fn(u8) -> u8`
+//!                        found `dyn Fn(u8) -> u16`,
+//!                 >>,
+//!             )>,
+//!             _,
+//!         >
+//! ```
+
+use crate::infer::InferCtxt;
+use rustc_errors::{DiagnosticBuilder, DiagnosticStyledString};
+use rustc_middle::ty::{self, Ty};
+
+/// Types are compared as a tree if one of them is longer than this on one line.
+pub(super) const TYPE_DIFF_MIN_LEN: usize = 120;
+
+const INDENT: &str = "    ";
+
+enum TypeDiff {
+    /// The types are the same, shown as `_`.
+    Same,
+    /// The types have the same outer structure, shown as `open`, the diffs of their arguments and
+    /// `close`.
+    Nested { open: String, args: Vec<TypeDiff>, close: String },
+    /// The types differ here, shown with the parts that differ highlighted by `cmp`.
+    Differ(DiagnosticStyledString, DiagnosticStyledString),
+}
+
+impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
+    /// Adds a note with the tree of the differences between `expected` and `found`, unless they
+    /// have no outer structure in common. Returns whether it did.
+    pub(super) fn note_type_diff(
+        &self,
+        diag: &mut DiagnosticBuilder<'_>,
+        expected: Ty<'tcx>,
+        found: Ty<'tcx>,
+    ) -> bool {
+        let diff = self.type_diff(expected, found);
+        if !matches!(diff, TypeDiff::Nested { .. }) {
+            return false;
+        }
+        let mut msg = DiagnosticStyledString::normal("the expected and found types differ here:\n");
+        diff.render(&mut msg, 0);
+        diag.styled_note(msg);
+        true
+    }
+
+    fn type_diff(&self, t1: Ty<'tcx>, t2: Ty<'tcx>) -> TypeDiff {
+        if t1 == t2 {
+            return TypeDiff::Same;
+        }
+        let nested = match (t1.kind(), t2.kind()) {
+            (&ty::Adt(def1, sub1), &ty::Adt(def2, sub2)) if def1.did == def2.did => {
+                let sub1 = self.strip_generic_default_params(def1.did, sub1);
+                let sub2 = self.strip_generic_default_params(def2.did, sub2);
+                let types = sub1.types().zip(sub2.types()).map(|(a, b)| self.type_diff(a, b));
+                let consts = sub1.consts().zip(sub2.consts()).map(|(a, b)| {
+                    if a == b {
+                        TypeDiff::Same
+                    } else {
+                        TypeDiff::Differ(
+                            DiagnosticStyledString::highlighted(a.to_string()),
+                            DiagnosticStyledString::highlighted(b.to_string()),
+                        )
+                    }
+                });
+                let open = format!("{}<", self.tcx.def_path_str(def1.did));
+                let args: Vec<_> = types.chain(consts).collect();
+                (sub1.len() == sub2.len()).then(|| (open, args, ">"))
+            }
+            (&ty::Tuple(sub1), &ty::Tuple(sub2)) if sub1.len() == sub2.len() => {
+                let args = sub1.types().zip(sub2.types()).map(|(a, b)| self.type_diff(a, b));
+                Some(("(".to_owned(), args.collect::<Vec<_>>(), ")"))
+            }
+            _ => None,
+        };
+        let (open, mut args, close) = match nested {
+            // Types that only differ in their lifetimes are compared on one line.
+            Some((open, args, close)) if !args.iter().all(TypeDiff::is_same) => (open, args, close),
+            _ => {
+                let (expected, found) = self.cmp(t1, t2);
+                return TypeDiff::Differ(expected, found);
+            }
+        };
+        // A type with a single argument shares its lines with that argument, like `Option<Box<`,
+        // unless it is a tuple, which would lose its trailing comma.
+        let single_nested = args.len() == 1 && matches!(args[0], TypeDiff::Nested { .. });
+        if single_nested && open != "(" {
+            if let Some(TypeDiff::Nested { open: inner, args, close: inner_close }) = args.pop() {
+                return TypeDiff::Nested { open: open + &inner, args, close: inner_close + close };
+            }
+        }
+        TypeDiff::Nested { open, args, close: close.to_owned() }
+    }
+}
+
+impl TypeDiff {
+    fn is_same(&self) -> bool {
+        matches!(self, TypeDiff::Same)
+    }
+
+    /// Appends the diff to `msg`, with the lines after its first one indented by `depth` levels.
+    fn render(self, msg: &mut DiagnosticStyledString, depth: usize) {
+        match self {
+            TypeDiff::Same => msg.push_normal("_"),
+            TypeDiff::Nested { open, args, close } => {
+                msg.push_normal(open);
+                for arg in args {
+                    msg.push_normal(format!("\n{}", INDENT.repeat(depth + 1)));
+                    arg.render(msg, depth + 1);
+                    msg.push_normal(",");
+                }
+                msg.push_normal(format!("\n{}{}", INDENT.repeat(depth), close));
+            }
+            TypeDiff::Differ(expected, found) => {
+                msg.push_normal("expected `");
+                msg.0.extend(expected.0);
+                msg.push_normal(format!("`\n{}   found `", INDENT.repeat(depth)));
+                msg.0.extend(found.0);
+                msg.push_normal("`");
+            }
+        }
+    }
+}
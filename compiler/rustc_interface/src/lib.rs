@@ -10,6 +10,7 @@
 mod passes;
 mod proc_macro_decls;
 mod queries;
+pub mod tool;
 pub mod util;
 
 pub use interface::{run_compiler, Config};
@@ -12,6 +12,7 @@
 use rustc_data_structures::temp_dir::MaybeTempDir;
 use rustc_errors::{ErrorReported, PResult};
 use rustc_expand::base::ExtCtxt;
+use rustc_expand::proc_macro_cache::ProcMacroCache;
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_hir::Crate;
 use rustc_lint::LintStore;
@@ -355,10 +356,15 @@ fn configure_and_expand_inner<'a>(
             (krate.attrs, krate.items)
         };
         let mut ecx = ExtCtxt::new(&sess, cfg, &mut resolver, Some(&extern_mod_loaded));
+        ecx.proc_macro_cache = ProcMacroCache::load(sess).map(RefCell::new);
 
         // Expand macros now!
         let krate = sess.time("expand_crate", || ecx.monotonic_expander().expand_crate(krate));
 
+        if let Some(cache) = &ecx.proc_macro_cache {
+            sess.time("save_proc_macro_cache", || cache.borrow().save(sess));
+        }
+
         // The rest is error reporting
 
         sess.time("check_unused_macros", || {
@@ -858,6 +864,8 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
     let sess = tcx.sess;
     let mut entry_point = None;
 
+    mir::transform::check_mir_passes(sess);
+
     sess.time("misc_checking_1", || {
         parallel!(
             {
@@ -0,0 +1,122 @@
+//! A small interface for tools that run the compiler on a crate and inspect the result, like
+//! lints, verifiers and code generators.
+//!
+//! [`Config`], [`Queries`](crate::Queries) and the order in which the driver runs them change
+//! whenever the compiler needs them to. The items of this module are meant to be the supported
+//! way of embedding the compiler instead: a change that breaks a tool using them comes with a new
+//! [`VERSION`]. This only covers the items of this module, not the types they hand out, like the
+//! `TyCtxt` returned by [`AnalysisContext::tcx`].
+
+use crate::interface::{self, run_compiler, Config};
+use rustc_ast as ast;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_middle::mir::Body;
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::Session;
+
+/// The version of this interface, increased with every change that breaks tools using it.
+pub const VERSION: u32 = 1;
+
+/// Whether to stop or continue compilation after a callback.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Compilation {
+    Stop,
+    Continue,
+}
+
+/// Overrides of query providers, see [`override_queries`]. The second parameter are the providers
+/// for the local crate, the third one those for external crates.
+pub type QueryOverrides = fn(&Session, &mut Providers, &mut Providers);
+
+/// The callbacks [`run_tool`] calls while compiling a crate.
+pub trait ToolCallbacks {
+    /// Called before the compiler is created, to adjust its configuration.
+    fn config(&mut self, _config: &mut Config) {}
+
+    /// Called after macro expansion, with the expanded crate.
+    fn after_expansion(&mut self, _krate: &ast::Crate) -> Compilation {
+        Compilation::Continue
+    }
+
+    /// Called after type checking and the other analyses succeeded, before code generation.
+    fn after_analysis<'tcx>(&mut self, _cx: AnalysisContext<'tcx>) -> Compilation {
+        Compilation::Continue
+    }
+}
+
+/// The view of the analyzed crate that [`ToolCallbacks::after_analysis`] gets.
+#[derive(Copy, Clone)]
+pub struct AnalysisContext<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> AnalysisContext<'tcx> {
+    pub fn session(self) -> &'tcx Session {
+        self.tcx.sess
+    }
+
+    /// All items of the local crate that have a body: functions, closures, constants and statics.
+    pub fn body_owners(self) -> impl Iterator<Item = LocalDefId> + 'tcx {
+        self.tcx.body_owners()
+    }
+
+    /// The path of `def_id`, as it is printed in diagnostics.
+    pub fn def_path_str(self, def_id: DefId) -> String {
+        self.tcx.def_path_str(def_id)
+    }
+
+    /// The optimized MIR of a function or closure, the MIR that code is generated from.
+    pub fn optimized_mir(self, def_id: DefId) -> &'tcx Body<'tcx> {
+        self.tcx.optimized_mir(def_id)
+    }
+
+    /// The type context, for everything else. Its interface is not covered by [`VERSION`].
+    pub fn tcx(self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+}
+
+/// Replaces the query providers of `config` with `overrides`, which are called after the default
+/// providers have been set up. Overrides set before are dropped.
+pub fn override_queries(config: &mut Config, overrides: QueryOverrides) {
+    config.override_queries = Some(overrides);
+}
+
+/// Compiles the crate described by `config` like `rustc` does, calling `callbacks` on the way.
+/// Unlike the driver, this doesn't handle the options that only print something and exit, like
+/// `--print` or `-Zunpretty`.
+pub fn run_tool(
+    mut config: Config,
+    callbacks: &mut (dyn ToolCallbacks + Send),
+) -> interface::Result<()> {
+    callbacks.config(&mut config);
+    run_compiler(config, |compiler| {
+        let sess = compiler.session();
+        let linker = compiler.enter(|queries| {
+            let early_exit = || sess.compile_status().map(|_| None);
+
+            if callbacks.after_expansion(&queries.expansion()?.peek().0) == Compilation::Stop {
+                return early_exit();
+            }
+
+            queries.prepare_outputs()?;
+            let after_analysis =
+                queries.global_ctxt()?.peek_mut().enter(|tcx| -> interface::Result<_> {
+                    tcx.analysis(())?;
+                    Ok(callbacks.after_analysis(AnalysisContext { tcx }))
+                })?;
+            if after_analysis == Compilation::Stop {
+                return early_exit();
+            }
+
+            queries.ongoing_codegen()?;
+            queries.linker().map(Some)
+        })?;
+
+        if let Some(linker) = linker {
+            linker.link()?;
+        }
+        sess.compile_status()
+    })
+}
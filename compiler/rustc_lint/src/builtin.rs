@@ -46,18 +46,19 @@
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::subst::{GenericArgKind, Subst};
 use rustc_middle::ty::Instance;
-use rustc_middle::ty::{self, layout::LayoutError, Ty, TyCtxt};
+use rustc_middle::ty::{self, layout::LayoutError, ScalarInt, Ty, TyCtxt};
 use rustc_session::Session;
 use rustc_span::edition::Edition;
 use rustc_span::source_map::Spanned;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{BytePos, Span};
-use rustc_target::abi::{LayoutOf, VariantIdx};
+use rustc_target::abi::{Abi, LayoutOf, Primitive, Size, TagEncoding, VariantIdx, Variants};
 use rustc_trait_selection::traits::misc::can_type_implement_copy;
 
 use crate::nonstandard_style::{method_context, MethodLateContext};
 
 use std::fmt::Write;
+use std::ops::RangeInclusive;
 use tracing::{debug, trace};
 
 // hardwired lints from librustc_middle
@@ -2469,6 +2470,39 @@ fn is_multi_variant(adt: &ty::AdtDef) -> bool {
             existing_variants > 1
         }
 
+        /// Return the size and valid range of `ty` if it is laid out as a single integer scalar.
+        fn int_scalar_valid_range<'tcx>(
+            tcx: TyCtxt<'tcx>,
+            ty: Ty<'tcx>,
+        ) -> Option<(Size, RangeInclusive<u128>)> {
+            let layout = tcx.layout_of(ty::ParamEnv::reveal_all().and(ty)).ok()?;
+            match layout.abi {
+                Abi::Scalar(ref scalar) if matches!(scalar.value, Primitive::Int(..)) => {
+                    Some((scalar.value.size(&tcx), scalar.valid_range.clone()))
+                }
+                _ => None,
+            }
+        }
+
+        /// Render `valid_range` as a range of bit patterns of the given `size`.
+        fn render_valid_range(size: Size, valid_range: &RangeInclusive<u128>) -> Option<String> {
+            let start = ScalarInt::try_from_uint(*valid_range.start(), size)?;
+            let end = ScalarInt::try_from_uint(*valid_range.end(), size)?;
+            Some(format!("`{:?}..={:?}`", start, end))
+        }
+
+        /// Explain that the all-zero bit pattern of the given `size` is not in `valid_range`.
+        fn zero_outside_valid_range(
+            size: Size,
+            valid_range: &RangeInclusive<u128>,
+        ) -> Option<String> {
+            Some(format!(
+                "the bit pattern `{:?}` is outside of the valid range {}",
+                ScalarInt::null(size),
+                render_valid_range(size, valid_range)?,
+            ))
+        }
+
         /// Return `Some` only if we are sure this type does *not*
         /// allow zero initialization.
         fn ty_find_init_error<'tcx>(
@@ -2504,18 +2538,29 @@ fn ty_find_init_error<'tcx>(
                         // return `Bound::Excluded`.  (And we have tests checking that we
                         // handle the attribute correctly.)
                         (Bound::Included(lo), _) if lo > 0 => {
-                            return Some((format!("`{}` must be non-null", ty), None));
+                            let mut msg = format!("`{}` must be non-null", ty);
+                            if init == InitKind::Zeroed {
+                                if let Some(why) = int_scalar_valid_range(tcx, ty)
+                                    .and_then(|(size, range)| zero_outside_valid_range(size, &range))
+                                {
+                                    write!(&mut msg, ": {}", why).unwrap();
+                                }
+                            }
+                            return Some((msg, None));
                         }
                         (Bound::Included(_), _) | (_, Bound::Included(_))
                             if init == InitKind::Uninit =>
                         {
-                            return Some((
-                                format!(
-                                    "`{}` must be initialized inside its custom valid range",
-                                    ty,
-                                ),
-                                None,
-                            ));
+                            let mut msg = format!(
+                                "`{}` must be initialized inside its custom valid range",
+                                ty,
+                            );
+                            if let Some(range) = int_scalar_valid_range(tcx, ty)
+                                .and_then(|(size, range)| render_valid_range(size, &range))
+                            {
+                                write!(&mut msg, " {}", range).unwrap();
+                            }
+                            return Some((msg, None));
                         }
                         _ => {}
                     }
@@ -2556,9 +2601,38 @@ fn ty_find_init_error<'tcx>(
                                     "enums have to be initialized to a variant".to_string(),
                                     Some(span),
                                 ))
+                            } else if init == InitKind::Zeroed {
+                                // If the tag directly stores the discriminant and no discriminant
+                                // is 0, then there is no variant for the all-zero bit pattern.
+                                // In principle, we could also figure out which variant corresponds
+                                // to a niche of 0, and check that... but for now we accept those.
+                                let layout =
+                                    tcx.layout_of(ty::ParamEnv::reveal_all().and(ty)).ok()?;
+                                match layout.variants {
+                                    Variants::Multiple {
+                                        ref tag,
+                                        tag_encoding: TagEncoding::Direct,
+                                        ..
+                                    } if matches!(tag.value, Primitive::Int(..))
+                                        && tag.valid_range.start() <= tag.valid_range.end()
+                                        && *tag.valid_range.start() > 0 =>
+                                    {
+                                        let why = zero_outside_valid_range(
+                                            tag.value.size(&tcx),
+                                            &tag.valid_range,
+                                        )?;
+                                        Some((
+                                            format!(
+                                                "enums have to be initialized to a variant: {} \
+                                                 of the discriminant",
+                                                why,
+                                            ),
+                                            Some(tcx.def_span(adt_def.did)),
+                                        ))
+                                    }
+                                    _ => None,
+                                }
                             } else {
-                                // In principle, for zero-initialization we could figure out which variant corresponds
-                                // to tag 0, and check that... but for now we just accept all zero-initializations.
                                 None
                             }
                         }
@@ -7,12 +7,12 @@
 use rustc_hir::{is_range_literal, ExprKind, Node};
 use rustc_middle::ty::layout::{IntegerExt, SizeSkeleton};
 use rustc_middle::ty::subst::SubstsRef;
-use rustc_middle::ty::{self, AdtKind, Ty, TyCtxt, TypeFoldable};
+use rustc_middle::ty::{self, AdtKind, ScalarInt, Ty, TyCtxt, TypeFoldable};
 use rustc_span::source_map;
 use rustc_span::symbol::sym;
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::abi::Abi;
-use rustc_target::abi::{Integer, LayoutOf, TagEncoding, Variants};
+use rustc_target::abi::{Integer, LayoutOf, Size, TagEncoding, Variants};
 use rustc_target::spec::abi::Abi as SpecAbi;
 
 use std::cmp;
@@ -150,7 +150,13 @@ fn lint_overflowing_range_endpoint<'tcx>(
                         LitKind::Int(_, LitIntType::Unsuffixed) => "",
                         _ => bug!(),
                     };
-                    let suggestion = format!("{}..={}{}", start, lit_val - 1, suffix);
+                    // The inclusive end is the maximum of the type, so use its name.
+                    let end = match cx.typeck_results().node_type(expr.hir_id).kind() {
+                        ty::Int(t) => format!("{}::MAX", t.name_str()),
+                        ty::Uint(t) => format!("{}::MAX", t.name_str()),
+                        _ => format!("{}{}", lit_val - 1, suffix),
+                    };
+                    let suggestion = format!("{}..={}", start, end);
                     err.span_suggestion(
                         parent_expr.span,
                         &"use an inclusive range instead",
@@ -191,6 +197,41 @@ fn uint_ty_range(uint_ty: ty::UintTy) -> (u128, u128) {
     (0, max)
 }
 
+/// Returns the path of the associated constant of the integer type `ty` (such as `u8::MAX` or
+/// `i32::MIN`) that is equal to the literal `val`, which is negated if `negative` is set.
+/// `uN::MIN` is never returned, as it is more clearly written as `0`.
+fn named_bound_for_literal(
+    cx: &LateContext<'_>,
+    ty: Ty<'_>,
+    val: u128,
+    negative: bool,
+) -> Option<String> {
+    match *ty.kind() {
+        ty::Int(t) => {
+            let size = Integer::from_int_ty(&cx.tcx, t).size();
+            let int = ScalarInt::try_from_uint(val, size)?;
+            // The magnitude of `iN::MIN` has the same bits as `iN::MIN` itself.
+            if !negative && int.is_signed_max(size) {
+                Some(format!("{}::MAX", t.name_str()))
+            } else if negative && int.is_signed_min(size) {
+                Some(format!("{}::MIN", t.name_str()))
+            } else {
+                None
+            }
+        }
+        ty::Uint(t) => {
+            let size = Integer::from_uint_ty(&cx.tcx, t).size();
+            let int = ScalarInt::try_from_uint(val, size)?;
+            if !negative && int.is_unsigned_max(size) {
+                Some(format!("{}::MAX", t.name_str()))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
 fn get_bin_hex_repr(cx: &LateContext<'_>, lit: &hir::Lit) -> Option<String> {
     let src = cx.sess().source_map().span_to_snippet(lit.span).ok()?;
     let firstch = src.chars().next()?;
@@ -214,20 +255,14 @@ fn report_bin_hex_error(
     negative: bool,
 ) {
     let size = Integer::from_attr(&cx.tcx, ty).size();
+    let (int, _) = ScalarInt::from_int_literal(val, negative, size, ty.is_signed());
+    let bits = int.assert_bits(size);
     cx.struct_span_lint(OVERFLOWING_LITERALS, expr.span, |lint| {
         let (t, actually) = match ty {
             attr::IntType::SignedInt(t) => {
-                let actually = if negative {
-                    -(size.sign_extend(val) as i128)
-                } else {
-                    size.sign_extend(val) as i128
-                };
-                (t.name_str(), actually.to_string())
-            }
-            attr::IntType::UnsignedInt(t) => {
-                let actually = size.truncate(val);
-                (t.name_str(), actually.to_string())
+                (t.name_str(), (size.sign_extend(bits) as i128).to_string())
             }
+            attr::IntType::UnsignedInt(t) => (t.name_str(), bits.to_string()),
         };
         let mut err = lint.build(&format!("literal out of range for `{}`", t));
         if negative {
@@ -324,10 +359,11 @@ fn lint_int_literal<'tcx>(
     let (min, max) = int_ty_range(int_type);
     let max = max as u128;
     let negative = type_limits.negated_expr_id == Some(e.hir_id);
+    let size = Integer::from_int_ty(&cx.tcx, t).size();
 
     // Detect literal value out of range [min, max] inclusive
-    // avoiding use of -min to prevent overflow/panic
-    if (negative && v > max + 1) || (!negative && v > max) {
+    let (_, fits) = ScalarInt::from_int_literal(v, negative, size, true);
+    if !fits {
         if let Some(repr_str) = get_bin_hex_repr(cx, lit) {
             report_bin_hex_error(
                 cx,
@@ -388,21 +424,29 @@ fn lint_uint_literal<'tcx>(
         ast::LitKind::Int(v, _) => v,
         _ => bug!(),
     };
-    if lit_val < min || lit_val > max {
+    let size = Integer::from_uint_ty(&cx.tcx, t).size();
+    let (_, fits) = ScalarInt::from_int_literal(lit_val, false, size, false);
+    if !fits {
         let parent_id = cx.tcx.hir().get_parent_node(e.hir_id);
         if let Node::Expr(par_e) = cx.tcx.hir().get(parent_id) {
             match par_e.kind {
                 hir::ExprKind::Cast(..) => {
-                    if let ty::Char = cx.typeck_results().expr_ty(par_e).kind() {
+                    let char_ty = cx.typeck_results().expr_ty(par_e);
+                    if let ty::Char = char_ty.kind() {
+                        // Values that aren't a valid `char` can't be written as a literal.
+                        let sugg = ScalarInt::try_from_uint(lit_val, Size::from_bytes(4))
+                            .and_then(|int| int.to_literal_suggestion(char_ty, false));
                         cx.struct_span_lint(OVERFLOWING_LITERALS, par_e.span, |lint| {
-                            lint.build("only `u8` can be cast into `char`")
-                                .span_suggestion(
+                            let mut err = lint.build("only `u8` can be cast into `char`");
+                            if let Some(sugg) = sugg {
+                                err.span_suggestion(
                                     par_e.span,
                                     &"use a `char` literal instead",
-                                    format!("'\\u{{{:X}}}'", lit_val),
+                                    sugg,
                                     Applicability::MachineApplicable,
-                                )
-                                .emit();
+                                );
+                            }
+                            err.emit();
                         });
                         return;
                     }
@@ -501,7 +545,18 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx hir::Expr<'tcx>) {
             hir::ExprKind::Binary(binop, ref l, ref r) => {
                 if is_comparison(binop) && !check_limits(cx, binop, &l, &r) {
                     cx.struct_span_lint(UNUSED_COMPARISONS, e.span, |lint| {
-                        lint.build("comparison is useless due to type limits").emit()
+                        let mut err = lint.build("comparison is useless due to type limits");
+                        if let Some((span, bound)) = named_bound_operand(cx, l)
+                            .or_else(|| named_bound_operand(cx, r))
+                        {
+                            err.span_suggestion_verbose(
+                                span,
+                                "use the associated constant instead",
+                                bound,
+                                Applicability::MachineApplicable,
+                            );
+                        }
+                        err.emit()
                     });
                 }
             }
@@ -509,6 +564,28 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx hir::Expr<'tcx>) {
             _ => {}
         };
 
+        /// If `operand` is an integer literal equal to the `MIN` or `MAX` of its type, returns
+        /// its span and the path of that associated constant.
+        fn named_bound_operand(
+            cx: &LateContext<'_>,
+            operand: &hir::Expr<'_>,
+        ) -> Option<(Span, String)> {
+            let (lit, negative) = match operand.kind {
+                hir::ExprKind::Lit(ref lit) => (lit, false),
+                hir::ExprKind::Unary(hir::UnOp::Neg, ref inner) => match inner.kind {
+                    hir::ExprKind::Lit(ref lit) => (lit, true),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            let val = match lit.node {
+                ast::LitKind::Int(v, _) => v,
+                _ => return None,
+            };
+            let ty = cx.typeck_results().node_type(operand.hir_id);
+            named_bound_for_literal(cx, ty, val, negative).map(|bound| (operand.span, bound))
+        }
+
         fn is_valid<T: cmp::PartialOrd>(binop: hir::BinOp, v: T, min: T, max: T) -> bool {
             match binop.node {
                 hir::BinOpKind::Lt => v > min && v <= max,
@@ -97,7 +97,7 @@ fn check_stmt(&mut self, cx: &LateContext<'_>, s: &hir::Stmt<'_>) {
             _ => return,
         };
 
-        if let hir::ExprKind::Ret(..) = expr.kind {
+        if let hir::ExprKind::Ret(..) | hir::ExprKind::Become(..) = expr.kind {
             return;
         }
 
@@ -513,7 +513,10 @@ fn is_expr_delims_necessary(inner: &ast::Expr, followed_by_block: bool) -> bool
         lhs_needs_parens
             || (followed_by_block
                 && match inner.kind {
-                    ExprKind::Ret(_) | ExprKind::Break(..) | ExprKind::Yield(..) => true,
+                    ExprKind::Ret(_)
+                    | ExprKind::Become(_)
+                    | ExprKind::Break(..)
+                    | ExprKind::Yield(..) => true,
                     _ => parser::contains_exterior_struct_lit(&inner),
                 })
     }
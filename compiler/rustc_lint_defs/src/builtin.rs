@@ -2914,6 +2914,35 @@
     "detects large moves or copies",
 }
 
+declare_lint! {
+    /// The `enum_discriminant_overflow` lint detects enum variants whose
+    /// implicit discriminant does not fit into the enum's representation
+    /// type.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #[repr(u8)]
+    /// enum Foo {
+    ///     A = 255,
+    ///     B,
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// A variant without an explicit discriminant gets the discriminant of
+    /// the previous variant plus one. If that value is out of the range of
+    /// the representation type, it wraps around, which is almost certainly
+    /// not what was intended. If the wrapped-around value is desired, it
+    /// should be assigned explicitly.
+    pub ENUM_DISCRIMINANT_OVERFLOW,
+    Deny,
+    "implicit enum discriminant overflows the representation type"
+}
+
 declare_lint_pass! {
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// that are used by other parts of the compiler.
@@ -3001,6 +3030,7 @@
         PROC_MACRO_BACK_COMPAT,
         OR_PATTERNS_BACK_COMPAT,
         LARGE_ASSIGNMENTS,
+        ENUM_DISCRIMINANT_OVERFLOW,
     ]
 }
 
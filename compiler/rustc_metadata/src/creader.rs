@@ -2,6 +2,7 @@
 
 use crate::dynamic_lib::DynamicLibrary;
 use crate::locator::{CrateError, CrateLocator, CratePaths};
+use crate::proc_macro_server;
 use crate::rmeta::{CrateDep, CrateMetadata, CrateNumMap, CrateRoot, MetadataBlob};
 
 use rustc_ast::expand::allocator::AllocatorKind;
@@ -10,6 +11,7 @@
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::sync::Lrc;
 use rustc_expand::base::SyntaxExtension;
+use rustc_expand::proc_macro_process::{ProcMacroProcess, ServerConfig};
 use rustc_hir::def_id::{CrateNum, LocalDefId, StableCrateId, LOCAL_CRATE};
 use rustc_hir::definitions::Definitions;
 use rustc_index::vec::IndexVec;
@@ -17,7 +19,7 @@
 use rustc_middle::middle::cstore::{ExternCrateSource, MetadataLoaderDyn};
 use rustc_middle::ty::TyCtxt;
 use rustc_serialize::json::ToJson;
-use rustc_session::config::{self, CrateType, ExternLocation};
+use rustc_session::config::{self, CrateType, ExternLocation, ProcMacroExecution};
 use rustc_session::lint::{self, BuiltinLintDiagnostics, ExternDepSpec};
 use rustc_session::output::validate_crate_name;
 use rustc_session::search_paths::PathKind;
@@ -401,7 +403,7 @@ fn register_crate(
 
         let cnum_map = self.resolve_crate_deps(root, &crate_root, &metadata, cnum, dep_kind)?;
 
-        let raw_proc_macros = if crate_root.is_proc_macro_crate() {
+        let (raw_proc_macros, proc_macro_process) = if crate_root.is_proc_macro_crate() {
             let temp_root;
             let (dlsym_source, dlsym_root) = match &host_lib {
                 Some(host_lib) => (&host_lib.source, {
@@ -411,9 +413,13 @@ fn register_crate(
                 None => (&source, &crate_root),
             };
             let dlsym_dylib = dlsym_source.dylib.as_ref().expect("no dylib for a proc-macro crate");
-            Some(self.dlsym_proc_macros(&dlsym_dylib.0, dlsym_root.disambiguator())?)
+            let disambiguator = dlsym_root.disambiguator();
+            (
+                Some(self.dlsym_proc_macros(&dlsym_dylib.0, disambiguator)?),
+                self.proc_macro_process(&dlsym_dylib.0, disambiguator),
+            )
         } else {
-            None
+            (None, None)
         };
 
         // Perform some verification *after* resolve_crate_deps() above is
@@ -429,6 +435,7 @@ fn register_crate(
             metadata,
             crate_root,
             raw_proc_macros,
+            proc_macro_process,
             cnum,
             cnum_map,
             dep_kind,
@@ -689,6 +696,34 @@ fn dlsym_proc_macros(
         Ok(decls)
     }
 
+    /// Returns the process to run the proc macros in `path` in, if they don't run in this one.
+    fn proc_macro_process(
+        &self,
+        path: &Path,
+        disambiguator: CrateDisambiguator,
+    ) -> Option<Lrc<ProcMacroProcess>> {
+        match self.sess.opts.debugging_opts.proc_macro_execution {
+            ProcMacroExecution::SameThread => None,
+            ProcMacroExecution::External if !proc_macro_server::driver_runs_server() => {
+                // Identical errors are only emitted once, no matter how many crates are loaded.
+                let msg = "`-Z proc-macro-execution=external` is not supported by this driver";
+                self.sess
+                    .struct_err(msg)
+                    .note(
+                        "the driver has to call `rustc_metadata::proc_macro_server::\
+                         run_if_requested` before anything else",
+                    )
+                    .emit();
+                None
+            }
+            ProcMacroExecution::External => Some(Lrc::new(ProcMacroProcess::new(ServerConfig {
+                dylib: env::current_dir().unwrap().join(path),
+                decls_symbol: self.sess.generate_proc_macro_decls_symbol(disambiguator),
+                memory_limit: self.sess.opts.debugging_opts.proc_macro_memory_limit,
+            }))),
+        }
+    }
+
     fn inject_panic_runtime(&mut self, krate: &ast::Crate) {
         // If we're only compiling an rlib, then there's no need to select a
         // panic runtime, so we just skip this section entirely.
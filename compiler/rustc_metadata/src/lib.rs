@@ -29,5 +29,6 @@
 pub mod creader;
 pub mod dynamic_lib;
 pub mod locator;
+pub mod proc_macro_server;
 
 pub use rmeta::METADATA_HEADER;
@@ -0,0 +1,85 @@
+//! The entry point of the process that runs proc macros with `-Z proc-macro-execution=external`,
+//! see `rustc_expand::proc_macro_process` for the compiler side.
+
+use crate::dynamic_lib::DynamicLibrary;
+
+use proc_macro::bridge::client::ProcMacro;
+use rustc_expand::proc_macro_process::{self, ServerConfig};
+use std::io::{self, Write};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the driver called [`run_if_requested`], so that copies of the current executable can
+/// run proc macros.
+static DRIVER_RUNS_SERVER: AtomicBool = AtomicBool::new(false);
+
+/// Runs the proc macros the compiler asks for and exits if this process was started to do that,
+/// returns otherwise. Drivers that support `-Z proc-macro-execution=external` call this before
+/// anything else, since the proc macro process is a copy of the current executable.
+pub fn run_if_requested() {
+    DRIVER_RUNS_SERVER.store(true, Ordering::Relaxed);
+    let config = match ServerConfig::from_env() {
+        Some(config) => config,
+        None => return,
+    };
+    if let Err(err) = run(config) {
+        eprintln!("error: failed to run proc macros: {}", err);
+        process::exit(1);
+    }
+    process::exit(0)
+}
+
+/// Whether starting a copy of the current executable starts a proc macro process.
+crate fn driver_runs_server() -> bool {
+    DRIVER_RUNS_SERVER.load(Ordering::Relaxed)
+}
+
+fn run(config: ServerConfig) -> io::Result<()> {
+    let output = redirect_stdout()?;
+    if let Some(limit) = config.memory_limit {
+        limit_memory(limit)?;
+    }
+    let other = |err| io::Error::new(io::ErrorKind::Other, err);
+    let lib = DynamicLibrary::open(&config.dylib).map_err(other)?;
+    let macros = unsafe { *lib.symbol::<&[ProcMacro]>(&config.decls_symbol).map_err(other)? };
+    proc_macro_process::serve(macros, io::stdin(), output)
+}
+
+/// Proc macros print to the standard output for debugging, which must not get mixed into the
+/// messages to the compiler. The messages are written to a copy of it instead, and the standard
+/// output is redirected to the standard error.
+#[cfg(unix)]
+fn redirect_stdout() -> io::Result<impl Write> {
+    use std::fs::File;
+    use std::os::unix::io::FromRawFd;
+
+    unsafe {
+        let fd = libc::dup(libc::STDOUT_FILENO);
+        if fd < 0 || libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(File::from_raw_fd(fd))
+    }
+}
+
+#[cfg(not(unix))]
+fn redirect_stdout() -> io::Result<io::Stdout> {
+    // Rejected when parsing the options.
+    Err(io::Error::new(io::ErrorKind::Other, "only supported on Unix"))
+}
+
+/// Limits the address space of this process to `mib` MiB, which makes allocations beyond it fail.
+#[cfg(unix)]
+fn limit_memory(mib: usize) -> io::Result<()> {
+    let bytes = (mib as libc::rlim_t).saturating_mul(1024 * 1024);
+    let limit = libc::rlimit { rlim_cur: bytes, rlim_max: bytes };
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_mib: usize) -> io::Result<()> {
+    Ok(())
+}
@@ -14,6 +14,7 @@
 use rustc_errors::ErrorReported;
 use rustc_expand::base::{SyntaxExtension, SyntaxExtensionKind};
 use rustc_expand::proc_macro::{AttrProcMacro, BangProcMacro, ProcMacroDerive};
+use rustc_expand::proc_macro_process::{ExternalProcMacro, ProcMacroProcess};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, DefIndex, CRATE_DEF_INDEX, LOCAL_CRATE};
@@ -74,6 +75,9 @@
         FxHashMap<(u32, DefIndex), Lazy<[(DefIndex, Option<ty::fast_reject::SimplifiedType>)]>>,
     /// Proc macro descriptions for this crate, if it's a proc macro crate.
     raw_proc_macros: Option<&'static [ProcMacro]>,
+    /// The process that runs the proc macros of this crate with
+    /// `-Z proc-macro-execution=external`.
+    proc_macro_process: Option<Lrc<ProcMacroProcess>>,
     /// Source maps for code from the crate.
     source_map_import_info: OnceCell<Vec<ImportedSourceFile>>,
     /// For every definition in this crate, maps its `DefPathHash` to its
@@ -646,18 +650,20 @@ impl CrateRoot<'_> {
 
 impl<'a, 'tcx> CrateMetadataRef<'a> {
     fn raw_proc_macro(&self, id: DefIndex) -> &ProcMacro {
+        &self.raw_proc_macros.unwrap()[self.proc_macro_index(id)]
+    }
+
+    fn proc_macro_index(&self, id: DefIndex) -> usize {
         // DefIndex's in root.proc_macro_data have a one-to-one correspondence
         // with items in 'raw_proc_macros'.
-        let pos = self
-            .root
+        self.root
             .proc_macro_data
             .as_ref()
             .unwrap()
             .macros
             .decode(self)
             .position(|i| i == id)
-            .unwrap();
-        &self.raw_proc_macros.unwrap()[pos]
+            .unwrap()
     }
 
     fn try_item_ident(&self, item_index: DefIndex, sess: &Session) -> Result<Ident, String> {
@@ -717,7 +723,19 @@ fn get_span(&self, index: DefIndex, sess: &Session) -> Span {
     }
 
     fn load_proc_macro(&self, def_id: DefId, sess: &Session) -> SyntaxExtension {
-        let (name, kind, helper_attrs) = match *self.raw_proc_macro(def_id.index) {
+        let index = self.proc_macro_index(def_id.index);
+        let krate = def_id.krate;
+        let external = || {
+            let process = self.proc_macro_process.clone()?;
+            Some(ExternalProcMacro { process, index })
+        };
+        let attrs: Vec<_> = self.get_item_attrs(def_id.index, sess).collect();
+        let crate_hash = if attrs.iter().any(|attr| attr.has_name(sym::proc_macro_impure)) {
+            None
+        } else {
+            Some(self.root.hash)
+        };
+        let (name, kind, helper_attrs) = match self.raw_proc_macros.unwrap()[index] {
             ProcMacro::CustomDerive { trait_name, attributes, client } => {
                 let helper_attrs =
                     attributes.iter().cloned().map(Symbol::intern).collect::<Vec<_>>();
@@ -725,24 +743,35 @@ fn load_proc_macro(&self, def_id: DefId, sess: &Session) -> SyntaxExtension {
                     trait_name,
                     SyntaxExtensionKind::Derive(Box::new(ProcMacroDerive {
                         client,
-                        krate: def_id.krate,
+                        krate,
+                        external: external(),
+                        crate_hash,
                     })),
                     helper_attrs,
                 )
             }
             ProcMacro::Attr { name, client } => (
                 name,
-                SyntaxExtensionKind::Attr(Box::new(AttrProcMacro { client, krate: def_id.krate })),
+                SyntaxExtensionKind::Attr(Box::new(AttrProcMacro {
+                    client,
+                    krate,
+                    external: external(),
+                    crate_hash,
+                })),
                 Vec::new(),
             ),
             ProcMacro::Bang { name, client } => (
                 name,
-                SyntaxExtensionKind::Bang(Box::new(BangProcMacro { client, krate: def_id.krate })),
+                SyntaxExtensionKind::Bang(Box::new(BangProcMacro {
+                    client,
+                    krate,
+                    external: external(),
+                    crate_hash,
+                })),
                 Vec::new(),
             ),
         };
 
-        let attrs: Vec<_> = self.get_item_attrs(def_id.index, sess).collect();
         SyntaxExtension::new(
             sess,
             kind,
@@ -1192,6 +1221,14 @@ fn get_mir_abstract_const(
             .map_or(Ok(None), |v| Ok(Some(v.decode((self, tcx)))))
     }
 
+    fn get_const_alloc(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        id: DefIndex,
+    ) -> Option<mir::interpret::ConstAlloc<'tcx>> {
+        self.root.tables.const_allocs.get(self, id).map(|alloc| alloc.decode((self, tcx)))
+    }
+
     fn get_unused_generic_params(&self, id: DefIndex) -> FiniteBitSet<u32> {
         self.root
             .tables
@@ -1825,6 +1862,7 @@ impl CrateMetadata {
         blob: MetadataBlob,
         root: CrateRoot<'static>,
         raw_proc_macros: Option<&'static [ProcMacro]>,
+        proc_macro_process: Option<Lrc<ProcMacroProcess>>,
         cnum: CrateNum,
         cnum_map: CrateNumMap,
         dep_kind: CrateDepKind,
@@ -1845,6 +1883,7 @@ impl CrateMetadata {
             root,
             trait_impls,
             raw_proc_macros,
+            proc_macro_process,
             source_map_import_info: OnceCell::new(),
             def_path_hash_map: Default::default(),
             alloc_decoding_state,
@@ -596,6 +596,7 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
         // Encode MIR.
         i = self.position();
         self.encode_mir();
+        self.encode_const_allocs();
         let mir_bytes = self.position() - i;
 
         // Encode the items.
@@ -1254,6 +1255,37 @@ fn encode_fn_param_names(&mut self, param_names: &[Ident]) -> Lazy<[Ident]> {
         self.lazy(param_names.iter())
     }
 
+    /// Records the values of the non-generic constants of this crate, so other crates do not need
+    /// to evaluate them again.
+    fn encode_const_allocs(&mut self) {
+        if self.is_proc_macro {
+            return;
+        }
+
+        let tcx = self.tcx;
+        let mut consts = tcx
+            .mir_keys(())
+            .iter()
+            .copied()
+            .filter(|&def_id| {
+                matches!(tcx.def_kind(def_id), DefKind::Const | DefKind::AssocConst)
+                    && tcx.generics_of(def_id).count() == 0
+            })
+            .collect::<Vec<_>>();
+        // Sort to encode the values in a deterministic order.
+        consts.sort();
+        for def_id in consts {
+            let def_id = def_id.to_def_id();
+            let instance = ty::Instance::mono(tcx, def_id);
+            let cid = interpret::GlobalId { instance, promoted: None };
+            let param_env = tcx.param_env(def_id).with_reveal_all_normalized(tcx);
+            // Constants that fail to evaluate have already been reported, or linted against.
+            if let Ok(alloc) = tcx.eval_to_allocation_raw(tcx.erase_regions(param_env.and(cid))) {
+                record!(self.tables.const_allocs[def_id] <- alloc);
+            }
+        }
+    }
+
     fn encode_mir(&mut self) {
         if self.is_proc_macro {
             return;
@@ -306,6 +306,7 @@ fn encode(&self, buf: &mut Encoder) -> LazyTables<'tcx> {
     mir_for_ctfe: Table<DefIndex, Lazy!(mir::Body<'tcx>)>,
     promoted_mir: Table<DefIndex, Lazy!(IndexVec<mir::Promoted, mir::Body<'tcx>>)>,
     mir_abstract_consts: Table<DefIndex, Lazy!(&'tcx [mir::abstract_const::Node<'tcx>])>,
+    const_allocs: Table<DefIndex, Lazy!(mir::interpret::ConstAlloc<'tcx>)>,
     const_defaults: Table<DefIndex, Lazy<rustc_middle::ty::Const<'tcx>>>,
     unused_generic_params: Table<DefIndex, Lazy<FiniteBitSet<u32>>>,
     // `def_keys` and `def_path_hashes` represent a lazy version of a
@@ -373,6 +374,9 @@ struct FnData {
 #[derive(TyEncodable, TyDecodable)]
 struct VariantData {
     ctor_kind: CtorKind,
+    /// Discriminant values are never stored here: implicit ones are encoded as
+    /// their (LEB128) distance from the last explicit one, and explicit ones
+    /// refer to the constant that is evaluated on demand.
     discr: ty::VariantDiscr,
     /// If this is unit or tuple-variant/struct, then this is the index of the ctor id.
     ctor: Option<DefIndex>,
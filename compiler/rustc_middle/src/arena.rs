@@ -34,6 +34,7 @@ macro_rules! arena_types {
                 rustc_middle::mir::BorrowCheckResult<$tcx>,
             [decode] unsafety_check_result: rustc_middle::mir::UnsafetyCheckResult,
             [decode] code_region: rustc_middle::mir::coverage::CodeRegion,
+            [] instr_profile_counts: rustc_middle::mir::InstrProfileCounts,
             [] const_allocs: rustc_middle::mir::interpret::Allocation,
             // Required for the incremental on-disk cache
             [few] mir_keys: rustc_hir::def_id::DefIdSet,
@@ -86,6 +87,7 @@ macro_rules! arena_types {
             [] attribute: rustc_ast::Attribute,
             [] name_set: rustc_data_structures::fx::FxHashSet<rustc_span::symbol::Symbol>,
             [] hir_id_set: rustc_hir::HirIdSet,
+            [] expansion_step: rustc_span::ExpansionStep,
 
             // Interned types
             [] tys: rustc_middle::ty::TyS<$tcx>,
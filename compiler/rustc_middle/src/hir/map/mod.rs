@@ -603,7 +603,7 @@ pub fn get_return_block(&self, id: HirId) -> Option<HirId> {
         let mut iter = self.parent_iter(id).peekable();
         let mut ignore_tail = false;
         if let Some(node) = self.find(id) {
-            if let Node::Expr(Expr { kind: ExprKind::Ret(_), .. }) = node {
+            if let Node::Expr(Expr { kind: ExprKind::Ret(_) | ExprKind::Become(_), .. }) = node {
                 // When dealing with `return` statements, we don't care about climbing only tail
                 // expressions.
                 ignore_tail = true;
@@ -625,7 +625,10 @@ pub fn get_return_block(&self, id: HirId) -> Option<HirId> {
                 | Node::Expr(Expr { kind: ExprKind::Closure(..), .. })
                 | Node::ImplItem(_) => return Some(hir_id),
                 // Ignore `return`s on the first iteration
-                Node::Expr(Expr { kind: ExprKind::Loop(..) | ExprKind::Ret(..), .. })
+                Node::Expr(Expr {
+                    kind: ExprKind::Loop(..) | ExprKind::Ret(..) | ExprKind::Become(..),
+                    ..
+                })
                 | Node::Local(_) => {
                     return None;
                 }
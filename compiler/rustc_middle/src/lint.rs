@@ -3,7 +3,7 @@
 use crate::ich::StableHashingContext;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
-use rustc_errors::{DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{DiagnosticAnchor, DiagnosticBuilder, DiagnosticId};
 use rustc_hir::HirId;
 use rustc_session::lint::{
     builtin::{self, FORBIDDEN_LINT_GROUPS},
@@ -230,6 +230,12 @@ pub fn build(mut self, msg: &str) -> DiagnosticBuilder<'a> {
         self.0
     }
 
+    /// Sets what the lint is about, see `TyCtxt::diagnostic_anchor`.
+    pub fn with_anchor(mut self, anchor: Option<DiagnosticAnchor>) -> LintDiagnosticBuilder<'a> {
+        self.0.set_anchor(anchor);
+        self
+    }
+
     /// Create a LintDiagnosticBuilder from some existing DiagnosticBuilder.
     pub fn new(err: DiagnosticBuilder<'a>) -> LintDiagnosticBuilder<'a> {
         LintDiagnosticBuilder(err)
@@ -8,11 +8,16 @@
 //! on how deeply they recurse to prevent stack overflow. Users can override
 //! this via an attribute on the crate like `#![recursion_limit="22"]`. This pass
 //! just peeks and looks for that attribute.
+//!
+//! The `const_eval_limit` can also be overridden for the constants, statics and functions
+//! in a single item, see `const_eval_limit`.
 
 use crate::bug;
+use crate::ty::TyCtxt;
 use rustc_ast as ast;
 use rustc_data_structures::sync::OnceCell;
-use rustc_session::Session;
+use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX};
+use rustc_session::{Limit, Session};
 use rustc_span::symbol::{sym, Symbol};
 
 use std::num::IntErrorKind;
@@ -36,37 +41,58 @@ fn update_limit(
             continue;
         }
 
-        if let Some(s) = attr.value_str() {
-            match s.as_str().parse() {
-                Ok(n) => {
-                    limit.set(From::from(n)).unwrap();
-                    return;
+        if let Some(n) = parse_limit(sess, attr) {
+            limit.set(From::from(n)).unwrap();
+            return;
+        }
+    }
+    limit.set(From::from(default)).unwrap();
+}
+
+/// Returns the step limit for evaluating `def_id`: the value of the `#[const_eval_limit]`
+/// attribute on `def_id` or on the innermost item containing it, or the crate's limit if there
+/// is none. The attribute on the crate itself is the crate's limit, so it is not looked at.
+pub fn const_eval_limit(tcx: TyCtxt<'_>, def_id: DefId) -> Limit {
+    let mut def_id = Some(def_id);
+    while let Some(id) = def_id {
+        if id.index == CRATE_DEF_INDEX {
+            break;
+        }
+        for attr in tcx.get_attrs(id).iter() {
+            if tcx.sess.check_name(attr, sym::const_eval_limit) {
+                if let Some(n) = parse_limit(tcx.sess, attr) {
+                    return Limit::new(n);
                 }
-                Err(e) => {
-                    let mut err =
-                        sess.struct_span_err(attr.span, "`limit` must be a non-negative integer");
+            }
+        }
+        def_id = tcx.parent(id);
+    }
+    tcx.sess.const_eval_limit()
+}
 
-                    let value_span = attr
-                        .meta()
-                        .and_then(|meta| meta.name_value_literal_span())
-                        .unwrap_or(attr.span);
+/// Parses the value of a limit attribute, reporting an error if it is not a valid limit.
+fn parse_limit(sess: &Session, attr: &ast::Attribute) -> Option<usize> {
+    let s = attr.value_str()?;
+    match s.as_str().parse() {
+        Ok(n) => Some(n),
+        Err(e) => {
+            let mut err = sess.struct_span_err(attr.span, "`limit` must be a non-negative integer");
 
-                    let error_str = match e.kind() {
-                        IntErrorKind::PosOverflow => "`limit` is too large",
-                        IntErrorKind::Empty => "`limit` must be a non-negative integer",
-                        IntErrorKind::InvalidDigit => "not a valid integer",
-                        IntErrorKind::NegOverflow => {
-                            bug!("`limit` should never negatively overflow")
-                        }
-                        IntErrorKind::Zero => bug!("zero is a valid `limit`"),
-                        kind => bug!("unimplemented IntErrorKind variant: {:?}", kind),
-                    };
+            let value_span =
+                attr.meta().and_then(|meta| meta.name_value_literal_span()).unwrap_or(attr.span);
 
-                    err.span_label(value_span, error_str);
-                    err.emit();
-                }
-            }
+            let error_str = match e.kind() {
+                IntErrorKind::PosOverflow => "`limit` is too large",
+                IntErrorKind::Empty => "`limit` must be a non-negative integer",
+                IntErrorKind::InvalidDigit => "not a valid integer",
+                IntErrorKind::NegOverflow => bug!("`limit` should never negatively overflow"),
+                IntErrorKind::Zero => bug!("zero is a valid `limit`"),
+                kind => bug!("unimplemented IntErrorKind variant: {:?}", kind),
+            };
+
+            err.span_label(value_span, error_str);
+            err.emit();
+            None
         }
     }
-    limit.set(From::from(default)).unwrap();
 }
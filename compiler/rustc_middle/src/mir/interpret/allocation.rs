@@ -10,6 +10,8 @@
 use rustc_data_structures::sorted_map::SortedMap;
 use rustc_target::abi::{Align, HasDataLayout, Size};
 
+use crate::ty::ScalarInt;
+
 use super::{
     read_target_uint, write_target_uint, AllocId, InterpError, Pointer, Scalar, ScalarMaybeUninit,
     UndefinedBehaviorInfo, UninitBytesAccess, UnsupportedOpInfo,
@@ -364,6 +366,41 @@ pub fn write_scalar(
 
         Ok(())
     }
+
+    /// Reads a *non-ZST* integer, requiring its bytes to be initialized and free of relocations.
+    ///
+    /// It is the caller's responsibility to check bounds and alignment beforehand.
+    pub fn read_scalar_int(
+        &self,
+        cx: &impl HasDataLayout,
+        range: AllocRange,
+    ) -> AllocResult<ScalarInt> {
+        let bytes = self.get_bytes(cx, range)?;
+        let bits = read_target_uint(cx.data_layout().endian, bytes).unwrap();
+        Ok(ScalarInt::try_from_uint(bits, range.size).unwrap())
+    }
+
+    /// Writes a *non-ZST* integer of exactly `range.size` bytes. This marks the bytes as
+    /// initialized and removes any relocations they were part of.
+    ///
+    /// It is the caller's responsibility to check bounds and alignment beforehand.
+    pub fn write_scalar_int(&mut self, cx: &impl HasDataLayout, range: AllocRange, val: ScalarInt) {
+        let bits = val.assert_bits(range.size);
+        let endian = cx.data_layout().endian;
+        let dst = self.get_bytes_mut(cx, range);
+        write_target_uint(endian, dst, bits).unwrap();
+    }
+
+    /// Reads the offset of the pointer whose relocation starts at `offset`, which is stored in
+    /// the bytes of the relocation.
+    ///
+    /// Panics if no relocation starts at `offset`.
+    pub fn read_relocation_offset(&self, cx: &impl HasDataLayout, offset: Size) -> Size {
+        assert!(self.relocations.get(&offset).is_some(), "no relocation at {:?}", offset);
+        let range = alloc_range(offset, cx.data_layout().pointer_size);
+        let bytes = &self.bytes[range.start.bytes_usize()..range.end().bytes_usize()];
+        Size::from_bytes(read_target_uint(cx.data_layout().endian, bytes).unwrap())
+    }
 }
 
 /// Relocations.
@@ -1,7 +1,7 @@
 use super::{AllocId, ConstAlloc, Pointer, Scalar};
 
 use crate::mir::interpret::ConstValue;
-use crate::ty::{layout, query::TyCtxtAt, tls, FnSig, Ty};
+use crate::ty::{layout, query::TyCtxtAt, tls, FnSig, ScalarIntCastError, Ty};
 
 use rustc_data_structures::sync::Lock;
 use rustc_errors::{pluralize, struct_span_err, DiagnosticBuilder, ErrorReported};
@@ -262,6 +262,20 @@ pub enum UndefinedBehaviorInfo<'tcx> {
         path: Option<String>,
         msg: String,
     },
+    /// Validation found memory that can be mutated in the final value of a `const`, either behind
+    /// a mutable reference or inside an `UnsafeCell`. Like `ValidationFailure`, but with enough
+    /// information to suggest alternatives.
+    MutableInConst {
+        /// The "path" to the value in question, like for `ValidationFailure`.
+        path: Option<String>,
+        /// The same path as projections in Rust syntax, like `.x[2]`, if it can be written like
+        /// that. Dereferences are left to auto-deref, except for a trailing one, see `deref`.
+        rust_path: Option<String>,
+        /// Whether the path ends by dereferencing a reference.
+        deref: bool,
+        /// Whether the value is an `UnsafeCell`, rather than a mutable reference.
+        unsafe_cell: bool,
+    },
     /// Using a non-boolean `u8` as bool.
     InvalidBool(u8),
     /// Using a non-character `u32` as character.
@@ -283,6 +297,21 @@ pub enum UndefinedBehaviorInfo<'tcx> {
     },
 }
 
+impl From<ScalarIntCastError> for UndefinedBehaviorInfo<'_> {
+    fn from(err: ScalarIntCastError) -> Self {
+        match err {
+            ScalarIntCastError::SizeMismatch { expected, actual, .. } => {
+                UndefinedBehaviorInfo::ScalarSizeMismatch {
+                    target_size: expected.bytes(),
+                    data_size: actual.bytes(),
+                }
+            }
+            ScalarIntCastError::InvalidBool(b) => UndefinedBehaviorInfo::InvalidBool(b),
+            ScalarIntCastError::InvalidChar(c) => UndefinedBehaviorInfo::InvalidChar(c),
+        }
+    }
+}
+
 impl fmt::Display for UndefinedBehaviorInfo<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use UndefinedBehaviorInfo::*;
@@ -340,6 +369,14 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             ValidationFailure { path: Some(path), msg } => {
                 write!(f, "type validation failed at {}: {}", path, msg)
             }
+            MutableInConst { path, unsafe_cell, .. } => {
+                write!(f, "type validation failed")?;
+                if let Some(path) = path {
+                    write!(f, " at {}", path)?;
+                }
+                let what = if *unsafe_cell { "`UnsafeCell`" } else { "mutable reference" };
+                write!(f, ": encountered {} in a `const`", what)
+            }
             InvalidBool(b) => {
                 write!(f, "interpreting an invalid 8-bit value as a bool: 0x{:02x}", b)
             }
@@ -423,6 +460,19 @@ pub enum ResourceExhaustionInfo {
     ///
     /// The exact limit is set by the `const_eval_limit` attribute.
     StepLimitReached,
+    /// The program allocated too much memory.
+    ///
+    /// The exact limit is set by `-Z const-eval-memory-limit`.
+    MemoryLimitReached {
+        /// The maximum number of bytes that may be allocated at the same time.
+        limit: Size,
+        /// The size of the allocation that would have exceeded the limit.
+        requested: Size,
+        /// The number of allocations that existed at that point.
+        allocations: usize,
+        /// The size of the largest of these allocations.
+        largest: Size,
+    },
 }
 
 impl fmt::Display for ResourceExhaustionInfo {
@@ -435,6 +485,17 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             StepLimitReached => {
                 write!(f, "exceeded interpreter step limit (see `#[const_eval_limit]`)")
             }
+            MemoryLimitReached { limit, requested, allocations, largest } => write!(
+                f,
+                "allocating {} bytes exceeded the memory limit of {} bytes \
+                (see `-Z const-eval-memory-limit`); {} allocation{} existed, \
+                the largest of {} bytes",
+                requested.bytes(),
+                limit.bytes(),
+                allocations,
+                pluralize!(*allocations),
+                largest.bytes(),
+            ),
         }
     }
 }
@@ -514,6 +575,7 @@ pub fn formatted_string(&self) -> bool {
         match self {
             InterpError::Unsupported(UnsupportedOpInfo::Unsupported(_))
             | InterpError::UndefinedBehavior(UndefinedBehaviorInfo::ValidationFailure { .. })
+            | InterpError::UndefinedBehavior(UndefinedBehaviorInfo::MutableInConst { .. })
             | InterpError::UndefinedBehavior(UndefinedBehaviorInfo::Ub(_)) => true,
             _ => false,
         }
@@ -525,6 +587,9 @@ pub fn is_hard_err(&self) -> bool {
         use InterpError::*;
         match *self {
             MachineStop(ref err) => err.is_hard_err(),
+            // The limit protects the compiler itself, so allowing the `const_err` lint must not
+            // silence it.
+            ResourceExhaustion(ResourceExhaustionInfo::MemoryLimitReached { .. }) => true,
             InterpError::UndefinedBehavior(_) => true,
             _ => false,
         }
@@ -5,12 +5,16 @@
     ieee::{Double, Single},
     Float,
 };
+use rustc_errors::ErrorReported;
 use rustc_macros::HashStable;
+use rustc_span::Span;
 use rustc_target::abi::{HasDataLayout, Size, TargetDataLayout};
 
 use crate::ty::{Lift, ParamEnv, ScalarInt, Ty, TyCtxt};
 
-use super::{AllocId, AllocRange, Allocation, InterpResult, Pointer, PointerArithmetic};
+use super::{
+    AllocId, AllocRange, Allocation, InterpError, InterpResult, Pointer, PointerArithmetic,
+};
 
 /// Represents the result of const evaluation via the `eval_to_allocation` query.
 #[derive(Copy, Clone, HashStable, TyEncodable, TyDecodable, Debug, Hash, Eq, PartialEq)]
@@ -382,6 +386,25 @@ pub fn assert_int(self) -> ScalarInt {
         }
     }
 
+    /// Like [`Scalar::assert_int`], but instead of aborting on a pointer, this reports a delayed
+    /// bug at `span` that mentions the offending scalar.
+    #[inline]
+    pub fn try_assert_int(self, tcx: TyCtxt<'_>, span: Span) -> Result<ScalarInt, ErrorReported>
+    where
+        Tag: fmt::Debug,
+    {
+        match self {
+            Scalar::Ptr(_) => {
+                tcx.sess.delay_span_bug(
+                    span,
+                    &format!("expected an int but got an abstract pointer `{:?}`", self),
+                );
+                Err(ErrorReported)
+            }
+            Scalar::Int(int) => Ok(int),
+        }
+    }
+
     #[inline]
     pub fn assert_ptr(self) -> Pointer<Tag> {
         match self {
@@ -403,19 +426,18 @@ pub fn is_ptr(self) -> bool {
     }
 
     pub fn to_bool(self) -> InterpResult<'tcx, bool> {
-        let val = self.to_u8()?;
-        match val {
-            0 => Ok(false),
-            1 => Ok(true),
-            _ => throw_ub!(InvalidBool(val)),
+        match self {
+            Scalar::Int(int) => bool::try_from(int)
+                .map_err(|err| InterpError::UndefinedBehavior(err.into()).into()),
+            Scalar::Ptr(_) => throw_unsup!(ReadPointerAsBytes),
         }
     }
 
     pub fn to_char(self) -> InterpResult<'tcx, char> {
-        let val = self.to_u32()?;
-        match std::char::from_u32(val) {
-            Some(c) => Ok(c),
-            None => throw_ub!(InvalidChar(val)),
+        match self {
+            Scalar::Int(int) => char::try_from(int)
+                .map_err(|err| InterpError::UndefinedBehavior(err.into()).into()),
+            Scalar::Ptr(_) => throw_unsup!(ReadPointerAsBytes),
         }
     }
 
@@ -2,6 +2,7 @@
 
 use crate::mir::{abstract_const, Body, Promoted};
 use crate::ty::{self, Ty, TyCtxt};
+use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync::Lrc;
 use rustc_data_structures::vec_map::VecMap;
 use rustc_errors::ErrorReported;
@@ -382,6 +383,26 @@ pub struct DestructuredConst<'tcx> {
     pub fields: &'tcx [&'tcx ty::Const<'tcx>],
 }
 
+/// How a scalar constant found by the `scalar_constants` query is used in the MIR body.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, HashStable)]
+pub enum ScalarConstantUsage {
+    /// The constant appears as an operand of a statement or terminator.
+    Operand,
+    /// The constant is a value that a `SwitchInt` terminator branches on.
+    SwitchTarget,
+    /// The constant is an operand of an `Assert` terminator's message, e.g. an array length.
+    AssertBound,
+}
+
+/// A scalar constant appearing in a MIR body, as returned by the `scalar_constants` query.
+#[derive(Copy, Clone, Debug, HashStable)]
+pub struct ScalarConstant<'tcx> {
+    /// The value of the constant. Its size is the size of `ty`.
+    pub value: ty::ScalarInt,
+    pub ty: Ty<'tcx>,
+    pub usage: ScalarConstantUsage,
+}
+
 /// Coverage information summarized from a MIR if instrumented for source code coverage (see
 /// compiler option `-Zinstrument-coverage`). This information is generated by the
 /// `InstrumentCoverage` MIR pass and can be retrieved via the `coverageinfo` query.
@@ -394,6 +415,29 @@ pub struct CoverageInfo {
     pub num_expressions: u32,
 }
 
+/// Block counts read from the `-Cprofile-use` profile, used by the MIR inliner to tell hot
+/// functions from cold ones. This is computed by the `instr_profile_counts` query and is empty if
+/// no profile is used.
+#[derive(Clone, Debug, Default, HashStable)]
+pub struct InstrProfileCounts {
+    /// The count of the most executed block of every function in the profile, by symbol name.
+    pub max_block_counts: FxHashMap<String, u64>,
+
+    /// Block counts at or above this are hot.
+    pub hot_count: u64,
+
+    /// Block counts at or below this are cold.
+    pub cold_count: u64,
+}
+
+impl InstrProfileCounts {
+    /// Returns the count of the most executed block of the function with the given symbol name,
+    /// if the profile contains it.
+    pub fn max_block_count(&self, symbol_name: &str) -> Option<u64> {
+        self.max_block_counts.get(symbol_name).copied()
+    }
+}
+
 /// Shims which make dealing with `WithOptConstParam` easier.
 ///
 /// For more information on why this is needed, consider looking
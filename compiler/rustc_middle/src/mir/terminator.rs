@@ -78,6 +78,11 @@ pub fn all_targets(&self) -> &[BasicBlock] {
     pub fn all_targets_mut(&mut self) -> &mut [BasicBlock] {
         &mut self.targets
     }
+
+    /// Returns the target that is jumped to when the tested value equals `value`.
+    pub fn target_for_value(&self, value: u128) -> BasicBlock {
+        self.iter().find_map(|(v, t)| (v == value).then_some(t)).unwrap_or_else(|| self.otherwise())
+    }
 }
 
 pub struct SwitchTargetsIter<'a> {
@@ -192,6 +197,30 @@ pub enum TerminatorKind<'tcx> {
         fn_span: Span,
     },
 
+    /// Tail call.
+    ///
+    /// Roughly speaking this is a chimera of [`Call`] and [`Return`]: it calls `func` with
+    /// `args` like a call, but the callee reuses the stack frame of the current function and
+    /// returns directly to its caller. All locals of the current function, except the ones that
+    /// are moved into the arguments, must be dead at this point.
+    ///
+    /// The signature of the callee must match the one of the current function, which is checked
+    /// when building MIR. A tail call cannot unwind into the current function, since there is
+    /// no current function anymore once the callee runs.
+    ///
+    /// [`Call`]: TerminatorKind::Call
+    /// [`Return`]: TerminatorKind::Return
+    TailCall {
+        /// The function that’s being called.
+        func: Operand<'tcx>,
+        /// Arguments the function is called with.
+        /// These are owned by the callee, which is free to modify them.
+        args: Vec<Operand<'tcx>>,
+        /// This `Span` is the span of the function, without the dot and receiver
+        /// (e.g. `foo(a, b)` in `x.foo(a, b)`
+        fn_span: Span,
+    },
+
     /// Jump to the target if the condition has the expected value,
     /// otherwise panic with a message and a cleanup target.
     Assert {
@@ -308,6 +337,7 @@ pub fn successors(&self) -> Successors<'_> {
             | GeneratorDrop
             | Return
             | Unreachable
+            | TailCall { .. }
             | Call { destination: None, cleanup: None, .. }
             | InlineAsm { destination: None, .. } => None.into_iter().chain(&[]),
             Goto { target: ref t }
@@ -342,6 +372,7 @@ pub fn successors_mut(&mut self) -> SuccessorsMut<'_> {
             | GeneratorDrop
             | Return
             | Unreachable
+            | TailCall { .. }
             | Call { destination: None, cleanup: None, .. }
             | InlineAsm { destination: None, .. } => None.into_iter().chain(&mut []),
             Goto { target: ref mut t }
@@ -376,6 +407,7 @@ pub fn unwind(&self) -> Option<&Option<BasicBlock>> {
             | TerminatorKind::Return
             | TerminatorKind::Unreachable
             | TerminatorKind::GeneratorDrop
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Yield { .. }
             | TerminatorKind::SwitchInt { .. }
             | TerminatorKind::FalseEdge { .. }
@@ -396,6 +428,7 @@ pub fn unwind_mut(&mut self) -> Option<&mut Option<BasicBlock>> {
             | TerminatorKind::Return
             | TerminatorKind::Unreachable
             | TerminatorKind::GeneratorDrop
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Yield { .. }
             | TerminatorKind::SwitchInt { .. }
             | TerminatorKind::FalseEdge { .. }
@@ -483,6 +516,16 @@ pub fn fmt_head<W: Write>(&self, fmt: &mut W) -> fmt::Result {
                 }
                 write!(fmt, ")")
             }
+            TailCall { func, args, .. } => {
+                write!(fmt, "tailcall {:?}(", func)?;
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "{:?}", arg)?;
+                }
+                write!(fmt, ")")
+            }
             Assert { cond, expected, msg, .. } => {
                 write!(fmt, "assert(")?;
                 if !expected {
@@ -547,7 +590,7 @@ pub fn fmt_head<W: Write>(&self, fmt: &mut W) -> fmt::Result {
     pub fn fmt_successor_labels(&self) -> Vec<Cow<'static, str>> {
         use self::TerminatorKind::*;
         match *self {
-            Return | Resume | Abort | Unreachable | GeneratorDrop => vec![],
+            Return | Resume | Abort | Unreachable | GeneratorDrop | TailCall { .. } => vec![],
             Goto { .. } => vec!["".into()],
             SwitchInt { ref targets, switch_ty, .. } => ty::tls::with(|tcx| {
                 let param_env = ty::ParamEnv::empty();
@@ -53,6 +53,9 @@ fn super_fold_with<F: TypeFolder<'tcx>>(self, folder: &mut F) -> Self {
                     fn_span,
                 }
             }
+            TailCall { func, args, fn_span } => {
+                TailCall { func: func.fold_with(folder), args: args.fold_with(folder), fn_span }
+            }
             Assert { cond, expected, msg, target, cleanup } => {
                 use AssertKind::*;
                 let msg = match msg {
@@ -108,6 +111,10 @@ fn super_visit_with<V: TypeVisitor<'tcx>>(&self, visitor: &mut V) -> ControlFlow
                 func.visit_with(visitor)?;
                 args.visit_with(visitor)
             }
+            TailCall { ref func, ref args, .. } => {
+                func.visit_with(visitor)?;
+                args.visit_with(visitor)
+            }
             Assert { ref cond, ref msg, .. } => {
                 cond.visit_with(visitor)?;
                 use AssertKind::*;
@@ -550,6 +550,17 @@ fn super_terminator(&mut self,
                         }
                     }
 
+                    TerminatorKind::TailCall {
+                        func,
+                        args,
+                        fn_span: _,
+                    } => {
+                        self.visit_operand(func, location);
+                        for arg in args {
+                            self.visit_operand(arg, location);
+                        }
+                    }
+
                     TerminatorKind::Assert {
                         cond,
                         expected: _,
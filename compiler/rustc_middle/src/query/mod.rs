@@ -102,6 +102,12 @@
         desc { |tcx| "computing the optional const parameter of `{}`", tcx.def_path_str(key.to_def_id()) }
     }
 
+    /// Computes the discriminant of an enum variant, given the variant's `DefId`. The result has
+    /// the size of the enum's `repr` type; whether it is signed is determined by that type, too.
+    query variant_discriminant(variant: DefId) -> ty::ScalarInt {
+        desc { |tcx| "computing the discriminant of `{}`", tcx.def_path_str(variant) }
+    }
+
     /// Given the def_id of a const-generic parameter, computes the associated default const
     /// parameter. e.g. `fn example<const N: usize=3>` called on `N` would return `3`.
     query const_param_default(param: DefId) -> &'tcx ty::Const<'tcx> {
@@ -336,6 +342,14 @@
         cache_on_disk_if { key.is_local() }
     }
 
+    /// Returns all scalar constants appearing in the optimized MIR of a function, together with
+    /// their type and whether they are used as operands, switch targets or assertion bounds.
+    /// Constants that are still generic or zero-sized are not included. Returns an empty list for
+    /// items without optimized MIR, like constants and statics.
+    query scalar_constants(key: DefId) -> &'tcx [mir::ScalarConstant<'tcx>] {
+        desc { |tcx| "collecting the scalar constants used by `{}`", tcx.def_path_str(key) }
+    }
+
     /// Returns coverage summary info for a function, after executing the `InstrumentCoverage`
     /// MIR pass (assuming the -Zinstrument-coverage option is enabled).
     query coverageinfo(key: ty::InstanceDef<'tcx>) -> mir::CoverageInfo {
@@ -790,6 +804,12 @@
         }
     }
 
+    /// Reads the block counts of the `-Cprofile-use` profile for the MIR inliner.
+    query instr_profile_counts(_: ()) -> &'tcx mir::InstrProfileCounts {
+        eval_always
+        desc { "reading the profile-guided optimization data" }
+    }
+
     /// Evaluates a constant and returns the computed allocation.
     ///
     /// **Do not use this** directly, use the `tcx.eval_static_initializer` wrapper.
@@ -802,6 +822,14 @@
         cache_on_disk_if { true }
     }
 
+    /// Returns the allocation that the crate defining the constant item `key` recorded as its
+    /// value, if any. Only non-generic constants of other crates can have a recorded value.
+    ///
+    /// **Do not use this** directly, `eval_to_allocation_raw` already consults it.
+    query const_alloc_from_metadata(key: DefId) -> Option<mir::interpret::ConstAlloc<'tcx>> {
+        desc { |tcx| "loading the value of `{}` from crate metadata", tcx.def_path_str(key) }
+    }
+
     /// Evaluates const items or anonymous constants
     /// (such as enum variant explicit discriminants or array lengths)
     /// into a representation suitable for the type system and const generics.
@@ -841,6 +869,15 @@
         desc { "deref constant" }
     }
 
+    /// Evaluates the length of an array type. Returns `None` if the length still depends on
+    /// generic parameters. Use `ty::Const::try_eval_array_len` instead of calling this directly,
+    /// it skips the query for lengths that are already known.
+    query array_len_scalar(
+        key: ty::ParamEnvAnd<'tcx, &'tcx ty::Const<'tcx>>
+    ) -> Option<Result<ty::ScalarInt, ErrorReported>> {
+        desc { "evaluating array length `{}`", key.value }
+    }
+
     query const_caller_location(key: (rustc_span::Symbol, u32, u32)) -> ConstValue<'tcx> {
         desc { "get a &core::panic::Location referring to a span" }
     }
@@ -904,6 +941,13 @@
         eval_always
     }
 
+    /// The chain of macro invocations and compiler transformations that produced `span`, starting
+    /// with the innermost one, see `Span::expansion_trace`.
+    query expansion_trace(span: Span) -> &'tcx [rustc_span::ExpansionStep] {
+        eval_always
+        desc { "computing the expansion trace of a span" }
+    }
+
     query def_ident_span(def_id: DefId) -> Option<Span> {
         desc { |tcx| "looking up span for `{}`'s identifier", tcx.def_path_str(def_id) }
     }
@@ -1074,6 +1118,13 @@
         desc { "computing layout of `{}`", env.value }
     }
 
+    /// The niches of the layout of a type, see `LayoutNiche`.
+    query layout_niches(
+        env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>
+    ) -> Result<&'tcx [ty::layout::LayoutNiche], ty::layout::LayoutError<'tcx>> {
+        desc { "computing the niches of the layout of `{}`", env.value }
+    }
+
     query dylib_dependency_formats(_: CrateNum)
                                     -> &'tcx [(CrateNum, LinkagePreference)] {
         desc { "dylib dependency formats of crate" }
@@ -303,6 +303,10 @@ pub enum ExprKind<'tcx> {
     Return {
         value: Option<ExprId>,
     },
+    /// A `become`. `value` is the call that is made as a guaranteed tail call.
+    Become {
+        value: ExprId,
+    },
     ConstBlock {
         value: &'tcx Const<'tcx>,
     },
@@ -1,12 +1,15 @@
 use crate::mir::interpret::ConstValue;
-use crate::mir::interpret::{LitToConstInput, Scalar};
+use crate::mir::interpret::{ConstAlloc, LitToConstInput, Scalar};
 use crate::ty::subst::InternalSubsts;
-use crate::ty::{self, Ty, TyCtxt};
+use crate::ty::{self, Ty, TyCtxt, TypeFoldable};
 use crate::ty::{ParamEnv, ParamEnvAnd};
 use rustc_errors::ErrorReported;
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_macros::HashStable;
+use rustc_span::DUMMY_SP;
+use rustc_target::abi::Size;
+use std::convert::TryFrom;
 
 mod int;
 mod kind;
@@ -137,15 +140,19 @@ pub fn zero_sized(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> &'tcx Self {
     }
 
     #[inline]
-    /// Creates an interned bool constant.
+    /// Returns the interned bool constant.
     pub fn from_bool(tcx: TyCtxt<'tcx>, v: bool) -> &'tcx Self {
-        Self::from_bits(tcx, v as u128, ParamEnv::empty().and(tcx.types.bool))
+        if v { tcx.consts.true_ } else { tcx.consts.false_ }
     }
 
     #[inline]
-    /// Creates an interned usize constant.
+    /// Creates an interned usize constant. Small values are interned up front, so they do not
+    /// need to be looked up.
     pub fn from_usize(tcx: TyCtxt<'tcx>, n: u64) -> &'tcx Self {
-        Self::from_bits(tcx, n as u128, ParamEnv::empty().and(tcx.types.usize))
+        match usize::try_from(n).ok().and_then(|n| tcx.consts.small_usizes.get(n)) {
+            Some(&ct) => ct,
+            None => Self::from_scalar(tcx, Scalar::from_machine_usize(n, &tcx), tcx.types.usize),
+        }
     }
 
     #[inline]
@@ -174,6 +181,33 @@ pub fn try_eval_usize(&self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Op
         self.val.eval(tcx, param_env).try_to_machine_usize(tcx)
     }
 
+    /// Evaluates this constant as the length of an array type, caching the result. Returns
+    /// `None` if the length still depends on generic parameters.
+    #[inline]
+    pub fn try_eval_array_len(
+        &'tcx self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Result<ScalarInt, ErrorReported>> {
+        match self.val {
+            // Literal lengths are already evaluated, so there is nothing worth caching.
+            ConstKind::Value(ConstValue::Scalar(Scalar::Int(len))) => Some(Ok(len)),
+            // Inference variables must not end up in query keys.
+            _ if self.needs_infer() => array_len_scalar(tcx, param_env.and(self)),
+            _ => tcx.array_len_scalar(tcx.erase_regions(param_env.and(self))),
+        }
+    }
+
+    #[inline]
+    /// Panics if the value cannot be evaluated as the length of an array type, see
+    /// `try_eval_array_len`.
+    pub fn eval_array_len(&'tcx self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> u64 {
+        self.try_eval_array_len(tcx, param_env)
+            .and_then(Result::ok)
+            .and_then(|len| len.try_to_machine_usize(tcx).ok())
+            .unwrap_or_else(|| bug!("expected array length, got {:#?}", self))
+    }
+
     #[inline]
     /// Tries to evaluate the constant if it is `Unevaluated`. If that doesn't succeed, return the
     /// unevaluated constant.
@@ -201,6 +235,47 @@ pub fn eval_usize(&self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> u64 {
         self.try_eval_usize(tcx, param_env)
             .unwrap_or_else(|| bug!("expected usize, got {:#?}", self))
     }
+
+    /// Converts an evaluated constant to a `ValTree`. Returns `None` if the constant is not
+    /// evaluated yet, or cannot be represented as a valtree.
+    pub fn try_to_valtree(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<ValTree<'tcx>> {
+        match self.val.try_to_value()? {
+            ConstValue::Scalar(Scalar::Int(int)) if self.ty.is_scalar() => Some(ValTree::Leaf(int)),
+            ConstValue::ByRef { alloc, offset } if offset == Size::ZERO => {
+                let alloc_id = tcx.create_memory_alloc(alloc);
+                tcx.const_to_valtree(param_env.and(ConstAlloc { alloc_id, ty: self.ty }))
+            }
+            _ => None,
+        }
+    }
+
+    /// Renders an evaluated constant as a Rust expression, see `ValTree::to_user_expr`.
+    pub fn try_to_user_expr(&self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<String> {
+        Some(self.try_to_valtree(tcx, param_env)?.to_user_expr(tcx, self.ty))
+    }
+}
+
+pub fn array_len_scalar<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ParamEnvAnd<'tcx, &'tcx Const<'tcx>>,
+) -> Option<Result<ScalarInt, ErrorReported>> {
+    let (param_env, len) = key.into_parts();
+    let val = match len.val {
+        ConstKind::Value(val) => val,
+        ConstKind::Error(_) => return Some(Err(ErrorReported)),
+        kind => match kind.try_eval(tcx, param_env)? {
+            Ok(val) => val,
+            Err(ErrorReported) => return Some(Err(ErrorReported)),
+        },
+    };
+    Some(val.try_to_scalar_int().ok_or_else(|| {
+        tcx.sess.delay_span_bug(DUMMY_SP, &format!("array length `{:?}` is not an integer", len));
+        ErrorReported
+    }))
 }
 
 pub fn const_param_default<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> &'tcx Const<'tcx> {
@@ -1,14 +1,24 @@
 use rustc_apfloat::ieee::{Double, Single};
 use rustc_apfloat::Float;
+use rustc_errors::ErrorReported;
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
-use rustc_target::abi::{Size, TargetDataLayout};
+use rustc_span::Span;
+use rustc_target::abi::{Endian, Size, TargetDataLayout};
+use smallvec::SmallVec;
+use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 
-use crate::ty::TyCtxt;
+use crate::ty::{self, Ty, TyCtxt};
 
-#[derive(Copy, Clone)]
+#[cfg(test)]
+mod tests;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
 /// A type for representing any integer. Only used for printing.
+///
+/// `ConstInt`s are ordered by their numeric value, taking their signedness into account,
+/// so diagnostics can list several of them in ascending order.
 pub struct ConstInt {
     /// The "untyped" variant of `ConstInt`.
     int: ScalarInt,
@@ -22,6 +32,130 @@ impl ConstInt {
     pub fn new(int: ScalarInt, signed: bool, is_ptr_sized_integral: bool) -> Self {
         Self { int, signed, is_ptr_sized_integral }
     }
+
+    /// Returns the value of `self`, without its type.
+    #[inline]
+    pub fn int(self) -> ScalarInt {
+        self.int
+    }
+
+    /// Returns whether `self` is of a signed integer type.
+    #[inline]
+    pub fn is_signed(self) -> bool {
+        self.signed
+    }
+
+    /// Parses an integer literal as written in Rust source, like `255u8`, `-1_i32` or `0xff`.
+    /// The literal may be negated and use the `0x`, `0o` and `0b` prefixes and underscores as
+    /// digit separators. Unsuffixed literals are `i32`s, and `usize` and `isize` get the
+    /// pointer size of `dl`. Like the literal lowering, this accepts the minimum of a signed
+    /// type, e.g. `-128i8`, even though its magnitude does not fit into the type.
+    pub fn parse_literal(lit: &str, dl: &TargetDataLayout) -> Result<Self, ParseIntLiteralError> {
+        let (neg, lit) = match lit.strip_prefix('-') {
+            Some(lit) => (true, lit),
+            None => (false, lit),
+        };
+        let (radix, lit) = match lit.get(..2) {
+            Some("0x") => (16, &lit[2..]),
+            Some("0o") => (8, &lit[2..]),
+            Some("0b") => (2, &lit[2..]),
+            _ => (10, lit),
+        };
+        // Neither `i` nor `u` is a hexadecimal digit, so they always start the suffix.
+        let (digits, suffix) = match lit.find(|c| c == 'i' || c == 'u') {
+            Some(pos) => (&lit[..pos], &lit[pos..]),
+            None => (lit, "i32"),
+        };
+        let (size, signed, is_ptr_sized_integral) = match suffix {
+            "i8" => (Size::from_bits(8), true, false),
+            "i16" => (Size::from_bits(16), true, false),
+            "i32" => (Size::from_bits(32), true, false),
+            "i64" => (Size::from_bits(64), true, false),
+            "i128" => (Size::from_bits(128), true, false),
+            "isize" => (dl.pointer_size, true, true),
+            "u8" => (Size::from_bits(8), false, false),
+            "u16" => (Size::from_bits(16), false, false),
+            "u32" => (Size::from_bits(32), false, false),
+            "u64" => (Size::from_bits(64), false, false),
+            "u128" => (Size::from_bits(128), false, false),
+            "usize" => (dl.pointer_size, false, true),
+            _ => return Err(ParseIntLiteralError::Invalid),
+        };
+        if !digits.starts_with(|c: char| c.is_digit(radix)) {
+            return Err(ParseIntLiteralError::Invalid);
+        }
+        let mut n = 0_u128;
+        let mut overflow = false;
+        for c in digits.chars().filter(|&c| c != '_') {
+            let digit = c.to_digit(radix).ok_or(ParseIntLiteralError::Invalid)?;
+            let (shifted, o1) = n.overflowing_mul(radix.into());
+            let (sum, o2) = shifted.overflowing_add(digit.into());
+            n = sum;
+            overflow |= o1 || o2;
+        }
+        if neg && !signed {
+            return Err(ParseIntLiteralError::NegativeUnsigned);
+        }
+        let (int, fits) = ScalarInt::from_int_literal(n, neg, size, signed);
+        let int = Self::new(int, signed, is_ptr_sized_integral);
+        if fits && !overflow { Ok(int) } else { Err(ParseIntLiteralError::Overflow(int)) }
+    }
+
+    /// Returns a builder for rendering `self` with `Display`. By default the value is printed
+    /// in decimal without a type suffix, which is also what `ConstInt`'s own `Display` does.
+    pub fn display(self) -> ConstIntDisplay {
+        ConstIntDisplay { int: self, suffix: false, hex: false, hex_bits: false }
+    }
+
+    /// Returns the name of the integer type of `self`, e.g. `"u8"` or `"isize"`.
+    fn type_name(self) -> &'static str {
+        match (self.int.size().bytes(), self.signed, self.is_ptr_sized_integral) {
+            (_, true, true) => "isize",
+            (_, false, true) => "usize",
+            (1, true, _) => "i8",
+            (2, true, _) => "i16",
+            (4, true, _) => "i32",
+            (8, true, _) => "i64",
+            (16, true, _) => "i128",
+            (1, false, _) => "u8",
+            (2, false, _) => "u16",
+            (4, false, _) => "u32",
+            (8, false, _) => "u64",
+            (16, false, _) => "u128",
+            (size, signed, _) => {
+                bug!("ConstInt {:?} with size = {} and signed = {}", self.int, size, signed)
+            }
+        }
+    }
+
+    /// Returns a key that orders integers by their numeric value: all negative values come
+    /// first, and within each half the two's complement bits are ordered like the values.
+    fn numeric_key(self) -> (bool, u128) {
+        if self.signed {
+            let value = self.int.size().sign_extend(self.int.data) as i128;
+            (value >= 0, value as u128)
+        } else {
+            (true, self.int.data)
+        }
+    }
+}
+
+impl PartialOrd for ConstInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ConstInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Integers of different types that have the same value are ordered arbitrarily, but
+        // consistently with `Eq`.
+        self.numeric_key()
+            .cmp(&other.numeric_key())
+            .then_with(|| self.int.size().cmp(&other.int.size()))
+            .then(self.signed.cmp(&other.signed))
+            .then(self.is_ptr_sized_integral.cmp(&other.is_ptr_sized_integral))
+    }
 }
 
 impl std::fmt::Debug for ConstInt {
@@ -30,10 +164,7 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let size = int.size().bytes();
         let raw = int.data;
         if signed {
-            let bit_size = size * 8;
-            let min = 1u128 << (bit_size - 1);
-            let max = min - 1;
-            if raw == min {
+            if int.is_signed_min(int.size()) {
                 match (size, is_ptr_sized_integral) {
                     (_, true) => write!(fmt, "isize::MIN"),
                     (1, _) => write!(fmt, "i8::MIN"),
@@ -43,7 +174,7 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     (16, _) => write!(fmt, "i128::MIN"),
                     _ => bug!("ConstInt 0x{:x} with size = {} and signed = {}", raw, size, signed),
                 }
-            } else if raw == max {
+            } else if int.is_signed_max(int.size()) {
                 match (size, is_ptr_sized_integral) {
                     (_, true) => write!(fmt, "isize::MAX"),
                     (1, _) => write!(fmt, "i8::MAX"),
@@ -54,30 +185,14 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     _ => bug!("ConstInt 0x{:x} with size = {} and signed = {}", raw, size, signed),
                 }
             } else {
-                match size {
-                    1 => write!(fmt, "{}", raw as i8)?,
-                    2 => write!(fmt, "{}", raw as i16)?,
-                    4 => write!(fmt, "{}", raw as i32)?,
-                    8 => write!(fmt, "{}", raw as i64)?,
-                    16 => write!(fmt, "{}", raw as i128)?,
-                    _ => bug!("ConstInt 0x{:x} with size = {} and signed = {}", raw, size, signed),
-                }
+                write!(fmt, "{}", int.to_string_radix(10, signed))?;
                 if fmt.alternate() {
-                    match (size, is_ptr_sized_integral) {
-                        (_, true) => write!(fmt, "_isize")?,
-                        (1, _) => write!(fmt, "_i8")?,
-                        (2, _) => write!(fmt, "_i16")?,
-                        (4, _) => write!(fmt, "_i32")?,
-                        (8, _) => write!(fmt, "_i64")?,
-                        (16, _) => write!(fmt, "_i128")?,
-                        _ => bug!(),
-                    }
+                    write!(fmt, "_{}", self.type_name())?;
                 }
                 Ok(())
             }
         } else {
-            let max = Size::from_bytes(size).truncate(u128::MAX);
-            if raw == max {
+            if int.is_unsigned_max(int.size()) {
                 match (size, is_ptr_sized_integral) {
                     (_, true) => write!(fmt, "usize::MAX"),
                     (1, _) => write!(fmt, "u8::MAX"),
@@ -88,24 +203,9 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     _ => bug!("ConstInt 0x{:x} with size = {} and signed = {}", raw, size, signed),
                 }
             } else {
-                match size {
-                    1 => write!(fmt, "{}", raw as u8)?,
-                    2 => write!(fmt, "{}", raw as u16)?,
-                    4 => write!(fmt, "{}", raw as u32)?,
-                    8 => write!(fmt, "{}", raw as u64)?,
-                    16 => write!(fmt, "{}", raw as u128)?,
-                    _ => bug!("ConstInt 0x{:x} with size = {} and signed = {}", raw, size, signed),
-                }
+                write!(fmt, "{}", int.to_string_radix(10, signed))?;
                 if fmt.alternate() {
-                    match (size, is_ptr_sized_integral) {
-                        (_, true) => write!(fmt, "_usize")?,
-                        (1, _) => write!(fmt, "_u8")?,
-                        (2, _) => write!(fmt, "_u16")?,
-                        (4, _) => write!(fmt, "_u32")?,
-                        (8, _) => write!(fmt, "_u64")?,
-                        (16, _) => write!(fmt, "_u128")?,
-                        _ => bug!(),
-                    }
+                    write!(fmt, "_{}", self.type_name())?;
                 }
                 Ok(())
             }
@@ -113,6 +213,143 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
+impl std::fmt::Display for ConstInt {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display().fmt(fmt)
+    }
+}
+
+/// Error returned by `ConstInt::parse_literal`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseIntLiteralError {
+    /// The string is not an integer literal, e.g. because it has an unknown suffix or a digit
+    /// that is invalid in its radix.
+    Invalid,
+    /// The literal is negative but has an unsigned type, like `-1u8`.
+    NegativeUnsigned,
+    /// The literal does not fit into its type, like `256u8`. Carries the value the literal
+    /// wraps around to.
+    Overflow(ConstInt),
+}
+
+/// Renders a `ConstInt` for users, see `ConstInt::display`. Unlike `ConstInt`'s `Debug` impl,
+/// this never abbreviates extreme values to `MIN` or `MAX` constants.
+#[derive(Copy, Clone)]
+pub struct ConstIntDisplay {
+    int: ConstInt,
+    suffix: bool,
+    hex: bool,
+    hex_bits: bool,
+}
+
+impl ConstIntDisplay {
+    /// Whether to append the type of the integer, as in `3_u8`.
+    pub fn with_suffix(self, suffix: bool) -> Self {
+        Self { suffix, ..self }
+    }
+
+    /// Whether to print the integer in hexadecimal, as in `0xff`. Negative values are printed
+    /// as a minus sign followed by their magnitude, as in `-0x80`.
+    pub fn hex(self, hex: bool) -> Self {
+        Self { hex, ..self }
+    }
+
+    /// Whether to print the bits of the integer in hexadecimal, zero-padded to the width of its
+    /// type, as in `0x00ff` for `255_u16` or `0xff` for `-1_i8`. This takes precedence over
+    /// `hex`.
+    pub fn hex_bits(self, hex_bits: bool) -> Self {
+        Self { hex_bits, ..self }
+    }
+}
+
+impl std::fmt::Display for ConstIntDisplay {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ConstInt { int, signed, .. } = self.int;
+        if self.hex_bits {
+            write!(fmt, "0x{:x}", int)?;
+        } else {
+            let (radix, prefix) = if self.hex { (16, true) } else { (10, false) };
+            fmt.write_str(&int.render_radix(radix, signed, prefix))?;
+        }
+        if self.suffix {
+            write!(fmt, "_{}", self.int.type_name())?;
+        }
+        Ok(())
+    }
+}
+
+/// A type for representing any float. Only used for printing, like `ConstInt`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct ConstFloat {
+    /// The bits of the float.
+    int: ScalarInt,
+    /// The type of the float, which determines the size of `int`.
+    ty: ty::FloatTy,
+}
+
+impl ConstFloat {
+    pub fn new(int: ScalarInt, ty: ty::FloatTy) -> Self {
+        Self { int, ty }
+    }
+
+    /// Returns the name of the associated constant of the float type that `self` is equal to,
+    /// if any. All NaNs are called `NAN`, regardless of their sign and payload.
+    fn constant_name(self) -> Option<&'static str> {
+        macro_rules! constant_name {
+            ($ty:ident, $float:ty) => {{
+                let bits = self.int.assert_bits(Size::from_bytes(std::mem::size_of::<$ty>()));
+                let f = <$float>::from_bits(bits);
+                if f.is_nan() {
+                    return Some("NAN");
+                }
+                [
+                    ($ty::INFINITY, "INFINITY"),
+                    ($ty::NEG_INFINITY, "NEG_INFINITY"),
+                    ($ty::MAX, "MAX"),
+                    ($ty::MIN, "MIN"),
+                    ($ty::EPSILON, "EPSILON"),
+                ]
+                .iter()
+                .find(|(c, _)| u128::from(c.to_bits()) == bits)
+                .map(|&(_, name)| name)
+            }};
+        }
+        match self.ty {
+            ty::FloatTy::F32 => constant_name!(f32, Single),
+            ty::FloatTy::F64 => constant_name!(f64, Double),
+        }
+    }
+}
+
+/// Prints `self` as the name of an associated constant like `f32::MAX` if it is one, and as the
+/// shortest decimal that round-trips to the same float otherwise. The alternate mode appends a
+/// type suffix to the latter, as in `0.5_f64`.
+impl std::fmt::Debug for ConstFloat {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = self.constant_name() {
+            return write!(fmt, "{}::{}", self.ty.name_str(), name);
+        }
+        match self.ty {
+            ty::FloatTy::F32 => write!(fmt, "{}", Single::try_from(self.int).unwrap())?,
+            ty::FloatTy::F64 => write!(fmt, "{}", Double::try_from(self.int).unwrap())?,
+        }
+        if fmt.alternate() {
+            write!(fmt, "_{}", self.ty.name_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// The reason a [`ScalarInt`] could not be converted to a pointer-sized integer of the host.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MachineIntError {
+    /// The scalar does not have the target's pointer size. Carries the scalar's actual size.
+    WrongSize(Size),
+    /// The scalar has the right size, but its value does not fit into 64 bits.
+    /// Carries the raw bits of the scalar.
+    Overflow(u128),
+}
+
 /// The raw bytes of a simple value.
 ///
 /// This is a packed struct in order to allow this type to be optimally embedded in enums
@@ -139,16 +376,18 @@ fn hash_stable(&self, hcx: &mut CTX, hasher: &mut crate::ty::StableHasher) {
     }
 }
 
+// Only the `size` low bytes of `data` can be non-zero, so encode just those, in one raw write
+// instead of a variable-length `u128`. Decoding then needs a single read of a known length. The
+// incremental cache can store the fixed-width layout of `to_fixed_bytes` instead.
 impl<S: Encoder> Encodable<S> for ScalarInt {
-    fn encode(&self, s: &mut S) -> Result<(), S::Error> {
-        s.emit_u128(self.data)?;
-        s.emit_u8(self.size)
+    default fn encode(&self, s: &mut S) -> Result<(), S::Error> {
+        self.encode_compact(s)
     }
 }
 
 impl<D: Decoder> Decodable<D> for ScalarInt {
-    fn decode(d: &mut D) -> Result<ScalarInt, D::Error> {
-        Ok(ScalarInt { data: d.read_u128()?, size: d.read_u8()? })
+    default fn decode(d: &mut D) -> Result<ScalarInt, D::Error> {
+        ScalarInt::decode_compact(d)
     }
 }
 
@@ -159,6 +398,48 @@ impl ScalarInt {
 
     pub const ZST: ScalarInt = ScalarInt { data: 0_u128, size: 0 };
 
+    /// The length of the fixed-width layout of `to_fixed_bytes`.
+    pub const FIXED_ENCODED_LEN: usize = 17;
+
+    /// Encodes the size followed by the `size` low bytes of the value, in little-endian order.
+    pub fn encode_compact<S: Encoder>(self, s: &mut S) -> Result<(), S::Error> {
+        s.emit_u8(self.size)?;
+        s.emit_raw_bytes(&self.to_le_bytes())
+    }
+
+    /// Decodes the output of `encode_compact`.
+    pub fn decode_compact<D: Decoder>(d: &mut D) -> Result<ScalarInt, D::Error> {
+        let size = d.read_u8()?;
+        let mut bytes = [0; 16];
+        let len = usize::from(size);
+        if len > bytes.len() {
+            return Err(d.error(&format!("invalid `ScalarInt` size {}", size)));
+        }
+        d.read_raw_bytes_into(&mut bytes[..len])?;
+        Ok(ScalarInt::from_le_bytes(&bytes[..len]).unwrap())
+    }
+
+    /// Returns the size followed by all 16 bytes of the value, in little-endian order. Every
+    /// `ScalarInt` takes the same space in this layout, so it can be read in one go without
+    /// looking at the size first.
+    pub fn to_fixed_bytes(self) -> [u8; Self::FIXED_ENCODED_LEN] {
+        let mut bytes = [0; Self::FIXED_ENCODED_LEN];
+        bytes[0] = self.size;
+        bytes[1..].copy_from_slice(&{ self.data }.to_le_bytes());
+        bytes
+    }
+
+    /// Reads the output of `to_fixed_bytes`. Returns `None` if the size is larger than 16 bytes
+    /// or the value does not fit in it.
+    pub fn from_fixed_bytes(bytes: [u8; Self::FIXED_ENCODED_LEN]) -> Option<Self> {
+        let size = bytes[0];
+        let data = u128::from_le_bytes(bytes[1..].try_into().unwrap());
+        if size > 16 || Size::from_bytes(size).truncate(data) != data {
+            return None;
+        }
+        Some(ScalarInt { data, size })
+    }
+
     #[inline]
     pub fn size(self) -> Size {
         Size::from_bytes(self.size)
@@ -183,9 +464,18 @@ fn check_data(self) {
         );
     }
 
+    /// Converts `size` to the representation of the `size` field. Any size of at most 16 bytes is
+    /// allowed, including ones like 3 or 7 bytes that no Rust integer type has, so a `ScalarInt`
+    /// can also hold the value of a `_BitInt(N)` or an LLVM `iN` padded to whole bytes.
+    #[inline]
+    fn size_field(size: Size) -> u8 {
+        assert!(size.bytes() <= 16, "`ScalarInt` cannot hold {} bytes", size.bytes());
+        size.bytes() as u8
+    }
+
     #[inline]
     pub fn null(size: Size) -> Self {
-        Self { data: 0, size: size.bytes() as u8 }
+        Self { data: 0, size: Self::size_field(size) }
     }
 
     #[inline]
@@ -206,7 +496,7 @@ pub(crate) fn ptr_sized_op<E>(
     pub fn try_from_uint(i: impl Into<u128>, size: Size) -> Option<Self> {
         let data = i.into();
         if size.truncate(data) == data {
-            Some(Self { data, size: size.bytes() as u8 })
+            Some(Self { data, size: Self::size_field(size) })
         } else {
             None
         }
@@ -218,12 +508,79 @@ pub fn try_from_int(i: impl Into<i128>, size: Size) -> Option<Self> {
         // `into` performed sign extension, we have to truncate
         let truncated = size.truncate(i as u128);
         if size.sign_extend(truncated) as i128 == i {
-            Some(Self { data: truncated, size: size.bytes() as u8 })
+            Some(Self { data: truncated, size: Self::size_field(size) })
         } else {
             None
         }
     }
 
+    /// Creates a `ScalarInt` of the given size for an integer literal with the magnitude `n`,
+    /// which is negated if `neg` is set. Returns the value the literal evaluates to, wrapping
+    /// around if necessary, together with whether the literal fits into the signed (if `signed`
+    /// is set) or unsigned integer type of that size.
+    #[inline]
+    pub fn from_int_literal(n: u128, neg: bool, size: Size, signed: bool) -> (Self, bool) {
+        let data = size.truncate(if neg { n.wrapping_neg() } else { n });
+        let fits = if signed {
+            // The magnitude of the minimum is one more than the maximum, e.g. 128 for `i8`.
+            let max = size.signed_int_max() as u128;
+            if neg { n <= max + 1 } else { n <= max }
+        } else {
+            n <= size.unsigned_int_max()
+        };
+        (Self { data, size: Self::size_field(size) }, fits)
+    }
+
+    /// Returns the `size` bytes of `self` in little-endian order.
+    #[inline]
+    pub fn to_le_bytes(self) -> SmallVec<[u8; 16]> {
+        SmallVec::from_slice(&{ self.data }.to_le_bytes()[..usize::from(self.size)])
+    }
+
+    /// Returns the `size` bytes of `self` in big-endian order.
+    #[inline]
+    pub fn to_be_bytes(self) -> SmallVec<[u8; 16]> {
+        SmallVec::from_slice(&{ self.data }.to_be_bytes()[16 - usize::from(self.size)..])
+    }
+
+    /// Returns the `size` bytes of `self` in the byte order of the target, i.e. the way `self`
+    /// is laid out in the target's memory.
+    #[inline]
+    pub fn to_target_endian_bytes(self, dl: &TargetDataLayout) -> SmallVec<[u8; 16]> {
+        match dl.endian {
+            Endian::Little => self.to_le_bytes(),
+            Endian::Big => self.to_be_bytes(),
+        }
+    }
+
+    /// Creates a `ScalarInt` from its bytes in little-endian order. Its size is the number of
+    /// bytes, so this returns `None` if there are more than 16 of them.
+    #[inline]
+    pub fn from_le_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut buf = [0; 16];
+        buf.get_mut(..bytes.len())?.copy_from_slice(bytes);
+        Some(Self { data: u128::from_le_bytes(buf), size: bytes.len() as u8 })
+    }
+
+    /// Creates a `ScalarInt` from its bytes in big-endian order, see `from_le_bytes`.
+    #[inline]
+    pub fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut buf = [0; 16];
+        let start = 16_usize.checked_sub(bytes.len())?;
+        buf[start..].copy_from_slice(bytes);
+        Some(Self { data: u128::from_be_bytes(buf), size: bytes.len() as u8 })
+    }
+
+    /// Creates a `ScalarInt` from its bytes in the byte order of the target, see
+    /// `from_le_bytes`.
+    #[inline]
+    pub fn from_target_endian_bytes(bytes: &[u8], dl: &TargetDataLayout) -> Option<Self> {
+        match dl.endian {
+            Endian::Little => Self::from_le_bytes(bytes),
+            Endian::Big => Self::from_be_bytes(bytes),
+        }
+    }
+
     #[inline]
     pub fn assert_bits(self, target_size: Size) -> u128 {
         self.to_bits(target_size).unwrap_or_else(|size| {
@@ -231,6 +588,30 @@ pub fn assert_bits(self, target_size: Size) -> u128 {
         })
     }
 
+    /// Like [`ScalarInt::assert_bits`], but instead of aborting on a size mismatch, this reports
+    /// a delayed bug at `span` that mentions the offending scalar. Meant for code paths like
+    /// diagnostics and pretty-printing, which can degrade gracefully on malformed constants.
+    #[inline]
+    pub fn try_assert_bits(
+        self,
+        tcx: TyCtxt<'_>,
+        span: Span,
+        target_size: Size,
+    ) -> Result<u128, ErrorReported> {
+        self.to_bits(target_size).map_err(|size| {
+            tcx.sess.delay_span_bug(
+                span,
+                &format!(
+                    "expected int of size {}, but got `{:?}` of size {}",
+                    target_size.bytes(),
+                    self,
+                    size.bytes()
+                ),
+            );
+            ErrorReported
+        })
+    }
+
     #[inline]
     pub fn to_bits(self, target_size: Size) -> Result<u128, Size> {
         assert_ne!(target_size.bytes(), 0, "you should never look at the bits of a ZST");
@@ -242,9 +623,424 @@ pub fn to_bits(self, target_size: Size) -> Result<u128, Size> {
         }
     }
 
+    /// Like `to_bits`, but describes a size mismatch by a `ScalarIntCastError`, for the
+    /// conversions to primitive types.
+    #[inline]
+    fn cast_bits(self, expected: Size) -> Result<u128, ScalarIntCastError> {
+        self.to_bits(expected).map_err(|actual| ScalarIntCastError::SizeMismatch {
+            expected,
+            actual,
+            bits: self.data,
+        })
+    }
+
+    /// Converts a pointer-sized integer to a `u64`.
+    ///
+    /// Fails if `self` does not have the target's pointer size, or if its value does not fit
+    /// into a `u64`.
     #[inline]
-    pub fn try_to_machine_usize(&self, tcx: TyCtxt<'tcx>) -> Result<u64, Size> {
-        Ok(self.to_bits(tcx.data_layout.pointer_size)? as u64)
+    pub fn try_to_machine_usize(&self, tcx: TyCtxt<'tcx>) -> Result<u64, MachineIntError> {
+        let bits =
+            self.to_bits(tcx.data_layout.pointer_size).map_err(MachineIntError::WrongSize)?;
+        u64::try_from(bits).map_err(|_| MachineIntError::Overflow(bits))
+    }
+
+    /// Converts a pointer-sized integer to an `i64`, interpreting it as signed.
+    ///
+    /// Fails if `self` does not have the target's pointer size, or if its value does not fit
+    /// into an `i64`.
+    #[inline]
+    pub fn try_to_machine_isize(&self, tcx: TyCtxt<'tcx>) -> Result<i64, MachineIntError> {
+        let size = tcx.data_layout.pointer_size;
+        let bits = self.to_bits(size).map_err(MachineIntError::WrongSize)?;
+        i64::try_from(size.sign_extend(bits) as i128).map_err(|_| MachineIntError::Overflow(bits))
+    }
+
+    /// Returns the value following `self` in the integer type of `self`'s size, which is signed
+    /// if `signed` is set. Returns `None` if `self` is the maximum value of that type.
+    #[inline]
+    pub fn checked_succ(self, signed: bool) -> Option<Self> {
+        let size = self.size();
+        if size == Size::ZERO {
+            return None;
+        }
+        let is_max = if signed { self.is_signed_max(size) } else { self.is_unsigned_max(size) };
+        if is_max {
+            return None;
+        }
+        Some(Self { data: size.truncate(self.data.wrapping_add(1)), size: self.size })
+    }
+
+    /// Returns the value preceding `self` in the integer type of `self`'s size, which is signed
+    /// if `signed` is set. Returns `None` if `self` is the minimum value of that type.
+    #[inline]
+    pub fn checked_pred(self, signed: bool) -> Option<Self> {
+        let size = self.size();
+        if size == Size::ZERO {
+            return None;
+        }
+        let is_min = if signed { self.is_signed_min(size) } else { self.is_null() };
+        if is_min {
+            return None;
+        }
+        Some(Self { data: size.truncate(self.data.wrapping_sub(1)), size: self.size })
+    }
+
+    /// Applies `op` to `self` and `rhs`, read as signed or unsigned integers of their common size.
+    /// Returns the result wrapped around to that size, and whether the operation overflowed.
+    fn overflowing_op(
+        self,
+        rhs: Self,
+        signed: bool,
+        op_signed: impl FnOnce(i128, i128) -> (i128, bool),
+        op_unsigned: impl FnOnce(u128, u128) -> (u128, bool),
+    ) -> (Self, bool) {
+        let size = self.size();
+        if size != rhs.size() {
+            bug!("arithmetic on ScalarInts of different sizes: {:?} and {:?}", self, rhs);
+        }
+        let (data, overflow) = if signed {
+            let l = size.sign_extend(self.data) as i128;
+            let r = size.sign_extend(rhs.data) as i128;
+            let (res, overflow) = op_signed(l, r);
+            let truncated = size.truncate(res as u128);
+            // If the truncation loses information, the operation overflowed at this size.
+            (truncated, overflow || size.sign_extend(truncated) as i128 != res)
+        } else {
+            let (res, overflow) = op_unsigned(self.data, rhs.data);
+            let truncated = size.truncate(res);
+            (truncated, overflow || truncated != res)
+        };
+        (Self { data, size: self.size }, overflow)
+    }
+
+    /// Adds `rhs` to `self`, which must have the same size, treating both as signed integers if
+    /// `signed` is set. Returns the wrapped result and whether the addition overflowed.
+    #[inline]
+    pub fn checked_add(self, rhs: Self, signed: bool) -> (Self, bool) {
+        self.overflowing_op(rhs, signed, i128::overflowing_add, u128::overflowing_add)
+    }
+
+    /// Subtracts `rhs` from `self`, see `checked_add`.
+    #[inline]
+    pub fn checked_sub(self, rhs: Self, signed: bool) -> (Self, bool) {
+        self.overflowing_op(rhs, signed, i128::overflowing_sub, u128::overflowing_sub)
+    }
+
+    /// Multiplies `self` by `rhs`, see `checked_add`.
+    #[inline]
+    pub fn checked_mul(self, rhs: Self, signed: bool) -> (Self, bool) {
+        self.overflowing_op(rhs, signed, i128::overflowing_mul, u128::overflowing_mul)
+    }
+
+    /// Divides `self` by `rhs`, see `checked_add`. Returns `None` if `rhs` is zero. The only
+    /// overflowing division is that of the signed minimum by `-1`.
+    #[inline]
+    pub fn checked_div(self, rhs: Self, signed: bool) -> Option<(Self, bool)> {
+        if rhs.is_null() {
+            return None;
+        }
+        Some(self.overflowing_op(rhs, signed, i128::overflowing_div, u128::overflowing_div))
+    }
+
+    /// Computes the remainder of dividing `self` by `rhs`, see `checked_div`. Like the
+    /// primitive integers, the remainder of the signed minimum by `-1` counts as overflowing.
+    #[inline]
+    pub fn checked_rem(self, rhs: Self, signed: bool) -> Option<(Self, bool)> {
+        if rhs.is_null() {
+            return None;
+        }
+        let (res, overflow) =
+            self.overflowing_op(rhs, signed, i128::overflowing_rem, u128::overflowing_rem);
+        let size = self.size();
+        let div_overflows = signed && self.is_signed_min(size) && rhs.is_unsigned_max(size);
+        Some((res, overflow || div_overflows))
+    }
+
+    /// Zero-extends or truncates `self` to `size`, reading it as an unsigned integer. Returns the
+    /// resized value and whether any nonzero bits were cut off.
+    #[inline]
+    pub fn zext_to(self, size: Size) -> (Self, bool) {
+        let data = size.truncate(self.data);
+        (Self { data, size: Self::size_field(size) }, data != self.data)
+    }
+
+    /// Sign-extends or truncates `self` to `size`, reading it as a signed integer. Returns the
+    /// resized value and whether it no longer denotes the same signed integer.
+    #[inline]
+    pub fn sext_to(self, size: Size) -> (Self, bool) {
+        let value = self.size().sign_extend(self.data);
+        let data = size.truncate(value);
+        (Self { data, size: Self::size_field(size) }, size.sign_extend(data) != value)
+    }
+
+    /// Truncates `self` to `size`, which must not be larger than `self`'s size. Returns the
+    /// truncated value and whether any nonzero bits were cut off.
+    #[inline]
+    pub fn truncate_to(self, size: Size) -> (Self, bool) {
+        if size > self.size() {
+            bug!("cannot truncate {:?} to the larger size {}", self, size.bytes());
+        }
+        self.zext_to(size)
+    }
+
+    /// Checks that `self` and `rhs` have the same size, for the binary bitwise operations.
+    #[inline]
+    fn assert_same_size(self, rhs: Self) {
+        if self.size() != rhs.size() {
+            bug!("bitwise operation on ScalarInts of different sizes: {:?} and {:?}", self, rhs);
+        }
+    }
+
+    /// Computes the bitwise AND of `self` and `rhs`, which must have the same size.
+    #[inline]
+    pub fn and(self, rhs: Self) -> Self {
+        self.assert_same_size(rhs);
+        Self { data: self.data & rhs.data, size: self.size }
+    }
+
+    /// Computes the bitwise OR of `self` and `rhs`, which must have the same size.
+    #[inline]
+    pub fn or(self, rhs: Self) -> Self {
+        self.assert_same_size(rhs);
+        Self { data: self.data | rhs.data, size: self.size }
+    }
+
+    /// Computes the bitwise XOR of `self` and `rhs`, which must have the same size.
+    #[inline]
+    pub fn xor(self, rhs: Self) -> Self {
+        self.assert_same_size(rhs);
+        Self { data: self.data ^ rhs.data, size: self.size }
+    }
+
+    /// Computes the bitwise negation of `self`, keeping only the bits within its size.
+    #[inline]
+    pub fn bitwise_not(self) -> Self {
+        Self { data: self.size().truncate(!self.data), size: self.size }
+    }
+
+    /// Reduces a shift amount modulo the bit width of `self`, returning the masked amount and
+    /// whether the original amount was out of range.
+    #[inline]
+    fn mask_shift_amount(self, amount: u128) -> (u32, bool) {
+        let bits = u128::from(self.size().bits());
+        assert_ne!(bits, 0, "cannot shift a zero-sized ScalarInt");
+        // We masked so this will always fit.
+        (u32::try_from(amount % bits).unwrap(), amount >= bits)
+    }
+
+    /// Shifts `self` left by `amount` bits. Like the `Shl` MIR operator, the amount is masked to
+    /// the bit width of `self`, and the returned flag tells whether masking was necessary.
+    #[inline]
+    pub fn overflowing_shl(self, amount: u128) -> (Self, bool) {
+        let (amount, overflow) = self.mask_shift_amount(amount);
+        let data = self.size().truncate(self.data << amount);
+        (Self { data, size: self.size }, overflow)
+    }
+
+    /// Shifts `self` right by `amount` bits, masking the amount like `overflowing_shl`. If
+    /// `signed` is set, this is an arithmetic shift that fills in copies of the sign bit,
+    /// otherwise it is a logical shift that fills in zeros.
+    #[inline]
+    pub fn overflowing_shr(self, amount: u128, signed: bool) -> (Self, bool) {
+        let (amount, overflow) = self.mask_shift_amount(amount);
+        let size = self.size();
+        let data = if signed {
+            size.truncate((size.sign_extend(self.data) as i128 >> amount) as u128)
+        } else {
+            self.data >> amount
+        };
+        (Self { data, size: self.size }, overflow)
+    }
+
+    /// Returns the number of bits of the 128-bit `data` field that lie outside of `self`'s size.
+    #[inline]
+    fn unused_bits(self) -> u32 {
+        assert_ne!(self.size, 0, "cannot count the bits of a zero-sized ScalarInt");
+        128 - self.size().bits() as u32
+    }
+
+    /// Returns the number of ones in the binary representation of `self`.
+    #[inline]
+    pub fn count_ones(self) -> u32 {
+        self.data.count_ones()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of `self`, counting
+    /// only the bits within its size.
+    #[inline]
+    pub fn leading_zeros(self) -> u32 {
+        self.data.leading_zeros() - self.unused_bits()
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of `self`. This is the
+    /// bit width of `self` if `self` is zero.
+    #[inline]
+    pub fn trailing_zeros(self) -> u32 {
+        (self.data << self.unused_bits()).trailing_zeros() - self.unused_bits()
+    }
+
+    /// Rotates the bits of `self` left by `amount`, modulo the bit width of `self`.
+    #[inline]
+    pub fn rotate_left(self, amount: u128) -> Self {
+        let (amount, _) = self.mask_shift_amount(amount);
+        let bits = 128 - self.unused_bits();
+        // Rotating by the full width is a no-op, but shifting by it would overflow.
+        let data = if amount == 0 {
+            self.data
+        } else {
+            self.size().truncate(self.data << amount) | (self.data >> (bits - amount))
+        };
+        Self { data, size: self.size }
+    }
+
+    /// Rotates the bits of `self` right by `amount`, modulo the bit width of `self`.
+    #[inline]
+    pub fn rotate_right(self, amount: u128) -> Self {
+        let (amount, _) = self.mask_shift_amount(amount);
+        let bits = 128 - self.unused_bits();
+        self.rotate_left(u128::from((bits - amount) % bits))
+    }
+
+    /// Reverses the byte order of `self`, considering only the bytes within its size.
+    #[inline]
+    pub fn swap_bytes(self) -> Self {
+        Self { data: (self.data << self.unused_bits()).swap_bytes(), size: self.size }
+    }
+
+    /// Reverses the order of the bits of `self`, considering only the bits within its size.
+    #[inline]
+    pub fn reverse_bits(self) -> Self {
+        Self { data: (self.data << self.unused_bits()).reverse_bits(), size: self.size }
+    }
+
+    /// Returns the `char` following `self`, skipping the surrogate code points
+    /// `0xD800..=0xDFFF`. Returns `None` if `self` is not a valid `char` or is `char::MAX`.
+    #[inline]
+    pub fn char_succ(self) -> Option<Self> {
+        let next = match u32::from(char::try_from(self).ok()?) {
+            0xD7FF => 0xE000,
+            c => c + 1,
+        };
+        char::from_u32(next).map(Self::from)
+    }
+
+    /// Returns the `char` preceding `self`, skipping the surrogate code points
+    /// `0xD800..=0xDFFF`. Returns `None` if `self` is not a valid `char` or is `'\0'`.
+    #[inline]
+    pub fn char_pred(self) -> Option<Self> {
+        let prev = match u32::from(char::try_from(self).ok()?) {
+            0xE000 => 0xD7FF,
+            c => c.checked_sub(1)?,
+        };
+        char::from_u32(prev).map(Self::from)
+    }
+
+    /// Returns the bits of `self`, with the sign bit flipped if `signed` is set. This encoding
+    /// orders integers numerically when comparing the results as unsigned numbers: for a size
+    /// of one byte, `i8::MIN` is encoded as `0` and `i8::MAX` as `255`.
+    #[inline]
+    pub fn to_order_preserving_bits(self, signed: bool) -> u128 {
+        self.check_data();
+        if signed { self.data ^ (1 << (self.size().bits() - 1)) } else { self.data }
+    }
+
+    /// The inverse of [`ScalarInt::to_order_preserving_bits`]. Returns `None` if `bits` do not
+    /// fit into `size`.
+    #[inline]
+    pub fn from_order_preserving_bits(bits: u128, size: Size, signed: bool) -> Option<Self> {
+        let int = Self::try_from_uint(bits, size)?;
+        if signed {
+            Some(Self { data: int.data ^ (1 << (size.bits() - 1)), size: int.size })
+        } else {
+            Some(int)
+        }
+    }
+
+    /// Returns whether `self` has the given size and is the minimum value of the signed integer
+    /// type of that size, e.g. `i8::MIN` for a size of one byte.
+    #[inline]
+    pub fn is_signed_min(self, size: Size) -> bool {
+        self.to_bits(size)
+            .map_or(false, |bits| bits == size.truncate(size.signed_int_min() as u128))
+    }
+
+    /// Returns whether `self` has the given size and is the maximum value of the signed integer
+    /// type of that size, e.g. `i8::MAX` for a size of one byte.
+    #[inline]
+    pub fn is_signed_max(self, size: Size) -> bool {
+        self.to_bits(size).map_or(false, |bits| bits == size.signed_int_max() as u128)
+    }
+
+    /// Returns whether `self` has the given size and is the maximum value of the unsigned
+    /// integer type of that size, e.g. `u8::MAX` for a size of one byte.
+    #[inline]
+    pub fn is_unsigned_max(self, size: Size) -> bool {
+        self.to_bits(size).map_or(false, |bits| bits == size.unsigned_int_max())
+    }
+
+    /// Renders the value of `self` in the given radix, which must be 2, 8, 10 or 16. If `signed`
+    /// is set, the value is interpreted as a signed integer of `self`'s size, and negative
+    /// values are prefixed with a minus sign. Digits above 9 are rendered in lowercase.
+    pub fn to_string_radix(self, radix: u32, signed: bool) -> String {
+        self.render_radix(radix, signed, false)
+    }
+
+    /// Like [`ScalarInt::to_string_radix`], but prefixes the digits with `0b`, `0o` or `0x`
+    /// for radices other than 10, e.g. `-0x80` for `i8::MIN` in radix 16.
+    pub fn to_prefixed_string_radix(self, radix: u32, signed: bool) -> String {
+        self.render_radix(radix, signed, true)
+    }
+
+    fn render_radix(self, radix: u32, signed: bool, prefix: bool) -> String {
+        self.check_data();
+        let (negative, magnitude) = if signed && self.size != 0 {
+            let value = self.size().sign_extend(self.data) as i128;
+            (value < 0, value.unsigned_abs())
+        } else {
+            (false, self.data)
+        };
+        let sign = if negative { "-" } else { "" };
+        let prefix_of = |p| if prefix { p } else { "" };
+        match radix {
+            2 => format!("{}{}{:b}", sign, prefix_of("0b"), magnitude),
+            8 => format!("{}{}{:o}", sign, prefix_of("0o"), magnitude),
+            10 => format!("{}{}", sign, magnitude),
+            16 => format!("{}{}{:x}", sign, prefix_of("0x"), magnitude),
+            _ => bug!("unsupported radix {} for rendering `{:?}`", radix, self),
+        }
+    }
+
+    /// Renders `self` as a literal of type `ty` that is valid Rust syntax, for structured
+    /// suggestions that splice constants into user code. Integers are suffixed with their type,
+    /// characters are written as `'\u{..}'` escapes, and negative integers are parenthesized if
+    /// `parenthesize_negative` is set, e.g. because the literal will be a method receiver.
+    /// Returns `None` if `ty` is not a boolean, character or integer type, or if `self` is not
+    /// a valid value of it.
+    pub fn to_literal_suggestion(self, ty: Ty<'_>, parenthesize_negative: bool) -> Option<String> {
+        let size = self.size();
+        let lit = match *ty.kind() {
+            ty::Bool => bool::try_from(self).ok()?.to_string(),
+            ty::Char => format!("'\\u{{{:X}}}'", u32::from(char::try_from(self).ok()?)),
+            ty::Int(ity) => {
+                let value = size.sign_extend(self.to_bits(size).ok()?) as i128;
+                let lit = format!("{}{}", value, ity.name_str());
+                if value < 0 && parenthesize_negative { format!("({})", lit) } else { lit }
+            }
+            ty::Uint(uty) => format!("{}{}", self.to_bits(size).ok()?, uty.name_str()),
+            _ => return None,
+        };
+        Some(lit)
+    }
+
+    /// Returns the bits in which `self` and `other` differ, as a value of the same size.
+    /// Returns `None` if the two values do not have the same size.
+    #[inline]
+    pub fn differing_bits(self, other: ScalarInt) -> Option<ScalarInt> {
+        if self.size() != other.size() {
+            return None;
+        }
+        Some(Self { data: self.data ^ other.data, size: self.size })
     }
 }
 
@@ -268,12 +1064,12 @@ macro_rules! try_from {
     ($($ty:ty),*) => {
         $(
             impl TryFrom<ScalarInt> for $ty {
-                type Error = Size;
+                type Error = ScalarIntCastError;
                 #[inline]
-                fn try_from(int: ScalarInt) -> Result<Self, Size> {
-                    // The `unwrap` cannot fail because to_bits (if it succeeds)
+                fn try_from(int: ScalarInt) -> Result<Self, ScalarIntCastError> {
+                    // The `unwrap` cannot fail because cast_bits (if it succeeds)
                     // is guaranteed to return a value that fits into the size.
-                    int.to_bits(Size::from_bytes(std::mem::size_of::<$ty>()))
+                    int.cast_bits(Size::from_bytes(std::mem::size_of::<$ty>()))
                        .map(|u| u.try_into().unwrap())
                 }
             }
@@ -281,18 +1077,78 @@ fn try_from(int: ScalarInt) -> Result<Self, Size> {
     }
 }
 
+// A `ScalarInt` is only equal to a primitive integer if it has the primitive's size and, read
+// with the primitive's signedness, the same value. This makes `int == 0u32` false for an `int`
+// of any size other than 4 bytes, instead of ignoring the size like comparing raw bits would.
+macro_rules! partial_eq {
+    ($($ty:ty),*) => {
+        $(
+            impl PartialEq<$ty> for ScalarInt {
+                #[inline]
+                fn eq(&self, other: &$ty) -> bool {
+                    let size = Size::from_bytes(std::mem::size_of::<$ty>());
+                    // Truncation to `size` makes this correct for negative values, too.
+                    self.to_bits(size).map_or(false, |bits| bits == size.truncate(*other as u128))
+                }
+            }
+
+            impl PartialEq<ScalarInt> for $ty {
+                #[inline]
+                fn eq(&self, other: &ScalarInt) -> bool {
+                    other == self
+                }
+            }
+        )*
+    }
+}
+
 from!(u8, u16, u32, u64, u128, bool);
 try_from!(u8, u16, u32, u64, u128);
+partial_eq!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Error returned by the conversions of a `ScalarInt` to primitive types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScalarIntCastError {
+    /// The scalar does not have the size of the target type.
+    SizeMismatch { expected: Size, actual: Size, bits: u128 },
+    /// The scalar has the size of a `bool`, but is neither 0 nor 1.
+    InvalidBool(u8),
+    /// The scalar has the size of a `char`, but is not a valid unicode scalar value.
+    InvalidChar(u32),
+}
+
+impl fmt::Display for ScalarIntCastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ScalarIntCastError::SizeMismatch { expected, actual, bits } => write!(
+                f,
+                "expected a scalar of size {}, but got 0x{:x} of size {}",
+                expected.bytes(),
+                bits,
+                actual.bytes()
+            ),
+            ScalarIntCastError::InvalidBool(b) => {
+                write!(f, "0x{:02x} is not a valid value for a `bool`", b)
+            }
+            ScalarIntCastError::InvalidChar(c) => {
+                write!(f, "0x{:08x} is not a valid value for a `char`", c)
+            }
+        }
+    }
+}
 
 impl TryFrom<ScalarInt> for bool {
-    type Error = Size;
+    type Error = ScalarIntCastError;
     #[inline]
-    fn try_from(int: ScalarInt) -> Result<Self, Size> {
-        int.to_bits(Size::from_bytes(1)).and_then(|u| match u {
+    fn try_from(int: ScalarInt) -> Result<Self, ScalarIntCastError> {
+        let bits = int.cast_bits(Size::from_bytes(1))?;
+        match bits {
             0 => Ok(false),
             1 => Ok(true),
-            _ => Err(Size::from_bytes(1)),
-        })
+            // The cast cannot truncate because `cast_bits` (if it succeeds)
+            // is guaranteed to return a value that fits into the size.
+            _ => Err(ScalarIntCastError::InvalidBool(bits as u8)),
+        }
     }
 }
 
@@ -304,11 +1160,14 @@ fn from(c: char) -> Self {
 }
 
 impl TryFrom<ScalarInt> for char {
-    type Error = Size;
+    type Error = ScalarIntCastError;
     #[inline]
-    fn try_from(int: ScalarInt) -> Result<Self, Size> {
-        int.to_bits(Size::from_bytes(std::mem::size_of::<char>()))
-            .map(|u| char::from_u32(u.try_into().unwrap()).unwrap())
+    fn try_from(int: ScalarInt) -> Result<Self, ScalarIntCastError> {
+        let bits = int.cast_bits(Size::from_bytes(std::mem::size_of::<char>()))?;
+        // The `unwrap` cannot fail because `cast_bits` (if it succeeds)
+        // is guaranteed to return a value that fits into the size.
+        let c: u32 = bits.try_into().unwrap();
+        char::from_u32(c).ok_or(ScalarIntCastError::InvalidChar(c))
     }
 }
 
@@ -321,10 +1180,10 @@ fn from(f: Single) -> Self {
 }
 
 impl TryFrom<ScalarInt> for Single {
-    type Error = Size;
+    type Error = ScalarIntCastError;
     #[inline]
-    fn try_from(int: ScalarInt) -> Result<Self, Size> {
-        int.to_bits(Size::from_bytes(4)).map(Self::from_bits)
+    fn try_from(int: ScalarInt) -> Result<Self, ScalarIntCastError> {
+        int.cast_bits(Size::from_bytes(4)).map(Self::from_bits)
     }
 }
 
@@ -337,10 +1196,34 @@ fn from(f: Double) -> Self {
 }
 
 impl TryFrom<ScalarInt> for Double {
-    type Error = Size;
+    type Error = ScalarIntCastError;
+    #[inline]
+    fn try_from(int: ScalarInt) -> Result<Self, ScalarIntCastError> {
+        int.cast_bits(Size::from_bytes(8)).map(Self::from_bits)
+    }
+}
+
+/// Reinterprets the bits of a 4 byte scalar as a host `f32`, without any rounding or NaN
+/// canonicalization. This is meant for handing immediates to backend APIs that take host floats;
+/// const evaluation itself must use `Single` to stay independent of the host.
+impl TryFrom<ScalarInt> for f32 {
+    type Error = ScalarIntCastError;
+    #[inline]
+    fn try_from(int: ScalarInt) -> Result<Self, ScalarIntCastError> {
+        // The `unwrap` cannot fail because `cast_bits` (if it succeeds)
+        // is guaranteed to return a value that fits into the size.
+        int.cast_bits(Size::from_bytes(4)).map(|u| f32::from_bits(u.try_into().unwrap()))
+    }
+}
+
+/// Reinterprets the bits of an 8 byte scalar as a host `f64`, see `TryFrom<ScalarInt> for f32`.
+impl TryFrom<ScalarInt> for f64 {
+    type Error = ScalarIntCastError;
     #[inline]
-    fn try_from(int: ScalarInt) -> Result<Self, Size> {
-        int.to_bits(Size::from_bytes(8)).map(Self::from_bits)
+    fn try_from(int: ScalarInt) -> Result<Self, ScalarIntCastError> {
+        // The `unwrap` cannot fail because `cast_bits` (if it succeeds)
+        // is guaranteed to return a value that fits into the size.
+        int.cast_bits(Size::from_bytes(8)).map(|u| f64::from_bits(u.try_into().unwrap()))
     }
 }
 
@@ -351,7 +1234,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             write!(f, "<ZST>")
         } else {
             // Dispatch to LowerHex below.
-            write!(f, "0x{:x}", self)
+            write!(f, "0x{:x}", self)?;
+            // The alternate form also shows the size, which is what size mismatches are about.
+            if f.alternate() { write!(f, " ({} bytes)", { self.size }) } else { Ok(()) }
         }
     }
 }
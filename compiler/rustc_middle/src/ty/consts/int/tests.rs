@@ -0,0 +1,165 @@
+use super::*;
+use rustc_serialize::opaque;
+
+/// A value of `size` bytes with distinct nonzero bytes, so byte order mistakes are visible.
+fn sample(size: u64) -> ScalarInt {
+    let bytes: Vec<u8> = (1..=size as u8).collect();
+    ScalarInt::from_le_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn le_bytes_round_trip() {
+    for size in 1..=16 {
+        let int = sample(size);
+        let bytes = int.to_le_bytes();
+        assert_eq!(bytes.len() as u64, size);
+        assert_eq!(bytes[0], 1);
+        assert_eq!(ScalarInt::from_le_bytes(&bytes), Some(int));
+    }
+}
+
+#[test]
+fn be_bytes_round_trip() {
+    for size in 1..=16 {
+        let int = sample(size);
+        let bytes = int.to_be_bytes();
+        assert_eq!(bytes.len() as u64, size);
+        assert_eq!(bytes[0], size as u8);
+        assert_eq!(ScalarInt::from_be_bytes(&bytes), Some(int));
+    }
+}
+
+#[test]
+fn target_endian_bytes_round_trip() {
+    for &endian in &[Endian::Little, Endian::Big] {
+        let dl = TargetDataLayout { endian, ..TargetDataLayout::default() };
+        for size in 1..=16 {
+            let int = sample(size);
+            let bytes = int.to_target_endian_bytes(&dl);
+            let expected = match endian {
+                Endian::Little => int.to_le_bytes(),
+                Endian::Big => int.to_be_bytes(),
+            };
+            assert_eq!(bytes, expected);
+            assert_eq!(ScalarInt::from_target_endian_bytes(&bytes, &dl), Some(int));
+        }
+    }
+}
+
+#[test]
+fn from_bytes_rejects_too_many_bytes() {
+    assert_eq!(ScalarInt::from_le_bytes(&[0; 17]), None);
+    assert_eq!(ScalarInt::from_be_bytes(&[0; 17]), None);
+}
+
+fn parse(lit: &str) -> Result<ConstInt, ParseIntLiteralError> {
+    let dl = TargetDataLayout { pointer_size: Size::from_bits(64), ..TargetDataLayout::default() };
+    ConstInt::parse_literal(lit, &dl)
+}
+
+#[test]
+fn parse_literal_types() {
+    let int = parse("255u8").unwrap();
+    assert_eq!((int.int(), int.is_signed()), (ScalarInt::from(255_u8), false));
+    let int = parse("-1_i32").unwrap();
+    assert_eq!((int.int(), int.is_signed()), (ScalarInt::from(u32::MAX), true));
+    let int = parse("0xff").unwrap();
+    assert_eq!((int.int(), int.is_signed()), (ScalarInt::from(255_u32), true));
+    assert_eq!(parse("0b1_0usize").unwrap().int(), ScalarInt::from(2_u64));
+    assert_eq!(parse("0o17isize").unwrap().int(), ScalarInt::from(15_u64));
+}
+
+#[test]
+fn parse_literal_signed_min() {
+    let int = parse("-170141183460469231731687303715884105728i128").unwrap();
+    assert_eq!(int.int(), ScalarInt::from(1_u128 << 127));
+    assert!(parse("-128i8").is_ok());
+    assert!(matches!(parse("128i8"), Err(ParseIntLiteralError::Overflow(_))));
+}
+
+#[test]
+fn parse_literal_errors() {
+    assert_eq!(parse("256u8"), Err(ParseIntLiteralError::Overflow(parse("0u8").unwrap())));
+    assert_eq!(parse("-1u8"), Err(ParseIntLiteralError::NegativeUnsigned));
+    assert!(matches!(
+        parse("340282366920938463463374607431768211456u128"),
+        Err(ParseIntLiteralError::Overflow(_))
+    ));
+    assert_eq!(parse(""), Err(ParseIntLiteralError::Invalid));
+    assert_eq!(parse("_1"), Err(ParseIntLiteralError::Invalid));
+    assert_eq!(parse("12f32"), Err(ParseIntLiteralError::Invalid));
+    assert_eq!(parse("0b12"), Err(ParseIntLiteralError::Invalid));
+    assert_eq!(parse("1u7"), Err(ParseIntLiteralError::Invalid));
+}
+
+#[test]
+fn odd_sizes() {
+    for &bytes in &[3, 5, 6, 7, 9, 15] {
+        let size = Size::from_bytes(bytes);
+        let max = size.unsigned_int_max();
+        let int = ScalarInt::try_from_uint(max, size).unwrap();
+        assert_eq!(int.size(), size);
+        assert_eq!(ScalarInt::try_from_uint(max + 1, size), None);
+        assert_eq!(ScalarInt::try_from_int(-1, size), Some(int));
+        assert!(int.is_unsigned_max(size));
+        // Sign extension of -1 to a larger odd size is still -1.
+        let wider = Size::from_bytes(bytes + 1);
+        assert_eq!(int.sext_to(wider), (ScalarInt::try_from_int(-1, wider).unwrap(), false));
+        assert_eq!(int.zext_to(wider), (ScalarInt::try_from_uint(max, wider).unwrap(), false));
+        assert_eq!(ScalarInt::from_le_bytes(&int.to_le_bytes()), Some(int));
+    }
+}
+
+#[test]
+#[should_panic]
+fn oversized() {
+    ScalarInt::null(Size::from_bytes(17));
+}
+
+#[test]
+fn bit_manipulation_matches_primitives() {
+    for &x in &[0_u16, 1, 0x00f0, 0x1234, 0x8001, u16::MAX] {
+        let int = ScalarInt::from(x);
+        assert_eq!(int.count_ones(), x.count_ones());
+        assert_eq!(int.leading_zeros(), x.leading_zeros());
+        assert_eq!(int.trailing_zeros(), x.trailing_zeros());
+        assert_eq!(int.swap_bytes(), ScalarInt::from(x.swap_bytes()));
+        assert_eq!(int.reverse_bits(), ScalarInt::from(x.reverse_bits()));
+        for &n in &[0_u32, 1, 7, 15, 16, 17, 100] {
+            assert_eq!(int.rotate_left(n.into()), ScalarInt::from(x.rotate_left(n)));
+            assert_eq!(int.rotate_right(n.into()), ScalarInt::from(x.rotate_right(n)));
+        }
+    }
+}
+
+#[test]
+fn compact_encoding_round_trip() {
+    for size in 0..=16 {
+        let int = sample(size);
+        let mut encoder = opaque::Encoder::new(Vec::new());
+        int.encode(&mut encoder).unwrap();
+        assert_eq!(encoder.data.len() as u64, 1 + size);
+        let mut decoder = opaque::Decoder::new(&encoder.data, 0);
+        assert_eq!(ScalarInt::decode(&mut decoder), Ok(int));
+    }
+}
+
+#[test]
+fn fixed_bytes_round_trip() {
+    for size in 0..=16 {
+        let int = sample(size);
+        let bytes = int.to_fixed_bytes();
+        assert_eq!(bytes[0] as u64, size);
+        assert_eq!(ScalarInt::from_fixed_bytes(bytes), Some(int));
+    }
+}
+
+#[test]
+fn fixed_bytes_are_validated() {
+    let mut bytes = sample(4).to_fixed_bytes();
+    bytes[5] = 1;
+    assert_eq!(ScalarInt::from_fixed_bytes(bytes), None);
+    let mut bytes = sample(16).to_fixed_bytes();
+    bytes[0] = 17;
+    assert_eq!(ScalarInt::from_fixed_bytes(bytes), None);
+}
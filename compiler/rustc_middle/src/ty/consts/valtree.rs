@@ -1,5 +1,13 @@
-use super::ScalarInt;
+use super::{ConstFloat, ConstInt, ScalarInt};
+use crate::ty::{self, Ty, TyCtxt};
+use rustc_hir::def::CtorKind;
+use rustc_hir::def_id::DefId;
 use rustc_macros::HashStable;
+use rustc_target::abi::{Size, VariantIdx};
+
+use std::convert::TryFrom;
+use std::iter;
+use std::ops::Bound;
 
 #[derive(Copy, Clone, Debug, Hash, TyEncodable, TyDecodable, Eq, PartialEq, Ord, PartialOrd)]
 #[derive(HashStable)]
@@ -13,8 +21,8 @@
 /// two constants whose value is `&42`. Furthermore any kind of struct that has padding will
 /// have arbitrary values within that padding, even if the values of the struct are the same.
 ///
-/// `ValTree` does not have this problem with representation, as it only contains integers or
-/// lists of (nested) `ValTree`.
+/// `ValTree` does not have this problem with representation, as it only contains integers,
+/// pointers identified by the static they point into, or lists of (nested) `ValTree`.
 pub enum ValTree<'tcx> {
     /// ZSTs, integers, `bool`, `char` are represented as scalars.
     /// See the `ScalarInt` documentation for how `ScalarInt` guarantees that equal values
@@ -25,10 +33,93 @@ pub enum ValTree<'tcx> {
     /// Enums are represented by storing their discriminant as a field, followed by all
     /// the fields of the variant.
     Branch(&'tcx [ValTree<'tcx>]),
+    /// Raw pointers into a static that is not thread local, at an offset within the static.
+    /// Since such a static has a single address and doesn't overlap with other items, two such
+    /// pointers are equal if and only if their valtrees are. Pointers without provenance, like
+    /// those cast from integers, are represented as `Leaf`s.
+    Pointer(DefId, Size),
 }
 
 impl ValTree<'tcx> {
     pub fn zst() -> Self {
         Self::Branch(&[])
     }
+
+    /// Renders `self`, a value of type `ty`, as a Rust expression that evaluates to it, like
+    /// `Foo { x: 1, y: [2, 3] }` or `Some('a')`, for showing it to users. Parts of the value
+    /// that cannot be written as an expression, like pointers into the middle of a static, are
+    /// rendered as `_`.
+    pub fn to_user_expr(self, tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> String {
+        let fields = |fields: &[ValTree<'tcx>], tys: &mut dyn Iterator<Item = Ty<'tcx>>| {
+            iter::zip(fields, tys)
+                .map(|(field, ty)| field.to_user_expr(tcx, ty))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match (self, ty.kind()) {
+            (_, ty::FnDef(def_id, substs)) => tcx.def_path_str_with_substs(*def_id, substs),
+            (ValTree::Leaf(int), ty::Bool) if int == ScalarInt::TRUE => "true".to_string(),
+            (ValTree::Leaf(int), ty::Bool) if int == ScalarInt::FALSE => "false".to_string(),
+            (ValTree::Leaf(int), ty::Int(_) | ty::Uint(_)) => {
+                let signed = matches!(ty.kind(), ty::Int(_));
+                ConstInt::new(int, signed, ty.is_ptr_sized_integral()).display().to_string()
+            }
+            (ValTree::Leaf(int), ty::Float(fty)) => format!("{:?}", ConstFloat::new(int, *fty)),
+            (ValTree::Leaf(int), ty::Char) if char::try_from(int).is_ok() => {
+                format!("{:?}", char::try_from(int).unwrap())
+            }
+            (ValTree::Leaf(int), ty::RawPtr(_)) => format!("0x{:x} as {}", int, ty),
+            (ValTree::Pointer(def_id, offset), ty::RawPtr(_)) if offset == Size::ZERO => {
+                format!("core::ptr::addr_of!({}) as {}", tcx.def_path_str(def_id), ty)
+            }
+            (ValTree::Branch(branches), ty::Tuple(tys)) => {
+                let fields = fields(branches, &mut tys.types());
+                if branches.len() == 1 { format!("({},)", fields) } else { format!("({})", fields) }
+            }
+            (ValTree::Branch(branches), ty::Array(elem, _)) => {
+                format!("[{}]", fields(branches, &mut iter::repeat(*elem)))
+            }
+            // Integer newtypes that can't be zero are rendered the way the pretty printer renders
+            // them, through their constructor, as their field is usually private.
+            (ValTree::Branch([field]), ty::Adt(def, substs))
+                if def.is_struct()
+                    && def.non_enum_variant().fields[0].ty(tcx, substs).is_integral()
+                    && tcx.layout_scalar_valid_range(def.did)
+                        == (Bound::Included(1), Bound::Unbounded) =>
+            {
+                let field_ty = def.non_enum_variant().fields[0].ty(tcx, substs);
+                let path = tcx.def_path_str_with_substs(def.did, substs);
+                format!("{}::new({}).unwrap()", path, field.to_user_expr(tcx, field_ty))
+            }
+            (ValTree::Branch(branches), ty::Adt(def, substs)) if !def.is_union() => {
+                // Enums store the index of their variant before the variant's fields.
+                let (variant, branches) = match branches.split_first() {
+                    Some((ValTree::Leaf(index), branches)) if def.is_enum() => {
+                        match u32::try_from(*index) {
+                            Ok(index) => (&def.variants[VariantIdx::from_u32(index)], branches),
+                            Err(_) => return "_".to_string(),
+                        }
+                    }
+                    _ if def.is_enum() => return "_".to_string(),
+                    _ => (def.non_enum_variant(), branches),
+                };
+                let path = tcx.def_path_str(variant.def_id);
+                let mut tys = variant.fields.iter().map(|field| field.ty(tcx, substs));
+                match variant.ctor_kind {
+                    CtorKind::Const => path,
+                    CtorKind::Fn => format!("{}({})", path, fields(branches, &mut tys)),
+                    CtorKind::Fictive if branches.is_empty() => format!("{} {{}}", path),
+                    CtorKind::Fictive => {
+                        let values =
+                            iter::zip(branches, tys).map(|(field, ty)| field.to_user_expr(tcx, ty));
+                        let named: Vec<_> = iter::zip(&variant.fields, values)
+                            .map(|(field, value)| format!("{}: {}", field.ident, value))
+                            .collect();
+                        format!("{} {{ {} }}", path, named.join(", "))
+                    }
+                }
+            }
+            _ => "_".to_string(),
+        }
+    }
 }
@@ -35,7 +35,7 @@
 use rustc_data_structures::steal::Steal;
 use rustc_data_structures::sync::{self, Lock, Lrc, WorkerLocal};
 use rustc_data_structures::vec_map::VecMap;
-use rustc_errors::ErrorReported;
+use rustc_errors::{DiagnosticAnchor, ErrorReported};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, DefIdMap, LocalDefId, LOCAL_CRATE};
@@ -204,8 +204,16 @@ pub struct CommonLifetimes<'tcx> {
 
 pub struct CommonConsts<'tcx> {
     pub unit: &'tcx Const<'tcx>,
+    pub true_: &'tcx Const<'tcx>,
+    pub false_: &'tcx Const<'tcx>,
+    /// The `usize` constants below `SMALL_USIZE_CONSTS`, indexed by their value. These are
+    /// common as array lengths.
+    pub small_usizes: Vec<&'tcx Const<'tcx>>,
 }
 
+/// The number of `usize` constants that are interned up front, see `CommonConsts`.
+const SMALL_USIZE_CONSTS: u64 = 64;
+
 pub struct LocalTableInContext<'a, V> {
     hir_owner: LocalDefId,
     data: &'a ItemLocalMap<V>,
@@ -880,14 +888,23 @@ fn new(interners: &CtxtInterners<'tcx>) -> CommonLifetimes<'tcx> {
 }
 
 impl<'tcx> CommonConsts<'tcx> {
-    fn new(interners: &CtxtInterners<'tcx>, types: &CommonTypes<'tcx>) -> CommonConsts<'tcx> {
+    fn new(
+        interners: &CtxtInterners<'tcx>,
+        types: &CommonTypes<'tcx>,
+        data_layout: &TargetDataLayout,
+    ) -> CommonConsts<'tcx> {
         let mk_const = |c| interners.const_.intern(c, |c| Interned(interners.arena.alloc(c))).0;
+        let mk_scalar = |scalar, ty| {
+            mk_const(ty::Const { val: ty::ConstKind::Value(ConstValue::Scalar(scalar)), ty })
+        };
 
         CommonConsts {
-            unit: mk_const(ty::Const {
-                val: ty::ConstKind::Value(ConstValue::Scalar(Scalar::ZST)),
-                ty: types.unit,
-            }),
+            unit: mk_scalar(Scalar::ZST, types.unit),
+            true_: mk_scalar(Scalar::from_bool(true), types.bool),
+            false_: mk_scalar(Scalar::from_bool(false), types.bool),
+            small_usizes: (0..SMALL_USIZE_CONSTS)
+                .map(|n| mk_scalar(Scalar::from_machine_usize(n, data_layout), types.usize))
+                .collect(),
         }
     }
 }
@@ -1135,7 +1152,7 @@ pub fn create_global_ctxt(
         let interners = CtxtInterners::new(arena);
         let common_types = CommonTypes::new(&interners);
         let common_lifetimes = CommonLifetimes::new(&interners);
-        let common_consts = CommonConsts::new(&interners, &common_types);
+        let common_consts = CommonConsts::new(&interners, &common_types, &data_layout);
         let cstore = resolutions.cstore;
 
         GlobalCtxt {
@@ -2625,7 +2642,10 @@ pub fn struct_span_lint_hir(
         decorate: impl for<'a> FnOnce(LintDiagnosticBuilder<'a>),
     ) {
         let (level, src) = self.lint_level_at_node(lint, hir_id);
-        struct_lint_level(self.sess, lint, level, src, Some(span.into()), decorate);
+        let anchor = self.hir_diagnostic_anchor(hir_id);
+        struct_lint_level(self.sess, lint, level, src, Some(span.into()), |lint| {
+            decorate(lint.with_anchor(anchor))
+        });
     }
 
     pub fn struct_lint_node(
@@ -2635,7 +2655,30 @@ pub fn struct_lint_node(
         decorate: impl for<'a> FnOnce(LintDiagnosticBuilder<'a>),
     ) {
         let (level, src) = self.lint_level_at_node(lint, id);
-        struct_lint_level(self.sess, lint, level, src, None, decorate);
+        let anchor = self.hir_diagnostic_anchor(id);
+        struct_lint_level(self.sess, lint, level, src, None, |lint| {
+            decorate(lint.with_anchor(anchor))
+        });
+    }
+
+    /// The anchor of a diagnostic about `def_id`, with `-Z diagnostic-anchors`. Unlike a span,
+    /// it stays the same when unrelated code is edited.
+    pub fn diagnostic_anchor(self, def_id: DefId) -> Option<DiagnosticAnchor> {
+        if !self.sess.opts.debugging_opts.diagnostic_anchors {
+            return None;
+        }
+        let def_path = format!(
+            "{}{}",
+            self.crate_name(def_id.krate),
+            self.def_path(def_id).to_string_no_crate_verbose()
+        );
+        Some(DiagnosticAnchor { def_path, hir_local_id: None, mir_location: None })
+    }
+
+    /// The anchor of a diagnostic about the HIR node `hir_id`, see `diagnostic_anchor`.
+    pub fn hir_diagnostic_anchor(self, hir_id: HirId) -> Option<DiagnosticAnchor> {
+        let anchor = self.diagnostic_anchor(hir_id.owner.to_def_id())?;
+        Some(DiagnosticAnchor { hir_local_id: Some(hir_id.local_id.as_u32()), ..anchor })
     }
 
     pub fn in_scope_traits(self, id: HirId) -> Option<&'tcx [TraitCandidate]> {
@@ -2781,6 +2824,7 @@ pub fn provide(providers: &mut ty::query::Providers) {
     providers.maybe_unused_extern_crates = |tcx, ()| &tcx.maybe_unused_extern_crates[..];
     providers.names_imported_by_glob_use =
         |tcx, id| tcx.arena.alloc(tcx.glob_map.get(&id).cloned().unwrap_or_default());
+    providers.expansion_trace = |tcx, span| tcx.arena.alloc_from_iter(span.expansion_trace());
 
     providers.lookup_stability = |tcx, id| {
         let id = tcx.hir().local_def_id_to_hir_id(id.expect_local());
@@ -1,7 +1,7 @@
 use crate::traits::{ObligationCause, ObligationCauseCode};
 use crate::ty::diagnostics::suggest_constraining_type_param;
 use crate::ty::print::{FmtPrinter, Printer};
-use crate::ty::{self, BoundRegionKind, Region, Ty, TyCtxt};
+use crate::ty::{self, BoundRegionKind, Region, ScalarInt, Ty, TyCtxt};
 use rustc_errors::Applicability::{MachineApplicable, MaybeIncorrect};
 use rustc_errors::{pluralize, DiagnosticBuilder};
 use rustc_hir as hir;
@@ -196,9 +196,12 @@ fn report_maybe_different(
                 &format!("trait `{}`", values.expected),
                 &format!("trait `{}`", values.found),
             ),
-            ConstMismatch(ref values) => {
-                write!(f, "expected `{}`, found `{}`", values.expected, values.found)
-            }
+            ConstMismatch(ref values) => write!(
+                f,
+                "expected `{}`, found `{}`",
+                render_mismatched_const(values.expected),
+                render_mismatched_const(values.found)
+            ),
             IntrinsicCast => write!(f, "cannot coerce intrinsics to function pointers"),
             TargetFeatureCast(_) => write!(
                 f,
@@ -578,6 +581,20 @@ fn foo(&self, x: T) -> T { x }
                 );
                 db.span_labels(target_spans, "`#[target_feature]` added here");
             }
+            ConstMismatch(values) => {
+                if let (Some(expected), Some(found)) =
+                    (values.expected.val.try_to_scalar_int(), values.found.val.try_to_scalar_int())
+                {
+                    if values.expected.ty.is_integral()
+                        && looks_like_bitmask(expected)
+                        && looks_like_bitmask(found)
+                    {
+                        if let Some(diff) = expected.differing_bits(found) {
+                            db.note(&render_bitmask_diff(expected, found, diff));
+                        }
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -963,3 +980,41 @@ fn format_generic_args(self, args: &[ty::GenericArg<'tcx>]) -> String {
         item_args
     }
 }
+
+/// Renders a mismatched constant. Aggregates are rendered the way they would be written in
+/// source, see `ValTree::to_user_expr`.
+fn render_mismatched_const(ct: &ty::Const<'_>) -> String {
+    let expr = match ct.ty.kind() {
+        ty::Adt(..) | ty::Tuple(_) | ty::Array(..) => ty::tls::with(|tcx| {
+            tcx.lift(ct)?.try_to_user_expr(tcx, ty::ParamEnv::reveal_all())
+        }),
+        _ => None,
+    };
+    expr.unwrap_or_else(|| ct.to_string())
+}
+
+/// Heuristically determines whether an integer constant is used as a bitmask rather than as a
+/// number: its hexadecimal representation must differ from its decimal one, and each of its
+/// hex digits must be either empty, full, or a single bit.
+fn looks_like_bitmask(int: ScalarInt) -> bool {
+    let bits = int.assert_bits(int.size());
+    bits > 9 && format!("{:x}", int).chars().all(|c| matches!(c, '0' | '1' | '2' | '4' | '8' | 'f'))
+}
+
+/// Renders two masks of the same size in hexadecimal, one above the other, and marks the
+/// hex digits in which they differ.
+fn render_bitmask_diff(expected: ScalarInt, found: ScalarInt, diff: ScalarInt) -> String {
+    let marker: String =
+        format!("{:x}", diff).chars().map(|c| if c == '0' { ' ' } else { '^' }).collect();
+    format!(
+        "the masks differ in the bits `{:?}`\n\
+         expected `{:?}`\n   \
+         found `{:?}`\n\
+         {:12}{}",
+        diff,
+        expected,
+        found,
+        "",
+        marker.trim_end(),
+    )
+}
@@ -3,7 +3,7 @@
 use crate::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use crate::mir::{GeneratorLayout, GeneratorSavedLocal};
 use crate::ty::subst::Subst;
-use crate::ty::{self, subst::SubstsRef, ReprOptions, Ty, TyCtxt, TypeFoldable};
+use crate::ty::{self, subst::SubstsRef, ReprOptions, ScalarInt, Ty, TyCtxt, TypeFoldable};
 
 use rustc_ast as ast;
 use rustc_attr as attr;
@@ -12,7 +12,7 @@
 use rustc_hir::lang_items::LangItem;
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::{Idx, IndexVec};
-use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, SizeKind, VariantInfo, VariantTag};
 use rustc_span::symbol::{Ident, Symbol};
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::call::{
@@ -242,8 +242,97 @@ fn layout_raw<'tcx>(
     })
 }
 
+/// A scalar that is present in every value of a type, but of which some values are invalid.
+/// Enums can store their discriminant in those invalid values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, HashStable)]
+pub struct LayoutNiche {
+    /// The offset of the scalar from the start of a value of the type.
+    pub offset: Size,
+    /// The first valid value of the scalar. The valid values wrap around if this is larger than
+    /// `valid_end`, like the `valid_range` of a `Scalar`.
+    pub valid_start: ScalarInt,
+    /// The last valid value of the scalar.
+    pub valid_end: ScalarInt,
+}
+
+impl LayoutNiche {
+    /// The size of the scalar.
+    pub fn size(&self) -> Size {
+        self.valid_start.size()
+    }
+}
+
+fn layout_niches<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    query: ty::ParamEnvAnd<'tcx, Ty<'tcx>>,
+) -> Result<&'tcx [LayoutNiche], LayoutError<'tcx>> {
+    let (param_env, ty) = query.into_parts();
+    let cx = LayoutCx { tcx, param_env };
+    let mut niches = vec![];
+    collect_niches(&cx, cx.layout_of(ty)?, Size::ZERO, &mut niches)?;
+    niches.sort_by_key(|niche| niche.offset);
+    Ok(tcx.arena.alloc_from_iter(niches))
+}
+
+/// Collects the niches of `layout`, which is at `offset` in the outermost value, into `niches`.
+fn collect_niches<'tcx>(
+    cx: &LayoutCx<'tcx, TyCtxt<'tcx>>,
+    layout: TyAndLayout<'tcx>,
+    offset: Size,
+    niches: &mut Vec<LayoutNiche>,
+) -> Result<(), LayoutError<'tcx>> {
+    match (&layout.variants, &layout.abi) {
+        // Of the fields of an enum, only its tag is present in every value.
+        (Variants::Multiple { tag, tag_field, .. }, _) => {
+            push_niche(cx, niches, offset + layout.fields.offset(*tag_field), tag);
+        }
+        // The valid range of a scalar can be narrower than that of its fields, e.g. for
+        // `NonZeroU32`, so scalars are used as they are.
+        (Variants::Single { .. }, Abi::Scalar(scalar)) => push_niche(cx, niches, offset, scalar),
+        (Variants::Single { .. }, Abi::ScalarPair(a, b)) => {
+            push_niche(cx, niches, offset, a);
+            let b_offset = a.value.size(cx).align_to(b.value.align(cx).abi);
+            push_niche(cx, niches, offset + b_offset, b);
+        }
+        (Variants::Single { .. }, _) => match layout.fields {
+            // The fields of a union overlap, so none of them is present in every value.
+            FieldsShape::Primitive | FieldsShape::Union(_) => {}
+            // All elements have the same niches, so only those of the first one are reported.
+            FieldsShape::Array { count, .. } => {
+                if count > 0 {
+                    collect_niches(cx, layout.field(cx, 0)?, offset, niches)?;
+                }
+            }
+            FieldsShape::Arbitrary { .. } => {
+                for i in 0..layout.fields.count() {
+                    let field_offset = offset + layout.fields.offset(i);
+                    collect_niches(cx, layout.field(cx, i)?, field_offset, niches)?;
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+fn push_niche(
+    cx: &impl HasDataLayout,
+    niches: &mut Vec<LayoutNiche>,
+    offset: Size,
+    scalar: &Scalar,
+) {
+    if Niche::from_scalar(cx, offset, scalar.clone()).is_some() {
+        let size = scalar.value.size(cx);
+        let int = |value| ScalarInt::try_from_uint(size.truncate(value), size).unwrap();
+        niches.push(LayoutNiche {
+            offset,
+            valid_start: int(*scalar.valid_range.start()),
+            valid_end: int(*scalar.valid_range.end()),
+        });
+    }
+}
+
 pub fn provide(providers: &mut ty::query::Providers) {
-    *providers = ty::query::Providers { layout_raw, ..*providers };
+    *providers = ty::query::Providers { layout_raw, layout_niches, ..*providers };
 }
 
 pub struct LayoutCx<'tcx, C> {
@@ -587,7 +676,11 @@ fn layout_raw_uncached(&self, ty: Ty<'tcx>) -> Result<&'tcx Layout, LayoutError<
                     }
                 }
 
-                let count = count.try_eval_usize(tcx, param_env).ok_or(LayoutError::Unknown(ty))?;
+                let count = count
+                    .try_eval_array_len(tcx, param_env)
+                    .and_then(Result::ok)
+                    .and_then(|count| count.try_to_machine_usize(tcx).ok())
+                    .ok_or(LayoutError::Unknown(ty))?;
                 let element = self.layout_of(element)?;
                 let size =
                     element.size.checked_mul(count, dl).ok_or(LayoutError::SizeOverflow(ty))?;
@@ -1761,7 +1854,10 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
         let adt_kind = adt_def.adt_kind();
         let adt_packed = adt_def.repr.pack.is_some();
 
-        let build_variant_info = |n: Option<Ident>, flds: &[Symbol], layout: TyAndLayout<'tcx>| {
+        let build_variant_info = |n: Option<Ident>,
+                                  flds: &[Symbol],
+                                  tag: Option<VariantTag>,
+                                  layout: TyAndLayout<'tcx>| {
             let mut min_size = Size::ZERO;
             let field_info: Vec<_> = flds
                 .iter()
@@ -1791,6 +1887,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                 kind: if layout.is_unsized() { SizeKind::Min } else { SizeKind::Exact },
                 align: layout.align.abi.bytes(),
                 size: if min_size.bytes() == 0 { layout.size.bytes() } else { min_size.bytes() },
+                tag,
                 fields: field_info,
             }
         };
@@ -1805,7 +1902,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         adt_kind.into(),
                         adt_packed,
                         None,
-                        vec![build_variant_info(Some(variant_def.ident), &fields, layout)],
+                        vec![build_variant_info(Some(variant_def.ident), &fields, None, layout)],
                     );
                 } else {
                     // (This case arises for *empty* enums; so give it
@@ -1820,6 +1917,22 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                     layout.ty,
                     adt_def.variants.len()
                 );
+                let tag_size = tag.value.size(self);
+                let variant_tag = |i: VariantIdx| match *tag_encoding {
+                    TagEncoding::Direct => {
+                        let discr = adt_def.discriminant_for_variant(self.tcx, i);
+                        Some(VariantTag::Discriminant(discr.to_string()))
+                    }
+                    TagEncoding::Niche { dataful_variant, ref niche_variants, niche_start } => {
+                        if i == dataful_variant || !niche_variants.contains(&i) {
+                            return None;
+                        }
+                        let relative = (i.as_u32() - niche_variants.start().as_u32()) as u128;
+                        let niche = tag_size.truncate(relative.wrapping_add(niche_start));
+                        let niche = ScalarInt::try_from_uint(niche, tag_size).unwrap();
+                        Some(VariantTag::Niche(format!("{:?}", niche)))
+                    }
+                };
                 let variant_infos: Vec<_> = adt_def
                     .variants
                     .iter_enumerated()
@@ -1829,6 +1942,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         build_variant_info(
                             Some(variant_def.ident),
                             &fields,
+                            variant_tag(i),
                             layout.for_variant(self, i),
                         )
                     })
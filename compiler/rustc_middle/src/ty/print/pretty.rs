@@ -1,8 +1,9 @@
 use crate::middle::cstore::{ExternCrate, ExternCrateSource};
 use crate::mir::interpret::{AllocRange, ConstValue, GlobalAlloc, Pointer, Scalar};
 use crate::ty::subst::{GenericArg, GenericArgKind, Subst};
-use crate::ty::{self, ConstInt, DefIdTree, ParamConst, ScalarInt, Ty, TyCtxt, TypeFoldable};
-use rustc_apfloat::ieee::{Double, Single};
+use crate::ty::{
+    self, ConstFloat, ConstInt, DefIdTree, ParamConst, ScalarInt, Ty, TyCtxt, TypeFoldable,
+};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sso::SsoHashSet;
 use rustc_hir as hir;
@@ -10,8 +11,9 @@
 use rustc_hir::def_id::{DefId, DefIdSet, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_hir::definitions::{DefPathData, DefPathDataName, DisambiguatedDefPathData};
 use rustc_hir::ItemKind;
-use rustc_session::config::TrimmedDefPaths;
+use rustc_session::config::{ConstPrintStyle, TrimmedDefPaths};
 use rustc_span::symbol::{kw, Ident, Symbol};
+use rustc_span::DUMMY_SP;
 use rustc_target::abi::Size;
 use rustc_target::spec::abi::Abi;
 
@@ -21,7 +23,7 @@
 use std::convert::TryFrom;
 use std::fmt::{self, Write as _};
 use std::iter;
-use std::ops::{ControlFlow, Deref, DerefMut};
+use std::ops::{Bound, ControlFlow, Deref, DerefMut};
 
 // `pretty` is a separate module only for organization.
 use super::*;
@@ -1004,12 +1006,19 @@ fn pretty_print_const_scalar_ptr(
                 _,
             ) => match self.tcx().get_global_alloc(ptr.alloc_id) {
                 Some(GlobalAlloc::Memory(alloc)) => {
-                    let len = int.assert_bits(self.tcx().data_layout.pointer_size);
-                    let range = AllocRange { start: ptr.offset, size: Size::from_bytes(len) };
-                    if let Ok(byte_str) = alloc.get_bytes(&self.tcx(), range) {
-                        p!(pretty_print_byte_str(byte_str))
+                    let tcx = self.tcx();
+                    let len = int.try_assert_int(tcx, DUMMY_SP).and_then(|int| {
+                        int.try_assert_bits(tcx, DUMMY_SP, tcx.data_layout.pointer_size)
+                    });
+                    if let Ok(len) = len {
+                        let range = AllocRange { start: ptr.offset, size: Size::from_bytes(len) };
+                        if let Ok(byte_str) = alloc.get_bytes(&tcx, range) {
+                            p!(pretty_print_byte_str(byte_str))
+                        } else {
+                            p!("<too short allocation>")
+                        }
                     } else {
-                        p!("<too short allocation>")
+                        p!("<malformed length>")
                     }
                 }
                 // FIXME: for statics and functions, we could in principle print more detail.
@@ -1052,17 +1061,21 @@ fn pretty_print_const_scalar_int(
             ty::Bool if int == ScalarInt::FALSE => p!("false"),
             ty::Bool if int == ScalarInt::TRUE => p!("true"),
             // Float
-            ty::Float(ty::FloatTy::F32) => {
-                p!(write("{}f32", Single::try_from(int).unwrap()))
-            }
-            ty::Float(ty::FloatTy::F64) => {
-                p!(write("{}f64", Double::try_from(int).unwrap()))
+            ty::Float(fty) => {
+                let float = ConstFloat::new(int, *fty);
+                if print_ty { p!(write("{:#?}", float)) } else { p!(write("{:?}", float)) }
             }
             // Int
             ty::Uint(_) | ty::Int(_) => {
                 let int =
                     ConstInt::new(int, matches!(ty.kind(), ty::Int(_)), ty.is_ptr_sized_integral());
-                if print_ty { p!(write("{:#?}", int)) } else { p!(write("{:?}", int)) }
+                if self.tcx().sess.opts.debugging_opts.print_consts == ConstPrintStyle::Hex {
+                    p!(write("{}", int.display().hex_bits(true).with_suffix(print_ty)))
+                } else if print_ty {
+                    p!(write("{:#?}", int))
+                } else {
+                    p!(write("{:?}", int))
+                }
             }
             // Char
             ty::Char if char::try_from(int).is_ok() => {
@@ -1070,10 +1083,15 @@ fn pretty_print_const_scalar_int(
             }
             // Raw pointers
             ty::RawPtr(_) | ty::FnPtr(_) => {
-                let data = int.assert_bits(self.tcx().data_layout.pointer_size);
+                let tcx = self.tcx();
+                let data = int.try_assert_bits(tcx, DUMMY_SP, tcx.data_layout.pointer_size);
                 self = self.typed_value(
                     |mut this| {
-                        write!(this, "0x{:x}", data)?;
+                        match data {
+                            Ok(data) => write!(this, "0x{:x}", data)?,
+                            // Already reported as a delayed bug, print the raw scalar instead.
+                            Err(_) => write!(this, "{:?}", int)?,
+                        }
                         Ok(this)
                     },
                     |this| this.print_type(ty),
@@ -1220,6 +1238,20 @@ fn pretty_print_const_value(
                     ty::Adt(def, substs) if def.variants.is_empty() => {
                         p!(print_value_path(def.did, substs));
                     }
+                    // Integer newtypes that can't be zero, like `NonZeroU32`, usually have a
+                    // private field, so print them the way they would be constructed.
+                    ty::Adt(def, substs)
+                        if def.is_struct()
+                            && contents.fields.len() == 1
+                            && contents.fields[0].ty.is_integral()
+                            && self.tcx().layout_scalar_valid_range(def.did)
+                                == (Bound::Included(1), Bound::Unbounded) =>
+                    {
+                        p!(print_value_path(def.did, substs), "::new(");
+                        p!(pretty_print_const(contents.fields[0], false), ").unwrap()");
+                    }
+                    // The types of the fields are implied by the ADT, so they are printed without
+                    // integer suffixes, the way the value would be written in source.
                     ty::Adt(def, substs) => {
                         let variant_id =
                             contents.variant.expect("destructed const of adt without variant id");
@@ -1229,7 +1261,16 @@ fn pretty_print_const_value(
                         match variant_def.ctor_kind {
                             CtorKind::Const => {}
                             CtorKind::Fn => {
-                                p!("(", comma_sep(fields), ")");
+                                p!("(");
+                                let mut first = true;
+                                for field in fields {
+                                    if !first {
+                                        p!(", ");
+                                    }
+                                    p!(pretty_print_const(field, false));
+                                    first = false;
+                                }
+                                p!(")");
                             }
                             CtorKind::Fictive => {
                                 p!(" {{ ");
@@ -1238,7 +1279,8 @@ fn pretty_print_const_value(
                                     if !first {
                                         p!(", ");
                                     }
-                                    p!(write("{}: ", field_def.ident), print(field));
+                                    p!(write("{}: ", field_def.ident));
+                                    p!(pretty_print_const(field, false));
                                     first = false;
                                 }
                                 p!(" }}");
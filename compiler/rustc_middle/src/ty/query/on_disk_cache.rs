@@ -27,6 +27,7 @@
 use rustc_span::CachingSourceMapView;
 use rustc_span::{BytePos, ExpnData, SourceFile, Span, DUMMY_SP};
 use std::collections::hash_map::Entry;
+use std::convert::TryInto;
 use std::mem;
 
 const TAG_FILE_FOOTER: u128 = 0xC0FFEE_C0FFEE_C0FFEE_C0FFEE_C0FFEE;
@@ -803,6 +804,20 @@ fn decode(decoder: &mut CacheDecoder<'a, 'tcx>) -> Result<Self, String> {
     }
 }
 
+// With `-Z incremental-fixed-width-scalars`, every `ScalarInt` in the cache takes the same 17
+// bytes. Decoding one is a single read whose result is validated, instead of reading the size
+// before the value.
+impl<'a, 'tcx> Decodable<CacheDecoder<'a, 'tcx>> for ty::ScalarInt {
+    fn decode(d: &mut CacheDecoder<'a, 'tcx>) -> Result<Self, String> {
+        if !d.tcx.sess.opts.debugging_opts.incremental_fixed_width_scalars {
+            return ty::ScalarInt::decode_compact(d);
+        }
+        let bytes = d.opaque.read_raw_bytes(ty::ScalarInt::FIXED_ENCODED_LEN);
+        ty::ScalarInt::from_fixed_bytes(bytes.try_into().unwrap())
+            .ok_or_else(|| format!("invalid fixed-width `ScalarInt` {:?}", bytes))
+    }
+}
+
 impl<'a, 'tcx> Decodable<CacheDecoder<'a, 'tcx>> for CrateNum {
     fn decode(d: &mut CacheDecoder<'a, 'tcx>) -> Result<Self, String> {
         let stable_id = StableCrateId::decode(d)?;
@@ -1017,6 +1032,19 @@ fn encode_alloc_id(&mut self, alloc_id: &interpret::AllocId) -> Result<(), Self:
     }
 }
 
+impl<'a, 'tcx, E> Encodable<CacheEncoder<'a, 'tcx, E>> for ty::ScalarInt
+where
+    E: 'a + OpaqueEncoder,
+{
+    fn encode(&self, s: &mut CacheEncoder<'a, 'tcx, E>) -> Result<(), E::Error> {
+        if s.tcx.sess.opts.debugging_opts.incremental_fixed_width_scalars {
+            s.emit_raw_bytes(&self.to_fixed_bytes())
+        } else {
+            self.encode_compact(s)
+        }
+    }
+}
+
 impl<'a, 'tcx, E> Encodable<CacheEncoder<'a, 'tcx, E>> for CrateNum
 where
     E: 'a + OpaqueEncoder,
@@ -7,7 +7,7 @@
 use crate::ty::query::TyCtxtAt;
 use crate::ty::subst::{GenericArgKind, Subst, SubstsRef};
 use crate::ty::TyKind::*;
-use crate::ty::{self, DefIdTree, List, Ty, TyCtxt, TypeFoldable};
+use crate::ty::{self, DefIdTree, List, ScalarInt, Ty, TyCtxt, TypeFoldable};
 use rustc_apfloat::Float as _;
 use rustc_ast as ast;
 use rustc_attr::{self as attr, SignedInt, UnsignedInt};
@@ -45,18 +45,6 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
-fn signed_min(size: Size) -> i128 {
-    size.sign_extend(1_u128 << (size.bits() - 1)) as i128
-}
-
-fn signed_max(size: Size) -> i128 {
-    i128::MAX >> (128 - size.bits())
-}
-
-fn unsigned_max(size: Size) -> u128 {
-    u128::MAX >> (128 - size.bits())
-}
-
 fn int_size_and_signed<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> (Size, bool) {
     let (int, signed) = match *ty.kind() {
         Int(ity) => (Integer::from_int_ty(&tcx, ity), true),
@@ -67,15 +55,33 @@ fn int_size_and_signed<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> (Size, bool) {
 }
 
 impl<'tcx> Discr<'tcx> {
+    /// Returns the smallest and the largest discriminant representable in the integer type `ty`.
+    pub fn bounds(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> (Self, Self) {
+        let (size, signed) = int_size_and_signed(tcx, ty);
+        let (min, max) = if signed {
+            (size.truncate(size.signed_int_min() as u128), size.signed_int_max() as u128)
+        } else {
+            (0, size.unsigned_int_max())
+        };
+        (Self { val: min, ty }, Self { val: max, ty })
+    }
+
     /// Adds `1` to the value and wraps around if the maximum for the type is reached.
     pub fn wrap_incr(self, tcx: TyCtxt<'tcx>) -> Self {
         self.checked_add(tcx, 1).0
     }
+
+    /// Adds `1` to the value, or returns `None` if the maximum for the type is reached.
+    pub fn checked_incr(self, tcx: TyCtxt<'tcx>) -> Option<Self> {
+        let (new, oflo) = self.checked_add(tcx, 1);
+        if oflo { None } else { Some(new) }
+    }
+
     pub fn checked_add(self, tcx: TyCtxt<'tcx>, n: u128) -> (Self, bool) {
         let (size, signed) = int_size_and_signed(tcx, self.ty);
         let (val, oflo) = if signed {
-            let min = signed_min(size);
-            let max = signed_max(size);
+            let min = size.signed_int_min();
+            let max = size.signed_int_max();
             let val = size.sign_extend(self.val) as i128;
             assert!(n < (i128::MAX as u128));
             let n = n as i128;
@@ -86,7 +92,7 @@ pub fn checked_add(self, tcx: TyCtxt<'tcx>, n: u128) -> (Self, bool) {
             let val = size.truncate(val);
             (val, oflo)
         } else {
-            let max = unsigned_max(size);
+            let max = size.unsigned_int_max();
             let val = self.val;
             let oflo = val > max - n;
             let val = if oflo { n - (max - val) - 1 } else { val + n };
@@ -127,8 +133,7 @@ fn initial_discriminant<'tcx>(&self, tcx: TyCtxt<'tcx>) -> Discr<'tcx> {
     fn disr_incr<'tcx>(&self, tcx: TyCtxt<'tcx>, val: Option<Discr<'tcx>>) -> Option<Discr<'tcx>> {
         if let Some(val) = val {
             assert_eq!(self.to_ty(tcx), val.ty);
-            let (new, oflo) = val.checked_add(tcx, 1);
-            if oflo { None } else { Some(new) }
+            val.checked_incr(tcx)
         } else {
             Some(self.initial_discriminant(tcx))
         }
@@ -167,6 +172,49 @@ pub fn has_error_field(self, ty: Ty<'tcx>) -> bool {
         false
     }
 
+    /// Returns an iterator over all valid values of `ty`, if `ty` is `bool`, `char`, an integer
+    /// type or an enum without fields, and has at most `limit` values. The values of an enum are
+    /// its discriminants, in the order of its variants. All other values are in ascending order.
+    pub fn enumerate_values(
+        self,
+        ty: Ty<'tcx>,
+        limit: u128,
+    ) -> Option<Box<dyn Iterator<Item = ScalarInt> + 'tcx>> {
+        // All code points except for the surrogates `0xD800..=0xDFFF`.
+        const CHAR_COUNT: u128 = 0x11_0000 - 0x800;
+        match *ty.kind() {
+            Bool if limit >= 2 => {
+                Some(Box::new(iter::once(ScalarInt::FALSE).chain(iter::once(ScalarInt::TRUE))))
+            }
+            Char if limit >= CHAR_COUNT => {
+                Some(Box::new(iter::successors(Some(ScalarInt::from('\0')), |c| c.char_succ())))
+            }
+            Int(_) | Uint(_) => {
+                let (size, signed) = int_size_and_signed(self, ty);
+                if size.bits() >= 128 || 1 << size.bits() > limit {
+                    return None;
+                }
+                let min = if signed {
+                    ScalarInt::try_from_int(size.signed_int_min(), size).unwrap()
+                } else {
+                    ScalarInt::null(size)
+                };
+                Some(Box::new(iter::successors(Some(min), move |int| int.checked_succ(signed))))
+            }
+            Adt(def, _)
+                if def.is_enum()
+                    && def.variants.len() as u128 <= limit
+                    && def.variants.iter().all(|variant| variant.fields.is_empty()) =>
+            {
+                Some(Box::new(def.discriminants(self).map(move |(_, discr)| {
+                    let (size, _) = int_size_and_signed(self, discr.ty);
+                    ScalarInt::try_from_uint(discr.val, size).unwrap()
+                })))
+            }
+            _ => None,
+        }
+    }
+
     /// Attempts to returns the deeply last field of nested structures, but
     /// does not apply any normalization in its search. Returns the same type
     /// if input `ty` is not a structure at all.
@@ -619,7 +667,8 @@ pub fn numeric_max_val(&'tcx self, tcx: TyCtxt<'tcx>) -> Option<&'tcx ty::Const<
         let val = match self.kind() {
             ty::Int(_) | ty::Uint(_) => {
                 let (size, signed) = int_size_and_signed(tcx, self);
-                let val = if signed { signed_max(size) as u128 } else { unsigned_max(size) };
+                let val =
+                    if signed { size.signed_int_max() as u128 } else { size.unsigned_int_max() };
                 Some(val)
             }
             ty::Char => Some(std::char::MAX as u128),
@@ -638,7 +687,7 @@ pub fn numeric_min_val(&'tcx self, tcx: TyCtxt<'tcx>) -> Option<&'tcx ty::Const<
         let val = match self.kind() {
             ty::Int(_) | ty::Uint(_) => {
                 let (size, signed) = int_size_and_signed(tcx, self);
-                let val = if signed { size.truncate(signed_min(size) as u128) } else { 0 };
+                let val = if signed { size.truncate(size.signed_int_min() as u128) } else { 0 };
                 Some(val)
             }
             ty::Char => Some(0),
@@ -1070,6 +1119,14 @@ pub fn normalize_opaque_types(
     val.fold_with(&mut visitor)
 }
 
+fn variant_discriminant(tcx: TyCtxt<'_>, variant: DefId) -> ty::ScalarInt {
+    let adt = tcx.adt_def(tcx.parent(variant).unwrap());
+    let discr = adt.discriminant_for_variant(tcx, adt.variant_index_with_id(variant));
+    let size = Integer::from_attr(&tcx, adt.repr.discr_type()).size();
+    ty::ScalarInt::try_from_uint(size.truncate(discr.val), size).unwrap()
+}
+
 pub fn provide(providers: &mut ty::query::Providers) {
-    *providers = ty::query::Providers { normalize_opaque_types, ..*providers }
+    *providers =
+        ty::query::Providers { normalize_opaque_types, variant_discriminant, ..*providers }
 }
@@ -401,6 +401,8 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
                 err.span_note(deref_target, "deref defined here");
             }
 
+            // Move errors are only buffered at the end, so they are anchored here.
+            err.set_anchor(self.diagnostic_anchor_at(location));
             if let Some((_, mut old_err)) =
                 self.move_error_reported.insert(move_out_indices, (used_place, err))
             {
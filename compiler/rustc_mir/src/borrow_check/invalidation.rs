@@ -185,6 +185,21 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
 
                 self.mutate_place(location, *resume_arg, Deep, JustWrite);
             }
+            TerminatorKind::TailCall { ref func, ref args, fn_span: _ } => {
+                self.consume_operand(location, func);
+                for arg in args {
+                    self.consume_operand(location, arg);
+                }
+
+                // The current frame is gone once the callee runs, so this is like a return.
+                let borrow_set = self.borrow_set;
+                let start = self.location_table.start_index(location);
+                for (i, data) in borrow_set.iter_enumerated() {
+                    if borrow_of_local_data(data.borrowed_place) {
+                        self.all_facts.invalidates.push((start, i));
+                    }
+                }
+            }
             TerminatorKind::Resume | TerminatorKind::Return | TerminatorKind::GeneratorDrop => {
                 // Invalidate all borrows of local places
                 let borrow_set = self.borrow_set;
@@ -2,7 +2,10 @@
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::graph::dominators::Dominators;
-use rustc_errors::{Applicability, Diagnostic, DiagnosticBuilder, ErrorReported};
+use rustc_errors::{
+    Applicability, Diagnostic, DiagnosticAnchor, DiagnosticBuilder, DiagnosticMirLocation,
+    ErrorReported,
+};
 use rustc_hir as hir;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::Node;
@@ -435,7 +438,12 @@ fn do_mir_borrowck<'a, 'tcx>(
     if !mbcx.errors_buffer.is_empty() {
         mbcx.errors_buffer.sort_by_key(|diag| diag.sort_span);
 
-        for diag in mbcx.errors_buffer.drain(..) {
+        // The errors that aren't about a particular location are anchored to the body.
+        let anchor = tcx.diagnostic_anchor(def.did.to_def_id());
+        for mut diag in mbcx.errors_buffer.drain(..) {
+            if diag.anchor.is_none() {
+                diag.anchor = anchor.clone();
+            }
             mbcx.infcx.tcx.sess.diagnostic().emit_diagnostic(&diag);
         }
     }
@@ -559,6 +567,7 @@ fn visit_statement_before_primary_effect(
         stmt: &'cx Statement<'tcx>,
         location: Location,
     ) {
+        let errors = self.errors_buffer.len();
         debug!("MirBorrowckCtxt::process_statement({:?}, {:?}): {:?}", location, stmt, flow_state);
         let span = stmt.source_info.span;
 
@@ -650,6 +659,7 @@ fn visit_statement_before_primary_effect(
                 );
             }
         }
+        self.anchor_errors(errors, location);
     }
 
     fn visit_terminator_before_primary_effect(
@@ -658,6 +668,7 @@ fn visit_terminator_before_primary_effect(
         term: &'cx Terminator<'tcx>,
         loc: Location,
     ) {
+        let errors = self.errors_buffer.len();
         debug!("MirBorrowckCtxt::process_terminator({:?}, {:?}): {:?}", loc, term, flow_state);
         let span = term.source_info.span;
 
@@ -707,6 +718,12 @@ fn visit_terminator_before_primary_effect(
                     self.mutate_place(loc, (dest, span), Deep, JustWrite, flow_state);
                 }
             }
+            TerminatorKind::TailCall { ref func, ref args, fn_span: _ } => {
+                self.consume_operand(loc, (func, span), flow_state);
+                for arg in args {
+                    self.consume_operand(loc, (arg, span), flow_state);
+                }
+            }
             TerminatorKind::Assert { ref cond, expected: _, ref msg, target: _, cleanup: _ } => {
                 self.consume_operand(loc, (cond, span), flow_state);
                 use rustc_middle::mir::AssertKind;
@@ -774,6 +791,7 @@ fn visit_terminator_before_primary_effect(
                 // no data used, thus irrelevant to borrowck
             }
         }
+        self.anchor_errors(errors, loc);
     }
 
     fn visit_terminator_after_primary_effect(
@@ -782,6 +800,7 @@ fn visit_terminator_after_primary_effect(
         term: &'cx Terminator<'tcx>,
         loc: Location,
     ) {
+        let errors = self.errors_buffer.len();
         let span = term.source_info.span;
 
         match term.kind {
@@ -796,8 +815,12 @@ fn visit_terminator_after_primary_effect(
                 }
             }
 
-            TerminatorKind::Resume | TerminatorKind::Return | TerminatorKind::GeneratorDrop => {
+            TerminatorKind::Resume
+            | TerminatorKind::Return
+            | TerminatorKind::GeneratorDrop
+            | TerminatorKind::TailCall { .. } => {
                 // Returning from the function implicitly kills storage for all locals and statics.
+                // A tail call does the same, since the callee replaces the current frame.
                 // Often, the storage will already have been killed by an explicit
                 // StorageDead, but we don't always emit those (notably on unwind paths),
                 // so this "extra check" serves as a kind of backup.
@@ -820,6 +843,7 @@ fn visit_terminator_after_primary_effect(
             | TerminatorKind::Unreachable
             | TerminatorKind::InlineAsm { .. } => {}
         }
+        self.anchor_errors(errors, loc);
     }
 }
 
@@ -957,6 +981,25 @@ fn body(&self) -> &'cx Body<'tcx> {
         self.body
     }
 
+    /// The anchor of an error at `location`, see `TyCtxt::diagnostic_anchor`.
+    fn diagnostic_anchor_at(&self, location: Location) -> Option<DiagnosticAnchor> {
+        let anchor = self.infcx.tcx.diagnostic_anchor(self.mir_def_id().to_def_id())?;
+        let mir_location = DiagnosticMirLocation {
+            basic_block: location.block.as_u32(),
+            statement_index: location.statement_index as u32,
+        };
+        Some(DiagnosticAnchor { mir_location: Some(mir_location), ..anchor })
+    }
+
+    /// Anchors the errors buffered since there were `errors` of them at `location`.
+    fn anchor_errors(&mut self, errors: usize, location: Location) {
+        if let Some(anchor) = self.diagnostic_anchor_at(location) {
+            for diag in &mut self.errors_buffer[errors..] {
+                diag.anchor.get_or_insert_with(|| anchor.clone());
+            }
+        }
+    }
+
     /// Checks an access to the given place to see if it is allowed. Examines the set of borrows
     /// that are in scope, as well as which paths have been initialized, to ensure that (a) the
     /// place is initialized and (b) it is not borrowed in some way that would prevent this
@@ -1602,7 +1602,8 @@ fn check_terminator(
                 }
                 // FIXME: check the values
             }
-            TerminatorKind::Call { ref func, ref args, ref destination, from_hir_call, .. } => {
+            TerminatorKind::Call { ref func, ref args, .. }
+            | TerminatorKind::TailCall { ref func, ref args, .. } => {
                 let func_ty = func.ty(body, tcx);
                 debug!("check_terminator: call, func_ty={:?}", func_ty);
                 let sig = match func_ty.kind() {
@@ -1618,7 +1619,16 @@ fn check_terminator(
                     sig,
                 );
                 let sig = self.normalize(sig, term_location);
-                self.check_call_dest(body, term, &sig, destination, term_location);
+                let from_hir_call = match term.kind {
+                    TerminatorKind::Call { ref destination, from_hir_call, .. } => {
+                        self.check_call_dest(body, term, &sig, destination, term_location);
+                        from_hir_call
+                    }
+                    _ => {
+                        self.check_tail_call_output(body, term, &sig, term_location);
+                        true
+                    }
+                };
 
                 self.prove_predicates(
                     sig.inputs_and_output.iter().map(|ty| ty::PredicateKind::WellFormed(ty.into())),
@@ -1685,6 +1695,33 @@ fn check_terminator(
         }
     }
 
+    /// The callee of a tail call returns directly to the caller of the current function, so its
+    /// return value ends up where the return value of the current function would.
+    fn check_tail_call_output(
+        &mut self,
+        body: &Body<'tcx>,
+        term: &Terminator<'tcx>,
+        sig: &ty::FnSig<'tcx>,
+        term_location: Location,
+    ) {
+        let return_ty = self.normalize(body.local_decls[RETURN_PLACE].ty, term_location);
+        if let Err(terr) = self.sub_types_or_anon(
+            sig.output(),
+            return_ty,
+            term_location.to_locations(),
+            ConstraintCategory::Return(ReturnConstraint::Normal),
+        ) {
+            span_mirbug!(
+                self,
+                term,
+                "tail call output mismatch ({:?} <- {:?}): {:?}",
+                return_ty,
+                sig.output(),
+                terr
+            );
+        }
+    }
+
     fn check_call_dest(
         &mut self,
         body: &Body<'tcx>,
@@ -1818,6 +1855,11 @@ fn check_iscleanup(&mut self, body: &Body<'tcx>, block_data: &BasicBlockData<'tc
                     span_mirbug!(self, block_data, "return on cleanup block")
                 }
             }
+            TerminatorKind::TailCall { .. } => {
+                if is_cleanup {
+                    span_mirbug!(self, block_data, "tail call on cleanup block")
+                }
+            }
             TerminatorKind::GeneratorDrop { .. } => {
                 if is_cleanup {
                     span_mirbug!(self, block_data, "generator_drop in cleanup block")
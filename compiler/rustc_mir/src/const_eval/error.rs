@@ -167,7 +167,13 @@ fn struct_generic(
             // Add spans for the stacktrace. Don't print a single-line backtrace though.
             if self.stacktrace.len() > 1 {
                 for frame_info in &self.stacktrace {
-                    err.span_label(frame_info.span, frame_info.to_string());
+                    // Frames in other crates can only be shown with their source if it is
+                    // available; otherwise the path and location of the frame have to do.
+                    if frame_info.has_source(*tcx) {
+                        err.span_label(frame_info.span, frame_info.to_string());
+                    } else {
+                        err.note(&frame_info.to_string());
+                    }
                 }
             }
             // Let the caller finish the job.
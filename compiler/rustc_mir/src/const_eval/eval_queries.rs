@@ -7,17 +7,21 @@
 };
 use crate::util::pretty::display_allocation;
 
-use rustc_errors::ErrorReported;
+use rustc_errors::{DiagnosticBuilder, ErrorReported};
 use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::middle::limits::const_eval_limit;
 use rustc_middle::mir;
-use rustc_middle::mir::interpret::ErrorHandled;
+use rustc_middle::mir::interpret::{ErrorHandled, InterpError, ResourceExhaustionInfo};
 use rustc_middle::traits::Reveal;
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, subst::Subst, TyCtxt};
 use rustc_span::source_map::Span;
+use rustc_span::Symbol;
 use rustc_target::abi::{Abi, LayoutOf};
 use std::borrow::Cow;
 use std::convert::TryInto;
+use std::time::Instant;
 
 pub fn note_on_undefined_behavior_error() -> &'static str {
     "The rules on what exactly is undefined behavior aren't clear, \
@@ -25,6 +29,35 @@ pub fn note_on_undefined_behavior_error() -> &'static str {
      repository if you believe it should not be considered undefined behavior."
 }
 
+/// Explains that the memory behind the references in the `const` `name` is shared by all of its
+/// uses, so it cannot be mutable, and suggests alternatives.
+fn note_mutable_in_const(
+    diag: &mut DiagnosticBuilder<'_>,
+    name: Symbol,
+    rust_path: Option<&str>,
+    deref: bool,
+    unsafe_cell: bool,
+) {
+    if let Some(rust_path) = rust_path {
+        let place = format!("{}{}{}", if deref { "*" } else { "" }, name, rust_path);
+        let what = if unsafe_cell { "an `UnsafeCell`" } else { "a mutable reference" };
+        diag.note(&format!(
+            "`{}` is {}, but the memory behind the references in a `const` is shared by all uses",
+            place, what
+        ));
+    }
+    if unsafe_cell {
+        diag.help(&format!("to share one value between all uses, make `{}` a `static`", name));
+    } else {
+        diag.help(&format!(
+            "to share one value between all uses, make `{}` a `static` and replace the mutable \
+             reference with a type based on `UnsafeCell`, like an atomic or a `Mutex`",
+            name
+        ));
+    }
+    diag.help(&format!("to create a new value for every use, turn `{}` into a `const fn`", name));
+}
+
 // Returns a pointer to where the result lives
 fn eval_body_using_ecx<'mir, 'tcx>(
     ecx: &mut CompileTimeEvalContext<'mir, 'tcx>,
@@ -48,7 +81,7 @@ fn eval_body_using_ecx<'mir, 'tcx>(
     );
     let layout = ecx.layout_of(body.return_ty().subst(tcx, cid.instance.substs))?;
     assert!(!layout.is_unsized());
-    let ret = ecx.allocate(layout, MemoryKind::Stack);
+    let ret = ecx.allocate(layout, MemoryKind::Stack)?;
 
     let name =
         with_no_trimmed_paths(|| ty::tls::with(|tcx| tcx.def_path_str(cid.instance.def_id())));
@@ -87,18 +120,30 @@ fn eval_body_using_ecx<'mir, 'tcx>(
 /// that inform us about the generic bounds of the constant. E.g., using an associated constant
 /// of a function's generic parameter will require knowledge about the bounds on the generic
 /// parameter. These bounds are passed to `mk_eval_cx` via the `ParamEnv` argument.
+///
+/// If the context works on the value of an item, the step limit is the one of that item, see
+/// `const_eval_limit`.
 pub(super) fn mk_eval_cx<'mir, 'tcx>(
     tcx: TyCtxt<'tcx>,
     root_span: Span,
     param_env: ty::ParamEnv<'tcx>,
     can_access_statics: bool,
+    item: Option<DefId>,
 ) -> CompileTimeEvalContext<'mir, 'tcx> {
     debug!("mk_eval_cx: {:?}", param_env);
+    let limit = match item {
+        Some(item) => const_eval_limit(tcx, item),
+        None => tcx.sess.const_eval_limit(),
+    };
     InterpCx::new(
         tcx,
         root_span,
         param_env,
-        CompileTimeInterpreter::new(tcx.sess.const_eval_limit()),
+        CompileTimeInterpreter::new(
+            limit,
+            tcx.sess.opts.debugging_opts.const_eval_report_progress,
+            false,
+        ),
         MemoryExtra { can_access_statics },
     )
 }
@@ -193,7 +238,7 @@ fn turn_into_const_value<'tcx>(
     let cid = key.value;
     let def_id = cid.instance.def.def_id();
     let is_static = tcx.is_static(def_id);
-    let ecx = mk_eval_cx(tcx, tcx.def_span(key.value.instance.def_id()), key.param_env, is_static);
+    let ecx = mk_eval_cx(tcx, tcx.def_span(def_id), key.param_env, is_static, Some(def_id));
 
     let mplace = ecx.raw_const_to_mplace(constant).expect(
         "can only fail if layout computation failed, \
@@ -276,6 +321,19 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
     let cid = key.value;
     let def = cid.instance.def.with_opt_param();
 
+    // The value of a non-generic constant of another crate may have been recorded in the
+    // metadata of that crate, which saves us from evaluating it again.
+    if !def.did.is_local()
+        && cid.promoted.is_none()
+        && cid.instance.substs.is_empty()
+        && matches!(cid.instance.def, ty::InstanceDef::Item(_))
+        && !tcx.sess.opts.debugging_opts.no_const_eval_metadata_cache
+    {
+        if let Some(alloc) = tcx.const_alloc_from_metadata(def.did) {
+            return Ok(alloc);
+        }
+    }
+
     if let Some(def) = def.as_local() {
         if tcx.has_typeck_results(def.did) {
             if let Some(error_reported) = tcx.typeck_opt_const_arg(def).tainted_by_errors {
@@ -300,14 +358,21 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
         tcx,
         tcx.def_span(def.did),
         key.param_env,
-        CompileTimeInterpreter::new(tcx.sess.const_eval_limit()),
+        CompileTimeInterpreter::new(
+            const_eval_limit(tcx, def.did),
+            tcx.sess.opts.debugging_opts.const_eval_report_progress,
+            tcx.sess.opts.debugging_opts.profile_const_eval.is_some(),
+        ),
         // Statics (and promoteds inside statics) may access other statics, because unlike consts
         // they do not have to behave "as if" they were evaluated at runtime.
         MemoryExtra { can_access_statics: is_static },
     );
 
+    let start = Instant::now();
     let res = ecx.load_mir(cid.instance.def, cid.promoted);
-    match res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)) {
+    let res = res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body));
+    ecx.record_profile(cid, start.elapsed());
+    match res {
         Err(error) => {
             let err = ConstEvalErr::new(&ecx, error, None);
             // Some CTFE errors raise just a lint, not a hard error; see
@@ -323,7 +388,15 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
             };
 
             if is_hard_err {
-                let msg = if is_static {
+                let msg = if let InterpError::ResourceExhaustion(
+                    ResourceExhaustionInfo::MemoryLimitReached { .. },
+                ) = err.error
+                {
+                    // The evaluation may have been stopped deep inside some callee, so name the
+                    // item whose evaluation is aborted.
+                    let path = with_no_trimmed_paths(|| tcx.def_path_str(def.did));
+                    Cow::from(format!("evaluation of `{}` ran out of memory", path))
+                } else if is_static {
                     Cow::from("could not evaluate static initializer")
                 } else {
                     // If the current item has generics, we'd like to enrich the message with the
@@ -371,11 +444,24 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
             };
             if let Err(error) = validation {
                 // Validation failed, report an error. This is always a hard error.
+                let mutable_in_const = match error.kind() {
+                    err_ub!(MutableInConst { rust_path, deref, unsafe_cell, .. })
+                        if cid.promoted.is_none() =>
+                    {
+                        tcx.opt_item_name(cid.instance.def_id())
+                            .map(|name| (name.name, rust_path.clone(), *deref, *unsafe_cell))
+                    }
+                    _ => None,
+                };
                 let err = ConstEvalErr::new(&ecx, error, None);
                 Err(err.struct_error(
                     ecx.tcx,
                     "it is undefined behavior to use this value",
                     |mut diag| {
+                        if let Some((name, rust_path, deref, unsafe_cell)) = mutable_in_const {
+                            let rust_path = rust_path.as_deref();
+                            note_mutable_in_const(&mut diag, name, rust_path, deref, unsafe_cell);
+                        }
                         diag.note(note_on_undefined_behavior_error());
                         diag.note(&format!(
                             "the raw bytes of the constant ({}",
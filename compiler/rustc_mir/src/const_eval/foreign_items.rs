@@ -0,0 +1,87 @@
+//! Shims for the few foreign functions that const evaluation can call, see
+//! `-Z const-eval-foreign-shims`. They are computed on the memory of the interpreter, so their
+//! results do not depend on the C library of the host.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::symbol::{sym, Symbol};
+use rustc_target::abi::Size;
+
+use crate::interpret::{InterpCx, InterpResult, OpTy, PlaceTy, Scalar};
+
+use super::CompileTimeInterpreter;
+
+/// Returns the name of the foreign function `def_id` if const evaluation has a shim for it.
+pub fn foreign_shim_name(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Symbol> {
+    if !tcx.sess.opts.debugging_opts.const_eval_foreign_shims || !tcx.is_foreign_item(def_id) {
+        return None;
+    }
+    let name = tcx.item_name(def_id);
+    matches!(name, sym::memchr | sym::memcmp | sym::strlen).then_some(name)
+}
+
+impl<'mir, 'tcx> InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>> {
+    /// Evaluates a call to a foreign function if it has a shim. Returns `false` if it does not.
+    pub(super) fn emulate_foreign_item(
+        &mut self,
+        instance: ty::Instance<'tcx>,
+        args: &[OpTy<'tcx>],
+        ret: Option<(&PlaceTy<'tcx>, mir::BasicBlock)>,
+    ) -> InterpResult<'tcx, bool> {
+        let name = match foreign_shim_name(*self.tcx, instance.def_id()) {
+            Some(name) => name,
+            None => return Ok(false),
+        };
+        let (dest, ret) = match ret {
+            Some(p) => p,
+            None => throw_ub_format!("`{}` does not return", name),
+        };
+        if args.len() != if name == sym::strlen { 1 } else { 3 } {
+            throw_ub_format!("calling `{}` with {} arguments", name, args.len());
+        }
+
+        match name {
+            sym::memcmp => {
+                let left = self.read_scalar(&args[0])?.check_init()?;
+                let right = self.read_scalar(&args[1])?.check_init()?;
+                let n = Size::from_bytes(self.read_scalar(&args[2])?.to_machine_usize(self)?);
+                let left = self.memory.read_bytes(left, n)?;
+                let right = self.memory.read_bytes(right, n)?;
+                // Only the sign of the result is specified, and `-1`, `0` and `1` is what
+                // `Ord::cmp` of the bytes would give as well.
+                let res = left.cmp(right) as i8;
+                self.write_scalar(Scalar::from_int(res, dest.layout.size), dest)?;
+            }
+            sym::memchr => {
+                let ptr = self.read_scalar(&args[0])?.check_init()?;
+                // The byte is passed as an `int`, but only its lowest eight bits are compared.
+                let byte = self.read_scalar(&args[1])?.to_i32()? as u8;
+                let n = self.read_scalar(&args[2])?.to_machine_usize(self)?;
+                let bytes = self.memory.read_bytes(ptr, Size::from_bytes(n))?;
+                let res = match bytes.iter().position(|&b| b == byte) {
+                    Some(i) => ptr.ptr_offset(Size::from_bytes(i), self)?,
+                    None => Scalar::null_ptr(self),
+                };
+                self.write_scalar(res, dest)?;
+            }
+            sym::strlen => {
+                let ptr = self.read_scalar(&args[0])?.check_init()?;
+                // Read one byte at a time, so the string may end right at the end of its
+                // allocation.
+                let mut len = 0;
+                loop {
+                    let byte_ptr = ptr.ptr_offset(Size::from_bytes(len), self)?;
+                    if self.memory.read_bytes(byte_ptr, Size::from_bytes(1))?[0] == 0 {
+                        break;
+                    }
+                    len += 1;
+                }
+                self.write_scalar(Scalar::from_machine_usize(len, self), dest)?;
+            }
+            _ => bug!("no shim for `{}`", name),
+        }
+        self.go_to_block(ret);
+        Ok(true)
+    }
+}
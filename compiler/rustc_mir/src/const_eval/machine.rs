@@ -1,8 +1,10 @@
 use rustc_middle::mir;
-use rustc_middle::ty::{self, Ty};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, Ty, TyCtxt};
 use std::borrow::Borrow;
 use std::collections::hash_map::Entry;
 use std::hash::Hash;
+use std::time::Duration;
 
 use rustc_data_structures::fx::FxHashMap;
 use std::fmt;
@@ -10,14 +12,14 @@
 use rustc_ast::Mutability;
 use rustc_hir::def_id::DefId;
 use rustc_middle::mir::AssertMessage;
-use rustc_session::Limit;
+use rustc_session::{ConstEvalProfile, Limit};
 use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::{Align, Size};
 use rustc_target::spec::abi::Abi;
 
 use crate::interpret::{
-    self, compile_time_machine, AllocId, Allocation, Frame, ImmTy, InterpCx, InterpResult, Memory,
-    OpTy, PlaceTy, Pointer, Scalar, StackPopUnwind,
+    self, compile_time_machine, AllocId, Allocation, Frame, GlobalId, ImmTy, InterpCx,
+    InterpResult, Memory, OpTy, PlaceTy, Pointer, Scalar, StackPopUnwind,
 };
 
 use super::error::*;
@@ -58,6 +60,17 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
     /// Setting this to `0` disables the limit and allows the interpreter to run forever.
     pub steps_remaining: usize,
 
+    /// The number of terminators evaluated so far.
+    steps_taken: usize,
+
+    /// If set, the current call stack is reported every time this many more terminators have
+    /// been evaluated, see `-Zconst-eval-report-progress`.
+    report_progress_every: Option<usize>,
+
+    /// If set, the number of terminators evaluated with each call stack, see
+    /// `-Z profile-const-eval`.
+    stack_samples: Option<FxHashMap<Vec<ty::Instance<'tcx>>, u64>>,
+
     /// The virtual call stack.
     pub(crate) stack: Vec<Frame<'mir, 'tcx, (), ()>>,
 }
@@ -74,8 +87,55 @@ pub struct MemoryExtra {
 }
 
 impl<'mir, 'tcx> CompileTimeInterpreter<'mir, 'tcx> {
-    pub(super) fn new(const_eval_limit: Limit) -> Self {
-        CompileTimeInterpreter { steps_remaining: const_eval_limit.0, stack: Vec::new() }
+    pub(super) fn new(
+        const_eval_limit: Limit,
+        report_progress_every: Option<usize>,
+        profile: bool,
+    ) -> Self {
+        CompileTimeInterpreter {
+            steps_remaining: const_eval_limit.0,
+            steps_taken: 0,
+            report_progress_every: report_progress_every.filter(|&every| every != 0),
+            stack_samples: if profile { Some(FxHashMap::default()) } else { None },
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl<'mir, 'tcx> InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>> {
+    /// Tells the user how long the evaluation has been running, and which code it is currently
+    /// executing, so they can tell where a constant is stuck.
+    fn report_progress(&self) {
+        let msg = format!("constant evaluation has taken {} steps", self.machine.steps_taken);
+        let mut diag = self.tcx.sess.struct_note_without_error(&msg);
+        diag.set_span(self.cur_span());
+        for frame in self.generate_stacktrace() {
+            diag.span_note(frame.span, &frame.to_string());
+        }
+        diag.emit();
+    }
+
+    /// Records how long the evaluation of `cid` took and where it spent its steps, if it was
+    /// profiled.
+    pub(super) fn record_profile(&mut self, cid: GlobalId<'tcx>, duration: Duration) {
+        let stack_samples = match self.machine.stack_samples.take() {
+            Some(stack_samples) => stack_samples,
+            None => return,
+        };
+        let stacks = with_no_trimmed_paths(|| {
+            stack_samples
+                .into_iter()
+                .map(|(stack, steps)| {
+                    (stack.iter().map(|frame| frame.to_string()).collect(), steps)
+                })
+                .collect()
+        });
+        self.tcx.sess.code_stats.record_const_eval(ConstEvalProfile {
+            item: cid.display(*self.tcx),
+            duration,
+            steps: self.machine.steps_taken as u64,
+            stacks,
+        });
     }
 }
 
@@ -201,6 +261,16 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
 
     type MemoryExtra = MemoryExtra;
 
+    #[inline(always)]
+    fn enforce_validity(ecx: &InterpCx<'mir, 'tcx, Self>) -> bool {
+        ecx.tcx.sess.opts.debugging_opts.extra_const_ub_checks
+    }
+
+    #[inline(always)]
+    fn memory_limit(tcx: TyCtxt<'tcx>, _memory_extra: &MemoryExtra) -> Option<Size> {
+        tcx.sess.opts.debugging_opts.const_eval_memory_limit.map(Size::from_bytes)
+    }
+
     fn load_mir(
         ecx: &InterpCx<'mir, 'tcx, Self>,
         instance: ty::InstanceDef<'tcx>,
@@ -222,11 +292,16 @@ fn find_mir_or_eval_fn(
         instance: ty::Instance<'tcx>,
         _abi: Abi,
         args: &[OpTy<'tcx>],
-        _ret: Option<(&PlaceTy<'tcx>, mir::BasicBlock)>,
+        ret: Option<(&PlaceTy<'tcx>, mir::BasicBlock)>,
         _unwind: StackPopUnwind, // unwinding is not supported in consts
     ) -> InterpResult<'tcx, Option<&'mir mir::Body<'tcx>>> {
         debug!("find_mir_or_eval_fn: {:?}", instance);
 
+        // Some foreign functions have shims, see `-Z const-eval-foreign-shims`.
+        if ecx.emulate_foreign_item(instance, args, ret)? {
+            return Ok(None);
+        }
+
         // Only check non-glue functions
         if let ty::InstanceDef::Item(def) = instance.def {
             // Execution might have wandered off into other crates, so we cannot do a stability-
@@ -240,6 +315,17 @@ fn find_mir_or_eval_fn(
                 // though, so be sure we return here.
                 throw_unsup_format!("calling non-const function `{}`", instance)
             }
+            // Functions like those in `core::arch` may use instructions that only exist with
+            // certain target features, so they can only be evaluated where they could be called.
+            let available = &ecx.tcx.sess.target_features;
+            let required = &ecx.tcx.codegen_fn_attrs(def.did).target_features;
+            if let Some(feature) = required.iter().find(|&feature| !available.contains(feature)) {
+                throw_unsup_format!(
+                    "calling `{}`, which requires the unavailable target feature `{}`",
+                    instance,
+                    feature
+                )
+            }
         }
         // This is a const fn. Call it.
         Ok(Some(match ecx.load_mir(instance.def, None) {
@@ -306,9 +392,26 @@ fn call_intrinsic(
                     Size::from_bytes(size as u64),
                     align,
                     interpret::MemoryKind::Machine(MemoryKind::Heap),
-                );
+                )?;
                 ecx.write_scalar(Scalar::Ptr(ptr), dest)?;
             }
+            sym::const_make_global => {
+                let scalar = ecx.read_scalar(&args[0])?.check_init()?;
+                let ptr = ecx.force_ptr(scalar)?;
+                let kind = ecx.memory.alloc_map().get(&ptr.alloc_id).map(|(kind, _)| *kind);
+                if ptr.offset.bytes() != 0
+                    || kind != Some(interpret::MemoryKind::Machine(MemoryKind::Heap))
+                {
+                    throw_ub_format!(
+                        "pointer passed to `const_make_global` does not point to the beginning \
+                         of a heap allocation"
+                    );
+                }
+                // Interning accepts immutable heap allocations in the final value of a constant,
+                // see `intern_const_alloc_recursive`.
+                ecx.memory.mark_immutable(ptr.alloc_id)?;
+                ecx.write_scalar(scalar, dest)?;
+            }
             _ => {
                 return Err(ConstEvalErrKind::NeedsRfc(format!(
                     "calling intrinsic `{}`",
@@ -356,12 +459,37 @@ fn ptr_to_int(_mem: &Memory<'mir, 'tcx, Self>, _ptr: Pointer) -> InterpResult<'t
     }
 
     fn binary_ptr_op(
-        _ecx: &InterpCx<'mir, 'tcx, Self>,
-        _bin_op: mir::BinOp,
-        _left: &ImmTy<'tcx>,
-        _right: &ImmTy<'tcx>,
+        ecx: &InterpCx<'mir, 'tcx, Self>,
+        bin_op: mir::BinOp,
+        left: &ImmTy<'tcx>,
+        right: &ImmTy<'tcx>,
     ) -> InterpResult<'tcx, (Scalar, bool, Ty<'tcx>)> {
-        Err(ConstEvalErrKind::NeedsRfc("pointer arithmetic or comparison".to_string()).into())
+        // The drop glue of arrays walks over the elements with a pointer until it equals a
+        // pointer to the end of the array (see `open_drop_for_array`). This is supported so that
+        // arrays of `impl const Drop` types can be dropped, but only within drop glue.
+        let in_drop_glue = matches!(ecx.frame().instance.def, ty::InstanceDef::DropGlue(..));
+        match bin_op {
+            _ if !in_drop_glue => {
+                Err(ConstEvalErrKind::NeedsRfc("pointer arithmetic or comparison".to_string())
+                    .into())
+            }
+            mir::BinOp::Offset => {
+                let pointee_ty = left.layout.ty.builtin_deref(true).unwrap().ty;
+                let offset_count = right.to_scalar()?.to_machine_isize(ecx)?;
+                let ptr = ecx.ptr_offset_inbounds(left.to_scalar()?, pointee_ty, offset_count)?;
+                Ok((ptr, false, left.layout.ty))
+            }
+            mir::BinOp::Eq | mir::BinOp::Ne => match (left.to_scalar()?, right.to_scalar()?) {
+                // Pointers into the same allocation are equal if their offsets are.
+                (Scalar::Ptr(l), Scalar::Ptr(r)) if l.alloc_id == r.alloc_id => {
+                    let eq = l.offset == r.offset;
+                    let res = if bin_op == mir::BinOp::Eq { eq } else { !eq };
+                    Ok((Scalar::from_bool(res), false, ecx.tcx.types.bool))
+                }
+                _ => throw_unsup_format!("comparing pointers into different allocations"),
+            },
+            _ => throw_unsup_format!("pointer operation `{:?}` in drop glue", bin_op),
+        }
     }
 
     fn box_alloc(
@@ -372,6 +500,17 @@ fn box_alloc(
     }
 
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        ecx.machine.steps_taken += 1;
+        if let Some(stack_samples) = &mut ecx.machine.stack_samples {
+            let stack = ecx.machine.stack.iter().map(|frame| frame.instance).collect();
+            *stack_samples.entry(stack).or_insert(0) += 1;
+        }
+        if let Some(every) = ecx.machine.report_progress_every {
+            if ecx.machine.steps_taken % every == 0 {
+                ecx.report_progress();
+            }
+        }
+
         // The step limit has already been hit in a previous call to `before_terminator`.
         if ecx.machine.steps_remaining == 0 {
             return Ok(());
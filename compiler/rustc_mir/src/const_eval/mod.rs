@@ -11,17 +11,20 @@
 use rustc_span::{source_map::DUMMY_SP, symbol::Symbol};
 
 use crate::interpret::{
-    intern_const_alloc_recursive, ConstValue, InternKind, InterpCx, MPlaceTy, MemPlaceMeta, Scalar,
+    intern_const_alloc_recursive, AllocCheck, ConstValue, GlobalAlloc, Immediate, InternKind,
+    InterpCx, MPlaceTy, MemPlaceMeta, Scalar, ScalarMaybeUninit,
 };
 
 mod error;
 mod eval_queries;
 mod fn_queries;
+mod foreign_items;
 mod machine;
 
 pub use error::*;
 pub use eval_queries::*;
 pub use fn_queries::*;
+pub use foreign_items::foreign_shim_name;
 pub use machine::*;
 
 pub(crate) fn const_caller_location(
@@ -29,7 +32,7 @@ pub(crate) fn const_caller_location(
     (file, line, col): (Symbol, u32, u32),
 ) -> ConstValue<'tcx> {
     trace!("const_caller_location: {}:{}:{}", file, line, col);
-    let mut ecx = mk_eval_cx(tcx, DUMMY_SP, ty::ParamEnv::reveal_all(), false);
+    let mut ecx = mk_eval_cx(tcx, DUMMY_SP, ty::ParamEnv::reveal_all(), false, None);
 
     let loc_place = ecx.alloc_caller_location(file, line, col);
     if intern_const_alloc_recursive(&mut ecx, InternKind::Constant, &loc_place).is_err() {
@@ -49,6 +52,7 @@ pub(crate) fn const_to_valtree<'tcx>(
         // It is absolutely crucial for soundness that
         // we do not read from static items or other mutable memory.
         false,
+        None,
     );
     let place = ecx.raw_const_to_mplace(raw).unwrap();
     const_to_valtree_inner(&ecx, &place)
@@ -84,11 +88,35 @@ fn const_to_valtree_inner<'tcx>(
             Some(ty::ValTree::Leaf(val.assert_int()))
         }
 
-        // Raw pointers are not allowed in type level constants, as we cannot properly test them for
-        // equality at compile-time (see `ptr_guaranteed_eq`/`_ne`).
-        // Technically we could allow function pointers (represented as `ty::Instance`), but this is not guaranteed to
-        // agree with runtime equality tests.
-        ty::FnPtr(_) | ty::RawPtr(_) => None,
+        // Function pointers are not allowed in type level constants, as two function pointers to
+        // the same `ty::Instance` are not guaranteed to be equal at runtime: functions may get
+        // duplicated across codegen units.
+        ty::FnPtr(_) => None,
+
+        // Raw pointers are represented by the static they point into. Pointers into any other
+        // memory are not allowed in type level constants, as we cannot properly test them for
+        // equality at compile-time (see `ptr_guaranteed_eq`/`_ne`). Neither are pointers to
+        // thread local statics, which have a different address in each thread, or pointers to
+        // the end of a static, which may be the address of another item. Wide pointers are not
+        // supported yet.
+        ty::RawPtr(_) => {
+            let val = ecx.read_immediate(&place.into()).unwrap();
+            let ptr = match *val {
+                Immediate::Scalar(ScalarMaybeUninit::Scalar(Scalar::Int(int))) => {
+                    return Some(ty::ValTree::Leaf(int));
+                }
+                Immediate::Scalar(ScalarMaybeUninit::Scalar(Scalar::Ptr(ptr))) => ptr,
+                _ => return None,
+            };
+            match ecx.tcx.get_global_alloc(ptr.alloc_id)? {
+                GlobalAlloc::Static(def_id) if !ecx.tcx.is_thread_local_static(def_id) => {
+                    let (size, _) =
+                        ecx.memory.get_size_and_align(ptr.alloc_id, AllocCheck::Live).ok()?;
+                    (ptr.offset < size).then(|| ty::ValTree::Pointer(def_id, ptr.offset))
+                }
+                _ => None,
+            }
+        }
         ty::Ref(..) => unimplemented!("need to use deref_const"),
 
         // Trait objects are not allowed in type level constants, as we have no concept for
@@ -142,7 +170,7 @@ pub(crate) fn destructure_const<'tcx>(
     val: &'tcx ty::Const<'tcx>,
 ) -> mir::DestructuredConst<'tcx> {
     trace!("destructure_const: {:?}", val);
-    let ecx = mk_eval_cx(tcx, DUMMY_SP, param_env, false);
+    let ecx = mk_eval_cx(tcx, DUMMY_SP, param_env, false, None);
     let op = ecx.const_to_op(val, None).unwrap();
 
     // We go to `usize` as we cannot allocate anything bigger anyway.
@@ -176,7 +204,7 @@ pub(crate) fn deref_const<'tcx>(
     val: &'tcx ty::Const<'tcx>,
 ) -> &'tcx ty::Const<'tcx> {
     trace!("deref_const: {:?}", val);
-    let ecx = mk_eval_cx(tcx, DUMMY_SP, param_env, false);
+    let ecx = mk_eval_cx(tcx, DUMMY_SP, param_env, false, None);
     let op = ecx.const_to_op(val, None).unwrap();
     let mplace = ecx.deref_operand(&op).unwrap();
     if let Scalar::Ptr(ptr) = mplace.ptr {
@@ -436,7 +436,7 @@ fn join_state_into_successors_of<A>(
     {
         use mir::TerminatorKind::*;
         match bb_data.terminator().kind {
-            Return | Resume | Abort | GeneratorDrop | Unreachable => {}
+            Return | Resume | Abort | GeneratorDrop | Unreachable | TailCall { .. } => {}
 
             Goto { target } => propagate(target, exit_state),
 
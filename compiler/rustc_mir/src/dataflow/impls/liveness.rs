@@ -1,6 +1,9 @@
+use rustc_data_structures::fx::FxHashMap;
 use rustc_index::bit_set::BitSet;
+use rustc_index::vec::{Idx, IndexVec};
 use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
-use rustc_middle::mir::{self, Local, Location};
+use rustc_middle::mir::{self, Field, Local, Location, PlaceRef, ProjectionElem};
+use rustc_middle::ty::{self, Ty};
 
 use crate::dataflow::{AnalysisDomain, Backward, GenKill, GenKillAnalysis};
 
@@ -119,6 +122,241 @@ fn visit_local(&mut self, &local: &Local, context: PlaceContext, _: Location) {
     }
 }
 
+rustc_index::newtype_index! {
+    pub struct TrackedPlace {
+        DEBUG_FORMAT = "tp{}"
+    }
+}
+
+/// The places whose liveness [`MaybeLivePlaces`] tracks: every local, and every field of a local
+/// that is accessed through a field projection directly on the local, like `_1.0`.
+///
+/// Fields are only tracked for locals of struct, tuple and closure types. The fields of a union
+/// overlap, and the fields of an enum are only reachable through a `Downcast`, and may share their
+/// memory with the discriminant and with the fields of other variants.
+pub struct TrackedPlaces {
+    /// Every tracked field of every local. The locals themselves come first, so the index of a
+    /// local as a `TrackedPlace` is the same as its index as a `Local`.
+    fields: IndexVec<Local, Vec<TrackedPlace>>,
+    field_indices: FxHashMap<(Local, Field), TrackedPlace>,
+    len: usize,
+}
+
+impl TrackedPlaces {
+    pub fn new(body: &mir::Body<'_>) -> Self {
+        struct FieldCollector<'a, 'tcx>(&'a mut TrackedPlaces, &'a mir::LocalDecls<'tcx>);
+
+        impl<'tcx> Visitor<'tcx> for FieldCollector<'_, 'tcx> {
+            fn visit_place(&mut self, place: &mir::Place<'tcx>, _: PlaceContext, _: Location) {
+                if !has_disjoint_fields(self.1[place.local].ty) {
+                    return;
+                }
+                if let Some(&ProjectionElem::Field(field, _)) = place.projection.first() {
+                    let places = &mut *self.0;
+                    let len = &mut places.len;
+                    let fields = &mut places.fields[place.local];
+                    places.field_indices.entry((place.local, field)).or_insert_with(|| {
+                        let index = TrackedPlace::new(*len);
+                        *len += 1;
+                        fields.push(index);
+                        index
+                    });
+                }
+            }
+        }
+
+        let mut places = TrackedPlaces {
+            fields: IndexVec::from_elem(Vec::new(), &body.local_decls),
+            field_indices: FxHashMap::default(),
+            len: body.local_decls.len(),
+        };
+        FieldCollector(&mut places, &body.local_decls).visit_body(body);
+        places
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn local(&self, local: Local) -> TrackedPlace {
+        TrackedPlace::new(local.index())
+    }
+
+    /// The tracked field `place` is in, if any.
+    fn field(&self, place: PlaceRef<'_>) -> Option<&TrackedPlace> {
+        match place.projection {
+            [ProjectionElem::Field(field, _), ..] => self.field_indices.get(&(place.local, *field)),
+            _ => None,
+        }
+    }
+
+    /// The tracked place that is read when `place` is read.
+    pub fn for_place(&self, place: PlaceRef<'_>) -> TrackedPlace {
+        self.field(place).copied().unwrap_or_else(|| self.local(place.local))
+    }
+
+    /// The tracked places whose contents may be read when `place` is read. A write to `place`
+    /// is dead if none of them are live afterwards.
+    pub fn overlapping(&self, place: PlaceRef<'_>) -> impl Iterator<Item = TrackedPlace> + '_ {
+        let local = self.local(place.local);
+        let fields = match self.field(place) {
+            Some(field) => std::slice::from_ref(field),
+            None => &self.fields[place.local][..],
+        };
+        std::iter::once(local).chain(fields.iter().copied())
+    }
+
+    /// Marks every part of `local` as dead, because it is overwritten as a whole.
+    fn kill_local(&self, trans: &mut impl GenKill<TrackedPlace>, local: Local) {
+        trans.kill(self.local(local));
+        trans.kill_all(self.fields[local].iter().copied());
+    }
+
+    /// Marks `place` as dead if it is a local or a tracked field that is overwritten as a whole.
+    fn kill_place(&self, trans: &mut impl GenKill<TrackedPlace>, place: mir::Place<'_>) {
+        match place.projection[..] {
+            [] => self.kill_local(trans, place.local),
+            [ProjectionElem::Field(..)] => {
+                if let Some(&field) = self.field(place.as_ref()) {
+                    trans.kill(field);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether the fields of a value of type `ty` never overlap, and are projected directly from it.
+fn has_disjoint_fields(ty: Ty<'_>) -> bool {
+    match ty.kind() {
+        ty::Adt(adt_def, _) => adt_def.is_struct(),
+        ty::Tuple(_) | ty::Closure(..) => true,
+        _ => false,
+    }
+}
+
+/// Like [`MaybeLiveLocals`], but the fields of a local that are accessed through field
+/// projections are tracked separately from the local itself. A write to one field is dead when
+/// only other fields of the same local are read afterwards.
+///
+/// A field being live does not make its local live, and the other way around. Use
+/// [`TrackedPlaces::overlapping`] to find out whether anything a place may contain is live.
+///
+/// This analysis treats references the same way as `MaybeLiveLocals` does, so it has to be
+/// combined with [`MaybeBorrowedLocals`] as well.
+///
+/// [`MaybeBorrowedLocals`]: super::MaybeBorrowedLocals
+pub struct MaybeLivePlaces<'a> {
+    places: &'a TrackedPlaces,
+}
+
+impl<'a> MaybeLivePlaces<'a> {
+    pub fn new(places: &'a TrackedPlaces) -> Self {
+        MaybeLivePlaces { places }
+    }
+}
+
+impl AnalysisDomain<'tcx> for MaybeLivePlaces<'_> {
+    type Domain = BitSet<TrackedPlace>;
+    type Direction = Backward;
+
+    const NAME: &'static str = "liveness_places";
+
+    fn bottom_value(&self, _: &mir::Body<'tcx>) -> Self::Domain {
+        // bottom = not live
+        BitSet::new_empty(self.places.len())
+    }
+
+    fn initialize_start_block(&self, _: &mir::Body<'tcx>, _: &mut Self::Domain) {
+        // No places are live until we observe a use
+    }
+}
+
+impl GenKillAnalysis<'tcx> for MaybeLivePlaces<'_> {
+    type Idx = TrackedPlace;
+
+    fn statement_effect(
+        &self,
+        trans: &mut impl GenKill<Self::Idx>,
+        statement: &mir::Statement<'tcx>,
+        location: Location,
+    ) {
+        PlaceTransferFunction(trans, self.places).visit_statement(statement, location);
+    }
+
+    fn terminator_effect(
+        &self,
+        trans: &mut impl GenKill<Self::Idx>,
+        terminator: &mir::Terminator<'tcx>,
+        location: Location,
+    ) {
+        PlaceTransferFunction(trans, self.places).visit_terminator(terminator, location);
+    }
+
+    fn call_return_effect(
+        &self,
+        trans: &mut impl GenKill<Self::Idx>,
+        _block: mir::BasicBlock,
+        _func: &mir::Operand<'tcx>,
+        _args: &[mir::Operand<'tcx>],
+        dest_place: mir::Place<'tcx>,
+    ) {
+        self.places.kill_place(trans, dest_place);
+    }
+
+    fn yield_resume_effect(
+        &self,
+        trans: &mut impl GenKill<Self::Idx>,
+        _resume_block: mir::BasicBlock,
+        resume_place: mir::Place<'tcx>,
+    ) {
+        self.places.kill_place(trans, resume_place);
+    }
+}
+
+struct PlaceTransferFunction<'a, T>(&'a mut T, &'a TrackedPlaces);
+
+impl<'tcx, T> Visitor<'tcx> for PlaceTransferFunction<'_, T>
+where
+    T: GenKill<TrackedPlace>,
+{
+    fn visit_statement(&mut self, statement: &mir::Statement<'tcx>, location: Location) {
+        match &statement.kind {
+            // Setting the discriminant only writes the tag, which may be stored in the niche of a
+            // field, so it neither reads nor overwrites anything as a whole. Writes to the fields
+            // of the variant before it are still observable afterwards.
+            mir::StatementKind::SetDiscriminant { place, .. } => {
+                let context = PlaceContext::MutatingUse(MutatingUseContext::Projection);
+                self.visit_projection(place.as_ref(), context, location);
+                if place.is_indirect() {
+                    self.0.gen(self.1.local(place.local));
+                }
+            }
+            _ => self.super_statement(statement, location),
+        }
+    }
+
+    fn visit_place(&mut self, place: &mir::Place<'tcx>, context: PlaceContext, location: Location) {
+        // See `TransferFunction::visit_place` for why `super_place` is not called.
+        self.visit_projection(place.as_ref(), context, location);
+
+        match DefUse::for_place(context) {
+            Some(_) if place.is_indirect() => self.0.gen(self.1.local(place.local)),
+            Some(DefUse::Def) => self.1.kill_place(self.0, *place),
+            Some(DefUse::Use) => self.0.gen(self.1.for_place(place.as_ref())),
+            None => {}
+        }
+    }
+
+    fn visit_local(&mut self, &local: &Local, context: PlaceContext, _: Location) {
+        match DefUse::for_place(context) {
+            Some(DefUse::Def) => self.1.kill_local(self.0, local),
+            Some(DefUse::Use) => self.0.gen(self.1.local(local)),
+            None => {}
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, Clone)]
 enum DefUse {
     Def,
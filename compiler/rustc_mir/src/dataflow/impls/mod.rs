@@ -29,7 +29,7 @@
 pub use self::borrowed_locals::{MaybeBorrowedLocals, MaybeMutBorrowedLocals};
 pub use self::borrows::Borrows;
 pub use self::init_locals::MaybeInitializedLocals;
-pub use self::liveness::MaybeLiveLocals;
+pub use self::liveness::{MaybeLiveLocals, MaybeLivePlaces, TrackedPlace, TrackedPlaces};
 pub use self::storage_liveness::{MaybeRequiresStorage, MaybeStorageLive};
 
 /// `MaybeInitializedPlaces` tracks all places that might be
@@ -216,6 +216,7 @@ fn before_terminator_effect(
             | TerminatorKind::Resume
             | TerminatorKind::Return
             | TerminatorKind::SwitchInt { .. }
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Unreachable => {}
         }
     }
@@ -251,6 +252,7 @@ fn terminator_effect(
             | TerminatorKind::Resume
             | TerminatorKind::Return
             | TerminatorKind::SwitchInt { .. }
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Unreachable => {}
         }
 
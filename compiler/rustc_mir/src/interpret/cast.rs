@@ -6,7 +6,7 @@
 use rustc_middle::mir::CastKind;
 use rustc_middle::ty::adjustment::PointerCast;
 use rustc_middle::ty::layout::{IntegerExt, TyAndLayout};
-use rustc_middle::ty::{self, FloatTy, Ty, TypeAndMut};
+use rustc_middle::ty::{self, FloatTy, ScalarInt, Ty, TypeAndMut};
 use rustc_target::abi::{Integer, LayoutOf, Variants};
 
 use super::{
@@ -198,8 +198,10 @@ pub(super) fn cast_from_scalar(
                     RawPtr(_) => self.pointer_size(),
                     _ => bug!(),
                 };
-                let v = size.truncate(v);
-                Scalar::from_uint(v, size)
+                let int = ScalarInt::try_from_uint(src_layout.size.truncate(v), src_layout.size)
+                    .unwrap();
+                let (int, _) = if signed { int.sext_to(size) } else { int.zext_to(size) };
+                Scalar::Int(int)
             }
 
             Float(FloatTy::F32) if signed => Scalar::from_f32(Single::from_i128(v as i128).value),
@@ -262,6 +262,18 @@ pub fn current_span(&self) -> Span {
     }
 }
 
+impl<'tcx> FrameInfo<'tcx> {
+    /// Whether the source code at `span` can be shown, which is not the case for frames in
+    /// other crates whose sources are not available.
+    pub fn has_source(&self, tcx: TyCtxt<'tcx>) -> bool {
+        if self.span.is_dummy() {
+            return false;
+        }
+        let source_map = tcx.sess.source_map();
+        source_map.ensure_source_file_source_present(source_map.lookup_source_file(self.span.lo()))
+    }
+}
+
 impl<'tcx> fmt::Display for FrameInfo<'tcx> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         ty::tls::with(|tcx| {
@@ -867,6 +879,37 @@ pub(super) fn pop_stack_frame(&mut self, unwinding: bool) -> InterpResult<'tcx>
         }
     }
 
+    /// Removes the frame right below the topmost one, after the topmost frame was pushed by a
+    /// tail call from it. The topmost frame then returns to wherever the removed frame would
+    /// have returned to.
+    pub(super) fn pop_tail_caller_frame(&mut self) -> InterpResult<'tcx> {
+        info!("popping stack frame (tail call)");
+
+        let caller_idx = self.frame_idx().checked_sub(1).expect("tail call without a caller frame");
+        let frame = self.stack_mut().remove(caller_idx);
+        let callee = self.frame_mut();
+        callee.return_place = frame.return_place;
+        callee.return_to_block = frame.return_to_block;
+
+        // Same as in `pop_stack_frame`.
+        let cleanup = match frame.return_to_block {
+            StackPopCleanup::Goto { .. } => true,
+            StackPopCleanup::None { cleanup, .. } => cleanup,
+        };
+        if !cleanup {
+            assert!(caller_idx == 0, "only the topmost frame should ever be leaked");
+            return Ok(());
+        }
+
+        for local in &frame.locals {
+            self.deallocate_local(local.value)?;
+        }
+
+        // Nothing is unwinding, so there is nowhere else for the hook to jump to.
+        M::after_stack_pop(self, frame, /* unwinding */ false)?;
+        Ok(())
+    }
+
     /// Mark a storage as live, killing the previous content.
     pub fn storage_live(&mut self, local: mir::Local) -> InterpResult<'tcx> {
         assert!(local != mir::RETURN_PLACE, "Cannot make return place live");
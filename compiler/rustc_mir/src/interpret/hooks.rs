@@ -0,0 +1,208 @@
+//! A stripped-down interface to the interpreter for tools outside of the compiler, like
+//! symbolic execution engines or taint trackers. Instead of implementing all of `Machine`, such
+//! a tool implements `InterpHooks` and runs the interpreter with a `HookedMachine`, which
+//! evaluates MIR like compile-time evaluation does and calls the hooks along the way.
+
+use std::marker::PhantomData;
+
+use rustc_middle::mir;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::Span;
+use rustc_target::abi::Size;
+use rustc_target::spec::abi::Abi;
+
+use super::{
+    compile_time_machine, Frame, ImmTy, InterpCx, InterpResult, Machine, Memory, OpTy, PlaceTy,
+    Pointer, Scalar, StackPopUnwind,
+};
+
+/// The interpreter that a tool implementing `InterpHooks` is driving.
+pub type HookedInterpCx<'mir, 'tcx, H> = InterpCx<'mir, 'tcx, HookedMachine<'mir, 'tcx, H>>;
+
+/// Hooks that are called while a `HookedMachine` evaluates MIR. Every hook has a default that
+/// does what compile-time evaluation would do.
+///
+/// The hooks are stored as the `MemoryExtra` of the machine, so the call hooks can reach them
+/// as `ecx.memory.extra`.
+pub trait InterpHooks<'mir, 'tcx>: Sized {
+    /// Called before `size` bytes are read at `ptr`.
+    #[inline(always)]
+    fn memory_read(&self, _ptr: Pointer, _size: Size) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
+    /// Called before `size` bytes are written at `ptr`.
+    #[inline(always)]
+    fn memory_written(&mut self, _ptr: Pointer, _size: Size) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
+    /// Called for every call of a function other than an intrinsic. Returns `true` if the hook
+    /// has written the return value of the call to `dest`, in which case the function itself is
+    /// not evaluated. Returning `false` evaluates the MIR of the function.
+    #[inline(always)]
+    fn call_fn(
+        _ecx: &mut HookedInterpCx<'mir, 'tcx, Self>,
+        _instance: ty::Instance<'tcx>,
+        _args: &[OpTy<'tcx>],
+        _dest: Option<&PlaceTy<'tcx>>,
+    ) -> InterpResult<'tcx, bool> {
+        Ok(false)
+    }
+
+    /// Called for every call of an intrinsic, before the interpreter tries to evaluate it
+    /// itself. Returns `true` if the hook has written the return value of the call to `dest`.
+    #[inline(always)]
+    fn call_intrinsic(
+        _ecx: &mut HookedInterpCx<'mir, 'tcx, Self>,
+        _instance: ty::Instance<'tcx>,
+        _args: &[OpTy<'tcx>],
+        _dest: Option<&PlaceTy<'tcx>>,
+    ) -> InterpResult<'tcx, bool> {
+        Ok(false)
+    }
+}
+
+/// A `Machine` that calls the `InterpHooks` of `H`.
+pub struct HookedMachine<'mir, 'tcx, H> {
+    stack: Vec<Frame<'mir, 'tcx, (), ()>>,
+    _hooks: PhantomData<H>,
+}
+
+impl<'mir, 'tcx, H: InterpHooks<'mir, 'tcx>> HookedMachine<'mir, 'tcx, H> {
+    /// Creates an interpreter that calls the given hooks.
+    pub fn new_interp_cx(
+        tcx: TyCtxt<'tcx>,
+        root_span: Span,
+        param_env: ty::ParamEnv<'tcx>,
+        hooks: H,
+    ) -> HookedInterpCx<'mir, 'tcx, H> {
+        let machine = HookedMachine { stack: Vec::new(), _hooks: PhantomData };
+        InterpCx::new(tcx, root_span, param_env, machine, hooks)
+    }
+}
+
+impl<'mir, 'tcx, H: InterpHooks<'mir, 'tcx>> Machine<'mir, 'tcx> for HookedMachine<'mir, 'tcx, H> {
+    compile_time_machine!(<'mir, 'tcx>);
+
+    type MemoryKind = !;
+
+    type MemoryExtra = H;
+
+    #[inline(always)]
+    fn enforce_validity(_ecx: &InterpCx<'mir, 'tcx, Self>) -> bool {
+        false
+    }
+
+    fn load_mir(
+        ecx: &InterpCx<'mir, 'tcx, Self>,
+        instance: ty::InstanceDef<'tcx>,
+    ) -> InterpResult<'tcx, &'tcx mir::Body<'tcx>> {
+        match instance {
+            ty::InstanceDef::Item(def) if !ecx.tcx.is_mir_available(def.did) => {
+                throw_unsup!(NoMirFor(def.did))
+            }
+            _ => Ok(ecx.tcx.instance_mir(instance)),
+        }
+    }
+
+    fn find_mir_or_eval_fn(
+        ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        instance: ty::Instance<'tcx>,
+        _abi: Abi,
+        args: &[OpTy<'tcx>],
+        ret: Option<(&PlaceTy<'tcx>, mir::BasicBlock)>,
+        _unwind: StackPopUnwind,
+    ) -> InterpResult<'tcx, Option<&'mir mir::Body<'tcx>>> {
+        if H::call_fn(ecx, instance, args, ret.map(|(dest, _)| dest))? {
+            ecx.return_to_block(ret.map(|(_, ret)| ret))?;
+            return Ok(None);
+        }
+        Ok(Some(ecx.load_mir(instance.def, None)?))
+    }
+
+    fn call_intrinsic(
+        ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        instance: ty::Instance<'tcx>,
+        args: &[OpTy<'tcx>],
+        ret: Option<(&PlaceTy<'tcx>, mir::BasicBlock)>,
+        _unwind: StackPopUnwind,
+    ) -> InterpResult<'tcx> {
+        if H::call_intrinsic(ecx, instance, args, ret.map(|(dest, _)| dest))? {
+            return ecx.return_to_block(ret.map(|(_, ret)| ret));
+        }
+        if ecx.emulate_intrinsic(instance, args, ret)? {
+            return Ok(());
+        }
+        throw_unsup_format!("calling intrinsic `{}`", ecx.tcx.item_name(instance.def_id()))
+    }
+
+    fn assert_panic(
+        _ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        msg: &mir::AssertMessage<'tcx>,
+        _unwind: Option<mir::BasicBlock>,
+    ) -> InterpResult<'tcx> {
+        throw_unsup_format!("evaluation panicked: {:?}", msg)
+    }
+
+    fn ptr_to_int(_mem: &Memory<'mir, 'tcx, Self>, _ptr: Pointer) -> InterpResult<'tcx, u64> {
+        throw_unsup!(ReadPointerAsBytes)
+    }
+
+    fn binary_ptr_op(
+        _ecx: &InterpCx<'mir, 'tcx, Self>,
+        _bin_op: mir::BinOp,
+        _left: &ImmTy<'tcx>,
+        _right: &ImmTy<'tcx>,
+    ) -> InterpResult<'tcx, (Scalar, bool, Ty<'tcx>)> {
+        throw_unsup_format!("pointer arithmetic or comparison is not supported")
+    }
+
+    fn box_alloc(
+        _ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        _dest: &PlaceTy<'tcx>,
+    ) -> InterpResult<'tcx> {
+        throw_unsup_format!("heap allocations via `box` keyword are not supported")
+    }
+
+    #[inline(always)]
+    fn memory_read(hooks: &H, _alloc_extra: &(), ptr: Pointer, size: Size) -> InterpResult<'tcx> {
+        hooks.memory_read(ptr, size)
+    }
+
+    #[inline(always)]
+    fn memory_written(
+        hooks: &mut H,
+        _alloc_extra: &mut (),
+        ptr: Pointer,
+        size: Size,
+    ) -> InterpResult<'tcx> {
+        hooks.memory_written(ptr, size)
+    }
+
+    #[inline(always)]
+    fn init_frame_extra(
+        ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        frame: Frame<'mir, 'tcx>,
+    ) -> InterpResult<'tcx, Frame<'mir, 'tcx>> {
+        // Enforce stack size limit. Add 1 because this is run before the new frame is pushed.
+        if !ecx.tcx.sess.recursion_limit().value_within_limit(ecx.stack().len() + 1) {
+            throw_exhaust!(StackFrameLimitReached)
+        }
+        Ok(frame)
+    }
+
+    #[inline(always)]
+    fn stack(
+        ecx: &'a InterpCx<'mir, 'tcx, Self>,
+    ) -> &'a [Frame<'mir, 'tcx, Self::PointerTag, Self::FrameExtra>] {
+        &ecx.machine.stack
+    }
+
+    #[inline(always)]
+    fn stack_mut(
+        ecx: &'a mut InterpCx<'mir, 'tcx, Self>,
+    ) -> &'a mut Vec<Frame<'mir, 'tcx, Self::PointerTag, Self::FrameExtra>> {
+        &mut ecx.machine.stack
+    }
+}
@@ -360,7 +360,7 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx, const_eval
 
     let mut todo: Vec<_> = leftover_allocations.iter().cloned().collect();
     while let Some(alloc_id) = todo.pop() {
-        if let Some((_, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
+        if let Some((kind, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
             // We can't call the `intern_shallow` method here, as its logic is tailored to safe
             // references and a `leftover_allocations` set (where we only have a todo-list here).
             // So we hand-roll the interning logic here again.
@@ -382,6 +382,17 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx, const_eval
                     // immutability is so important.
                     alloc.mutability = Mutability::Not;
                 }
+                InternKind::Constant
+                    if kind == MemoryKind::Machine(const_eval::MemoryKind::Heap)
+                        && alloc.mutability == Mutability::Not =>
+                {
+                    // Heap allocations made with `const_allocate` are usually only reachable
+                    // through raw pointers, e.g. the buffer of a `Vec`-like type. Passing them to
+                    // `const_make_global` opts into making them part of the constant, and makes
+                    // them immutable, so they become `'static` data like everything else in a
+                    // constant. Identical allocations are deduplicated by `intern_const_alloc`.
+                    // Heap allocations that were not made global are still rejected below.
+                }
                 InternKind::Constant => {
                     // If it's a constant, we should not have any "leftovers" as everything
                     // is tracked by const-checking.
@@ -429,7 +440,7 @@ pub(crate) fn intern_with_temp_alloc(
             &MPlaceTy<'tcx, M::PointerTag>,
         ) -> InterpResult<'tcx, ()>,
     ) -> InterpResult<'tcx, &'tcx Allocation> {
-        let dest = self.allocate(layout, MemoryKind::Stack);
+        let dest = self.allocate(layout, MemoryKind::Stack)?;
         f(self, &dest)?;
         let ptr = dest.ptr.assert_ptr();
         assert_eq!(ptr.offset, Size::ZERO);
@@ -12,7 +12,7 @@
 };
 use rustc_middle::ty;
 use rustc_middle::ty::subst::SubstsRef;
-use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_middle::ty::{ScalarInt, Ty, TyCtxt};
 use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::{Abi, Align, LayoutOf as _, Primitive, Size};
 
@@ -21,6 +21,8 @@
 };
 
 mod caller_location;
+mod float;
+mod simd;
 mod type_name;
 
 fn numeric_intrinsic<Tag>(name: Symbol, bits: u128, kind: Primitive) -> Scalar<Tag> {
@@ -28,16 +30,15 @@ fn numeric_intrinsic<Tag>(name: Symbol, bits: u128, kind: Primitive) -> Scalar<T
         Primitive::Int(integer, _) => integer.size(),
         _ => bug!("invalid `{}` argument: {:?}", name, bits),
     };
-    let extra = 128 - u128::from(size.bits());
-    let bits_out = match name {
-        sym::ctpop => u128::from(bits.count_ones()),
-        sym::ctlz => u128::from(bits.leading_zeros()) - extra,
-        sym::cttz => u128::from((bits << extra).trailing_zeros()) - extra,
-        sym::bswap => (bits << extra).swap_bytes(),
-        sym::bitreverse => (bits << extra).reverse_bits(),
+    let int = ScalarInt::try_from_uint(bits, size).unwrap();
+    match name {
+        sym::ctpop => Scalar::from_uint(int.count_ones(), size),
+        sym::ctlz => Scalar::from_uint(int.leading_zeros(), size),
+        sym::cttz => Scalar::from_uint(int.trailing_zeros(), size),
+        sym::bswap => Scalar::Int(int.swap_bytes()),
+        sym::bitreverse => Scalar::Int(int.reverse_bits()),
         _ => bug!("not a numeric intrinsic: {}", name),
-    };
-    Scalar::from_uint(bits_out, size)
+    }
 }
 
 /// The logic for all nullary intrinsics is implemented here. These intrinsics don't get evaluated
@@ -308,24 +309,18 @@ pub fn emulate_intrinsic(
                 self.write_scalar(val, dest)?;
             }
             sym::rotate_left | sym::rotate_right => {
-                // rotate_left: (X << (S % BW)) | (X >> ((BW - S) % BW))
-                // rotate_right: (X << ((BW - S) % BW)) | (X >> (S % BW))
                 let layout = self.layout_of(substs.type_at(0))?;
                 let val = self.read_scalar(&args[0])?.check_init()?;
-                let val_bits = self.force_bits(val, layout.size)?;
+                let val = ScalarInt::try_from_uint(self.force_bits(val, layout.size)?, layout.size)
+                    .unwrap();
                 let raw_shift = self.read_scalar(&args[1])?.check_init()?;
                 let raw_shift_bits = self.force_bits(raw_shift, layout.size)?;
-                let width_bits = u128::from(layout.size.bits());
-                let shift_bits = raw_shift_bits % width_bits;
-                let inv_shift_bits = (width_bits - shift_bits) % width_bits;
-                let result_bits = if intrinsic_name == sym::rotate_left {
-                    (val_bits << shift_bits) | (val_bits >> inv_shift_bits)
+                let result = if intrinsic_name == sym::rotate_left {
+                    val.rotate_left(raw_shift_bits)
                 } else {
-                    (val_bits >> shift_bits) | (val_bits << inv_shift_bits)
+                    val.rotate_right(raw_shift_bits)
                 };
-                let truncated_bits = self.truncate(result_bits, layout);
-                let result = Scalar::from_uint(truncated_bits, layout.size);
-                self.write_scalar(result, dest)?;
+                self.write_scalar(Scalar::Int(result), dest)?;
             }
             sym::copy => {
                 self.copy_intrinsic(&args[0], &args[1], &args[2], /*nonoverlapping*/ false)?;
@@ -462,6 +457,32 @@ pub fn emulate_intrinsic(
                 );
                 self.copy_op(&self.operand_index(&args[0], index)?, dest)?;
             }
+            sym::sqrtf32
+            | sym::sqrtf64
+            | sym::powif32
+            | sym::powif64
+            | sym::fmaf32
+            | sym::fmaf64
+            | sym::floorf32
+            | sym::floorf64
+            | sym::ceilf32
+            | sym::ceilf64
+            | sym::truncf32
+            | sym::truncf64
+            | sym::roundf32
+            | sym::roundf64
+            | sym::rintf32
+            | sym::rintf64
+            | sym::nearbyintf32
+            | sym::nearbyintf64
+            | sym::fabsf32
+            | sym::fabsf64
+            | sym::copysignf32
+            | sym::copysignf64
+            | sym::minnumf32
+            | sym::minnumf64
+            | sym::maxnumf32
+            | sym::maxnumf64 => self.emulate_float_intrinsic(intrinsic_name, args, dest)?,
             sym::likely | sym::unlikely => {
                 // These just return their argument
                 self.copy_op(&args[0], dest)?;
@@ -472,7 +493,11 @@ pub fn emulate_intrinsic(
                     throw_ub_format!("`assume` intrinsic called with `false`");
                 }
             }
-            _ => return Ok(false),
+            _ => {
+                if !self.emulate_simd_intrinsic(intrinsic_name, args, dest)? {
+                    return Ok(false);
+                }
+            }
         }
 
         trace!("{:?}", self.dump_place(**dest));
@@ -0,0 +1,137 @@
+//! The floating-point math intrinsics that have a correctly rounded result, like `sqrtf64`.
+//!
+//! These are computed with `rustc_apfloat`, so the result of a const evaluation does not depend
+//! on the host or the target. Intrinsics like `sinf64` or `expf64` have no correctly rounded
+//! implementation in `rustc_apfloat`, so they are not supported here.
+
+use rustc_apfloat::{
+    ieee::{Double, Single},
+    Float, Round,
+};
+use rustc_middle::mir::interpret::{InterpResult, Scalar};
+use rustc_middle::ty::{self, FloatTy};
+use rustc_span::symbol::{sym, Symbol};
+
+use crate::interpret::{
+    intrinsics::{InterpCx, Machine},
+    OpTy, PlaceTy,
+};
+
+/// Returns the integer square root of `n`, and whether it is inexact.
+fn isqrt(n: u128) -> (u128, bool) {
+    // The largest power of four that is not larger than `n`.
+    let mut bit = 1 << 126;
+    while bit > n {
+        bit >>= 2;
+    }
+    let mut rem = n;
+    let mut root = 0;
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    (root, rem != 0)
+}
+
+/// The correctly rounded square root of `x`.
+fn sqrt<F: Float>(x: F) -> F {
+    if x.is_nan() || (x.is_negative() && !x.is_zero()) {
+        return F::NAN;
+    }
+    if x.is_zero() || x.is_infinite() {
+        return x;
+    }
+    // Split `x` into `m * 2^e` with an integer `m` of `PRECISION` bits, such that `e` is even.
+    let precision = F::PRECISION as i16;
+    let ilogb = x.ilogb();
+    let mut m = x.scalbn(precision - 1 - ilogb).to_u128(128).value;
+    let mut e = ilogb - (precision - 1);
+    if e % 2 != 0 {
+        m <<= 1;
+        e -= 1;
+    }
+    // `m` has at most `PRECISION + 1` bits, so shifting it by 72 bits leaves the root with at
+    // least two more bits than `F` can represent. Setting the lowest bit if the root is inexact
+    // makes `from_u128` round it as if it was the exact root.
+    let (root, inexact) = isqrt(m << 72);
+    F::from_u128(root | u128::from(inexact)).value.scalbn(e / 2 - 36)
+}
+
+/// Computes `x^n` by repeated squaring, the same way as `__powidf2` of compiler-rt does.
+fn powi<F: Float>(mut x: F, n: i32) -> F {
+    let mut result = F::from_u128(1).value;
+    let mut exp = n;
+    loop {
+        if exp & 1 != 0 {
+            result = result.mul_r(x, Round::NearestTiesToEven).value;
+        }
+        exp /= 2;
+        if exp == 0 {
+            break;
+        }
+        x = x.mul_r(x, Round::NearestTiesToEven).value;
+    }
+    if n < 0 { F::from_u128(1).value.div_r(result, Round::NearestTiesToEven).value } else { result }
+}
+
+impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
+    /// Evaluates the floating-point math intrinsic `name`, whose result type is either `f32` or
+    /// `f64`.
+    crate fn emulate_float_intrinsic(
+        &mut self,
+        name: Symbol,
+        args: &[OpTy<'tcx, M::PointerTag>],
+        dest: &PlaceTy<'tcx, M::PointerTag>,
+    ) -> InterpResult<'tcx> {
+        let res = match *dest.layout.ty.kind() {
+            ty::Float(FloatTy::F32) => {
+                let res: Single = self.eval_float_intrinsic(name, args, |op| op.to_f32())?;
+                Scalar::from(res)
+            }
+            ty::Float(FloatTy::F64) => {
+                let res: Double = self.eval_float_intrinsic(name, args, |op| op.to_f64())?;
+                Scalar::from(res)
+            }
+            _ => span_bug!(self.cur_span(), "{} called with result type {}", name, dest.layout.ty),
+        };
+        self.write_scalar(res, dest)
+    }
+
+    fn eval_float_intrinsic<F: Float>(
+        &self,
+        name: Symbol,
+        args: &[OpTy<'tcx, M::PointerTag>],
+        to_float: impl Fn(Scalar<M::PointerTag>) -> InterpResult<'tcx, F>,
+    ) -> InterpResult<'tcx, F> {
+        let arg = |i: usize| to_float(self.read_scalar(&args[i])?.check_init()?);
+        let res = match name {
+            sym::sqrtf32 | sym::sqrtf64 => sqrt(arg(0)?),
+            sym::powif32 | sym::powif64 => {
+                powi(arg(0)?, self.read_scalar(&args[1])?.check_init()?.to_i32()?)
+            }
+            sym::fmaf32 | sym::fmaf64 => arg(0)?.mul_add(arg(1)?, arg(2)?).value,
+            sym::floorf32 | sym::floorf64 => arg(0)?.round_to_integral(Round::TowardNegative).value,
+            sym::ceilf32 | sym::ceilf64 => arg(0)?.round_to_integral(Round::TowardPositive).value,
+            sym::truncf32 | sym::truncf64 => arg(0)?.round_to_integral(Round::TowardZero).value,
+            sym::roundf32 | sym::roundf64 => {
+                arg(0)?.round_to_integral(Round::NearestTiesToAway).value
+            }
+            // There is no floating-point environment during const evaluation, so these only
+            // differ in whether they raise an inexact exception.
+            sym::rintf32 | sym::rintf64 | sym::nearbyintf32 | sym::nearbyintf64 => {
+                arg(0)?.round_to_integral(Round::NearestTiesToEven).value
+            }
+            sym::fabsf32 | sym::fabsf64 => arg(0)?.abs(),
+            sym::copysignf32 | sym::copysignf64 => arg(0)?.copy_sign(arg(1)?),
+            sym::minnumf32 | sym::minnumf64 => arg(0)?.min(arg(1)?),
+            sym::maxnumf32 | sym::maxnumf64 => arg(0)?.max(arg(1)?),
+            _ => span_bug!(self.cur_span(), "not a floating-point math intrinsic: {}", name),
+        };
+        Ok(res)
+    }
+}
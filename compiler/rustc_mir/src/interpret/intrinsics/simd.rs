@@ -0,0 +1,115 @@
+//! The lane-wise SIMD platform intrinsics that have no side effects, like `simd_add` or
+//! `simd_shuffle4`. Each lane is computed like the corresponding scalar operation.
+
+use rustc_middle::mir::{
+    interpret::{InterpResult, Scalar},
+    BinOp, UnOp,
+};
+use rustc_span::symbol::{sym, Symbol};
+
+use crate::interpret::{
+    intrinsics::{InterpCx, Machine},
+    OpTy, PlaceTy,
+};
+
+impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
+    /// Evaluates the SIMD intrinsic `name`. Returns `false` if it is not supported here.
+    crate fn emulate_simd_intrinsic(
+        &mut self,
+        name: Symbol,
+        args: &[OpTy<'tcx, M::PointerTag>],
+        dest: &PlaceTy<'tcx, M::PointerTag>,
+    ) -> InterpResult<'tcx, bool> {
+        let bin_op = match name {
+            sym::simd_add => Some(BinOp::Add),
+            sym::simd_sub => Some(BinOp::Sub),
+            sym::simd_mul => Some(BinOp::Mul),
+            sym::simd_div => Some(BinOp::Div),
+            sym::simd_rem => Some(BinOp::Rem),
+            sym::simd_shl => Some(BinOp::Shl),
+            sym::simd_shr => Some(BinOp::Shr),
+            sym::simd_and => Some(BinOp::BitAnd),
+            sym::simd_or => Some(BinOp::BitOr),
+            sym::simd_xor => Some(BinOp::BitXor),
+            sym::simd_eq => Some(BinOp::Eq),
+            sym::simd_ne => Some(BinOp::Ne),
+            sym::simd_lt => Some(BinOp::Lt),
+            sym::simd_le => Some(BinOp::Le),
+            sym::simd_gt => Some(BinOp::Gt),
+            sym::simd_ge => Some(BinOp::Ge),
+            _ => None,
+        };
+        let is_shuffle = name.as_str().starts_with("simd_shuffle");
+        if bin_op.is_none() && !is_shuffle && !matches!(name, sym::simd_neg | sym::simd_select) {
+            return Ok(false);
+        }
+        let (len, _) = dest.layout.ty.simd_size_and_type(*self.tcx);
+
+        if let Some(bin_op) = bin_op {
+            for i in 0..len {
+                let left = self.read_immediate(&self.operand_index(&args[0], i)?)?;
+                let right = self.read_immediate(&self.operand_index(&args[1], i)?)?;
+                let place = self.place_index(dest, i)?;
+                let (val, overflowed, ty) = self.overflowing_binary_op(bin_op, &left, &right)?;
+                if ty.is_bool() {
+                    // Comparisons set all bits of a lane of the resulting mask if they hold.
+                    let mask = if val.to_bool()? { -1 } else { 0 };
+                    self.write_scalar(Scalar::from_int(mask, place.layout.size), &place)?;
+                    continue;
+                }
+                // Like the LLVM instructions they are lowered to, arithmetic wraps around but
+                // overlong shifts and overflowing divisions are undefined.
+                if overflowed && !matches!(bin_op, BinOp::Add | BinOp::Sub | BinOp::Mul) {
+                    throw_ub_format!("overflow in `{}` in lane {}", name, i);
+                }
+                self.write_scalar(val, &place)?;
+            }
+            return Ok(true);
+        }
+
+        match name {
+            sym::simd_neg => {
+                for i in 0..len {
+                    let val = self.read_immediate(&self.operand_index(&args[0], i)?)?;
+                    let (val, _overflowed, _ty) = self.overflowing_unary_op(UnOp::Neg, &val)?;
+                    let place = self.place_index(dest, i)?;
+                    self.write_scalar(val, &place)?;
+                }
+            }
+            sym::simd_select => {
+                for i in 0..len {
+                    let mask = self.operand_index(&args[0], i)?;
+                    let bits = self.read_scalar(&mask)?.check_init()?;
+                    let source = if self.force_bits(bits, mask.layout.size)? != 0 { 1 } else { 2 };
+                    let val = self.operand_index(&args[source], i)?;
+                    let place = self.place_index(dest, i)?;
+                    self.copy_op(&val, &place)?;
+                }
+            }
+            _ => {
+                assert!(is_shuffle);
+                // The indices count into the lanes of both inputs one after the other.
+                let (input_len, _) = args[0].layout.ty.simd_size_and_type(*self.tcx);
+                for i in 0..len {
+                    let index = self.read_scalar(&self.operand_index(&args[2], i)?)?.to_u32()?;
+                    let index = u64::from(index);
+                    let val = if index < input_len {
+                        self.operand_index(&args[0], index)?
+                    } else if index < 2 * input_len {
+                        self.operand_index(&args[1], index - input_len)?
+                    } else {
+                        throw_ub_format!(
+                            "`{}` index {} is out of bounds of two vectors of length {}",
+                            name,
+                            index,
+                            input_len
+                        );
+                    };
+                    let place = self.place_index(dest, i)?;
+                    self.copy_op(&val, &place)?;
+                }
+            }
+        }
+        Ok(true)
+    }
+}
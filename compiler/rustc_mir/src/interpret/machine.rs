@@ -310,6 +310,13 @@ fn init_allocation_extra<'b>(
         kind: Option<MemoryKind<Self::MemoryKind>>,
     ) -> (Cow<'b, Allocation<Self::PointerTag, Self::AllocExtra>>, Self::PointerTag);
 
+    /// The maximum number of bytes that may be allocated by the interpreter at the same time,
+    /// not counting global allocations. Exceeding it raises a resource exhaustion error.
+    #[inline(always)]
+    fn memory_limit(_tcx: ty::TyCtxt<'tcx>, _memory_extra: &Self::MemoryExtra) -> Option<Size> {
+        None
+    }
+
     /// Hook for performing extra checks on a memory read access.
     ///
     /// Takes read-only access to the allocation so we can keep all the memory read
@@ -444,11 +451,6 @@ fn force_int_for_alignment_check(_memory_extra: &Self::MemoryExtra) -> bool {
         false
     }
 
-    #[inline(always)]
-    fn enforce_validity(_ecx: &InterpCx<$mir, $tcx, Self>) -> bool {
-        false // for now, we don't enforce validity
-    }
-
     #[inline(always)]
     fn call_extra_fn(
         _ecx: &mut InterpCx<$mir, $tcx, Self>,
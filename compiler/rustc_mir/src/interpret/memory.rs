@@ -111,6 +111,10 @@ pub struct Memory<'mir, 'tcx, M: Machine<'mir, 'tcx>> {
     // FIXME: this should not be public, but interning currently needs access to it
     pub(super) dead_alloc_map: FxHashMap<AllocId, (Size, Align)>,
 
+    /// The total size of the allocations made by `allocate_with` that have not been
+    /// deallocated yet, see `Machine::memory_limit`.
+    live_bytes: Size,
+
     /// Extra data added by the machine.
     pub extra: M::MemoryExtra,
 
@@ -149,6 +153,7 @@ pub fn new(tcx: TyCtxt<'tcx>, extra: M::MemoryExtra) -> Self {
             alloc_map: M::MemoryMap::default(),
             extra_fn_ptr_map: FxHashMap::default(),
             dead_alloc_map: FxHashMap::default(),
+            live_bytes: Size::ZERO,
             extra,
             tcx,
         }
@@ -211,9 +216,36 @@ pub fn allocate(
         size: Size,
         align: Align,
         kind: MemoryKind<M::MemoryKind>,
-    ) -> Pointer<M::PointerTag> {
+    ) -> InterpResult<'tcx, Pointer<M::PointerTag>> {
+        self.check_memory_limit(size, kind)?;
         let alloc = Allocation::uninit(size, align);
-        self.allocate_with(alloc, kind)
+        Ok(self.allocate_with(alloc, kind))
+    }
+
+    /// Makes sure that allocating another `size` bytes does not exceed `Machine::memory_limit`.
+    /// This happens before the allocation is created, so that the limit also protects the memory
+    /// of the host.
+    fn check_memory_limit(
+        &self,
+        size: Size,
+        kind: MemoryKind<M::MemoryKind>,
+    ) -> InterpResult<'tcx> {
+        // Vtables and caller locations are small, and not allocated on behalf of the program.
+        if let MemoryKind::Vtable | MemoryKind::CallerLocation = kind {
+            return Ok(());
+        }
+        if let Some(limit) = M::memory_limit(self.tcx, &self.extra) {
+            if self.live_bytes.bytes().saturating_add(size.bytes()) > limit.bytes() {
+                let sizes = self.alloc_map.filter_map_collect(|_, (_, alloc)| Some(alloc.size()));
+                throw_exhaust!(MemoryLimitReached {
+                    limit,
+                    requested: size,
+                    allocations: sizes.len(),
+                    largest: sizes.into_iter().max().unwrap_or(Size::ZERO),
+                })
+            }
+        }
+        Ok(())
     }
 
     pub fn allocate_bytes(
@@ -239,6 +271,7 @@ pub fn allocate_with(
             "dynamically allocating global memory"
         );
         // This is a new allocation, not a new global one, so no `global_base_ptr`.
+        self.live_bytes += alloc.size();
         let (alloc, tag) = M::init_allocation_extra(&self.extra, id, Cow::Owned(alloc), Some(kind));
         self.alloc_map.insert(id, (kind, alloc.into_owned()));
         Pointer::from(id).with_tag(tag)
@@ -261,7 +294,7 @@ pub fn reallocate(
 
         // For simplicities' sake, we implement reallocate as "alloc, copy, dealloc".
         // This happens so rarely, the perf advantage is outweighed by the maintenance cost.
-        let new_ptr = self.allocate(new_size, new_align, kind);
+        let new_ptr = self.allocate(new_size, new_align, kind)?;
         let old_size = match old_size_and_align {
             Some((size, _align)) => size,
             None => self.get_raw(ptr.alloc_id)?.size(),
@@ -350,6 +383,7 @@ pub fn deallocate(
         // Let the machine take some extra action
         let size = alloc.size();
         M::memory_deallocated(&mut self.extra, &mut alloc.extra, ptr, size)?;
+        self.live_bytes = self.live_bytes - size;
 
         // Don't forget to remember size and align of this now-dead allocation
         let old = self.dead_alloc_map.insert(ptr.alloc_id, (size, alloc.align));
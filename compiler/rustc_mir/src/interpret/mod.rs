@@ -2,6 +2,7 @@
 
 mod cast;
 mod eval_context;
+mod hooks;
 mod intern;
 mod intrinsics;
 mod machine;
@@ -21,6 +22,7 @@
 pub use self::eval_context::{
     Frame, FrameInfo, InterpCx, LocalState, LocalValue, StackPopCleanup, StackPopUnwind,
 };
+pub use self::hooks::{HookedInterpCx, HookedMachine, InterpHooks};
 pub use self::intern::{intern_const_alloc_recursive, InternKind};
 pub use self::machine::{compile_time_machine, AllocMap, Machine, MayLeak, StackPopJump};
 pub use self::memory::{AllocCheck, AllocRef, AllocRefMut, FnVal, Memory, MemoryKind};
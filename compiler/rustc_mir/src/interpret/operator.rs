@@ -1,9 +1,7 @@
-use std::convert::TryFrom;
-
 use rustc_apfloat::Float;
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::{InterpResult, Scalar};
-use rustc_middle::ty::{self, layout::TyAndLayout, FloatTy, Ty};
+use rustc_middle::ty::{self, layout::TyAndLayout, FloatTy, ScalarInt, Ty};
 use rustc_target::abi::LayoutOf;
 
 use super::{ImmTy, Immediate, InterpCx, Machine, PlaceTy};
@@ -128,27 +126,13 @@ fn binary_int_op(
         // Shift ops can have an RHS with a different numeric type.
         if bin_op == Shl || bin_op == Shr {
             let signed = left_layout.abi.is_signed();
-            let size = u128::from(left_layout.size.bits());
-            let overflow = r >= size;
-            let r = r % size; // mask to type size
-            let r = u32::try_from(r).unwrap(); // we masked so this will always fit
-            let result = if signed {
-                let l = self.sign_extend(l, left_layout) as i128;
-                let result = match bin_op {
-                    Shl => l.checked_shl(r).unwrap(),
-                    Shr => l.checked_shr(r).unwrap(),
-                    _ => bug!("it has already been checked that this is a shift op"),
-                };
-                result as u128
-            } else {
-                match bin_op {
-                    Shl => l.checked_shl(r).unwrap(),
-                    Shr => l.checked_shr(r).unwrap(),
-                    _ => bug!("it has already been checked that this is a shift op"),
-                }
+            let l = ScalarInt::try_from_uint(l, left_layout.size).unwrap();
+            let (result, overflow) = match bin_op {
+                Shl => l.overflowing_shl(r),
+                Shr => l.overflowing_shr(r, signed),
+                _ => bug!("it has already been checked that this is a shift op"),
             };
-            let truncated = self.truncate(result, left_layout);
-            return Ok((Scalar::from_uint(truncated, left_layout.size), overflow, left_layout.ty));
+            return Ok((Scalar::Int(result), overflow, left_layout.ty));
         }
 
         // For the remaining ops, the types must be the same on both sides
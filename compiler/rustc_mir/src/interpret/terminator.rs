@@ -68,34 +68,7 @@ pub(super) fn eval_terminator(
             Call { ref func, ref args, destination, ref cleanup, from_hir_call: _, fn_span: _ } => {
                 let old_stack = self.frame_idx();
                 let old_loc = self.frame().loc;
-                let func = self.eval_operand(func, None)?;
-                let (fn_val, abi, caller_can_unwind) = match *func.layout.ty.kind() {
-                    ty::FnPtr(sig) => {
-                        let caller_abi = sig.abi();
-                        let fn_ptr = self.read_scalar(&func)?.check_init()?;
-                        let fn_val = self.memory.get_fn(fn_ptr)?;
-                        (
-                            fn_val,
-                            caller_abi,
-                            self.fn_can_unwind(layout::fn_ptr_codegen_fn_attr_flags(), caller_abi),
-                        )
-                    }
-                    ty::FnDef(def_id, substs) => {
-                        let sig = func.layout.ty.fn_sig(*self.tcx);
-                        (
-                            FnVal::Instance(
-                                self.resolve(ty::WithOptConstParam::unknown(def_id), substs)?,
-                            ),
-                            sig.abi(),
-                            self.fn_can_unwind(self.tcx.codegen_fn_attrs(def_id).flags, sig.abi()),
-                        )
-                    }
-                    _ => span_bug!(
-                        terminator.source_info.span,
-                        "invalid callee of type {:?}",
-                        func.layout.ty
-                    ),
-                };
+                let (fn_val, abi, caller_can_unwind) = self.eval_callee(terminator, func)?;
                 let args = self.eval_operands(args)?;
                 let dest_place;
                 let ret = match destination {
@@ -123,6 +96,31 @@ pub(super) fn eval_terminator(
                 }
             }
 
+            TailCall { ref func, ref args, fn_span: _ } => {
+                let old_stack = self.frame_idx();
+                let (fn_val, abi, caller_can_unwind) = self.eval_callee(terminator, func)?;
+                let args = self.eval_operands(args)?;
+
+                // The callee writes its result to our return place, which is where an emulated
+                // function leaves it. A callee that pushes a frame is redirected to our return
+                // place once that frame exists, since the arguments may still live in our locals.
+                let return_place = self.eval_place(mir::Place::return_place())?;
+                let block = self.frame().loc.expect("tail call outside of a block").block;
+                // Unwinding out of the callee continues in our caller.
+                let unwind = match caller_can_unwind {
+                    true => StackPopUnwind::Skip,
+                    false => StackPopUnwind::NotAllowed,
+                };
+                self.eval_fn_call(fn_val, abi, &args[..], Some((&return_place, block)), unwind)?;
+
+                if self.frame_idx() == old_stack {
+                    // The call was emulated and its result is already in our return place.
+                    self.pop_stack_frame(/* unwinding */ false)?
+                } else {
+                    self.pop_tail_caller_frame()?
+                }
+            }
+
             Drop { place, target, unwind } => {
                 let place = self.eval_place(place)?;
                 let ty = place.layout.ty;
@@ -178,6 +176,41 @@ pub(super) fn eval_terminator(
         Ok(())
     }
 
+    /// Evaluates the callee of a `Call` or `TailCall` terminator, returning the function, its
+    /// ABI and whether the call is allowed to unwind.
+    fn eval_callee(
+        &mut self,
+        terminator: &mir::Terminator<'tcx>,
+        func: &mir::Operand<'tcx>,
+    ) -> InterpResult<'tcx, (FnVal<'tcx, M::ExtraFnVal>, Abi, bool)> {
+        let func = self.eval_operand(func, None)?;
+        Ok(match *func.layout.ty.kind() {
+            ty::FnPtr(sig) => {
+                let caller_abi = sig.abi();
+                let fn_ptr = self.read_scalar(&func)?.check_init()?;
+                let fn_val = self.memory.get_fn(fn_ptr)?;
+                (
+                    fn_val,
+                    caller_abi,
+                    self.fn_can_unwind(layout::fn_ptr_codegen_fn_attr_flags(), caller_abi),
+                )
+            }
+            ty::FnDef(def_id, substs) => {
+                let sig = func.layout.ty.fn_sig(*self.tcx);
+                (
+                    FnVal::Instance(self.resolve(ty::WithOptConstParam::unknown(def_id), substs)?),
+                    sig.abi(),
+                    self.fn_can_unwind(self.tcx.codegen_fn_attrs(def_id).flags, sig.abi()),
+                )
+            }
+            _ => span_bug!(
+                terminator.source_info.span,
+                "invalid callee of type {:?}",
+                func.layout.ty
+            ),
+        })
+    }
+
     fn check_argument_compat(
         rust_abi: bool,
         caller: TyAndLayout<'tcx>,
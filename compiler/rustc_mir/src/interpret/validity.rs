@@ -181,6 +181,25 @@ fn write_path(out: &mut String, path: &[PathElem]) {
     }
 }
 
+/// Writes `path` as projections in Rust syntax, leaving dereferences to auto-deref. Returns
+/// whether the path ends with a dereference, or `None` if the path cannot be written in Rust.
+fn write_rust_path(out: &mut String, path: &[PathElem]) -> Option<bool> {
+    use self::PathElem::*;
+
+    for elem in path.iter() {
+        match elem {
+            Field(name) => write!(out, ".{}", name),
+            TupleElem(idx) => write!(out, ".{}", idx),
+            ArrayElem(idx) => write!(out, "[{}]", idx),
+            Deref => Ok(()),
+            EnumTag | Variant(_) | GeneratorTag | GeneratorState(_) | CapturedVar(_)
+            | DynDowncast => return None,
+        }
+        .unwrap()
+    }
+    Some(matches!(path.last(), Some(Deref)))
+}
+
 // Test if a range that wraps at overflow contains `test`
 fn wrapping_range_contains(r: &RangeInclusive<u128>, test: u128) -> bool {
     let (lo, hi) = r.clone().into_inner();
@@ -225,6 +244,24 @@ struct ValidityVisitor<'rt, 'mir, 'tcx, M: Machine<'mir, 'tcx>> {
 }
 
 impl<'rt, 'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> ValidityVisitor<'rt, 'mir, 'tcx, M> {
+    /// Reports a mutable reference, or an `UnsafeCell` if `unsafe_cell` is set, at the current
+    /// path in the final value of a `const`.
+    fn throw_mutable_in_const(&self, unsafe_cell: bool) -> InterpResult<'tcx> {
+        let path = if self.path.is_empty() {
+            None
+        } else {
+            let mut path = String::new();
+            write_path(&mut path, &self.path);
+            Some(path)
+        };
+        let mut rust_path = String::new();
+        let (rust_path, deref) = match write_rust_path(&mut rust_path, &self.path) {
+            Some(deref) => (Some(rust_path), deref),
+            None => (None, false),
+        };
+        throw_ub!(MutableInConst { path, rust_path, deref, unsafe_cell })
+    }
+
     fn aggregate_field_path_elem(&mut self, layout: TyAndLayout<'tcx>, field: usize) -> PathElem {
         // First, check if we are projecting to a variant.
         match layout.variants {
@@ -581,7 +618,7 @@ fn try_visit_primitive(
                     // a ZST).
                     let layout = self.ecx.layout_of(ty)?;
                     if !layout.is_zst() {
-                        throw_validation_failure!(self.path, { "mutable reference in a `const`" });
+                        self.throw_mutable_in_const(false)?;
                     }
                 }
                 self.check_safe_pointer(value, "reference")?;
@@ -785,7 +822,7 @@ fn visit_value(&mut self, op: &OpTy<'tcx, M::PointerTag>) -> InterpResult<'tcx>
             if matches!(self.ctfe_mode, Some(CtfeValidationMode::Const { inner: true, .. }))
                 && Some(def.did) == self.ecx.tcx.lang_items().unsafe_cell_type()
             {
-                throw_validation_failure!(self.path, { "`UnsafeCell` in a `const`" });
+                self.throw_mutable_in_const(true)?;
             }
         }
 
@@ -947,7 +984,14 @@ fn validate_operand_internal(
         match visitor.visit_value(&op) {
             Ok(()) => Ok(()),
             // Pass through validation failures.
-            Err(err) if matches!(err.kind(), err_ub!(ValidationFailure { .. })) => Err(err),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    err_ub!(ValidationFailure { .. }) | err_ub!(MutableInConst { .. })
+                ) =>
+            {
+                Err(err)
+            }
             // Also pass through InvalidProgram, those just indicate that we could not
             // validate and each caller will know best what to do with them.
             Err(err) if matches!(err.kind(), InterpError::InvalidProgram(_)) => Err(err),
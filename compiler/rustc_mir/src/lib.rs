@@ -59,6 +59,8 @@ pub fn provide(providers: &mut Providers) {
     providers.const_caller_location = const_eval::const_caller_location;
     providers.mir_callgraph_reachable = transform::inline::cycle::mir_callgraph_reachable;
     providers.mir_inliner_callees = transform::inline::cycle::mir_inliner_callees;
+    providers.instr_profile_counts = transform::inline::profile::instr_profile_counts;
+    providers.scalar_constants = util::scalar_constants::scalar_constants;
     providers.destructure_const = |tcx, param_env_and_value| {
         let (param_env, value) = param_env_and_value.into_parts();
         const_eval::destructure_const(tcx, param_env, value)
@@ -197,9 +197,10 @@
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts};
 use rustc_middle::ty::{self, GenericParamDefKind, Instance, Ty, TyCtxt, TypeFoldable, VtblEntry};
+use rustc_middle::ty::{ConstInt, ScalarInt};
 use rustc_middle::{middle::codegen_fn_attrs::CodegenFnAttrFlags, mir::visit::TyContext};
 use rustc_session::config::EntryFnType;
-use rustc_session::lint::builtin::LARGE_ASSIGNMENTS;
+use rustc_session::lint::builtin::{ARITHMETIC_OVERFLOW, LARGE_ASSIGNMENTS};
 use rustc_span::source_map::{dummy_spanned, respan, Span, Spanned, DUMMY_SP};
 use rustc_target::abi::Size;
 use smallvec::SmallVec;
@@ -612,6 +613,65 @@ pub fn monomorphize<T>(&self, value: T) -> T
             value,
         )
     }
+
+    /// Const propagation cannot check shifts by constants that depend on generic parameters,
+    /// such as `x << N` for a const parameter `N`. Now that the instance is monomorphic, check
+    /// whether the shift amount exceeds the bit width of the shifted value.
+    fn check_polymorphic_shift_amount(
+        &self,
+        op: mir::BinOp,
+        lhs: &mir::Operand<'tcx>,
+        rhs: &mir::Operand<'tcx>,
+        location: Location,
+    ) {
+        let constant = match rhs {
+            mir::Operand::Constant(constant) if constant.literal.needs_subst() => constant,
+            _ => return,
+        };
+        let param_env = ty::ParamEnv::reveal_all();
+        let literal = self.monomorphize(constant.literal);
+        let rhs_ty = literal.ty();
+        let lhs_ty = self.monomorphize(lhs.ty(self.body, self.tcx));
+        let (lhs_size, rhs_size) = match (
+            self.tcx.layout_of(param_env.and(lhs_ty)),
+            self.tcx.layout_of(param_env.and(rhs_ty)),
+        ) {
+            (Ok(lhs_layout), Ok(rhs_layout)) => (lhs_layout.size, rhs_layout.size),
+            _ => return,
+        };
+        let amount = match literal.try_eval_bits(self.tcx, param_env, rhs_ty) {
+            Some(amount) if amount >= u128::from(lhs_size.bits()) => amount,
+            _ => return,
+        };
+        let source_info = self.body.source_info(location);
+        let lint_root = match source_info.scope.lint_root(&self.body.source_scopes) {
+            Some(lint_root) => lint_root,
+            // The shift is in a function from a foreign crate, which has been checked there
+            // as far as possible. We can't get a `HirId` for things in other crates.
+            None => return,
+        };
+        let amount = ConstInt::new(
+            ScalarInt::try_from_uint(amount, rhs_size).unwrap(),
+            rhs_ty.is_signed(),
+            rhs_ty.is_ptr_sized_integral(),
+        );
+        self.tcx.struct_span_lint_hir(ARITHMETIC_OVERFLOW, lint_root, source_info.span, |lint| {
+            let direction = if op == mir::BinOp::Shl { "left" } else { "right" };
+            let mut err = lint.build("this arithmetic operation will overflow");
+            err.span_label(
+                source_info.span,
+                format!("attempt to shift {} by `{:#?}`, which would overflow", direction, amount),
+            );
+            err.note(&format!(
+                "the shift amount depends on generic parameters and is `{:#?}` here, \
+                 but `{}` only has {} bits",
+                amount,
+                lhs_ty,
+                lhs_size.bits(),
+            ));
+            err.emit()
+        });
+    }
 }
 
 impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
@@ -687,6 +747,13 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                     self.output.push(create_fn_mono_item(self.tcx, instance, span));
                 }
             }
+            mir::Rvalue::BinaryOp(op @ (mir::BinOp::Shl | mir::BinOp::Shr), box (ref l, ref r))
+            | mir::Rvalue::CheckedBinaryOp(
+                op @ (mir::BinOp::Shl | mir::BinOp::Shr),
+                box (ref l, ref r),
+            ) => {
+                self.check_polymorphic_shift_amount(op, l, r, location);
+            }
             mir::Rvalue::ThreadLocalRef(def_id) => {
                 assert!(self.tcx.is_thread_local_static(def_id));
                 let instance = Instance::mono(self.tcx, def_id);
@@ -767,7 +834,8 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
 
         let tcx = self.tcx;
         match terminator.kind {
-            mir::TerminatorKind::Call { ref func, .. } => {
+            mir::TerminatorKind::Call { ref func, .. }
+            | mir::TerminatorKind::TailCall { ref func, .. } => {
                 let callee_ty = func.ty(self.body, tcx);
                 let callee_ty = self.monomorphize(callee_ty);
                 visit_fn_use(self.tcx, callee_ty, true, source, &mut self.output);
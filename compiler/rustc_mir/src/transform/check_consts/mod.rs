@@ -5,10 +5,11 @@
 //! it finds operations that are invalid in a certain context.
 
 use rustc_attr as attr;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_middle::mir;
-use rustc_middle::ty::{self, TyCtxt};
+use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::Symbol;
 
 pub use self::qualifs::Qualif;
@@ -72,6 +73,40 @@ pub fn fn_sig(&self) -> Option<&'tcx hir::FnSig<'tcx>> {
     }
 }
 
+/// Returns `true` if dropping a value of type `ty` only runs destructors of `impl const Drop`
+/// blocks, so the value can be dropped in a const context if `const_trait_impl` is enabled.
+pub fn has_const_drop_glue(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    ty: Ty<'tcx>,
+) -> bool {
+    let mut seen = FxHashSet::default();
+    let mut todo = vec![ty];
+    while let Some(ty) = todo.pop() {
+        if !seen.insert(ty) || !ty.needs_drop(tcx, param_env) {
+            continue;
+        }
+        match *ty.kind() {
+            ty::Array(elem, _) | ty::Slice(elem) => todo.push(elem),
+            ty::Tuple(_) => todo.extend(ty.tuple_fields()),
+            ty::Closure(_, substs) => todo.extend(substs.as_closure().upvar_tys()),
+            // Freeing the memory of a `Box` is not possible in a const context.
+            ty::Adt(def, _) if def.is_box() => return false,
+            ty::Adt(def, substs) => {
+                if let Some(dtor) = def.destructor(tcx) {
+                    if !tcx.is_const_fn_raw(dtor.did) {
+                        return false;
+                    }
+                }
+                todo.extend(def.all_fields().map(|field| field.ty(tcx, substs)));
+            }
+            // Trait objects, generators and type parameters may have any destructor.
+            _ => return false,
+        }
+    }
+    true
+}
+
 /// Returns `true` if this `DefId` points to one of the official `panic` lang items.
 pub fn is_lang_panic_fn(tcx: TyCtxt<'tcx>, def_id: DefId) -> bool {
     Some(def_id) == tcx.lang_items().panic_fn()
@@ -6,7 +6,7 @@
 use super::ops::{self, NonConstOp};
 use super::qualifs::{NeedsDrop, Qualif};
 use super::validation::Qualifs;
-use super::ConstCx;
+use super::{has_const_drop_glue, ConstCx};
 
 /// Returns `true` if we should use the more precise live drop checker that runs after drop
 /// elaboration.
@@ -89,6 +89,13 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
                     return;
                 }
 
+                // Destructors of `impl const Drop` blocks can run in const contexts.
+                if self.tcx.features().const_trait_impl
+                    && has_const_drop_glue(self.tcx, self.param_env, dropped_ty)
+                {
+                    return;
+                }
+
                 // Drop elaboration is not precise enough to accept code like
                 // `src/test/ui/consts/control-flow/drop-pass.rs`; e.g., when an `Option<Vec<T>>` is
                 // initialized with `None` and never changed, it still emits drop glue.
@@ -116,6 +123,7 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
             | mir::TerminatorKind::Resume
             | mir::TerminatorKind::Return
             | mir::TerminatorKind::SwitchInt { .. }
+            | mir::TerminatorKind::TailCall { .. }
             | mir::TerminatorKind::Unreachable
             | mir::TerminatorKind::Yield { .. } => {}
         }
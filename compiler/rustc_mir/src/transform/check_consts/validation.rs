@@ -22,8 +22,8 @@
 use super::ops::{self, NonConstOp, Status};
 use super::qualifs::{self, CustomEq, HasMutInterior, NeedsDrop};
 use super::resolver::FlowSensitiveAnalysis;
-use super::{is_lang_panic_fn, ConstCx, Qualif};
-use crate::const_eval::is_unstable_const_fn;
+use super::{has_const_drop_glue, is_lang_panic_fn, ConstCx, Qualif};
+use crate::const_eval::{foreign_shim_name, is_unstable_const_fn};
 use crate::dataflow::impls::MaybeMutBorrowedLocals;
 use crate::dataflow::{self, Analysis};
 
@@ -792,7 +792,8 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
         self.super_terminator(terminator, location);
 
         match &terminator.kind {
-            TerminatorKind::Call { func, args, .. } => {
+            TerminatorKind::Call { func, args, .. }
+            | TerminatorKind::TailCall { func, args, .. } => {
                 let ConstCx { tcx, body, param_env, .. } = *self.ccx;
                 let caller = self.def_id().to_def_id();
 
@@ -890,6 +891,12 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     return;
                 }
 
+                // The foreign functions that const evaluation has shims for can be called, see
+                // `-Z const-eval-foreign-shims`.
+                if foreign_shim_name(tcx, callee).is_some() {
+                    return;
+                }
+
                 if !tcx.is_const_fn_raw(callee) {
                     self.check_op(ops::FnCallNonConst);
                     return;
@@ -960,10 +967,15 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
 
                 // Check to see if the type of this place can ever have a drop impl. If not, this
                 // `Drop` terminator is frivolous.
-                let ty_needs_drop =
-                    dropped_place.ty(self.body, self.tcx).ty.needs_drop(self.tcx, self.param_env);
+                let dropped_ty = dropped_place.ty(self.body, self.tcx).ty;
+                if !dropped_ty.needs_drop(self.tcx, self.param_env) {
+                    return;
+                }
 
-                if !ty_needs_drop {
+                // Destructors of `impl const Drop` blocks can run in const contexts.
+                if self.tcx.features().const_trait_impl
+                    && has_const_drop_glue(self.tcx, self.param_env, dropped_ty)
+                {
                     return;
                 }
 
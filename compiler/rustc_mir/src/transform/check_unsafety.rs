@@ -70,7 +70,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 // safe (at least as emitted during MIR construction)
             }
 
-            TerminatorKind::Call { ref func, .. } => {
+            TerminatorKind::Call { ref func, .. } | TerminatorKind::TailCall { ref func, .. } => {
                 let func_ty = func.ty(self.body, self.tcx);
                 let sig = func_ty.fn_sig(self.tcx);
                 if let hir::Unsafety::Unsafe = sig.unsafety() {
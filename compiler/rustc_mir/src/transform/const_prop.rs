@@ -4,7 +4,7 @@
 use std::cell::Cell;
 
 use rustc_ast::Mutability;
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_hir::def::DefKind;
 use rustc_hir::HirId;
 use rustc_index::bit_set::BitSet;
@@ -13,9 +13,9 @@
     MutVisitor, MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor,
 };
 use rustc_middle::mir::{
-    AssertKind, BasicBlock, BinOp, Body, Constant, ConstantKind, Local, LocalDecl, LocalKind,
-    Location, Operand, Place, Rvalue, SourceInfo, SourceScope, SourceScopeData, Statement,
-    StatementKind, Terminator, TerminatorKind, UnOp, RETURN_PLACE,
+    AggregateKind, AssertKind, BasicBlock, BinOp, Body, Constant, ConstantKind, Local, LocalDecl,
+    LocalKind, Location, Operand, Place, ProjectionElem, Rvalue, SourceInfo, SourceScope,
+    SourceScopeData, Statement, StatementKind, Terminator, TerminatorKind, UnOp, RETURN_PLACE,
 };
 use rustc_middle::ty::layout::{HasTyCtxt, LayoutError, TyAndLayout};
 use rustc_middle::ty::subst::{InternalSubsts, Subst};
@@ -186,6 +186,11 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for ConstPropMachine<'mir, 'tcx>
 
     type MemoryExtra = ();
 
+    #[inline(always)]
+    fn enforce_validity(_ecx: &InterpCx<'mir, 'tcx, Self>) -> bool {
+        false
+    }
+
     fn load_mir(
         _ecx: &InterpCx<'mir, 'tcx, Self>,
         _instance: ty::InstanceDef<'tcx>,
@@ -331,6 +336,74 @@ struct ConstPropagator<'mir, 'tcx> {
     // Because we have `MutVisitor` we can't obtain the `SourceInfo` from a `Location`. So we store
     // the last known `SourceInfo` here and just keep revisiting it.
     source_info: Option<SourceInfo>,
+    // Maps the locals that compute the elements of an array to the location of the aggregate
+    // building that array and the index of the element.
+    array_elements: FxHashMap<Local, (Location, usize)>,
+    // Overflows while computing array elements, buffered until the array is built so that the
+    // same overflow at many indices is reported only once.
+    overflowing_elements: FxIndexMap<Location, Vec<OverflowingElement>>,
+}
+
+/// An arithmetic overflow in the computation of an array element.
+struct OverflowingElement {
+    index: usize,
+    source_info: SourceInfo,
+    label: String,
+}
+
+/// Collects the locals whose values end up as elements of array aggregates, see
+/// `ConstPropagator::array_elements`.
+fn collect_array_elements(body: &Body<'_>) -> FxHashMap<Local, (Location, usize)> {
+    let mut elements = FxHashMap::default();
+    for (block, data) in body.basic_blocks().iter_enumerated() {
+        for (statement_index, statement) in data.statements.iter().enumerate() {
+            if let StatementKind::Assign(box (
+                _,
+                Rvalue::Aggregate(box AggregateKind::Array(_), ref operands),
+            )) = statement.kind
+            {
+                let location = Location { block, statement_index };
+                for (index, operand) in operands.iter().enumerate() {
+                    if let Some(local) = operand.place().and_then(|place| place.as_local()) {
+                        elements.insert(local, (location, index));
+                    }
+                }
+            }
+        }
+    }
+    // With overflow checks, the element is moved out of the result of a checked operation
+    // (`_elem = move (_checked.0: T)`), so that result is an element, too.
+    for data in body.basic_blocks() {
+        for statement in &data.statements {
+            if let StatementKind::Assign(box (dest, Rvalue::Use(ref operand))) = statement.kind {
+                let element = dest.as_local().and_then(|local| elements.get(&local)).copied();
+                if let (Some(element), Some(src)) = (element, operand.place()) {
+                    if let [ProjectionElem::Field(..)] = src.projection[..] {
+                        elements.insert(src.local, element);
+                    }
+                }
+            }
+        }
+    }
+    elements
+}
+
+/// Renders the sorted `indices` as a list of ranges, e.g. `0..3, 5`.
+fn render_index_ranges(indices: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &index in indices {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == index => *end += 1,
+            _ => ranges.push((index, index + 1)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| {
+            if end - start == 1 { start.to_string() } else { format!("{}..{}", start, end) }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 impl<'mir, 'tcx> LayoutOf for ConstPropagator<'mir, 'tcx> {
@@ -393,7 +466,11 @@ fn new(
             .filter(|ret_layout| {
                 !ret_layout.is_zst() && ret_layout.size < Size::from_bytes(MAX_ALLOC_LIMIT)
             })
-            .map(|ret_layout| ecx.allocate(ret_layout, MemoryKind::Stack).into());
+            .map(|ret_layout| {
+                ecx.allocate(ret_layout, MemoryKind::Stack)
+                    .expect("const propagation has no memory limit")
+                    .into()
+            });
 
         ecx.push_stack_frame(
             Instance::new(def_id, substs),
@@ -413,6 +490,8 @@ fn new(
             //FIXME(wesleywiser) we can't steal this because `Visitor::super_visit_body()` needs it
             local_decls: body.local_decls.clone(),
             source_info: None,
+            array_elements: collect_array_elements(body),
+            overflowing_elements: FxIndexMap::default(),
         }
     }
 
@@ -538,11 +617,74 @@ fn report_assert_as_lint(
         }
     }
 
+    /// Reports an arithmetic overflow in the computation of `place`. Overflows in the
+    /// elements of an array are buffered and reported by `report_overflowing_elements`.
+    fn report_overflow(
+        &mut self,
+        source_info: SourceInfo,
+        place: Place<'tcx>,
+        panic: AssertKind<ConstInt>,
+    ) {
+        match place.as_local().and_then(|local| self.array_elements.get(&local)) {
+            Some(&(array, index)) => {
+                let label = format!("{:?}", panic);
+                self.overflowing_elements.entry(array).or_default().push(OverflowingElement {
+                    index,
+                    source_info,
+                    label,
+                });
+            }
+            None => self.report_assert_as_lint(
+                lint::builtin::ARITHMETIC_OVERFLOW,
+                source_info,
+                "this arithmetic operation will overflow",
+                panic,
+            ),
+        }
+    }
+
+    /// Reports the overflows buffered for the elements of one array. Elements that overflow
+    /// the same way are reported together, listing the indices at which the overflow occurred.
+    fn report_overflowing_elements(&self, elements: Vec<OverflowingElement>) {
+        let mut groups: FxIndexMap<_, Vec<OverflowingElement>> = FxIndexMap::default();
+        for element in elements {
+            let key = (element.label.clone(), self.lint_root(element.source_info));
+            groups.entry(key).or_default().push(element);
+        }
+        for ((label, lint_root), mut elements) in groups {
+            let lint_root = match lint_root {
+                Some(lint_root) => lint_root,
+                None => continue,
+            };
+            elements.sort_by_key(|element| element.index);
+            let span = elements[0].source_info.span;
+            self.tcx.struct_span_lint_hir(
+                lint::builtin::ARITHMETIC_OVERFLOW,
+                lint_root,
+                span,
+                |lint| {
+                    let mut err = lint.build("this arithmetic operation will overflow");
+                    err.span_label(span, label);
+                    if elements.len() > 1 {
+                        let indices: Vec<_> =
+                            elements.iter().map(|element| element.index).collect();
+                        err.note(&format!(
+                            "the same overflow occurs for the array elements at indices {}",
+                            render_index_ranges(&indices),
+                        ));
+                    }
+                    err.emit()
+                },
+            );
+        }
+    }
+
     fn check_unary_op(
         &mut self,
         op: UnOp,
         arg: &Operand<'tcx>,
         source_info: SourceInfo,
+        place: Place<'tcx>,
     ) -> Option<()> {
         if let (val, true) = self.use_ecx(|this| {
             let val = this.ecx.read_immediate(&this.ecx.eval_operand(arg, None)?)?;
@@ -552,12 +694,7 @@ fn check_unary_op(
             // `AssertKind` only has an `OverflowNeg` variant, so make sure that is
             // appropriate to use.
             assert_eq!(op, UnOp::Neg, "Neg is the only UnOp that can overflow");
-            self.report_assert_as_lint(
-                lint::builtin::ARITHMETIC_OVERFLOW,
-                source_info,
-                "this arithmetic operation will overflow",
-                AssertKind::OverflowNeg(val.to_const_int()),
-            );
+            self.report_overflow(source_info, place, AssertKind::OverflowNeg(val.to_const_int()));
             return None;
         }
 
@@ -570,6 +707,7 @@ fn check_binary_op(
         left: &Operand<'tcx>,
         right: &Operand<'tcx>,
         source_info: SourceInfo,
+        place: Place<'tcx>,
     ) -> Option<()> {
         let r = self.use_ecx(|this| this.ecx.read_immediate(&this.ecx.eval_operand(right, None)?));
         let l = self.use_ecx(|this| this.ecx.read_immediate(&this.ecx.eval_operand(left, None)?));
@@ -586,10 +724,9 @@ fn check_binary_op(
             let r_bits = r_bits.and_then(|r| r.to_bits_or_ptr(right_size, &self.tcx).ok());
             if r_bits.map_or(false, |b| b >= left_size.bits() as u128) {
                 debug!("check_binary_op: reporting assert for {:?}", source_info);
-                self.report_assert_as_lint(
-                    lint::builtin::ARITHMETIC_OVERFLOW,
+                self.report_overflow(
                     source_info,
-                    "this arithmetic operation will overflow",
+                    place,
                     AssertKind::Overflow(
                         op,
                         match l {
@@ -614,10 +751,9 @@ fn check_binary_op(
                 let (_res, overflow, _ty) = this.ecx.overflowing_binary_op(op, l, r)?;
                 Ok(overflow)
             })? {
-                self.report_assert_as_lint(
-                    lint::builtin::ARITHMETIC_OVERFLOW,
+                self.report_overflow(
                     source_info,
-                    "this arithmetic operation will overflow",
+                    place,
                     AssertKind::Overflow(op, l.to_const_int(), r.to_const_int()),
                 );
                 return None;
@@ -674,11 +810,11 @@ fn const_prop(
             // lint.
             Rvalue::UnaryOp(op, arg) => {
                 trace!("checking UnaryOp(op = {:?}, arg = {:?})", op, arg);
-                self.check_unary_op(*op, arg, source_info)?;
+                self.check_unary_op(*op, arg, source_info, place)?;
             }
             Rvalue::BinaryOp(op, box (left, right)) => {
                 trace!("checking BinaryOp(op = {:?}, left = {:?}, right = {:?})", op, left, right);
-                self.check_binary_op(*op, left, right, source_info)?;
+                self.check_binary_op(*op, left, right, source_info, place)?;
             }
             Rvalue::CheckedBinaryOp(op, box (left, right)) => {
                 trace!(
@@ -687,7 +823,7 @@ fn const_prop(
                     left,
                     right
                 );
-                self.check_binary_op(*op, left, right, source_info)?;
+                self.check_binary_op(*op, left, right, source_info, place)?;
             }
 
             // Do not try creating references (#67862)
@@ -1074,6 +1210,10 @@ fn visit_body(&mut self, body: &mut Body<'tcx>) {
         for (bb, data) in body.basic_blocks_mut().iter_enumerated_mut() {
             self.visit_basic_block_data(bb, data);
         }
+        // Report the overflows of arrays whose aggregate was visited before their elements.
+        for (_, elements) in std::mem::take(&mut self.overflowing_elements) {
+            self.report_overflowing_elements(elements);
+        }
     }
 
     fn visit_operand(&mut self, operand: &mut Operand<'tcx>, location: Location) {
@@ -1096,6 +1236,9 @@ fn visit_statement(&mut self, statement: &mut Statement<'tcx>, location: Locatio
         trace!("visit_statement: {:?}", statement);
         let source_info = statement.source_info;
         self.source_info = Some(source_info);
+        if let Some(elements) = self.overflowing_elements.remove(&location) {
+            self.report_overflowing_elements(elements);
+        }
         if let StatementKind::Assign(box (place, ref mut rval)) = statement.kind {
             let can_const_prop = self.ecx.machine.can_const_prop[place.local];
             if let Some(()) = self.const_prop(rval, source_info, place) {
@@ -1278,7 +1421,7 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             //
             // NOTE: because LLVM codegen gives slight performance regressions with it, so this is
             // gated on `mir_opt_level=3`.
-            TerminatorKind::Call { .. } => {}
+            TerminatorKind::Call { .. } | TerminatorKind::TailCall { .. } => {}
         }
 
         // We remove all Locals which are restricted in propagation to their containing blocks and
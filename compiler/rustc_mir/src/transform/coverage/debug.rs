@@ -830,6 +830,7 @@ pub(super) fn term_type(kind: &TerminatorKind<'tcx>) -> &'static str {
         TerminatorKind::Drop { .. } => "Drop",
         TerminatorKind::DropAndReplace { .. } => "DropAndReplace",
         TerminatorKind::Call { .. } => "Call",
+        TerminatorKind::TailCall { .. } => "TailCall",
         TerminatorKind::Assert { .. } => "Assert",
         TerminatorKind::Yield { .. } => "Yield",
         TerminatorKind::GeneratorDrop => "GeneratorDrop",
@@ -124,6 +124,7 @@ fn compute_basic_coverage_blocks(
                 TerminatorKind::Return { .. }
                 | TerminatorKind::Abort
                 | TerminatorKind::Yield { .. }
+                | TerminatorKind::TailCall { .. }
                 | TerminatorKind::SwitchInt { .. } => {
                     // The `bb` has more than one _outgoing_ edge, or exits the function. Save the
                     // current sequence of `basic_blocks` gathered to this point, as a new
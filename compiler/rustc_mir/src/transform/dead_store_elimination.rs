@@ -0,0 +1,89 @@
+//! This pass removes assignments whose value is never read:
+//! ```rust,ignore (example)
+//! (_1.0: i32) = const 1_i32;
+//! (_1.1: i32) = const 2_i32;
+//! _0 = (_1.1: i32);
+//! return;
+//! ```
+//! becomes
+//! ```rust,ignore (example)
+//! (_1.1: i32) = const 2_i32;
+//! _0 = (_1.1: i32);
+//! return;
+//! ```
+//! Liveness is computed per field for fields that are projected directly from a local of a struct,
+//! tuple or closure type, so a write to one field can be removed even if other fields of the same
+//! local are still used. The fields of unions and enums overlap, so they are not split up.
+//!
+//! Only writes to locals that are never borrowed, not even by a drop, are considered. The
+//! liveness analysis cannot see reads through pointers, but no pointer to such a local can exist,
+//! so no reference, raw pointer or unsafe code can observe the removed write. `Retag` counts as a
+//! read of the place it retags, so the write of a reference that is retagged afterwards is kept.
+//!
+//! With debuginfo, a debugger can show the value of a user variable even if the program never
+//! reads it again, so writes to the locals that debuginfo refers to are kept as well.
+
+use crate::dataflow::impls::{MaybeLivePlaces, TrackedPlaces};
+use crate::dataflow::Analysis;
+use crate::transform::MirPass;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::DebugInfo;
+
+use super::dest_prop::ever_borrowed_locals;
+
+pub struct DeadStoreElimination;
+
+impl<'tcx> MirPass<'tcx> for DeadStoreElimination {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if tcx.sess.mir_opt_level() < 2 {
+            return;
+        }
+        debug!("Running DeadStoreElimination on {:?}", body.source);
+
+        let mut kept = ever_borrowed_locals(body);
+        if tcx.sess.opts.debuginfo != DebugInfo::None {
+            for info in &body.var_debug_info {
+                if let VarDebugInfoContents::Place(place) = info.value {
+                    kept.insert(place.local);
+                }
+            }
+        }
+        let places = TrackedPlaces::new(body);
+        let mut live = MaybeLivePlaces::new(&places)
+            .into_engine(tcx, body)
+            .iterate_to_fixpoint()
+            .into_results_cursor(body);
+
+        let mut dead_stores = Vec::new();
+        for (block, data) in body.basic_blocks().iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                let place = match &statement.kind {
+                    StatementKind::Assign(box (place, _)) => *place,
+                    StatementKind::SetDiscriminant { place, .. } => **place,
+                    _ => continue,
+                };
+                if place.is_indirect() || kept.contains(place.local) {
+                    continue;
+                }
+                // This is a backward analysis, so the state before the statement's effect is the
+                // liveness right after the statement.
+                let location = Location { block, statement_index };
+                live.seek_before_primary_effect(location);
+                if places.overlapping(place.as_ref()).any(|p| live.contains(p)) {
+                    continue;
+                }
+                if tcx.consider_optimizing(|| {
+                    format!("DeadStoreElimination {:?} in {:?}", statement, body.source)
+                }) {
+                    dead_stores.push(location);
+                }
+            }
+        }
+
+        for location in dead_stores {
+            debug!("removing dead store at {:?}", location);
+            body.basic_blocks_mut()[location.block].statements[location.statement_index].make_nop();
+        }
+    }
+}
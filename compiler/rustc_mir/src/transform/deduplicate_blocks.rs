@@ -1,5 +1,8 @@
 //! This pass finds basic blocks that are completely equal,
 //! and replaces all uses with just one of them.
+//!
+//! It also runs when optimizing for size with `-Copt-level=z`, where the duplicated panic and
+//! drop paths left behind by inlining make up a noticeable part of the code.
 
 use std::{collections::hash_map::Entry, hash::Hash, hash::Hasher, iter};
 
@@ -9,6 +12,7 @@
 use rustc_middle::mir::visit::MutVisitor;
 use rustc_middle::mir::*;
 use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OptLevel;
 
 use super::simplify::simplify_cfg;
 
@@ -16,7 +20,8 @@
 
 impl<'tcx> MirPass<'tcx> for DeduplicateBlocks {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
-        if tcx.sess.mir_opt_level() < 4 {
+        let optimize_for_size = tcx.sess.opts.optimize == OptLevel::SizeMin;
+        if tcx.sess.mir_opt_level() < 4 && !(optimize_for_size && tcx.sess.mir_opt_level() > 0) {
             return;
         }
         debug!("Running DeduplicateBlocks on `{:?}`", body.source);
@@ -155,7 +160,7 @@ fn operand_hash<H: Hasher>(hasher: &mut H, operand: &Operand<'tcx>) {
     };
 }
 
-fn statement_eq<'tcx>(lhs: &StatementKind<'tcx>, rhs: &StatementKind<'tcx>) -> bool {
+pub(super) fn statement_eq<'tcx>(lhs: &StatementKind<'tcx>, rhs: &StatementKind<'tcx>) -> bool {
     let res = match (lhs, rhs) {
         (
             StatementKind::Assign(box (place, rvalue)),
@@ -176,7 +181,7 @@ fn rvalue_eq(lhs: &Rvalue<'tcx>, rhs: &Rvalue<'tcx>) -> bool {
     res
 }
 
-fn operand_eq(lhs: &Operand<'tcx>, rhs: &Operand<'tcx>) -> bool {
+pub(super) fn operand_eq(lhs: &Operand<'tcx>, rhs: &Operand<'tcx>) -> bool {
     let res = match (lhs, rhs) {
         (
             Operand::Constant(box Constant { user_ty: _, literal, span: _ }),
@@ -737,6 +737,7 @@ fn record_terminator_conflicts(&mut self, term: &Terminator<'_>) {
 
             TerminatorKind::Goto { .. }
             | TerminatorKind::Call { destination: None, .. }
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::SwitchInt { .. }
             | TerminatorKind::Resume
             | TerminatorKind::Abort
@@ -947,7 +948,7 @@ fn is_local_required(local: Local, body: &Body<'_>) -> bool {
 }
 
 /// Walks MIR to find all locals that have their address taken anywhere.
-fn ever_borrowed_locals(body: &Body<'_>) -> BitSet<Local> {
+crate fn ever_borrowed_locals(body: &Body<'_>) -> BitSet<Local> {
     let mut visitor = BorrowCollector { locals: BitSet::new_empty(body.local_decls.len()) };
     visitor.visit_body(body);
     visitor.locals
@@ -994,6 +995,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
             TerminatorKind::Abort
             | TerminatorKind::Assert { .. }
             | TerminatorKind::Call { .. }
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::FalseEdge { .. }
             | TerminatorKind::FalseUnwind { .. }
             | TerminatorKind::GeneratorDrop
@@ -1053,6 +1053,8 @@ fn can_unwind<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> bool {
                 unreachable!("`can_unwind` called before generator transform")
             }
 
+            TerminatorKind::TailCall { .. } => bug!("tail call in generator"),
+
             // These may unwind.
             TerminatorKind::Drop { .. }
             | TerminatorKind::DropAndReplace { .. }
@@ -1488,6 +1490,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
             TerminatorKind::InlineAsm { .. } => {}
 
             TerminatorKind::Call { .. }
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::Goto { .. }
             | TerminatorKind::SwitchInt { .. }
             | TerminatorKind::Resume
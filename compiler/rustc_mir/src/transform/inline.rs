@@ -7,8 +7,8 @@
 use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
 use rustc_middle::mir::visit::*;
 use rustc_middle::mir::*;
-use rustc_middle::ty::subst::Subst;
-use rustc_middle::ty::{self, ConstKind, Instance, InstanceDef, ParamEnv, Ty, TyCtxt};
+use rustc_middle::ty::subst::{InternalSubsts, Subst};
+use rustc_middle::ty::{self, ConstKind, Instance, InstanceDef, ParamEnv, Ty, TyCtxt, TypeFoldable};
 use rustc_span::{hygiene::ExpnKind, ExpnData, Span};
 use rustc_target::spec::abi::Abi;
 
@@ -18,6 +18,7 @@
 use std::ops::{Range, RangeFrom};
 
 crate mod cycle;
+crate mod profile;
 
 const INSTR_COST: usize = 5;
 const CALL_PENALTY: usize = 25;
@@ -37,6 +38,13 @@ struct CallSite<'tcx> {
     source_info: SourceInfo,
 }
 
+/// How often a call site is executed according to the `-Cprofile-use` data.
+#[derive(Copy, Clone, Debug)]
+enum Hotness {
+    Hot,
+    Cold,
+}
+
 /// Returns true if MIR inlining is enabled in the current compilation session.
 crate fn is_enabled(tcx: TyCtxt<'_>) -> bool {
     if let Some(enabled) = tcx.sess.opts.debugging_opts.inline_mir {
@@ -79,9 +87,13 @@ fn inline(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
         param_env: tcx.param_env_reveal_all_normalized(body.source.def_id()),
         codegen_fn_attrs: tcx.codegen_fn_attrs(body.source.def_id()),
         hir_id,
+        profile: tcx.instr_profile_counts(()),
+        caller_count: None,
         history: Vec::new(),
         changed: false,
     };
+    let caller = Instance::new(def_id, InternalSubsts::identity_for_item(tcx, def_id));
+    this.caller_count = this.max_block_count(caller);
     let blocks = BasicBlock::new(0)..body.basic_blocks().next_index();
     this.process_blocks(body, blocks);
     this.changed
@@ -94,6 +106,10 @@ struct Inliner<'tcx> {
     codegen_fn_attrs: &'tcx CodegenFnAttrs,
     /// Caller HirID.
     hir_id: hir::HirId,
+    /// Block counts from the `-Cprofile-use` profile, empty if there is none.
+    profile: &'tcx InstrProfileCounts,
+    /// Count of the most executed block of the caller, if it is in the profile.
+    caller_count: Option<u64>,
     /// Stack of inlined Instances.
     history: Vec<ty::Instance<'tcx>>,
     /// Indicates that the caller body has been modified.
@@ -328,6 +344,40 @@ fn check_codegen_attributes(
         Ok(())
     }
 
+    /// Returns the count of the most executed block of `instance` in the `-Cprofile-use` profile.
+    /// Functions are looked up by their symbol name, so this only works for instances that are
+    /// fully monomorphic.
+    fn max_block_count(&self, instance: Instance<'tcx>) -> Option<u64> {
+        if self.profile.max_block_counts.is_empty()
+            || !matches!(instance.def, InstanceDef::Item(_))
+            || instance.substs.needs_subst()
+        {
+            return None;
+        }
+        self.profile.max_block_count(self.tcx.symbol_name(instance).name)
+    }
+
+    /// Classifies the call site with the `-Cprofile-use` data. The profile only has counts for
+    /// whole functions, so a call site is considered cold if the caller or the callee is cold, and
+    /// hot if the callee is hot and the caller isn't known to be anything else.
+    fn call_site_hotness(&self, callsite: &CallSite<'tcx>) -> Option<Hotness> {
+        let callee_count = self.max_block_count(callsite.callee)?;
+        let is_cold = |count| count <= self.profile.cold_count;
+        let is_hot = |count| count >= self.profile.hot_count;
+        let hotness = if is_cold(callee_count) || self.caller_count.map_or(false, is_cold) {
+            Hotness::Cold
+        } else if is_hot(callee_count) && self.caller_count.map_or(true, is_hot) {
+            Hotness::Hot
+        } else {
+            return None;
+        };
+        debug!(
+            "    call site is {:?} [caller count={:?}, callee count={}]",
+            hotness, self.caller_count, callee_count
+        );
+        Some(hotness)
+    }
+
     /// Returns inlining decision that is based on the examination of callee MIR body.
     /// Assumes that codegen attributes have been checked for compatibility already.
     #[instrument(level = "debug", skip(self, callee_body))]
@@ -345,6 +395,22 @@ fn check_mir_body(
             self.tcx.sess.opts.debugging_opts.inline_mir_threshold.unwrap_or(50)
         };
 
+        // Make it easier to inline into hot call sites, where it pays off the most, and harder to
+        // inline into cold ones, where it only increases the code size.
+        match self.call_site_hotness(callsite) {
+            Some(Hotness::Hot) => {
+                let hot_threshold =
+                    self.tcx.sess.opts.debugging_opts.inline_mir_hot_threshold.unwrap_or(200);
+                threshold = threshold.max(hot_threshold);
+            }
+            Some(Hotness::Cold) => {
+                let cold_threshold =
+                    self.tcx.sess.opts.debugging_opts.inline_mir_cold_threshold.unwrap_or(10);
+                threshold = threshold.min(cold_threshold);
+            }
+            None => {}
+        }
+
         // Give a bonus functions with a small number of blocks,
         // We normally have two or three blocks for even
         // very small functions.
@@ -397,6 +463,9 @@ fn check_mir_body(
                     }
                 }
 
+                // The callee would return from the caller's frame.
+                TerminatorKind::TailCall { .. } => return Err("callee contains a tail call"),
+
                 TerminatorKind::Unreachable | TerminatorKind::Call { destination: None, .. }
                     if first_block =>
                 {
@@ -896,7 +965,9 @@ fn visit_terminator(&mut self, terminator: &mut Terminator<'tcx>, loc: Location)
         }
 
         match terminator.kind {
-            TerminatorKind::GeneratorDrop | TerminatorKind::Yield { .. } => bug!(),
+            TerminatorKind::GeneratorDrop
+            | TerminatorKind::Yield { .. }
+            | TerminatorKind::TailCall { .. } => bug!(),
             TerminatorKind::Goto { ref mut target } => {
                 *target = self.map_block(*target);
             }
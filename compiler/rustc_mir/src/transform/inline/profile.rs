@@ -0,0 +1,192 @@
+//! Reads the block counts of the indexed LLVM profile passed to `-Cprofile-use`, so the MIR inliner
+//! can tell hot functions from cold ones. Only the parts of the format needed for this are read:
+//! the hash table of function records and their counters.
+//!
+//! An indexed profile (versions 2 to 7) starts with a header holding the magic, the version and
+//! the offset of an on-disk hash table. The table is keyed by symbol name, prefixed with the name
+//! of the source file for functions with local linkage, and each key has one record per function
+//! with that name: a structural hash followed by the counters of the function and, since version 3,
+//! its value profiling data.
+//!
+//! The counters of a record belong to the regions instrumented by LLVM, which can't be mapped back
+//! to MIR locations. Hotness is therefore known per function only, as the largest count of any of
+//! its blocks, and not per call site: all calls from one caller to one callee are treated the same,
+//! whether they sit in a loop or on an error path. Keying the data by call site would need the
+//! instrumentation to record MIR locations, which it doesn't do.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir::InstrProfileCounts;
+use rustc_middle::ty::TyCtxt;
+use std::convert::TryInto;
+use std::fs;
+
+/// `"\xfflprofi\x81"` read as a little endian integer.
+const INDEXED_PROF_MAGIC: u64 = 0x8169_666f_7270_6cff;
+
+/// The high byte of the version holds flags that describe how the profile was generated.
+const VERSION_MASK: u64 = 0x00ff_ffff_ffff_ffff;
+
+/// The number of counters of a record is stored since version 2 and the layout of the records
+/// hasn't changed since.
+const MIN_VERSION: u64 = 2;
+const MAX_VERSION: u64 = 7;
+
+/// The records of version 3 and later are followed by value profiling data.
+const VALUE_PROF_VERSION: u64 = 3;
+
+/// Which part of the sum of all block counts, in millionths, is covered by the hot blocks and by
+/// the blocks that aren't cold. These are the same cutoffs LLVM uses in its profile summary.
+const HOT_CUTOFF: u128 = 990_000;
+const COLD_CUTOFF: u128 = 999_999;
+
+crate fn instr_profile_counts<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> &'tcx InstrProfileCounts {
+    let path = match &tcx.sess.opts.cg.profile_use {
+        Some(path) => path,
+        None => return tcx.arena.alloc(InstrProfileCounts::default()),
+    };
+    let counts = match fs::read(path) {
+        Ok(data) => read_indexed_profile(&data),
+        Err(err) => Err(err.to_string()),
+    };
+    match counts {
+        Ok(counts) => tcx.arena.alloc(counts),
+        Err(err) => {
+            tcx.sess.warn(&format!(
+                "cannot use profile `{}` for MIR inlining: {}",
+                path.display(),
+                err
+            ));
+            tcx.arena.alloc(InstrProfileCounts::default())
+        }
+    }
+}
+
+fn read_indexed_profile(data: &[u8]) -> Result<InstrProfileCounts, String> {
+    let mut header = Reader::new(data, 0)?;
+    if header.read_u64()? != INDEXED_PROF_MAGIC {
+        return Err("not an indexed profile".to_string());
+    }
+    let version = header.read_u64()? & VERSION_MASK;
+    if !(MIN_VERSION..=MAX_VERSION).contains(&version) {
+        return Err(format!("unsupported profile version {}", version));
+    }
+    let _unused = header.read_u64()?;
+    let _hash_type = header.read_u64()?;
+    let hash_offset = header.read_u64()?;
+
+    // The records are stored in buckets of an on-disk hash table. All offsets are relative to the
+    // start of the file, empty buckets have an offset of zero.
+    let mut table = Reader::new(data, hash_offset)?;
+    let num_buckets = table.read_u64()?;
+    let _num_entries = table.read_u64()?;
+
+    let mut max_block_counts = FxHashMap::default();
+    let mut all_counts = Vec::new();
+    for _ in 0..num_buckets {
+        let bucket_offset = table.read_u64()?;
+        if bucket_offset == 0 {
+            continue;
+        }
+        let mut bucket = Reader::new(data, bucket_offset)?;
+        for _ in 0..bucket.read_u16()? {
+            let _key_hash = bucket.read_u64()?;
+            let key_len = bucket.read_u64()?;
+            let data_len = bucket.read_u64()?;
+            let key = String::from_utf8_lossy(bucket.read_bytes(key_len)?);
+            // Functions with local linkage are prefixed with the name of their source file.
+            let name = match key.rfind(':') {
+                Some(colon) => &key[colon + 1..],
+                None => &key[..],
+            };
+
+            // A key has one record for each function with that name.
+            let mut records = Reader::new(bucket.read_bytes(data_len)?, 0)?;
+            while !records.is_empty() {
+                let _structural_hash = records.read_u64()?;
+                let num_counts = records.read_u64()?;
+                let mut max_count = 0;
+                for _ in 0..num_counts {
+                    let count = records.read_u64()?;
+                    max_count = max_count.max(count);
+                    all_counts.push(count);
+                }
+                if version >= VALUE_PROF_VERSION {
+                    // The size of the value profiling data includes the size field itself.
+                    let size = records.clone().read_u32()?;
+                    records.read_bytes(size.into())?;
+                }
+                let entry = max_block_counts.entry(name.to_string()).or_insert(0);
+                *entry = max_count.max(*entry);
+            }
+        }
+    }
+
+    all_counts.sort_unstable_by(|a, b| b.cmp(a));
+    let total: u128 = all_counts.iter().map(|&count| u128::from(count)).sum();
+    let (hot_count, cold_count) = if total == 0 {
+        // Nothing has been executed, so nothing is hot.
+        (u64::MAX, 0)
+    } else {
+        (
+            count_at_cutoff(&all_counts, total, HOT_CUTOFF),
+            count_at_cutoff(&all_counts, total, COLD_CUTOFF),
+        )
+    };
+    Ok(InstrProfileCounts { max_block_counts, hot_count, cold_count })
+}
+
+/// Returns the smallest count among the largest counts that together make up `cutoff` millionths
+/// of `total`. `counts` has to be sorted in descending order.
+fn count_at_cutoff(counts: &[u64], total: u128, cutoff: u128) -> u64 {
+    let desired = (total * cutoff + 999_999) / 1_000_000;
+    let mut sum = 0;
+    for &count in counts {
+        sum += u128::from(count);
+        if sum >= desired {
+            return count;
+        }
+    }
+    0
+}
+
+/// Reads the little endian integers the profile is made of.
+#[derive(Clone)]
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], offset: u64) -> Result<Self, String> {
+        let offset = offset.try_into().map_err(|_| "offset out of range".to_string())?;
+        match data.get(offset..) {
+            Some(data) => Ok(Reader { data }),
+            None => Err("offset out of range".to_string()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn read_bytes(&mut self, len: u64) -> Result<&'a [u8], String> {
+        let len: usize = len.try_into().map_err(|_| "unexpected end of file".to_string())?;
+        if len > self.data.len() {
+            return Err("unexpected end of file".to_string());
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+}
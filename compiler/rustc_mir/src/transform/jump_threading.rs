@@ -0,0 +1,280 @@
+//! This pass threads jumps to blocks that switch on the discriminant of an enum, if the value of
+//! the discriminant is already known on the way there, because it was just set or because the
+//! same discriminant was just switched on:
+//! ```rust,ignore (example)
+//! bb1: {
+//!     ((_2 as Some).0: i32) = move _3;
+//!     discriminant(_2) = 1;
+//!     goto -> bb3;
+//! }
+//!
+//! bb3: {
+//!     _4 = discriminant(_2);
+//!     switchInt(move _4) -> [0_isize: bb4, 1_isize: bb5, otherwise: bb6];
+//! }
+//! ```
+//! becomes
+//! ```rust,ignore (example)
+//! bb1: {
+//!     ((_2 as Some).0: i32) = move _3;
+//!     discriminant(_2) = 1;
+//!     _4 = discriminant(_2);
+//!     goto -> bb5;
+//! }
+//! ```
+//! This is common after inlining `Option` and `Result` combinators, which match on values that
+//! were just built or matched on by their caller.
+//!
+//! Only locals that are never borrowed are considered, so their discriminant can't be changed
+//! through a pointer in between.
+
+use crate::transform::MirPass;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+use super::dest_prop::ever_borrowed_locals;
+use super::simplify::simplify_cfg;
+
+pub struct JumpThreading;
+
+/// How many blocks with a single predecessor are walked back to find the discriminant.
+const MAX_BACKTRACK: usize = 8;
+
+impl<'tcx> MirPass<'tcx> for JumpThreading {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if tcx.sess.mir_opt_level() < 3 {
+            return;
+        }
+        debug!("Running JumpThreading on {:?}", body.source);
+
+        let finder = ThreadingFinder { tcx, body, borrowed: ever_borrowed_locals(body) };
+        let threads = finder.find_threads();
+        if threads.is_empty() {
+            return;
+        }
+
+        for thread in threads {
+            if !tcx.consider_optimizing(|| format!("JumpThreading {:?}", thread)) {
+                break;
+            }
+            debug!("threading {:?}", thread);
+            let statements = body.basic_blocks()[thread.switch].statements.clone();
+            match thread.value {
+                // The predecessor ends in a `goto`, so the statements of the switch block can be
+                // executed in it.
+                None => {
+                    let pred = &mut body.basic_blocks_mut()[thread.pred];
+                    pred.statements.extend(statements);
+                    pred.terminator_mut().kind = TerminatorKind::Goto { target: thread.target };
+                }
+                // The predecessor is a switch itself, so a new block is needed on the edge.
+                Some(value) => {
+                    let is_cleanup = body.basic_blocks()[thread.switch].is_cleanup;
+                    let source_info = body.basic_blocks()[thread.switch].terminator().source_info;
+                    let new_block = body.basic_blocks_mut().push(BasicBlockData {
+                        statements,
+                        terminator: Some(Terminator {
+                            source_info,
+                            kind: TerminatorKind::Goto { target: thread.target },
+                        }),
+                        is_cleanup,
+                    });
+                    let pred = &mut body.basic_blocks_mut()[thread.pred];
+                    let targets = match &mut pred.terminator_mut().kind {
+                        TerminatorKind::SwitchInt { targets, .. } => targets,
+                        kind => bug!("threading a jump from {:?}", kind),
+                    };
+                    let index = targets.iter().position(|(v, _)| v == value).unwrap();
+                    targets.all_targets_mut()[index] = new_block;
+                }
+            }
+        }
+
+        simplify_cfg(tcx, body);
+    }
+}
+
+/// A jump from `pred` to `switch` that can go to `target` directly.
+#[derive(Debug)]
+struct Thread {
+    pred: BasicBlock,
+    /// The value of the edge if `pred` ends with a `SwitchInt`, `None` if it ends with a `Goto`.
+    value: Option<u128>,
+    switch: BasicBlock,
+    target: BasicBlock,
+}
+
+struct ThreadingFinder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    borrowed: BitSet<Local>,
+}
+
+impl<'a, 'tcx> ThreadingFinder<'a, 'tcx> {
+    fn find_threads(&self) -> Vec<Thread> {
+        let mut threads = Vec::new();
+        for (switch, data) in self.body.basic_blocks().iter_enumerated() {
+            let place = match self.threadable_switch(data) {
+                Some(place) => place,
+                None => continue,
+            };
+            let targets = data.terminator().kind.as_switch().unwrap().2;
+
+            let mut preds = self.body.predecessors()[switch].clone();
+            preds.sort_unstable();
+            preds.dedup();
+            for pred in preds {
+                if pred == switch {
+                    continue;
+                }
+                match self.body[pred].terminator().kind {
+                    TerminatorKind::Goto { .. } => {
+                        if let Some(value) = self.known_discriminant(place, pred, switch) {
+                            let target = targets.target_for_value(value);
+                            threads.push(Thread { pred, value: None, switch, target });
+                        }
+                    }
+                    TerminatorKind::SwitchInt { targets: ref pred_targets, .. } => {
+                        // Every edge of a switch on the same discriminant has its own value.
+                        if self.switched_discriminant(&self.body[pred]) != Some(place) {
+                            continue;
+                        }
+                        for (value, pred_target) in pred_targets.iter() {
+                            if pred_target == switch {
+                                let target = targets.target_for_value(value);
+                                threads.push(Thread { pred, value: Some(value), switch, target });
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        threads
+    }
+
+    /// Returns the place whose discriminant the block switches on, if the block does nothing but
+    /// reading that discriminant and switching on it.
+    fn threadable_switch(&self, data: &BasicBlockData<'tcx>) -> Option<Place<'tcx>> {
+        let place = self.switched_discriminant(data)?;
+        if place.is_indirect() || self.borrowed.contains(place.local) {
+            return None;
+        }
+        let only_reads = data.statements.iter().all(|statement| match statement.kind {
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                local != place.local
+            }
+            StatementKind::Assign(box (_, Rvalue::Discriminant(_))) | StatementKind::Nop => true,
+            _ => false,
+        });
+        only_reads.then_some(place)
+    }
+
+    /// Returns the place whose discriminant the block switches on, if it is read in the same block
+    /// and not changed before the switch.
+    fn switched_discriminant(&self, data: &BasicBlockData<'tcx>) -> Option<Place<'tcx>> {
+        let switched = match &data.terminator().kind {
+            TerminatorKind::SwitchInt {
+                discr: Operand::Move(switched) | Operand::Copy(switched),
+                ..
+            } => *switched,
+            _ => return None,
+        };
+        for (index, statement) in data.statements.iter().enumerate().rev() {
+            match &statement.kind {
+                StatementKind::Assign(box (lhs, Rvalue::Discriminant(place)))
+                    if *lhs == switched =>
+                {
+                    // Make sure the discriminant is still the same when the switch happens.
+                    let later = &data.statements[index + 1..];
+                    return later.iter().all(|s| !mutates_local(s, place.local)).then_some(*place);
+                }
+                _ if mutates_local(statement, switched.local) => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Returns the discriminant of `place` on the edge from `block` to `succ`, if it is known.
+    fn known_discriminant(
+        &self,
+        place: Place<'tcx>,
+        mut block: BasicBlock,
+        mut succ: BasicBlock,
+    ) -> Option<u128> {
+        for _ in 0..MAX_BACKTRACK {
+            let data = &self.body[block];
+            if terminator_mutates_local(data.terminator(), place.local) {
+                return None;
+            }
+            if self.switched_discriminant(data) == Some(place) {
+                // The value is known from the edge that is taken, if only one value leads there.
+                let targets = data.terminator().kind.as_switch().unwrap().2;
+                if targets.otherwise() == succ {
+                    return None;
+                }
+                let mut values = targets.iter().filter(|&(_, target)| target == succ);
+                let (value, _) = values.next()?;
+                return values.next().is_none().then_some(value);
+            }
+            if let Some(statement) =
+                data.statements.iter().rev().find(|s| mutates_local(s, place.local))
+            {
+                return self.discriminant_set_by(statement, place);
+            }
+
+            let preds = &self.body.predecessors()[block];
+            if preds.len() != 1 {
+                return None;
+            }
+            succ = block;
+            block = preds[0];
+        }
+        None
+    }
+
+    /// Returns the discriminant `statement` gives to `place`, if it sets it at all.
+    fn discriminant_set_by(&self, statement: &Statement<'tcx>, place: Place<'tcx>) -> Option<u128> {
+        let variant_index = match &statement.kind {
+            StatementKind::SetDiscriminant { place: set, variant_index } if **set == place => {
+                *variant_index
+            }
+            StatementKind::Assign(box (
+                lhs,
+                Rvalue::Aggregate(box AggregateKind::Adt(_, variant_index, ..), _),
+            )) if *lhs == place => *variant_index,
+            _ => return None,
+        };
+        let ty = place.ty(self.body, self.tcx).ty;
+        Some(ty.discriminant_for_variant(self.tcx, variant_index)?.val)
+    }
+}
+
+fn mutates_local(statement: &Statement<'_>, local: Local) -> bool {
+    let mut finder = MutationFinder { local, found: false };
+    finder.visit_statement(statement, Location::START);
+    finder.found
+}
+
+fn terminator_mutates_local(terminator: &Terminator<'_>, local: Local) -> bool {
+    let mut finder = MutationFinder { local, found: false };
+    finder.visit_terminator(terminator, Location::START);
+    finder.found
+}
+
+/// Finds writes to a local, including storage markers.
+struct MutationFinder {
+    local: Local,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for MutationFinder {
+    fn visit_local(&mut self, &local: &Local, context: PlaceContext, _location: Location) {
+        if local == self.local && (context.is_mutating_use() || context.is_storage_marker()) {
+            self.found = true;
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! This pass hoists loop-invariant computations out of loops:
+//! ```rust,ignore (example)
+//! bb1: {
+//!     goto -> bb2;
+//! }
+//!
+//! bb2: {
+//!     _5 = Len((*_1));
+//!     _6 = Lt(_4, _5);
+//!     assert(move _6, ...) -> bb3;
+//! }
+//! ```
+//! becomes
+//! ```rust,ignore (example)
+//! bb1: {
+//!     _5 = Len((*_1));
+//!     goto -> bb2;
+//! }
+//!
+//! bb2: {
+//!     _6 = Lt(_4, _5);
+//!     assert(move _6, ...) -> bb3;
+//! }
+//! ```
+//!
+//! Only statements that have no side effects and cannot cause UB are hoisted, so it does not
+//! matter that they may now be evaluated even if the loop body never reaches them. A statement is
+//! moved into the *preheader* of the loop, which is the only block outside of the loop that jumps
+//! to the loop header. Loops without a preheader are left alone.
+
+use crate::transform::MirPass;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::graph::dominators::Dominators;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{
+    MutVisitor, MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor,
+};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+pub struct LoopInvariantCodeMotion;
+
+impl<'tcx> MirPass<'tcx> for LoopInvariantCodeMotion {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if tcx.sess.mir_opt_level() < 3 {
+            return;
+        }
+        debug!("Running LoopInvariantCodeMotion on {:?}", body.source);
+
+        let dominators = body.dominators();
+        let mut loops = find_loops(body, &dominators);
+        // Handle inner loops first, so that their invariants can be hoisted further out of the
+        // loops that contain them.
+        loops.sort_by_key(|l| l.blocks.count());
+
+        let mut hoisted = FxHashSet::default();
+        for l in &loops {
+            hoist_invariants(body, &dominators, l, &mut hoisted);
+        }
+
+        if !hoisted.is_empty() {
+            // A hoisted local is now assigned before the loop but used in every iteration, so its
+            // storage must not end inside of the loop. It is also read again in later iterations,
+            // so it must not be moved out of.
+            HoistedLocalFixup { tcx, hoisted }.visit_body(body);
+        }
+    }
+}
+
+struct Loop {
+    header: BasicBlock,
+    preheader: BasicBlock,
+    blocks: BitSet<BasicBlock>,
+}
+
+/// Finds the natural loops of `body` that have a preheader. Loops sharing a header are merged.
+fn find_loops(body: &Body<'_>, dominators: &Dominators<BasicBlock>) -> Vec<Loop> {
+    let predecessors = body.predecessors();
+    let mut loop_blocks: IndexVec<BasicBlock, Option<BitSet<BasicBlock>>> =
+        IndexVec::from_elem(None, body.basic_blocks());
+
+    for (bb, data) in body.basic_blocks().iter_enumerated() {
+        if !dominators.is_reachable(bb) {
+            continue;
+        }
+        for &header in data.terminator().successors() {
+            if !dominators.is_dominated_by(bb, header) {
+                continue;
+            }
+            // `bb -> header` is a back edge. The loop consists of every block that can reach
+            // `bb` without going through `header`.
+            let blocks = loop_blocks[header]
+                .get_or_insert_with(|| BitSet::new_empty(body.basic_blocks().len()));
+            blocks.insert(header);
+            let mut stack = vec![bb];
+            while let Some(block) = stack.pop() {
+                if blocks.insert(block) {
+                    stack.extend(
+                        predecessors[block].iter().filter(|&&p| dominators.is_reachable(p)),
+                    );
+                }
+            }
+        }
+    }
+
+    loop_blocks
+        .into_iter_enumerated()
+        .filter_map(|(header, blocks)| {
+            let blocks = blocks?;
+            let mut entries = predecessors[header].iter().filter(|&&p| !blocks.contains(p));
+            let preheader = *entries.next()?;
+            if entries.next().is_some() || !dominators.is_reachable(preheader) {
+                return None;
+            }
+            match body[preheader].terminator().kind {
+                TerminatorKind::Goto { target } if target == header => {}
+                _ => return None,
+            }
+            Some(Loop { header, preheader, blocks })
+        })
+        .collect()
+}
+
+/// Moves the invariant statements of `l` into its preheader until there are no more left.
+fn hoist_invariants<'tcx>(
+    body: &mut Body<'tcx>,
+    dominators: &Dominators<BasicBlock>,
+    l: &Loop,
+    hoisted: &mut FxHashSet<Local>,
+) {
+    // Every block from which the preheader can be reached. A local that may be killed in one of
+    // them could be dead when the loop is entered.
+    let mut reaches_preheader = BitSet::new_empty(body.basic_blocks().len());
+    let mut stack = vec![l.preheader];
+    while let Some(block) = stack.pop() {
+        if reaches_preheader.insert(block) {
+            stack.extend(body.predecessors()[block].iter().copied());
+        }
+    }
+
+    loop {
+        // The statements that have been hoisted in the previous round change where their locals
+        // are assigned, so the uses have to be collected again.
+        let mut collector = LocalUseCollector {
+            uses: IndexVec::from_elem(LocalUse::default(), &body.local_decls),
+        };
+        collector.visit_body(body);
+        let finder = InvariantFinder {
+            body,
+            dominators,
+            l,
+            reaches_preheader: &reaches_preheader,
+            uses: &collector.uses,
+        };
+
+        let mut candidates = Vec::new();
+        for block in l.blocks.iter() {
+            for (statement_index, statement) in body[block].statements.iter().enumerate() {
+                let location = Location { block, statement_index };
+                if let Some(local) = finder.hoistable_local(statement, location) {
+                    candidates.push((location, local));
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return;
+        }
+
+        for (location, local) in candidates {
+            debug!("hoisting {:?} out of loop at {:?}", location, l.header);
+            let statements = &mut body.basic_blocks_mut()[location.block].statements;
+            let statement = statements[location.statement_index].clone();
+            statements[location.statement_index].make_nop();
+            body.basic_blocks_mut()[l.preheader].statements.push(statement);
+            hoisted.insert(local);
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct LocalUse {
+    /// Whether the local is borrowed anywhere. Writes through a borrow cannot be tracked.
+    borrowed: bool,
+    /// Every location that writes to the local or to a part of it.
+    defs: Vec<Location>,
+    /// Blocks in which, or at whose start, the local has been assigned as a whole.
+    init_blocks: Vec<BasicBlock>,
+    /// Blocks that may leave the local dead or moved out of.
+    kills: Vec<BasicBlock>,
+}
+
+struct LocalUseCollector {
+    uses: IndexVec<Local, LocalUse>,
+}
+
+impl<'tcx> Visitor<'tcx> for LocalUseCollector {
+    fn visit_assign(&mut self, place: &Place<'tcx>, rvalue: &Rvalue<'tcx>, location: Location) {
+        if let Some(local) = place.as_local() {
+            self.uses[local].init_blocks.push(location.block);
+        }
+        self.super_assign(place, rvalue, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        if let TerminatorKind::Call { destination: Some((place, target)), .. } = &terminator.kind {
+            if let Some(local) = place.as_local() {
+                // The destination is only written if the call returns.
+                self.uses[local].init_blocks.push(*target);
+            }
+        }
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_local(&mut self, &local: &Local, context: PlaceContext, location: Location) {
+        if context.is_storage_marker() {
+            self.uses[local].kills.push(location.block);
+        }
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // Locals used in `Index` projections are only read, so there is no need to visit them.
+        let uses = &mut self.uses[place.local];
+        match context {
+            PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::SharedBorrow
+                | NonMutatingUseContext::ShallowBorrow
+                | NonMutatingUseContext::UniqueBorrow
+                | NonMutatingUseContext::AddressOf,
+            )
+            | PlaceContext::MutatingUse(
+                MutatingUseContext::Borrow | MutatingUseContext::AddressOf,
+            ) => uses.borrowed = true,
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::Move)
+            | PlaceContext::MutatingUse(MutatingUseContext::Drop) => {
+                uses.kills.push(location.block)
+            }
+            PlaceContext::MutatingUse(_) => uses.defs.push(location),
+            PlaceContext::NonMutatingUse(_) | PlaceContext::NonUse(_) => {}
+        }
+    }
+}
+
+struct InvariantFinder<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    dominators: &'a Dominators<BasicBlock>,
+    l: &'a Loop,
+    reaches_preheader: &'a BitSet<BasicBlock>,
+    uses: &'a IndexVec<Local, LocalUse>,
+}
+
+impl InvariantFinder<'_, 'tcx> {
+    /// Returns the local that `statement` assigns if the statement can be hoisted.
+    fn hoistable_local(&self, statement: &Statement<'tcx>, location: Location) -> Option<Local> {
+        let (place, rvalue) = match &statement.kind {
+            StatementKind::Assign(box (place, rvalue)) => (place, rvalue),
+            _ => return None,
+        };
+        let local = place.as_local()?;
+        let uses = &self.uses[local];
+        // Hoisting the only assignment of a local does not change its value anywhere the
+        // statement would have been evaluated.
+        if local == RETURN_PLACE
+            || self.is_arg(local)
+            || uses.borrowed
+            || uses.defs.as_slice() != [location]
+        {
+            return None;
+        }
+        self.is_invariant_rvalue(rvalue).then_some(local)
+    }
+
+    fn is_invariant_rvalue(&self, rvalue: &Rvalue<'tcx>) -> bool {
+        match rvalue {
+            // Division and remainder by zero and oversized shifts are UB, and the checks that
+            // rule them out stay in the loop.
+            Rvalue::BinaryOp(op, box (lhs, rhs)) | Rvalue::CheckedBinaryOp(op, box (lhs, rhs)) => {
+                !matches!(op, BinOp::Div | BinOp::Rem | BinOp::Shl | BinOp::Shr | BinOp::Offset)
+                    && self.is_invariant_operand(lhs)
+                    && self.is_invariant_operand(rhs)
+            }
+            Rvalue::UnaryOp(_, operand) => self.is_invariant_operand(operand),
+            // The length of a slice behind a reference only reads the metadata of the
+            // reference, not the memory it points to.
+            Rvalue::Len(place) => match place.projection.split_first() {
+                Some((ProjectionElem::Deref, rest)) => self.is_invariant_place(place.local, rest),
+                _ => self.is_invariant_place(place.local, place.projection),
+            },
+            Rvalue::Discriminant(place) => self.is_invariant_place(place.local, place.projection),
+            _ => false,
+        }
+    }
+
+    fn is_invariant_operand(&self, operand: &Operand<'tcx>) -> bool {
+        match operand {
+            Operand::Constant(_) => true,
+            Operand::Copy(place) | Operand::Move(place) => {
+                self.is_invariant_place(place.local, place.projection)
+            }
+        }
+    }
+
+    /// Field projections cannot fail, unlike indexing or dereferencing, which may be guarded by
+    /// a check inside of the loop.
+    fn is_invariant_place(&self, local: Local, projection: &[PlaceElem<'tcx>]) -> bool {
+        projection.iter().all(|elem| matches!(elem, ProjectionElem::Field(..)))
+            && self.is_invariant_local(local)
+    }
+
+    /// Whether `local` is initialized and live when the loop is entered, and keeps its value
+    /// while the loop runs.
+    fn is_invariant_local(&self, local: Local) -> bool {
+        let uses = &self.uses[local];
+        if local == RETURN_PLACE || uses.borrowed {
+            return false;
+        }
+        let dominates_preheader = |block| self.dominators.is_dominated_by(self.l.preheader, block);
+        let defs_outside_loop = uses.defs.iter().all(|def| {
+            !self.l.blocks.contains(def.block)
+                && (dominates_preheader(def.block) || !self.reaches_preheader.contains(def.block))
+        });
+        let never_killed = uses.kills.iter().all(|&block| {
+            !self.l.blocks.contains(block) && !self.reaches_preheader.contains(block)
+        });
+        let initialized =
+            self.is_arg(local) || uses.init_blocks.iter().any(|&block| dominates_preheader(block));
+        defs_outside_loop && never_killed && initialized
+    }
+
+    fn is_arg(&self, local: Local) -> bool {
+        local != RETURN_PLACE && local.index() <= self.body.arg_count
+    }
+}
+
+struct HoistedLocalFixup<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    hoisted: FxHashSet<Local>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for HoistedLocalFixup<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_statement(&mut self, statement: &mut Statement<'tcx>, location: Location) {
+        match statement.kind {
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local)
+                if self.hoisted.contains(&local) =>
+            {
+                statement.make_nop()
+            }
+            _ => self.super_statement(statement, location),
+        }
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, location: Location) {
+        if let Operand::Move(place) = operand {
+            if self.hoisted.contains(&place.local) {
+                *operand = Operand::Copy(*place);
+            }
+        }
+        self.super_operand(operand, location);
+    }
+}
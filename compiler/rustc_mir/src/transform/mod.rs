@@ -10,6 +10,7 @@
 use rustc_middle::mir::{traversal, Body, ConstQualifs, MirPhase, Promoted};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, TyCtxt, TypeFoldable};
+use rustc_session::Session;
 use rustc_span::{Span, Symbol};
 use std::borrow::Cow;
 
@@ -25,6 +26,7 @@
 pub mod const_goto;
 pub mod const_prop;
 pub mod coverage;
+pub mod dead_store_elimination;
 pub mod deaggregator;
 pub mod deduplicate_blocks;
 pub mod dest_prop;
@@ -35,6 +37,8 @@
 pub mod generator;
 pub mod inline;
 pub mod instcombine;
+pub mod jump_threading;
+pub mod loop_invariant_code_motion;
 pub mod lower_intrinsics;
 pub mod match_branches;
 pub mod multiple_return_terminators;
@@ -47,10 +51,12 @@
 pub mod remove_zsts;
 pub mod required_consts;
 pub mod rustc_peek;
+pub mod scalar_replacement_of_aggregates;
 pub mod simplify;
 pub mod simplify_branches;
 pub mod simplify_comparison_integral;
 pub mod simplify_try;
+pub mod tail_merge;
 pub mod uninhabited_enum_branching;
 pub mod unreachable_prop;
 pub mod validate;
@@ -167,7 +173,10 @@ pub fn run_passes(
     passes: &[&[&dyn MirPass<'tcx>]],
 ) {
     let phase_index = mir_phase.phase_index();
-    let validate = tcx.sess.opts.debugging_opts.validate_mir;
+    // A custom pipeline may break the assumptions of the passes in it, so it is always validated.
+    let custom_pipeline = tcx.sess.opts.debugging_opts.mir_passes.is_some();
+    let validate = tcx.sess.opts.debugging_opts.validate_mir
+        || (custom_pipeline && mir_phase >= MirPhase::GeneratorLowering);
 
     if body.phase >= mir_phase {
         return;
@@ -472,9 +481,11 @@ fn run_post_borrowck_cleanup_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tc
     run_passes(tcx, body, MirPhase::DropLowering, &[post_borrowck_cleanup]);
 }
 
-fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
-    let mir_opt_level = tcx.sess.mir_opt_level();
-
+/// Calls `f` with the optimizations that run before generator lowering, which ends them, and
+/// with the main optimizations. `-Zmir-passes` chooses from these.
+fn with_optimizations<'tcx, R>(
+    f: impl FnOnce(&[&dyn MirPass<'tcx>], &[&dyn MirPass<'tcx>]) -> R,
+) -> R {
     // Lowering generator control-flow and variables has to happen before we do anything else
     // to them. We run some optimizations before that, because they may be harder to do on the state
     // machine than on MIR with async primitives.
@@ -486,9 +497,6 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         &generator::StateTransform,
     ];
 
-    // Even if we don't do optimizations, we still have to lower generators for codegen.
-    let no_optimizations_with_generators: &[&dyn MirPass<'tcx>] = &[&generator::StateTransform];
-
     // The main optimizations that we do on MIR.
     let optimizations: &[&dyn MirPass<'tcx>] = &[
         &remove_storage_markers::RemoveStorageMarkers,
@@ -499,15 +507,20 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         // inst combine is after MatchBranchSimplification to clean up Ne(_1, false)
         &multiple_return_terminators::MultipleReturnTerminators,
         &instcombine::InstCombine,
+        &scalar_replacement_of_aggregates::ScalarReplacementOfAggregates,
         &const_prop::ConstProp,
         &simplify_branches::SimplifyBranches::new("after-const-prop"),
+        &jump_threading::JumpThreading,
         &early_otherwise_branch::EarlyOtherwiseBranch,
         &simplify_comparison_integral::SimplifyComparisonIntegral,
         &simplify_try::SimplifyArmIdentity,
         &simplify_try::SimplifyBranchSame,
+        &loop_invariant_code_motion::LoopInvariantCodeMotion,
         &dest_prop::DestinationPropagation,
+        &dead_store_elimination::DeadStoreElimination,
         &simplify_branches::SimplifyBranches::new("final"),
         &remove_noop_landing_pads::RemoveNoopLandingPads,
+        &tail_merge::TailMerge,
         &simplify::SimplifyCfg::new("final"),
         &nrvo::RenameReturnPlace,
         &const_debuginfo::ConstDebugInfo,
@@ -516,6 +529,26 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         &deduplicate_blocks::DeduplicateBlocks,
     ];
 
+    f(optimizations_with_generators, optimizations)
+}
+
+fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+    with_optimizations(|optimizations_with_generators, optimizations| {
+        run_optimizations(tcx, body, optimizations_with_generators, optimizations)
+    })
+}
+
+fn run_optimizations<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    optimizations_with_generators: &[&dyn MirPass<'tcx>],
+    optimizations: &[&dyn MirPass<'tcx>],
+) {
+    let mir_opt_level = tcx.sess.mir_opt_level();
+
+    // Even if we don't do optimizations, we still have to lower generators for codegen.
+    let no_optimizations_with_generators: &[&dyn MirPass<'tcx>] = &[&generator::StateTransform];
+
     // Optimizations to run even if mir optimizations have been disabled.
     let no_optimizations: &[&dyn MirPass<'tcx>] = &[
         // FIXME(#70073): This pass is responsible for both optimization as well as some lints.
@@ -529,6 +562,32 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         &dump_mir::Marker("PreCodegen"),
     ];
 
+    // A pipeline given with `-Zmir-passes` replaces all of the optimizations above, only
+    // generator lowering and the cleanup for codegen are kept. The passes that run before
+    // generator lowering by default keep doing so, in the given order.
+    let mut custom_generator_passes: Vec<&dyn MirPass<'tcx>> = Vec::new();
+    let mut custom_passes: Vec<&dyn MirPass<'tcx>> = Vec::new();
+    let (generator_passes, main_passes) = match &tcx.sess.opts.debugging_opts.mir_passes {
+        Some(names) => {
+            let known_generator_passes =
+                optimizations_with_generators.iter().filter(|pass| is_custom_pass(**pass));
+            for name in names {
+                let named = |pass: &&&dyn MirPass<'tcx>| pass.name() == name.as_str();
+                if let Some(pass) = known_generator_passes.clone().find(named) {
+                    custom_generator_passes.push(*pass);
+                } else if let Some(pass) = optimizations.iter().find(named) {
+                    custom_passes.push(*pass);
+                } else {
+                    bug!("unknown MIR pass `{}` passed `check_mir_passes`", name);
+                }
+            }
+            custom_generator_passes.extend(no_optimizations_with_generators);
+            (&custom_generator_passes[..], &custom_passes[..])
+        }
+        None if mir_opt_level > 0 => (optimizations_with_generators, optimizations),
+        None => (no_optimizations_with_generators, no_optimizations),
+    };
+
     // End of pass declarations, now actually run the passes.
     // Generator Lowering
     #[rustfmt::skip]
@@ -537,11 +596,7 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         body,
         MirPhase::GeneratorLowering,
         &[
-            if mir_opt_level > 0 {
-                optimizations_with_generators
-            } else {
-                no_optimizations_with_generators
-            }
+            generator_passes,
         ],
     );
 
@@ -552,12 +607,45 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         body,
         MirPhase::Optimization,
         &[
-            if mir_opt_level > 0 { optimizations } else { no_optimizations },
+            main_passes,
             pre_codegen_cleanup,
         ],
     );
 }
 
+/// Generator lowering always runs, so it can't be chosen with `-Zmir-passes`.
+fn is_custom_pass(pass: &dyn MirPass<'_>) -> bool {
+    pass.name() != "StateTransform"
+}
+
+/// Reports the first pass given with `-Zmir-passes` that doesn't exist. This is checked once,
+/// before anything is optimized, so it is reported even for crates without MIR to optimize.
+pub fn check_mir_passes(sess: &Session) {
+    let names = match &sess.opts.debugging_opts.mir_passes {
+        Some(names) => names,
+        None => return,
+    };
+    with_optimizations(|optimizations_with_generators, optimizations| {
+        let known_passes: Vec<_> = optimizations_with_generators
+            .iter()
+            .chain(optimizations)
+            .filter(|pass| is_custom_pass(**pass))
+            .collect();
+        for name in names {
+            if !known_passes.iter().any(|pass| pass.name() == name.as_str()) {
+                let mut known_names: Vec<_> = known_passes.iter().map(|pass| pass.name()).collect();
+                known_names.sort();
+                known_names.dedup();
+                sess.fatal(&format!(
+                    "unknown MIR pass `{}` in `-Zmir-passes`, known passes are: {}",
+                    name,
+                    known_names.join(", ")
+                ));
+            }
+        }
+    })
+}
+
 /// Optimize the MIR and prepare it for codegen.
 fn optimized_mir<'tcx>(tcx: TyCtxt<'tcx>, did: DefId) -> &'tcx Body<'tcx> {
     let did = did.expect_local();
@@ -0,0 +1,209 @@
+//! Scalar replacement of aggregates: splits locals of struct or tuple type whose fields are only
+//! ever accessed one at a time into a separate local for each field.
+//! ```rust,ignore (example)
+//! StorageLive(_2);
+//! (_2.0: u32) = const 1_u32;
+//! (_2.1: bool) = _1;
+//! _0 = (_2.0: u32);
+//! StorageDead(_2);
+//! ```
+//! becomes
+//! ```rust,ignore (example)
+//! StorageLive(_3);
+//! StorageLive(_4);
+//! _3 = const 1_u32;
+//! _4 = _1;
+//! _0 = _3;
+//! StorageDead(_3);
+//! StorageDead(_4);
+//! ```
+//! Other passes, like const propagation and destination propagation, only look at whole locals,
+//! so this lets them see through aggregates. The local that has been split up is left unused.
+//!
+//! A local is not split if it is used as a whole, like being copied, moved, borrowed, dropped or
+//! passed to a function, or if the address of one of its fields is taken.
+
+use crate::transform::MirPass;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{
+    MutVisitor, MutatingUseContext, NonMutatingUseContext, NonUseContext, PlaceContext, Visitor,
+};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::config::DebugInfo;
+
+pub struct ScalarReplacementOfAggregates;
+
+impl<'tcx> MirPass<'tcx> for ScalarReplacementOfAggregates {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if tcx.sess.mir_opt_level() < 2 {
+            return;
+        }
+        debug!("Running ScalarReplacementOfAggregates on {:?}", body.source);
+
+        // Without debuginfo, a user variable that is split up does not need to be described as a
+        // whole anymore.
+        let keep_debuginfo = tcx.sess.opts.debuginfo != DebugInfo::None;
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let mut finder = CandidateFinder {
+            candidates: body
+                .local_decls
+                .indices()
+                .map(|local| is_candidate(tcx, param_env, body, local).then(FxIndexMap::default))
+                .collect(),
+            keep_debuginfo,
+        };
+        finder.visit_body(body);
+
+        let mut replacements: IndexVec<Local, Option<FxIndexMap<Field, Local>>> =
+            IndexVec::from_elem(None, &body.local_decls);
+        for (local, fields) in finder.candidates.into_iter_enumerated() {
+            let fields = match fields {
+                Some(fields) if !fields.is_empty() => fields,
+                _ => continue,
+            };
+            if !tcx.consider_optimizing(|| {
+                format!("ScalarReplacementOfAggregates {:?} in {:?}", local, body.source)
+            }) {
+                continue;
+            }
+            debug!("splitting {:?} into fields {:?}", local, fields);
+            let source_info = body.local_decls[local].source_info;
+            replacements[local] = Some(
+                fields
+                    .into_iter()
+                    .map(|(field, ty)| {
+                        let decl = LocalDecl::with_source_info(ty, source_info);
+                        (field, body.local_decls.push(decl))
+                    })
+                    .collect(),
+            );
+        }
+        if replacements.iter().all(Option::is_none) {
+            return;
+        }
+
+        if !keep_debuginfo {
+            body.var_debug_info.retain(|info| match info.value {
+                VarDebugInfoContents::Place(place) => {
+                    !place.projection.is_empty() || replacements[place.local].is_none()
+                }
+                VarDebugInfoContents::Const(_) => true,
+            });
+        }
+
+        ReplacementVisitor { tcx, replacements: &replacements }.visit_body(body);
+
+        for data in body.basic_blocks_mut() {
+            data.expand_statements(|statement| {
+                let (local, live) = match statement.kind {
+                    StatementKind::StorageLive(local) => (local, true),
+                    StatementKind::StorageDead(local) => (local, false),
+                    _ => return None,
+                };
+                let fields = replacements[local].as_ref()?;
+                let source_info = statement.source_info;
+                Some(
+                    fields
+                        .values()
+                        .map(|&local| {
+                            let kind = if live {
+                                StatementKind::StorageLive(local)
+                            } else {
+                                StatementKind::StorageDead(local)
+                            };
+                            Statement { source_info, kind }
+                        })
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                )
+            });
+        }
+    }
+}
+
+/// Whether `local` has a type that can be split up at all.
+fn is_candidate(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    body: &Body<'tcx>,
+    local: Local,
+) -> bool {
+    // The return place and the arguments are laid out by the calling convention.
+    if local == RETURN_PLACE || local.index() <= body.arg_count {
+        return false;
+    }
+    let decl = &body.local_decls[local];
+    let splittable = match decl.ty.kind() {
+        ty::Tuple(_) => true,
+        // SIMD vectors are operated on as a whole by intrinsics.
+        ty::Adt(def, _) => def.is_struct() && !def.repr.simd(),
+        _ => false,
+    };
+    splittable && decl.ty.is_sized(tcx.at(decl.source_info.span), param_env)
+}
+
+struct CandidateFinder<'tcx> {
+    /// The fields accessed so far of every local that can still be split up, with their types.
+    candidates: IndexVec<Local, Option<FxIndexMap<Field, Ty<'tcx>>>>,
+    keep_debuginfo: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for CandidateFinder<'tcx> {
+    fn visit_local(&mut self, &local: &Local, context: PlaceContext, _location: Location) {
+        // Storage markers are the only uses of a bare local that do not go through a place.
+        if !context.is_storage_marker() {
+            self.candidates[local] = None;
+        }
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        let fields = match &mut self.candidates[place.local] {
+            Some(fields) => fields,
+            None => return self.super_place(place, context, location),
+        };
+        let takes_address = matches!(
+            context,
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::AddressOf)
+                | PlaceContext::MutatingUse(MutatingUseContext::AddressOf)
+        );
+        let is_debuginfo = context == PlaceContext::NonUse(NonUseContext::VarDebugInfo);
+        match place.projection.first() {
+            // A raw pointer to one field could be used to reach the others.
+            Some(&ProjectionElem::Field(field, ty)) if !takes_address => {
+                fields.insert(field, ty);
+            }
+            None if is_debuginfo && !self.keep_debuginfo => {}
+            _ => self.candidates[place.local] = None,
+        }
+        // The local itself has been handled, only visit the locals used for indexing.
+        self.visit_projection(place.as_ref(), context, location);
+    }
+}
+
+struct ReplacementVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    replacements: &'a IndexVec<Local, Option<FxIndexMap<Field, Local>>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for ReplacementVisitor<'_, 'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_place(&mut self, place: &mut Place<'tcx>, context: PlaceContext, location: Location) {
+        if let Some(fields) = &self.replacements[place.local] {
+            match place.projection[..] {
+                [ProjectionElem::Field(field, _), ref rest @ ..] => {
+                    *place = Place {
+                        local: fields[&field],
+                        projection: self.tcx.intern_place_elems(rest),
+                    };
+                }
+                _ => bug!("{:?} is used as a whole after being split up", place),
+            }
+        }
+        self.super_place(place, context, location);
+    }
+}
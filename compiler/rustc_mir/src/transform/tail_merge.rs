@@ -0,0 +1,204 @@
+//! This pass merges the common tails of blocks that end with the same terminator. Match lowering
+//! often produces arms that only differ in how they compute their result before the same call:
+//! ```rust,ignore (example)
+//! bb1: {
+//!     _3 = const 1_u32;
+//!     StorageLive(_4);
+//!     _0 = consume(move _3) -> bb4;
+//! }
+//!
+//! bb2: {
+//!     _3 = const 2_u32;
+//!     StorageLive(_4);
+//!     _0 = consume(move _3) -> bb4;
+//! }
+//! ```
+//! The shared statements and the terminator are moved into a new block:
+//! ```rust,ignore (example)
+//! bb1: {
+//!     _3 = const 1_u32;
+//!     goto -> bb5;
+//! }
+//!
+//! bb2: {
+//!     _3 = const 2_u32;
+//!     goto -> bb5;
+//! }
+//!
+//! bb5: {
+//!     StorageLive(_4);
+//!     _0 = consume(move _3) -> bb4;
+//! }
+//! ```
+//! Blocks that end up only jumping to the merged block are removed by `SimplifyCfg`, which has to
+//! run after this pass.
+
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::mem;
+
+use crate::transform::MirPass;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+use super::deduplicate_blocks::{operand_eq, statement_eq};
+
+pub struct TailMerge;
+
+/// Groups with more blocks than this are skipped, finding the tails to merge is quadratic in the
+/// number of blocks.
+const MAX_GROUP_SIZE: usize = 256;
+
+impl<'tcx> MirPass<'tcx> for TailMerge {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if tcx.sess.mir_opt_level() < 3 {
+            return;
+        }
+        debug!("Running TailMerge on {:?}", body.source);
+
+        for group in find_groups(body) {
+            merge_tails(tcx, body, group);
+        }
+    }
+}
+
+/// Returns the groups of blocks that end with the same terminator.
+fn find_groups(body: &Body<'_>) -> Vec<Vec<BasicBlock>> {
+    let mut groups: FxIndexMap<_, Vec<BasicBlock>> = FxIndexMap::default();
+    for (bb, data) in body.basic_blocks().iter_enumerated() {
+        let terminator = data.terminator();
+        let mergeable = match terminator.kind {
+            // A block that only jumps somewhere has nothing worth merging.
+            TerminatorKind::Goto { .. } => !data.statements.is_empty(),
+            // Returning from several places is cheaper than jumping to a common return.
+            TerminatorKind::Return
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Unreachable
+            | TerminatorKind::GeneratorDrop => false,
+            _ => true,
+        };
+        if mergeable {
+            let key = TerminatorHashable { kind: &terminator.kind, is_cleanup: data.is_cleanup };
+            groups.entry(key).or_default().push(bb);
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(_, group)| group)
+        .filter(|group| group.len() > 1 && group.len() <= MAX_GROUP_SIZE)
+        .collect()
+}
+
+/// Merges the tails of the blocks in `group`, which all end with the same terminator. Each block
+/// is merged at most once, with the other blocks that share the longest tail with the first
+/// remaining one.
+fn merge_tails<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, mut group: Vec<BasicBlock>) {
+    while let Some((&first, rest)) = group.split_first() {
+        let blocks = body.basic_blocks();
+        let tail_len = |bb: BasicBlock| common_tail_len(&blocks[first], &blocks[bb]);
+        let is_goto = matches!(blocks[first].terminator().kind, TerminatorKind::Goto { .. });
+
+        // Merging only the terminator saves nothing if it is a `goto`.
+        let len = rest.iter().map(|&bb| tail_len(bb)).filter(|&len| len > 0 || !is_goto).max();
+        let len = match len {
+            Some(len) => len,
+            None => {
+                group.remove(0);
+                continue;
+            }
+        };
+        let (merged, remaining): (Vec<_>, Vec<_>) =
+            group.iter().copied().partition(|&bb| bb == first || tail_len(bb) >= len);
+
+        if !tcx.consider_optimizing(|| format!("TailMerge {:?}", merged)) {
+            return;
+        }
+        debug!("merging the last {} statements of {:?}", len, merged);
+
+        let first_data = &body.basic_blocks()[first];
+        let tail = BasicBlockData {
+            statements: first_data.statements[first_data.statements.len() - len..].to_vec(),
+            terminator: first_data.terminator.clone(),
+            is_cleanup: first_data.is_cleanup,
+        };
+        let tail = body.basic_blocks_mut().push(tail);
+        for &bb in &merged {
+            let data = &mut body.basic_blocks_mut()[bb];
+            data.statements.truncate(data.statements.len() - len);
+            data.terminator_mut().kind = TerminatorKind::Goto { target: tail };
+        }
+
+        group = remaining;
+    }
+}
+
+/// Returns the number of statements at the end of `a` and `b` that are the same.
+fn common_tail_len(a: &BasicBlockData<'_>, b: &BasicBlockData<'_>) -> usize {
+    iter::zip(a.statements.iter().rev(), b.statements.iter().rev())
+        .take_while(|(a, b)| statement_eq(&a.kind, &b.kind))
+        .count()
+}
+
+/// Compares terminators without looking at the spans of constants, which differ between the arms
+/// of a match even if they call the same function.
+struct TerminatorHashable<'a, 'tcx> {
+    kind: &'a TerminatorKind<'tcx>,
+    is_cleanup: bool,
+}
+
+impl Hash for TerminatorHashable<'_, '_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self.kind).hash(state);
+        for successor in self.kind.successors() {
+            successor.hash(state);
+        }
+        self.is_cleanup.hash(state);
+    }
+}
+
+impl Eq for TerminatorHashable<'_, '_> {}
+
+impl PartialEq for TerminatorHashable<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_cleanup == other.is_cleanup && terminator_eq(self.kind, other.kind)
+    }
+}
+
+fn terminator_eq<'tcx>(lhs: &TerminatorKind<'tcx>, rhs: &TerminatorKind<'tcx>) -> bool {
+    let operands_eq = |lhs: &[Operand<'tcx>], rhs: &[Operand<'tcx>]| {
+        lhs.len() == rhs.len() && iter::zip(lhs, rhs).all(|(lhs, rhs)| operand_eq(lhs, rhs))
+    };
+    match (lhs, rhs) {
+        (
+            TerminatorKind::Call {
+                func,
+                args,
+                destination,
+                cleanup,
+                from_hir_call,
+                fn_span: _,
+            },
+            TerminatorKind::Call {
+                func: func2,
+                args: args2,
+                destination: destination2,
+                cleanup: cleanup2,
+                from_hir_call: from_hir_call2,
+                fn_span: _,
+            },
+        ) => {
+            operand_eq(func, func2)
+                && operands_eq(args, args2)
+                && destination == destination2
+                && cleanup == cleanup2
+                && from_hir_call == from_hir_call2
+        }
+        (
+            TerminatorKind::TailCall { func, args, fn_span: _ },
+            TerminatorKind::TailCall { func: func2, args: args2, fn_span: _ },
+        ) => operand_eq(func, func2) && operands_eq(args, args2),
+        (x, y) => x == y,
+    }
+}
@@ -459,6 +459,22 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     );
                 }
             }
+            TerminatorKind::TailCall { func, .. } => {
+                let func_ty = func.ty(&self.body.local_decls, self.tcx);
+                match func_ty.kind() {
+                    ty::FnPtr(..) | ty::FnDef(..) => {}
+                    _ => self.fail(
+                        location,
+                        format!(
+                            "encountered non-callable type {} in `TailCall` terminator",
+                            func_ty
+                        ),
+                    ),
+                }
+                if self.body.generator_kind().is_some() {
+                    self.fail(location, "`TailCall` cannot be in generators");
+                }
+            }
             TerminatorKind::Assert { cond, target, cleanup, .. } => {
                 let cond_ty = cond.ty(&self.body.local_decls, self.tcx);
                 if cond_ty != self.tcx.types.bool {
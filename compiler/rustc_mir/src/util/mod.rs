@@ -11,6 +11,7 @@
 pub(crate) mod generic_graphviz;
 mod graphviz;
 pub(crate) mod pretty;
+pub(crate) mod scalar_constants;
 pub(crate) mod spanview;
 
 pub use self::aggregate::expand_aggregate;
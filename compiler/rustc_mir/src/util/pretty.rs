@@ -12,12 +12,12 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def_id::DefId;
 use rustc_index::vec::Idx;
-use rustc_middle::mir::interpret::{
-    read_target_uint, AllocId, Allocation, ConstValue, GlobalAlloc, Pointer,
-};
+use rustc_middle::mir::interpret::{AllocId, Allocation, ConstValue, GlobalAlloc, Pointer};
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::*;
 use rustc_middle::ty::{self, TyCtxt, TyS, TypeFoldable, TypeVisitor};
+use rustc_serialize::json::Json;
+use rustc_session::config::MirDumpFormat;
 use rustc_target::abi::Size;
 use std::ops::ControlFlow;
 
@@ -121,30 +121,44 @@ fn dump_matched_mir_node<'tcx, F>(
 ) where
     F: FnMut(PassWhere, &mut dyn Write) -> io::Result<()>,
 {
-    let _: io::Result<()> = try {
-        let mut file =
-            create_dump_file(tcx, "mir", pass_num, pass_name, disambiguator, body.source)?;
-        let def_path = ty::print::with_forced_impl_filename_line(|| {
-            // see notes on #41697 above
-            tcx.def_path_str(body.source.def_id())
-        });
-        write!(file, "// MIR for `{}", def_path)?;
-        match body.source.promoted {
-            None => write!(file, "`")?,
-            Some(promoted) => write!(file, "::{:?}`", promoted)?,
+    let format = tcx.sess.opts.debugging_opts.dump_mir_format;
+    match format {
+        MirDumpFormat::Text => {
+            let _: io::Result<()> = try {
+                let mut file =
+                    create_dump_file(tcx, "mir", pass_num, pass_name, disambiguator, body.source)?;
+                let def_path = ty::print::with_forced_impl_filename_line(|| {
+                    // see notes on #41697 above
+                    tcx.def_path_str(body.source.def_id())
+                });
+                write!(file, "// MIR for `{}", def_path)?;
+                match body.source.promoted {
+                    None => write!(file, "`")?,
+                    Some(promoted) => write!(file, "::{:?}`", promoted)?,
+                }
+                writeln!(file, " {} {}", disambiguator, pass_name)?;
+                if let Some(ref layout) = body.generator_layout() {
+                    writeln!(file, "/* generator_layout = {:#?} */", layout)?;
+                }
+                writeln!(file)?;
+                extra_data(PassWhere::BeforeCFG, &mut file)?;
+                write_user_type_annotations(tcx, body, &mut file)?;
+                write_mir_fn(tcx, body, &mut extra_data, &mut file)?;
+                extra_data(PassWhere::AfterCFG, &mut file)?;
+            };
         }
-        writeln!(file, " {} {}", disambiguator, pass_name)?;
-        if let Some(ref layout) = body.generator_layout() {
-            writeln!(file, "/* generator_layout = {:#?} */", layout)?;
+        MirDumpFormat::Json => {
+            let _: io::Result<()> = try {
+                let mut file =
+                    create_dump_file(tcx, "json", pass_num, pass_name, disambiguator, body.source)?;
+                write_mir_fn_json(tcx, body, pass_name, disambiguator, &mut file)?;
+            };
         }
-        writeln!(file)?;
-        extra_data(PassWhere::BeforeCFG, &mut file)?;
-        write_user_type_annotations(tcx, body, &mut file)?;
-        write_mir_fn(tcx, body, &mut extra_data, &mut file)?;
-        extra_data(PassWhere::AfterCFG, &mut file)?;
-    };
+        // Written below, together with the `.dot` files of `-Z dump-mir-graphviz`.
+        MirDumpFormat::Dot => {}
+    }
 
-    if tcx.sess.opts.debugging_opts.dump_mir_graphviz {
+    if format == MirDumpFormat::Dot || tcx.sess.opts.debugging_opts.dump_mir_graphviz {
         let _: io::Result<()> = try {
             let mut file =
                 create_dump_file(tcx, "dot", pass_num, pass_name, disambiguator, body.source)?;
@@ -265,6 +279,131 @@ pub(crate) fn create_dump_file(
     )
 }
 
+/// The version of the JSON written by `-Z dump-mir-format=json`. It has to be increased whenever
+/// a field is removed or changes its meaning, adding fields keeps the version.
+pub const MIR_JSON_VERSION: u64 = 1;
+
+/// Writes a JSON description of `body` for tools that inspect MIR dumps. Statements and
+/// terminators are included both as the text of `.mir` files and with their kind, spans and
+/// types are written as strings.
+pub fn write_mir_fn_json<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    pass_name: &str,
+    disambiguator: &dyn Display,
+    w: &mut dyn Write,
+) -> io::Result<()> {
+    let span = |span| Json::String(tcx.sess.source_map().span_to_embeddable_string(span));
+    let def_path = ty::print::with_forced_impl_filename_line(|| {
+        // see notes on #41697 above
+        tcx.def_path_str(body.source.def_id())
+    });
+
+    let locals = body
+        .local_decls
+        .iter_enumerated()
+        .map(|(local, decl)| {
+            json_object(vec![
+                ("local", Json::String(format!("{:?}", local))),
+                ("ty", Json::String(decl.ty.to_string())),
+                ("mutable", Json::Boolean(decl.mutability == Mutability::Mut)),
+                ("span", span(decl.source_info.span)),
+            ])
+        })
+        .collect();
+
+    let blocks = body
+        .basic_blocks()
+        .iter_enumerated()
+        .map(|(bb, data)| {
+            let statements = data
+                .statements
+                .iter()
+                .map(|statement| {
+                    json_object(vec![
+                        ("kind", Json::String(statement_kind_name(&statement.kind).to_string())),
+                        ("text", Json::String(format!("{:?}", statement))),
+                        ("span", span(statement.source_info.span)),
+                    ])
+                })
+                .collect();
+            let terminator = data.terminator();
+            let successors =
+                terminator.successors().map(|succ| Json::String(format!("{:?}", succ))).collect();
+            json_object(vec![
+                ("block", Json::String(format!("{:?}", bb))),
+                ("is_cleanup", Json::Boolean(data.is_cleanup)),
+                ("statements", Json::Array(statements)),
+                (
+                    "terminator",
+                    json_object(vec![
+                        ("kind", Json::String(terminator_kind_name(&terminator.kind).to_string())),
+                        ("text", Json::String(format!("{:?}", terminator.kind))),
+                        ("successors", Json::Array(successors)),
+                        ("span", span(terminator.source_info.span)),
+                    ]),
+                ),
+            ])
+        })
+        .collect();
+
+    let json = json_object(vec![
+        ("version", Json::U64(MIR_JSON_VERSION)),
+        ("def_path", Json::String(def_path)),
+        ("promoted", body.source.promoted.map_or(Json::Null, |p| Json::String(format!("{:?}", p)))),
+        ("pass", Json::String(pass_name.to_string())),
+        ("disambiguator", Json::String(disambiguator.to_string())),
+        ("phase", Json::String(format!("{:?}", body.phase))),
+        ("arg_count", Json::U64(body.arg_count as u64)),
+        ("return_ty", Json::String(body.return_ty().to_string())),
+        ("span", span(body.span)),
+        ("locals", Json::Array(locals)),
+        ("blocks", Json::Array(blocks)),
+    ]);
+    writeln!(w, "{}", json.pretty())
+}
+
+fn json_object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+}
+
+fn statement_kind_name(kind: &StatementKind<'_>) -> &'static str {
+    match kind {
+        StatementKind::Assign(..) => "Assign",
+        StatementKind::FakeRead(..) => "FakeRead",
+        StatementKind::SetDiscriminant { .. } => "SetDiscriminant",
+        StatementKind::StorageLive(..) => "StorageLive",
+        StatementKind::StorageDead(..) => "StorageDead",
+        StatementKind::LlvmInlineAsm(..) => "LlvmInlineAsm",
+        StatementKind::Retag(..) => "Retag",
+        StatementKind::AscribeUserType(..) => "AscribeUserType",
+        StatementKind::Coverage(..) => "Coverage",
+        StatementKind::CopyNonOverlapping(..) => "CopyNonOverlapping",
+        StatementKind::Nop => "Nop",
+    }
+}
+
+fn terminator_kind_name(kind: &TerminatorKind<'_>) -> &'static str {
+    match kind {
+        TerminatorKind::Goto { .. } => "Goto",
+        TerminatorKind::SwitchInt { .. } => "SwitchInt",
+        TerminatorKind::Resume => "Resume",
+        TerminatorKind::Abort => "Abort",
+        TerminatorKind::Return => "Return",
+        TerminatorKind::Unreachable => "Unreachable",
+        TerminatorKind::Drop { .. } => "Drop",
+        TerminatorKind::DropAndReplace { .. } => "DropAndReplace",
+        TerminatorKind::Call { .. } => "Call",
+        TerminatorKind::TailCall { .. } => "TailCall",
+        TerminatorKind::Assert { .. } => "Assert",
+        TerminatorKind::Yield { .. } => "Yield",
+        TerminatorKind::GeneratorDrop => "GeneratorDrop",
+        TerminatorKind::FalseEdge { .. } => "FalseEdge",
+        TerminatorKind::FalseUnwind { .. } => "FalseUnwind",
+        TerminatorKind::InlineAsm { .. } => "InlineAsm",
+    }
+}
+
 /// Write out a human-readable textual representation for the given MIR.
 pub fn write_mir_pretty<'tcx>(
     tcx: TyCtxt<'tcx>,
@@ -857,11 +996,7 @@ fn write_allocation_bytes<Tag: Copy + Debug, Extra>(
         }
         if let Some(&(tag, target_id)) = alloc.relocations().get(&i) {
             // Memory with a relocation must be defined
-            let j = i.bytes_usize();
-            let offset = alloc
-                .inspect_with_uninit_and_ptr_outside_interpreter(j..j + ptr_size.bytes_usize());
-            let offset = read_target_uint(tcx.data_layout.endian, offset).unwrap();
-            let offset = Size::from_bytes(offset);
+            let offset = alloc.read_relocation_offset(&tcx, i);
             let relocation_width = |bytes| bytes * 3;
             let ptr = Pointer::new_with_tag(target_id, offset, tag);
             let mut target = format!("{:?}", ptr);
@@ -0,0 +1,70 @@
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::interpret::Scalar;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{
+    AssertMessage, Constant, Location, ScalarConstant, ScalarConstantUsage, Terminator,
+    TerminatorKind,
+};
+use rustc_middle::ty::{ParamEnv, ScalarInt, TyCtxt};
+
+/// Collects the scalar constants of the optimized MIR of `def_id`, in the order they appear.
+/// Constants and statics have no optimized MIR, so there is nothing to collect for them.
+crate fn scalar_constants<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> &'tcx [ScalarConstant<'tcx>] {
+    let is_fn_like = matches!(
+        tcx.def_kind(def_id),
+        DefKind::Fn | DefKind::AssocFn | DefKind::Closure | DefKind::Generator | DefKind::Ctor(..)
+    );
+    if !is_fn_like || !tcx.is_mir_available(def_id) {
+        return &[];
+    }
+    let body = tcx.optimized_mir(def_id);
+    let mut collector = ScalarConstantCollector {
+        tcx,
+        param_env: tcx.param_env(def_id),
+        usage: ScalarConstantUsage::Operand,
+        constants: vec![],
+    };
+    collector.visit_body(body);
+    tcx.arena.alloc_from_iter(collector.constants)
+}
+
+struct ScalarConstantCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    /// How the operands currently being visited are used.
+    usage: ScalarConstantUsage,
+    constants: Vec<ScalarConstant<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for ScalarConstantCollector<'tcx> {
+    fn visit_constant(&mut self, constant: &Constant<'tcx>, _location: Location) {
+        if let Some(Scalar::Int(value)) = constant.literal.try_to_scalar() {
+            // Function items and other zero-sized values carry no information.
+            if value.size().bytes() != 0 {
+                let ty = constant.literal.ty();
+                self.constants.push(ScalarConstant { value, ty, usage: self.usage });
+            }
+        }
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        if let TerminatorKind::SwitchInt { switch_ty, ref targets, .. } = terminator.kind {
+            if let Ok(layout) = self.tcx.layout_of(self.param_env.and(switch_ty)) {
+                for (bits, _) in targets.iter() {
+                    if let Some(value) = ScalarInt::try_from_uint(bits, layout.size) {
+                        let usage = ScalarConstantUsage::SwitchTarget;
+                        self.constants.push(ScalarConstant { value, ty: switch_ty, usage });
+                    }
+                }
+            }
+        }
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_assert_message(&mut self, msg: &AssertMessage<'tcx>, location: Location) {
+        let usage = std::mem::replace(&mut self.usage, ScalarConstantUsage::AssertBound);
+        self.super_assert_message(msg, location);
+        self.usage = usage;
+    }
+}
@@ -567,6 +567,7 @@ fn expr_as_place(
             | ExprKind::Break { .. }
             | ExprKind::Continue { .. }
             | ExprKind::Return { .. }
+            | ExprKind::Become { .. }
             | ExprKind::Literal { .. }
             | ExprKind::ConstBlock { .. }
             | ExprKind::StaticRef { .. }
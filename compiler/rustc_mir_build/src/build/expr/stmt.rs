@@ -1,8 +1,11 @@
 use crate::build::scope::BreakableTarget;
 use crate::build::{BlockAnd, BlockAndExtension, BlockFrame, Builder};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::middle::region;
 use rustc_middle::mir::*;
 use rustc_middle::thir::*;
+use rustc_middle::ty;
+use rustc_target::spec::abi::Abi;
 
 impl<'a, 'tcx> Builder<'a, 'tcx> {
     /// Builds a block of MIR statements to evaluate the THIR `expr`.
@@ -101,6 +104,19 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                 BreakableTarget::Return,
                 source_info,
             ),
+            ExprKind::Become { value } => {
+                if this.check_tail_call(value) {
+                    this.tail_call_expr(block, value)
+                } else {
+                    // The error has been reported, lower it like a `return` to keep going.
+                    this.break_scope(
+                        block,
+                        Some(&this.thir[value]),
+                        BreakableTarget::Return,
+                        source_info,
+                    )
+                }
+            }
             ExprKind::LlvmInlineAsm { asm, ref outputs, ref inputs } => {
                 debug!("stmt_expr LlvmInlineAsm block_context.push(SubExpr) : {:?}", expr);
                 this.block_context.push(BlockFrame::SubExpr);
@@ -180,4 +196,133 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
             }
         }
     }
+
+    /// Builds the tail call `expr` of a `become`, which has been checked by `check_tail_call`.
+    fn tail_call_expr(&mut self, mut block: BasicBlock, expr: ExprId) -> BlockAnd<()> {
+        let expr = &self.thir[expr];
+        let source_info = self.source_info(expr.span);
+        match expr.kind {
+            ExprKind::Scope { region_scope, lint_level, value } => {
+                self.in_scope((region_scope, source_info), lint_level, |this| {
+                    this.tail_call_expr(block, value)
+                })
+            }
+            ExprKind::Call { fun, ref args, fn_span, .. } => {
+                let fun = unpack!(block = self.as_local_operand(block, &self.thir[fun]));
+                let args: Vec<_> = args
+                    .into_iter()
+                    .copied()
+                    .map(|arg| unpack!(block = self.as_local_call_operand(block, &self.thir[arg])))
+                    .collect();
+
+                self.record_operands_moved(&args);
+
+                unpack!(block = self.break_for_tail_call(block, &args, source_info));
+
+                self.cfg.terminate(
+                    block,
+                    source_info,
+                    TerminatorKind::TailCall { func: fun, args, fn_span },
+                );
+
+                self.cfg.start_new_block().unit()
+            }
+            _ => span_bug!(expr.span, "unexpected tail call expression {:?}", expr),
+        }
+    }
+
+    /// Checks that the value of a `become` is a call that can be made as a tail call from the
+    /// current function, reporting an error otherwise.
+    fn check_tail_call(&self, expr: ExprId) -> bool {
+        let tcx = self.tcx;
+        let mut expr = &self.thir[expr];
+        while let ExprKind::Scope { value, .. } = expr.kind {
+            expr = &self.thir[value];
+        }
+        let fun = match expr.kind {
+            ExprKind::Call { fun, from_hir_call: true, .. } => &self.thir[fun],
+            _ => {
+                tcx.sess.span_err(expr.span, "`become` requires a function call");
+                return false;
+            }
+        };
+
+        if tcx.is_closure(self.def_id) {
+            tcx.sess.span_err(expr.span, "`become` is not allowed in closures and generators");
+            return false;
+        }
+        if tcx.codegen_fn_attrs(self.def_id).flags.contains(CodegenFnAttrFlags::TRACK_CALLER) {
+            tcx.sess.span_err(
+                expr.span,
+                "a function marked with `#[track_caller]` cannot perform a tail call",
+            );
+            return false;
+        }
+        if let ty::FnDef(def_id, _) = *fun.ty.kind() {
+            if matches!(tcx.fn_sig(def_id).abi(), Abi::RustIntrinsic | Abi::PlatformIntrinsic) {
+                tcx.sess.span_err(expr.span, "tail calling intrinsics is not allowed");
+                return false;
+            }
+            if tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::TRACK_CALLER) {
+                tcx.sess.span_err(
+                    expr.span,
+                    "a function marked with `#[track_caller]` cannot be tail called",
+                );
+                return false;
+            }
+        }
+
+        // The callee reuses the stack frame of the caller, so both have to be called the same way.
+        let erase = |sig| {
+            let sig = tcx.erase_late_bound_regions(sig);
+            tcx.normalize_erasing_regions(self.param_env, sig)
+        };
+        let caller_sig = erase(tcx.fn_sig(self.def_id));
+        let callee_sig = erase(fun.ty.fn_sig(tcx));
+        if caller_sig.inputs_and_output != callee_sig.inputs_and_output
+            || caller_sig.abi != callee_sig.abi
+            || caller_sig.c_variadic
+            || callee_sig.c_variadic
+        {
+            tcx.sess
+                .struct_span_err(expr.span, "mismatched signatures")
+                .note("`become` requires caller and callee to have matching signatures")
+                .note(&format!("caller signature: `{}`", caller_sig))
+                .note(&format!("callee signature: `{}`", callee_sig))
+                .emit();
+            return false;
+        }
+
+        // Matching signatures are not enough for the callee to take over the arguments and the
+        // return place of the caller. These checks do not depend on the generic arguments of the
+        // current function, so a `become` that passes them works for every instantiation.
+        if caller_sig.abi == Abi::RustCall {
+            tcx.sess.span_err(
+                expr.span,
+                "functions with the \"rust-call\" ABI cannot perform tail calls",
+            );
+            return false;
+        }
+        let span = tcx.at(expr.span);
+        if caller_sig.inputs().iter().any(|ty| !ty.is_sized(span, self.param_env)) {
+            tcx.sess.span_err(expr.span, "unsized arguments cannot be passed in tail calls");
+            return false;
+        }
+        if let ty::FnDef(def_id, substs) = *fun.ty.kind() {
+            // A method of a trait object receives a thin `self` pointer where the caller has a
+            // wide one. A possibly unsized `Self` may turn out to be a trait object.
+            let self_ty = substs.type_at(0);
+            if tcx.trait_of_item(def_id).is_some() && !self_ty.is_sized(span, self.param_env) {
+                tcx.sess
+                    .struct_span_err(
+                        expr.span,
+                        "methods of possibly unsized types cannot be tail called",
+                    )
+                    .note("the call may go through a trait object, which is passed differently")
+                    .emit();
+                return false;
+            }
+        }
+        true
+    }
 }
@@ -203,13 +203,13 @@ fn simplify_match_pair<'pat>(
                     }
                     ty::Int(ity) => {
                         let size = Integer::from_int_ty(&tcx, ity).size();
-                        let max = size.truncate(u128::MAX);
-                        let bias = 1u128 << (size.bits() - 1);
+                        let max = size.unsigned_int_max();
+                        let bias = size.truncate(size.signed_int_min() as u128);
                         (Some((0, max, size)), bias)
                     }
                     ty::Uint(uty) => {
                         let size = Integer::from_uint_ty(&tcx, uty).size();
-                        let max = size.truncate(u128::MAX);
+                        let max = size.unsigned_int_max();
                         (Some((0, max, size)), 0)
                     }
                     _ => (None, 0),
@@ -651,6 +651,92 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         self.cfg.start_new_block().unit()
     }
 
+    /// Sets up the drops for a tail call from `block` with the arguments `args`.
+    ///
+    /// Everything in scope is dropped before the call, except the innermost scope, which only
+    /// contains the temporaries for the arguments. Those are moved into the callee, so they only
+    /// have to be dropped if one of the other drops panics.
+    crate fn break_for_tail_call(
+        &mut self,
+        mut block: BasicBlock,
+        args: &[Operand<'tcx>],
+        source_info: SourceInfo,
+    ) -> BlockAnd<()> {
+        let arg_drops: Vec<_> = args
+            .iter()
+            .rev()
+            .filter_map(|arg| match arg {
+                Operand::Copy(_) => bug!("copy operand in tail call arguments"),
+                Operand::Move(place) => {
+                    let local = place.as_local()?;
+                    self.local_decls[local]
+                        .ty
+                        .needs_drop(self.tcx, self.param_env)
+                        .then_some(DropData { source_info, local, kind: DropKind::Value })
+                }
+                Operand::Constant(_) => None,
+            })
+            .collect();
+
+        // Make sure every scope has its unwind path cached, then start from the one of the scope
+        // right outside of the arguments.
+        self.diverge_cleanup();
+        let outer_scopes = self.scopes.scopes.len().saturating_sub(1);
+        let mut unwind_to = outer_scopes
+            .checked_sub(1)
+            .map_or(ROOT_NODE, |idx| self.scopes.scopes[idx].cached_unwind_block.unwrap());
+
+        let unwind_drops = &mut self.scopes.unwind_drops;
+        for scope in self.scopes.scopes[..outer_scopes].iter().rev() {
+            for drop_data in scope.drops.iter().rev() {
+                let source_info = drop_data.source_info;
+                let local = drop_data.local;
+
+                match drop_data.kind {
+                    DropKind::Value => {
+                        // Same as in `build_scope_drops`, except that the arguments of the call
+                        // have to be dropped as well if this drop panics.
+                        debug_assert_eq!(unwind_drops.drops[unwind_to].0.local, local);
+                        debug_assert_eq!(unwind_drops.drops[unwind_to].0.kind, drop_data.kind);
+                        unwind_to = unwind_drops.drops[unwind_to].1;
+
+                        if scope.moved_locals.iter().any(|&o| o == local) {
+                            continue;
+                        }
+
+                        let mut unwind_entry = unwind_to;
+                        for &drop in &arg_drops {
+                            unwind_entry = unwind_drops.add_drop(drop, unwind_entry);
+                        }
+                        unwind_drops.add_entry(block, unwind_entry);
+
+                        let next = self.cfg.start_new_block();
+                        self.cfg.terminate(
+                            block,
+                            source_info,
+                            TerminatorKind::Drop {
+                                place: local.into(),
+                                target: next,
+                                unwind: None,
+                            },
+                        );
+                        block = next;
+                    }
+                    DropKind::Storage => {
+                        // Only temps and vars need their storage dead.
+                        assert!(local.index() > self.arg_count);
+                        self.cfg.push(
+                            block,
+                            Statement { source_info, kind: StatementKind::StorageDead(local) },
+                        );
+                    }
+                }
+            }
+        }
+
+        block.unit()
+    }
+
     // Add a dummy `Assign` statement to the CFG, with the span for the source code's `continue`
     // statement.
     fn add_dummy_assignment(&mut self, span: &Span, block: BasicBlock, source_info: SourceInfo) {
@@ -1379,6 +1465,7 @@ fn add_entry(cfg: &mut CFG<'tcx>, from: BasicBlock, to: BasicBlock) {
             | TerminatorKind::Unreachable
             | TerminatorKind::Yield { .. }
             | TerminatorKind::GeneratorDrop
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::FalseEdge { .. }
             | TerminatorKind::InlineAsm { .. } => {
                 span_bug!(term.source_info.span, "cannot unwind from {:?}", term.kind)
@@ -116,6 +116,15 @@ fn node_examined(
             | TerminatorKind::Unreachable
             | TerminatorKind::Yield { .. } => ControlFlow::Break(NonRecursive),
 
+            // A tail call to another function returns to the caller through that function.
+            TerminatorKind::TailCall { ref func, .. } => {
+                if self.is_recursive_call(func) {
+                    ControlFlow::CONTINUE
+                } else {
+                    ControlFlow::Break(NonRecursive)
+                }
+            }
+
             // A diverging InlineAsm is treated as non-recursing
             TerminatorKind::InlineAsm { destination, .. } => {
                 if destination.is_some() {
@@ -140,7 +149,9 @@ fn node_examined(
     fn node_settled(&mut self, bb: BasicBlock) -> ControlFlow<Self::BreakVal> {
         // When we examine a node for the last time, remember it if it is a recursive call.
         let terminator = self.body[bb].terminator();
-        if let TerminatorKind::Call { func, .. } = &terminator.kind {
+        if let TerminatorKind::Call { func, .. } | TerminatorKind::TailCall { func, .. } =
+            &terminator.kind
+        {
             if self.is_recursive_call(func) {
                 self.reachable_recursive_calls.push(terminator.source_info.span);
             }
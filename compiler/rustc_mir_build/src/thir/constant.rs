@@ -3,7 +3,7 @@
 use rustc_middle::mir::interpret::{
     Allocation, ConstValue, LitToConstError, LitToConstInput, Scalar,
 };
-use rustc_middle::ty::{self, ParamEnv, TyCtxt};
+use rustc_middle::ty::{self, ParamEnv, ScalarInt, TyCtxt};
 use rustc_span::symbol::Symbol;
 use rustc_target::abi::Size;
 
@@ -16,10 +16,11 @@
     let trunc = |n| {
         let param_ty = ParamEnv::reveal_all().and(ty);
         let width = tcx.layout_of(param_ty).map_err(|_| LitToConstError::Reported)?.size;
-        trace!("trunc {} with size {} and shift {}", n, width.bits(), 128 - width.bits());
-        let result = width.truncate(n);
-        trace!("trunc result: {}", result);
-        Ok(ConstValue::Scalar(Scalar::from_uint(result, width)))
+        trace!("trunc {} with size {} and negation {}", n, width.bits(), neg);
+        // Overflowing literals are linted against separately, so just wrap around.
+        let (result, _) = ScalarInt::from_int_literal(n, neg, width, ty.is_signed());
+        trace!("trunc result: {:?}", result);
+        Ok(ConstValue::Scalar(Scalar::Int(result)))
     };
 
     let lit = match (lit, &ty.kind()) {
@@ -44,7 +45,7 @@
             ConstValue::Scalar(Scalar::from_uint(*n, Size::from_bytes(1)))
         }
         (ast::LitKind::Int(n, _), ty::Uint(_)) | (ast::LitKind::Int(n, _), ty::Int(_)) => {
-            trunc(if neg { (*n as i128).overflowing_neg().0 as u128 } else { *n })?
+            trunc(*n)?
         }
         (ast::LitKind::Float(n, _), ty::Float(fty)) => {
             parse_float(*n, *fty, neg).map_err(|_| LitToConstError::UnparseableFloat)?
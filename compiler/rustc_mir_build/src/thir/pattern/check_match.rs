@@ -1,3 +1,4 @@
+use super::deconstruct_pat::{missing_int_ranges, uncovered_int_values};
 use super::usefulness::{
     compute_match_usefulness, expand_pattern, is_wildcard, MatchArm, MatchCheckCtxt, Reachability,
     UsefulnessReport,
@@ -13,12 +14,14 @@
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::{HirId, Pat};
 use rustc_middle::thir::PatKind;
-use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_middle::ty::layout::IntegerExt;
+use rustc_middle::ty::{self, ConstInt, ScalarInt, Ty, TyCtxt};
 use rustc_session::lint::builtin::BINDINGS_WITH_VARIANT_NAME;
 use rustc_session::lint::builtin::{IRREFUTABLE_LET_PATTERNS, UNREACHABLE_PATTERNS};
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::{sym, Span};
+use rustc_target::abi::Integer;
 use std::slice;
 
 crate fn check_match(tcx: TyCtxt<'_>, def_id: DefId) {
@@ -159,10 +162,10 @@ fn new_cx(&self, hir_id: HirId) -> MatchCheckCtxt<'_, 'tcx> {
     fn check_match(
         &mut self,
         scrut: &hir::Expr<'_>,
-        arms: &'tcx [hir::Arm<'tcx>],
+        hir_arms: &'tcx [hir::Arm<'tcx>],
         source: hir::MatchSource,
     ) {
-        for arm in arms {
+        for arm in hir_arms {
             // Check the arm for some things unrelated to exhaustiveness.
             self.check_patterns(&arm.pat);
             if let Some(hir::Guard::IfLet(ref pat, _)) = arm.guard {
@@ -172,7 +175,7 @@ fn check_match(
 
         let mut cx = self.new_cx(scrut.hir_id);
 
-        for arm in arms {
+        for arm in hir_arms {
             if let Some(hir::Guard::IfLet(ref pat, _)) = arm.guard {
                 let tpat = self.lower_pattern(&mut cx, pat, &mut false).0;
                 check_if_let_guard(&mut cx, &tpat, pat.hir_id);
@@ -181,7 +184,7 @@ fn check_match(
 
         let mut have_errors = false;
 
-        let arms: Vec<_> = arms
+        let arms: Vec<_> = hir_arms
             .iter()
             .map(|hir::Arm { pat, guard, .. }| MatchArm {
                 pat: self.lower_pattern(&mut cx, pat, &mut have_errors).0,
@@ -205,12 +208,74 @@ fn check_match(
         // Note: An empty match isn't the same as an empty matrix for diagnostics purposes,
         // since an empty matrix can occur when there are arms, if those arms all have guards.
         let is_empty_match = arms.is_empty();
-        let witnesses = report.non_exhaustiveness_witnesses;
+        let mut witnesses = report.non_exhaustiveness_witnesses;
         if !witnesses.is_empty() {
-            non_exhaustive_match(&cx, scrut_ty, scrut.span, witnesses, is_empty_match);
+            if let Some(ranges) = missing_int_ranges(&cx, scrut_ty, &arms) {
+                witnesses = ranges;
+            }
+            if let Some(values) = self.enum_cast_values(scrut, scrut_ty) {
+                // The scrutinee can only be one of the discriminants of the enum, so only
+                // those have to be covered.
+                if let Some(uncovered) =
+                    uncovered_int_values(&cx, scrut.span, scrut_ty, &witnesses, &values)
+                {
+                    witnesses = uncovered;
+                }
+            }
+        }
+        if !witnesses.is_empty() {
+            non_exhaustive_match(&cx, scrut_ty, scrut.span, witnesses, hir_arms, is_empty_match);
         }
     }
 
+    /// If `scrut` casts a fieldless enum to an integer, returns the values the cast can produce,
+    /// i.e. the discriminants of the enum converted to the integer type.
+    fn enum_cast_values(
+        &self,
+        scrut: &hir::Expr<'_>,
+        scrut_ty: Ty<'tcx>,
+    ) -> Option<Vec<ScalarInt>> {
+        let inner = match scrut.kind {
+            hir::ExprKind::Cast(inner, _) => inner,
+            _ => return None,
+        };
+        let size = match *scrut_ty.kind() {
+            ty::Int(ity) => Integer::from_int_ty(&self.tcx, ity).size(),
+            ty::Uint(uty) => Integer::from_uint_ty(&self.tcx, uty).size(),
+            _ => return None,
+        };
+        let def = match self.typeck_results.expr_ty_adjusted(inner).kind() {
+            // Variants may be added to a foreign non-exhaustive enum, so its discriminants
+            // can't be relied upon.
+            ty::Adt(def, _)
+                if def.is_enum()
+                    && def.is_payloadfree()
+                    && !(def.is_variant_list_non_exhaustive() && !def.did.is_local()) =>
+            {
+                def
+            }
+            _ => return None,
+        };
+        let discr_signed = def.repr.discr_type().is_signed();
+        let mut values: Vec<_> = def
+            .variants
+            .iter()
+            .map(|variant| {
+                let discr = self.tcx.variant_discriminant(variant.def_id);
+                let discr_size = discr.size();
+                let bits = discr.assert_bits(discr_size);
+                let bits = if discr_signed { discr_size.sign_extend(bits) } else { bits };
+                ScalarInt::try_from_uint(size.truncate(bits), size).unwrap()
+            })
+            .collect();
+        // Sort the values numerically, so the uncovered ones are reported in ascending order.
+        let (signed, is_ptr_sized_integral) =
+            (scrut_ty.is_signed(), scrut_ty.is_ptr_sized_integral());
+        values.sort_by_key(|&value| ConstInt::new(value, signed, is_ptr_sized_integral));
+        values.dedup();
+        Some(values)
+    }
+
     fn check_irrefutable(&self, pat: &'tcx Pat<'tcx>, origin: &str, sp: Option<Span>) {
         let mut cx = self.new_cx(pat.hir_id);
 
@@ -472,6 +537,7 @@ fn non_exhaustive_match<'p, 'tcx>(
     scrut_ty: Ty<'tcx>,
     sp: Span,
     witnesses: Vec<super::Pat<'tcx>>,
+    arms: &[hir::Arm<'tcx>],
     is_empty_match: bool,
 ) {
     let non_empty_enum = match scrut_ty.kind() {
@@ -503,6 +569,7 @@ fn non_exhaustive_match<'p, 'tcx>(
     };
 
     adt_defined_here(cx, &mut err, scrut_ty, &witnesses);
+    suggest_missing_arms(cx, &mut err, arms, &witnesses);
     err.help(
         "ensure that all possible cases are being handled, \
               possibly by adding wildcards or more match arms",
@@ -538,6 +605,57 @@ fn non_exhaustive_match<'p, 'tcx>(
     err.emit();
 }
 
+/// Suggests adding arms for the uncovered integers and chars. Their witnesses are valid patterns,
+/// so this is only done if there are few enough of them to be written out by hand.
+fn suggest_missing_arms(
+    cx: &MatchCheckCtxt<'_, '_>,
+    err: &mut DiagnosticBuilder<'_>,
+    arms: &[hir::Arm<'_>],
+    witnesses: &[super::Pat<'_>],
+) {
+    const LIMIT: usize = 3;
+    let last = match arms {
+        [.., last] if !last.span.from_expansion() => last,
+        _ => return,
+    };
+    if witnesses.len() > LIMIT
+        || !witnesses.iter().all(|witness| {
+            matches!(witness.ty.kind(), ty::Int(_) | ty::Uint(_) | ty::Char)
+                && !is_wildcard(witness)
+        })
+    {
+        return;
+    }
+    // Only suggest new arms if the last arm is on a line of its own, whose indentation they
+    // can follow.
+    let indentation = match cx.tcx.sess.source_map().span_to_prev_source(last.span) {
+        Ok(prev) => prev.rsplit('\n').next().unwrap_or("").to_owned(),
+        Err(_) => return,
+    };
+    if !indentation.trim().is_empty() {
+        return;
+    }
+    // Arms whose body is a block don't need a trailing comma.
+    let comma = match last.body.kind {
+        hir::ExprKind::Block(..) if last.span.eq_ctxt(last.body.span) => "",
+        _ => ",",
+    };
+    let new_arms: Vec<_> = witnesses
+        .iter()
+        .map(|witness| format!("\n{}{} => todo!()", indentation, witness))
+        .collect();
+    err.span_suggestion_verbose(
+        last.span.shrink_to_hi(),
+        &format!(
+            "add {} for the uncovered pattern{}",
+            if witnesses.len() == 1 { "a match arm" } else { "match arms" },
+            rustc_errors::pluralize!(witnesses.len()),
+        ),
+        format!("{}{}", comma, new_arms.join(",")),
+        Applicability::HasPlaceholders,
+    );
+}
+
 fn joined_uncovered_patterns(witnesses: &[super::Pat<'_>]) -> String {
     const LIMIT: usize = 3;
     match witnesses {
@@ -46,17 +46,17 @@
 use self::SliceKind::*;
 
 use super::compare_const_vals;
-use super::usefulness::{is_wildcard, MatchCheckCtxt, PatCtxt};
+use super::usefulness::{is_wildcard, MatchArm, MatchCheckCtxt, PatCtxt};
 
 use rustc_data_structures::captures::Captures;
 use rustc_index::vec::Idx;
 
 use rustc_hir::{HirId, RangeEnd};
-use rustc_middle::mir::interpret::ConstValue;
+use rustc_middle::mir::interpret::{ConstValue, Scalar};
 use rustc_middle::mir::Field;
 use rustc_middle::thir::{FieldPat, Pat, PatKind, PatRange};
 use rustc_middle::ty::layout::IntegerExt;
-use rustc_middle::ty::{self, Const, Ty, TyCtxt};
+use rustc_middle::ty::{self, Const, ScalarInt, Ty, TyCtxt};
 use rustc_session::lint;
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::abi::{Integer, Size, VariantIdx};
@@ -72,7 +72,7 @@
 /// regardless of the signedness.
 /// For example, the pattern `-128..=127i8` is encoded as `0..=255`.
 /// This makes comparisons and arithmetic on interval endpoints much more
-/// straightforward. See `signed_bias` for details.
+/// straightforward. See `ScalarInt::to_order_preserving_bits` for details.
 ///
 /// `IntRange` is never used to encode an empty range or a "range" that wraps
 /// around the (offset) space: i.e., `range.lo <= range.hi`.
@@ -96,26 +96,37 @@ fn boundaries(&self) -> (u128, u128) {
     }
 
     #[inline]
-    fn integral_size_and_signed_bias(tcx: TyCtxt<'_>, ty: Ty<'_>) -> Option<(Size, u128)> {
+    fn integral_size_and_signedness(tcx: TyCtxt<'_>, ty: Ty<'_>) -> Option<(Size, bool)> {
         match *ty.kind() {
-            ty::Bool => Some((Size::from_bytes(1), 0)),
-            ty::Char => Some((Size::from_bytes(4), 0)),
-            ty::Int(ity) => {
-                let size = Integer::from_int_ty(&tcx, ity).size();
-                Some((size, 1u128 << (size.bits() as u128 - 1)))
-            }
-            ty::Uint(uty) => Some((Integer::from_uint_ty(&tcx, uty).size(), 0)),
+            ty::Bool => Some((Size::from_bytes(1), false)),
+            ty::Char => Some((Size::from_bytes(4), false)),
+            ty::Int(ity) => Some((Integer::from_int_ty(&tcx, ity).size(), true)),
+            ty::Uint(uty) => Some((Integer::from_uint_ty(&tcx, uty).size(), false)),
             _ => None,
         }
     }
 
+    /// Encodes the bits of a value of the integral type `ty` as an endpoint of an `IntRange`.
+    #[inline]
+    fn encode(tcx: TyCtxt<'_>, ty: Ty<'_>, bits: u128) -> Option<u128> {
+        let (size, signed) = Self::integral_size_and_signedness(tcx, ty)?;
+        Some(ScalarInt::try_from_uint(bits, size)?.to_order_preserving_bits(signed))
+    }
+
+    /// Decodes an endpoint of an `IntRange` of the integral type `ty` into a value of that type.
+    #[inline]
+    fn decode(tcx: TyCtxt<'_>, ty: Ty<'_>, endpoint: u128) -> ScalarInt {
+        let (size, signed) = Self::integral_size_and_signedness(tcx, ty).unwrap();
+        ScalarInt::from_order_preserving_bits(endpoint, size, signed).unwrap()
+    }
+
     #[inline]
     fn from_const<'tcx>(
         tcx: TyCtxt<'tcx>,
         param_env: ty::ParamEnv<'tcx>,
         value: &Const<'tcx>,
     ) -> Option<IntRange> {
-        if let Some((target_size, bias)) = Self::integral_size_and_signed_bias(tcx, value.ty) {
+        if let Some((target_size, _)) = Self::integral_size_and_signedness(tcx, value.ty) {
             let ty = value.ty;
             let val = (|| {
                 if let ty::ConstKind::Value(ConstValue::Scalar(scalar)) = value.val {
@@ -130,7 +141,7 @@ fn from_const<'tcx>(
                 // This is a more general form of the previous case.
                 value.try_eval_bits(tcx, param_env, ty)
             })()?;
-            let val = val ^ bias;
+            let val = Self::encode(tcx, ty, val)?;
             Some(IntRange { range: val..=val })
         } else {
             None
@@ -146,29 +157,51 @@ fn from_range<'tcx>(
         end: &RangeEnd,
     ) -> Option<IntRange> {
         if Self::is_integral(ty) {
-            // Perform a shift if the underlying types are signed,
+            let (size, signed) = Self::integral_size_and_signedness(tcx, ty)?;
+            let lo = ScalarInt::try_from_uint(lo, size)?;
+            let hi = ScalarInt::try_from_uint(hi, size)?;
+            // An exclusive range ends right before its upper bound.
+            let last = match end {
+                RangeEnd::Included => Some(hi),
+                RangeEnd::Excluded if ty.is_char() => hi.char_pred(),
+                RangeEnd::Excluded => hi.checked_pred(signed),
+            };
+            // Encode the endpoints so that signed integers are ordered like unsigned ones,
             // which makes the interval arithmetic simpler.
-            let bias = IntRange::signed_bias(tcx, ty);
-            let (lo, hi) = (lo ^ bias, hi ^ bias);
-            let offset = (*end == RangeEnd::Excluded) as u128;
-            if lo > hi || (lo == hi && *end == RangeEnd::Excluded) {
+            let lo_bits = lo.to_order_preserving_bits(signed);
+            match last.map(|last| last.to_order_preserving_bits(signed)) {
+                Some(hi_bits) if lo_bits <= hi_bits => Some(IntRange { range: lo_bits..=hi_bits }),
                 // This should have been caught earlier by E0030.
-                bug!("malformed range pattern: {}..={}", lo, (hi - offset));
+                _ => bug!("malformed range pattern: {:?}{}{:?}", lo, end, hi),
             }
-            Some(IntRange { range: lo..=(hi - offset) })
         } else {
             None
         }
     }
 
-    // The return value of `signed_bias` should be XORed with an endpoint to encode/decode it.
-    fn signed_bias(tcx: TyCtxt<'_>, ty: Ty<'_>) -> u128 {
-        match *ty.kind() {
-            ty::Int(ity) => {
-                let bits = Integer::from_int_ty(&tcx, ity).size().bits() as u128;
-                1u128 << (bits - 1)
+    /// The range of all values of the integer type `ty`.
+    fn full(tcx: TyCtxt<'_>, ty: Ty<'_>) -> Option<IntRange> {
+        let (size, _) = Self::integral_size_and_signedness(tcx, ty)?;
+        Some(IntRange { range: 0..=size.unsigned_int_max() })
+    }
+
+    /// Builds the range of values matched by `pat`, a wildcard, constant or range pattern of
+    /// integral type.
+    fn from_simple_pat<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+        pat: &Pat<'tcx>,
+    ) -> Option<IntRange> {
+        match *pat.kind {
+            PatKind::Wild => Self::full(tcx, pat.ty),
+            PatKind::Constant { value } => Self::from_const(tcx, param_env, value),
+            PatKind::Range(PatRange { lo, hi, end }) => {
+                let ty = lo.ty;
+                let lo = lo.try_eval_bits(tcx, param_env, ty)?;
+                let hi = hi.try_eval_bits(tcx, param_env, ty)?;
+                Self::from_range(tcx, lo, hi, ty, &end)
             }
-            _ => 0,
+            _ => None,
         }
     }
 
@@ -206,12 +239,8 @@ fn suspicious_intersection(&self, other: &Self) -> bool {
     fn to_pat<'tcx>(&self, tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Pat<'tcx> {
         let (lo, hi) = self.boundaries();
 
-        let bias = IntRange::signed_bias(tcx, ty);
-        let (lo, hi) = (lo ^ bias, hi ^ bias);
-
-        let env = ty::ParamEnv::empty().and(ty);
-        let lo_const = ty::Const::from_bits(tcx, lo, env);
-        let hi_const = ty::Const::from_bits(tcx, hi, env);
+        let lo_const = ty::Const::from_scalar(tcx, Scalar::Int(Self::decode(tcx, ty, lo)), ty);
+        let hi_const = ty::Const::from_scalar(tcx, Scalar::Int(Self::decode(tcx, ty, hi)), ty);
 
         let kind = if lo == hi {
             PatKind::Constant { value: lo_const }
@@ -278,6 +307,47 @@ pub(super) fn lint_overlapping_range_endpoints<'a, 'tcx: 'a>(
         }
     }
 
+    /// Returns the parts of `self` that are not covered by `other`.
+    fn subtract(&self, other: &Self) -> SmallVec<[IntRange; 2]> {
+        if self.intersection(other).is_none() {
+            return smallvec![self.clone()];
+        }
+        let (lo, hi) = self.boundaries();
+        let (other_lo, other_hi) = other.boundaries();
+        let mut remaining = SmallVec::new();
+        if lo < other_lo {
+            remaining.push(IntRange { range: lo..=other_lo - 1 });
+        }
+        if other_hi < hi {
+            remaining.push(IntRange { range: other_hi + 1..=hi });
+        }
+        remaining
+    }
+
+    /// Collects the ranges of values matched by `pat`, a pattern of integer type. Returns `None`
+    /// if the pattern isn't made up of constants, ranges and wildcards.
+    fn collect_from_pat<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+        pat: &Pat<'tcx>,
+        ranges: &mut Vec<IntRange>,
+    ) -> Option<()> {
+        match *pat.kind {
+            PatKind::Binding { subpattern: Some(ref subpattern), .. }
+            | PatKind::AscribeUserType { ref subpattern, .. } => {
+                Self::collect_from_pat(tcx, param_env, subpattern, ranges)?;
+            }
+            PatKind::Binding { subpattern: None, .. } => ranges.push(Self::full(tcx, pat.ty)?),
+            PatKind::Or { ref pats } => {
+                for pat in pats {
+                    Self::collect_from_pat(tcx, param_env, pat, ranges)?;
+                }
+            }
+            _ => ranges.push(Self::from_simple_pat(tcx, param_env, pat)?),
+        }
+        Some(())
+    }
+
     /// See `Constructor::is_covered_by`
     fn is_covered_by(&self, other: &Self) -> bool {
         if self.intersection(other).is_some() {
@@ -291,6 +361,73 @@ fn is_covered_by(&self, other: &Self) -> bool {
     }
 }
 
+/// Computes the minimal set of ranges of the integer type `ty` that aren't matched by any of the
+/// unguarded `arms`, by subtracting the ranges of the arms from the full range of `ty`.
+/// Returns `None` if an arm can't be interpreted as ranges of integers, or if no value is
+/// matched at all, since a wildcard is the better witness then.
+pub(super) fn missing_int_ranges<'p, 'tcx>(
+    cx: &MatchCheckCtxt<'p, 'tcx>,
+    ty: Ty<'tcx>,
+    arms: &[MatchArm<'p, 'tcx>],
+) -> Option<Vec<Pat<'tcx>>> {
+    // Without `precise_pointer_size_matching`, a wildcard is needed for pointer-sized integers
+    // no matter which ranges are covered.
+    match ty.kind() {
+        ty::Int(_) | ty::Uint(_) if !ty.is_ptr_sized_integral() => {}
+        _ => return None,
+    }
+    let full = IntRange::full(cx.tcx, ty)?;
+    let mut missing = vec![full.clone()];
+    for arm in arms.iter().filter(|arm| !arm.has_guard) {
+        let mut covered = Vec::new();
+        IntRange::collect_from_pat(cx.tcx, cx.param_env, arm.pat, &mut covered)?;
+        for range in &covered {
+            missing = missing.iter().flat_map(|part| part.subtract(range)).collect();
+        }
+    }
+    if missing.is_empty() || missing == [full] {
+        return None;
+    }
+    Some(missing.iter().map(|range| range.to_pat(cx.tcx, ty)).collect())
+}
+
+/// Given the `witnesses` of a non-exhaustive match on the integral type `ty`, returns the
+/// patterns for those of `values` that are not covered by the arms of the match, in the order
+/// of `values`. This is used when the scrutinee is known to only ever take one of `values`.
+/// Returns `None` if a witness can't be interpreted as a range of integers, or if one of
+/// `values` doesn't have the size of `ty`, in which case a delayed bug is reported at `span`.
+pub(super) fn uncovered_int_values<'tcx>(
+    cx: &MatchCheckCtxt<'_, 'tcx>,
+    span: Span,
+    ty: Ty<'tcx>,
+    witnesses: &[Pat<'tcx>],
+    values: &[ScalarInt],
+) -> Option<Vec<Pat<'tcx>>> {
+    let (size, signed) = IntRange::integral_size_and_signedness(cx.tcx, ty)?;
+    let missing = witnesses
+        .iter()
+        .map(|witness| IntRange::from_simple_pat(cx.tcx, cx.param_env, witness))
+        .collect::<Option<Vec<_>>>()?;
+    let values = values
+        .iter()
+        .map(|value| {
+            let bits = value.try_assert_bits(cx.tcx, span, size);
+            bits.map(|_| value.to_order_preserving_bits(signed))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    let uncovered: Vec<_> = values
+        .into_iter()
+        .filter(|&value| missing.iter().any(|range| range.range.contains(&value)))
+        .collect();
+    Some(
+        uncovered
+            .into_iter()
+            .map(|value| IntRange { range: value..=value }.to_pat(cx.tcx, ty))
+            .collect(),
+    )
+}
+
 /// Represents a border between 2 integers. Because the intervals spanning borders must be able to
 /// cover every integer, we need to be able to represent 2^128 + 1 such borders.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -937,10 +1074,13 @@ pub(super) fn new<'p>(pcx: PatCtxt<'_, 'p, 'tcx>) -> Self {
                 }
             }
             ty::Char => {
+                // The valid Unicode Scalar Value ranges, which surround the surrogate gap.
+                let before_gap = ScalarInt::from('\u{D7FF}');
+                let after_gap = before_gap.char_succ().unwrap();
+                let bits = |int: ScalarInt| int.assert_bits(Size::from_bytes(4));
                 smallvec![
-                    // The valid Unicode Scalar Value ranges.
-                    make_range('\u{0000}' as u128, '\u{D7FF}' as u128),
-                    make_range('\u{E000}' as u128, '\u{10FFFF}' as u128),
+                    make_range(0, bits(before_gap)),
+                    make_range(bits(after_gap), char::MAX as u128),
                 ]
             }
             ty::Int(_) | ty::Uint(_)
@@ -953,14 +1093,14 @@ pub(super) fn new<'p>(pcx: PatCtxt<'_, 'p, 'tcx>) -> Self {
                 smallvec![NonExhaustive]
             }
             &ty::Int(ity) => {
-                let bits = Integer::from_int_ty(&cx.tcx, ity).size().bits() as u128;
-                let min = 1u128 << (bits - 1);
-                let max = min - 1;
+                let size = Integer::from_int_ty(&cx.tcx, ity).size();
+                let min = size.truncate(size.signed_int_min() as u128);
+                let max = size.signed_int_max() as u128;
                 smallvec![make_range(min, max)]
             }
             &ty::Uint(uty) => {
                 let size = Integer::from_uint_ty(&cx.tcx, uty).size();
-                let max = size.truncate(u128::MAX);
+                let max = size.unsigned_int_max();
                 smallvec![make_range(0, max)]
             }
             // If `exhaustive_patterns` is disabled and our scrutinee is the never type, we cannot
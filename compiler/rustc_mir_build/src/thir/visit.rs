@@ -88,6 +88,7 @@ pub fn walk_expr<'a, 'tcx: 'a, V: Visitor<'a, 'tcx>>(visitor: &mut V, expr: &Exp
                 visitor.visit_expr(&visitor.thir()[value])
             }
         }
+        Become { value } => visitor.visit_expr(&visitor.thir()[value]),
         ConstBlock { value } => visitor.visit_const(value),
         Repeat { value, count } => {
             visitor.visit_expr(&visitor.thir()[value]);
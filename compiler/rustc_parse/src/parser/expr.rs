@@ -1151,6 +1151,8 @@ fn parse_bottom_expr(&mut self) -> PResult<'a, P<Expr>> {
             self.parse_try_block(lo, attrs)
         } else if self.eat_keyword(kw::Return) {
             self.parse_return_expr(attrs)
+        } else if self.eat_keyword(kw::Become) {
+            self.parse_become_expr(attrs)
         } else if self.eat_keyword(kw::Break) {
             self.parse_break_expr(attrs)
         } else if self.eat_keyword(kw::Yield) {
@@ -1375,6 +1377,15 @@ fn parse_return_expr(&mut self, attrs: AttrVec) -> PResult<'a, P<Expr>> {
         self.maybe_recover_from_bad_qpath(expr, true)
     }
 
+    /// Parse `"become" expr`.
+    fn parse_become_expr(&mut self, attrs: AttrVec) -> PResult<'a, P<Expr>> {
+        let lo = self.prev_token.span;
+        let kind = ExprKind::Become(self.parse_expr()?);
+        let span = lo.to(self.prev_token.span);
+        self.sess.gated_spans.gate(sym::explicit_tail_calls, span);
+        Ok(self.mk_expr(span, kind, attrs))
+    }
+
     /// Parse `"('label ":")? break expr?`.
     fn parse_break_expr(&mut self, attrs: AttrVec) -> PResult<'a, P<Expr>> {
         let lo = self.prev_token.span;
@@ -90,6 +90,7 @@ fn check_attributes(
                     self.check_rustc_allow_const_fn_unstable(hir_id, &attr, span, target)
                 }
                 sym::naked => self.check_naked(hir_id, attr, span, target),
+                sym::proc_macro_impure => self.check_proc_macro_impure(attr, span, attrs),
                 sym::rustc_legacy_const_generics => {
                     self.check_rustc_legacy_const_generics(&attr, span, target, item)
                 }
@@ -207,6 +208,20 @@ fn check_inline(&self, hir_id: HirId, attr: &Attribute, span: &Span, target: Tar
         }
     }
 
+    /// Checks if `#[proc_macro_impure]` is applied to a proc macro.
+    fn check_proc_macro_impure(&self, attr: &Attribute, span: &Span, attrs: &[Attribute]) -> bool {
+        if attrs.iter().any(|attr| self.tcx.sess.is_proc_macro_attr(attr)) {
+            true
+        } else {
+            self.tcx
+                .sess
+                .struct_span_err(attr.span, "attribute should be applied to a proc macro")
+                .span_label(*span, "not a proc macro")
+                .emit();
+            false
+        }
+    }
+
     /// Checks if `#[naked]` is applied to a function definition.
     fn check_naked(&self, hir_id: HirId, attr: &Attribute, span: &Span, target: Target) -> bool {
         match target {
@@ -80,6 +80,11 @@ fn dump_layout_of(&self, item_def_id: LocalDefId, item: &hir::Item<'tcx>, attr:
                             );
                         }
 
+                        sym::niches => {
+                            let niches = self.tcx.layout_niches(param_env.and(ty));
+                            self.tcx.sess.span_err(item.span, &format!("niches: {:?}", niches));
+                        }
+
                         sym::debug => {
                             let normalized_ty = self.tcx.normalize_erasing_regions(
                                 param_env.with_reveal_all_normalized(self.tcx),
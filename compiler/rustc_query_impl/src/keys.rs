@@ -351,6 +351,16 @@ fn default_span(&self, tcx: TyCtxt<'_>) -> Span {
     }
 }
 
+impl Key for Span {
+    #[inline(always)]
+    fn query_crate_is_local(&self) -> bool {
+        true
+    }
+    fn default_span(&self, _tcx: TyCtxt<'_>) -> Span {
+        *self
+    }
+}
+
 impl Key for Symbol {
     #[inline(always)]
     fn query_crate_is_local(&self) -> bool {
@@ -1,7 +1,13 @@
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
+use rustc_serialize::json::as_pretty_json;
 use rustc_target::abi::{Align, Size};
 use std::cmp::{self, Ordering};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -9,9 +15,19 @@ pub struct VariantInfo {
     pub kind: SizeKind,
     pub size: u64,
     pub align: u64,
+    pub tag: Option<VariantTag>,
     pub fields: Vec<FieldInfo>,
 }
 
+/// The value of an enum's tag that identifies a variant, already rendered for printing.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum VariantTag {
+    /// The tag is stored separately and holds the variant's discriminant.
+    Discriminant(String),
+    /// The tag is stored in a niche of another variant's field and holds this value.
+    Niche(String),
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum SizeKind {
     Exact,
@@ -45,9 +61,45 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+/// The profile of the evaluation of one constant, see `-Z profile-const-eval`.
+#[derive(Debug)]
+pub struct ConstEvalProfile {
+    /// The evaluated item, like `foo::BAR` or `foo::bar::promoted[0]`.
+    pub item: String,
+    pub duration: Duration,
+    /// The number of terminators that were evaluated.
+    pub steps: u64,
+    /// The number of terminators evaluated with each call stack, outermost frame first.
+    pub stacks: Vec<(Vec<String>, u64)>,
+}
+
+/// The number of frames listed for each item in the JSON report.
+const HOTTEST_FRAMES: usize = 10;
+
+#[derive(Encodable)]
+struct ConstEvalReport {
+    items: Vec<ConstEvalItemReport>,
+}
+
+#[derive(Encodable)]
+struct ConstEvalItemReport {
+    item: String,
+    nanos: u64,
+    steps: u64,
+    /// The functions that evaluated the most terminators themselves, excluding their callees.
+    hottest_frames: Vec<ConstEvalFrameReport>,
+}
+
+#[derive(Encodable)]
+struct ConstEvalFrameReport {
+    frame: String,
+    steps: u64,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    const_evals: Lock<Vec<ConstEvalProfile>>,
 }
 
 impl CodeStats {
@@ -116,17 +168,26 @@ pub fn print_type_sizes(&self) {
                 DataTypeKind::Enum | DataTypeKind::Union => false,
             };
             for (i, variant_info) in info.variants.iter().enumerate() {
-                let VariantInfo { ref name, kind: _, align: _, size, ref fields } = *variant_info;
+                let VariantInfo { ref name, kind: _, align: _, size, ref tag, ref fields } =
+                    *variant_info;
                 let indent = if !struct_like {
                     let name = match name.as_ref() {
                         Some(name) => name.to_owned(),
                         None => i.to_string(),
                     };
+                    let tag = match tag {
+                        Some(VariantTag::Discriminant(discr)) => {
+                            format!(", discriminant: {}", discr)
+                        }
+                        Some(VariantTag::Niche(niche)) => format!(", niche: {}", niche),
+                        None => String::new(),
+                    };
                     println!(
-                        "print-type-size {}variant `{}`: {} bytes",
+                        "print-type-size {}variant `{}`: {} bytes{}",
                         indent,
                         name,
-                        size - discr_size
+                        size - discr_size,
+                        tag
                     );
                     "        "
                 } else {
@@ -190,4 +251,55 @@ pub fn print_type_sizes(&self) {
             }
         }
     }
+
+    pub fn record_const_eval(&self, profile: ConstEvalProfile) {
+        self.const_evals.borrow_mut().push(profile);
+    }
+
+    /// Writes the recorded const evaluations to `path` as JSON, slowest first. Their call stacks
+    /// are written next to it, with the `folded` extension, in the format that flamegraph tools
+    /// read: one line per call stack, with the frames separated by `;` and followed by the number
+    /// of steps.
+    pub fn write_const_eval_profile(&self, path: &Path) -> io::Result<()> {
+        let mut const_evals = self.const_evals.borrow_mut();
+        const_evals.sort_by(|a, b| b.duration.cmp(&a.duration).then_with(|| a.item.cmp(&b.item)));
+
+        let items = const_evals
+            .iter()
+            .map(|profile| {
+                let mut self_steps = FxHashMap::default();
+                for (stack, steps) in &profile.stacks {
+                    if let Some(frame) = stack.last() {
+                        *self_steps.entry(frame).or_insert(0) += steps;
+                    }
+                }
+                let mut hottest_frames: Vec<_> = self_steps
+                    .into_iter()
+                    .map(|(frame, steps)| ConstEvalFrameReport { frame: frame.clone(), steps })
+                    .collect();
+                hottest_frames
+                    .sort_by(|a, b| b.steps.cmp(&a.steps).then_with(|| a.frame.cmp(&b.frame)));
+                hottest_frames.truncate(HOTTEST_FRAMES);
+                ConstEvalItemReport {
+                    item: profile.item.clone(),
+                    nanos: u64::try_from(profile.duration.as_nanos()).unwrap_or(u64::MAX),
+                    steps: profile.steps,
+                    hottest_frames,
+                }
+            })
+            .collect();
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", as_pretty_json(&ConstEvalReport { items }))?;
+        file.flush()?;
+
+        let mut file = BufWriter::new(File::create(path.with_extension("folded"))?);
+        for profile in const_evals.iter() {
+            for (stack, steps) in &profile.stacks {
+                // `;` separates the frames, so it must not occur in them, e.g. in `[u8; 4]`.
+                let frames: Vec<_> = stack.iter().map(|frame| frame.replace(';', ":")).collect();
+                writeln!(file, "{} {}", frames.join(";"), steps)?;
+            }
+        }
+        file.flush()
+    }
 }
@@ -125,6 +125,54 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The formats that `-Z dump-mir` can write MIR in, selected with `-Z dump-mir-format`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum MirDumpFormat {
+    /// The textual format of `.mir` files, the default.
+    Text,
+    /// A versioned JSON description of the body in `.json` files.
+    Json,
+    /// The control flow graph as a Graphviz `.dot` file.
+    Dot,
+}
+
+/// Where proc macros run, selected with `-Z proc-macro-execution`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum ProcMacroExecution {
+    /// On the thread that expands them, the default.
+    SameThread,
+    /// In a separate process, which a crashing proc macro only takes down by itself.
+    External,
+}
+
+/// The different settings that the `-Z macro-backtrace` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum MacroBacktrace {
+    /// `-Z macro-backtrace=no` (default): only the outermost macro invocation is shown.
+    No,
+    /// `-Z macro-backtrace`: every frame is shown, but the repeated frames of recursive macros
+    /// are grouped into one.
+    Folded,
+    /// `-Z macro-backtrace=full`: every frame is shown on its own.
+    Full,
+}
+
+impl MacroBacktrace {
+    pub fn enabled(self) -> bool {
+        self != MacroBacktrace::No
+    }
+}
+
+/// The different settings that the `-Z print-consts` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum ConstPrintStyle {
+    /// `-Z print-consts=decimal` (default)
+    Decimal,
+    /// `-Z print-consts=hex`: integers are printed as their bits in hexadecimal, zero-padded to
+    /// the width of their type.
+    Hex,
+}
+
 /// The different settings that the `-Z instrument-coverage` flag can have.
 ///
 /// Coverage instrumentation now supports combining `-Z instrument-coverage`
@@ -298,6 +346,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A SARIF log, for code scanning services and other tools that read static analysis results.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -708,6 +758,7 @@ fn default() -> Options {
             json_artifact_notifications: false,
             json_unused_externs: false,
             pretty: None,
+            explain: None,
         }
     }
 }
@@ -749,7 +800,7 @@ pub fn diagnostic_handler_flags(&self, can_emit_warnings: bool) -> HandlerFlags
             treat_err_as_bug: self.treat_err_as_bug,
             dont_buffer_diagnostics: self.dont_buffer_diagnostics,
             report_delayed_bugs: self.report_delayed_bugs,
-            macro_backtrace: self.macro_backtrace,
+            macro_backtrace: self.macro_backtrace.enabled(),
             deduplicate_diagnostics: self.deduplicate_diagnostics,
         }
     }
@@ -1302,6 +1353,7 @@ pub fn parse_error_format(
             }
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
+            Some("sarif") => ErrorOutputType::Sarif,
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
 
             Some(arg) => early_error(
@@ -1387,6 +1439,12 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                "`--error-format=sarif` is unstable",
+            );
+        }
     }
 }
 
@@ -1973,6 +2031,13 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     check_thread_count(&debugging_opts, error_format);
 
+    if debugging_opts.proc_macro_execution == ProcMacroExecution::External && !cfg!(unix) {
+        early_error(
+            error_format,
+            "`-Z proc-macro-execution=external` is only supported on Unix hosts",
+        );
+    }
+
     let incremental = cg.incremental.as_ref().map(PathBuf::from);
 
     if debugging_opts.profile && incremental.is_some() {
@@ -2080,6 +2145,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let pretty = parse_pretty(matches, &debugging_opts, error_format);
 
+    let explain = matches.opt_str("explain").map(|code| {
+        if code.starts_with('E') { code } else { format!("E{0:0>4}", code) }
+    });
+
     if !debugging_opts.unstable_options
         && !target_triple.triple().contains("apple")
         && cg.split_debuginfo.is_some()
@@ -2153,6 +2222,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         json_artifact_notifications,
         json_unused_externs,
         pretty,
+        explain,
     }
 }
 
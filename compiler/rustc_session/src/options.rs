@@ -8,6 +8,7 @@
 use rustc_target::spec::{CodeModel, LinkerFlavor, MergeFunctions, PanicStrategy, SanitizerSet};
 use rustc_target::spec::{RelocModel, RelroLevel, SplitDebuginfo, TargetTriple, TlsModel};
 
+use rustc_errors::TerminalUrl;
 use rustc_feature::UnstableFeatures;
 use rustc_span::edition::Edition;
 use rustc_span::SourceFileHashAlgorithm;
@@ -199,6 +200,10 @@ pub struct Options {
         json_unused_externs: bool [UNTRACKED],
 
         pretty: Option<PpMode> [UNTRACKED],
+
+        /// The error code given to `--explain`, normalized to the `E0000` form. If a crate is
+        /// compiled as well, its explanation is instantiated for the first error with that code.
+        explain: Option<String> [UNTRACKED],
     }
 );
 
@@ -346,9 +351,15 @@ mod desc {
     pub const parse_cfguard: &str =
         "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
     pub const parse_strip: &str = "either `none`, `debuginfo`, or `symbols`";
+    pub const parse_macro_backtrace: &str =
+        "either a boolean (`yes`, `no`, `on`, `off`, etc), or `full`";
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
+    pub const parse_const_print_style: &str = "either `decimal` or `hex`";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+    pub const parse_mir_dump_format: &str = "one of: `text` (default), `json`, or `dot`";
+    pub const parse_proc_macro_execution: &str = "either `same-thread` (default) or `external`";
+    pub const parse_terminal_url: &str = "one of: `no` (default), `yes`, or `auto`";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `except-unused-generics`, `except-unused-functions`, or `off`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
@@ -605,6 +616,19 @@ mod parse {
         true
     }
 
+    crate fn parse_macro_backtrace(slot: &mut MacroBacktrace, v: Option<&str>) -> bool {
+        let mut bool_arg = None;
+        if parse_opt_bool(&mut bool_arg, v) {
+            *slot = if bool_arg.unwrap() { MacroBacktrace::Folded } else { MacroBacktrace::No };
+            return true;
+        }
+        match v {
+            Some("full") => *slot = MacroBacktrace::Full,
+            _ => return false,
+        }
+        true
+    }
+
     crate fn parse_cfguard(slot: &mut CFGuard, v: Option<&str>) -> bool {
         if v.is_some() {
             let mut bool_arg = None;
@@ -687,6 +711,35 @@ mod parse {
         true
     }
 
+    crate fn parse_mir_dump_format(slot: &mut MirDumpFormat, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("text") => MirDumpFormat::Text,
+            Some("json") => MirDumpFormat::Json,
+            Some("dot") => MirDumpFormat::Dot,
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_proc_macro_execution(slot: &mut ProcMacroExecution, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("same-thread") => ProcMacroExecution::SameThread,
+            Some("external") => ProcMacroExecution::External,
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_terminal_url(slot: &mut TerminalUrl, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("no") => TerminalUrl::No,
+            Some("yes") => TerminalUrl::Yes,
+            Some("auto") => TerminalUrl::Auto,
+            _ => return false,
+        };
+        true
+    }
+
     crate fn parse_instrument_coverage(
         slot: &mut Option<InstrumentCoverage>,
         v: Option<&str>,
@@ -813,6 +866,15 @@ mod parse {
         true
     }
 
+    crate fn parse_const_print_style(slot: &mut ConstPrintStyle, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("decimal") => ConstPrintStyle::Decimal,
+            Some("hex") => ConstPrintStyle::Hex,
+            _ => return false,
+        };
+        true
+    }
+
     crate fn parse_symbol_mangling_version(
         slot: &mut Option<SymbolManglingVersion>,
         v: Option<&str>,
@@ -1018,6 +1080,13 @@ mod parse {
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    const_eval_foreign_shims: bool = (false, parse_bool, [TRACKED],
+        "allow const evaluation to call the foreign functions `memchr`, `memcmp` and `strlen` \
+        (default: no)"),
+    const_eval_memory_limit: Option<u64> = (None, parse_opt_number, [TRACKED],
+        "abort a const evaluation if the memory it has allocated exceeds this many bytes"),
+    const_eval_report_progress: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "during const evaluation, report the call stack every N evaluated terminators"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
     debug_macros: bool = (false, parse_bool, [TRACKED],
@@ -1030,6 +1099,12 @@ mod parse {
     dep_tasks: bool = (false, parse_bool, [UNTRACKED],
         "print tasks that execute and the color their dep node gets (requires debug build) \
         (default: no)"),
+    diagnostic_anchors: bool = (false, parse_bool, [UNTRACKED],
+        "include the definition path, HIR id and MIR location that a diagnostic is about in the \
+        JSON output, where known (default: no)"),
+    diagnostic_dedup_server: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "leave out the warnings that the diagnostic deduplication server listening on this socket \
+        has seen from another compiler (default: $RUSTC_DIAGNOSTIC_DEDUP_SERVER)"),
     dont_buffer_diagnostics: bool = (false, parse_bool, [UNTRACKED],
         "emit diagnostics rather than buffering (breaks NLL error downgrading, sorting) \
         (default: no)"),
@@ -1052,6 +1127,10 @@ mod parse {
         "the directory the MIR is dumped into (default: `mir_dump`)"),
     dump_mir_exclude_pass_number: bool = (false, parse_bool, [UNTRACKED],
         "exclude the pass number when dumping MIR (used in tests) (default: no)"),
+    dump_mir_format: MirDumpFormat = (MirDumpFormat::Text, parse_mir_dump_format, [UNTRACKED],
+        "the format MIR is dumped in: `text` writes `.mir` files, `json` writes `.json` files \
+        with a versioned description of the body, and `dot` writes Graphviz `.dot` files of the \
+        control flow graph (default: `text`)"),
     dump_mir_graphviz: bool = (false, parse_bool, [UNTRACKED],
         "in addition to `.mir` files, create graphviz `.dot` files (and with \
         `-Z instrument-coverage`, also create a `.dot` file for the MIR-derived \
@@ -1066,6 +1145,9 @@ mod parse {
         "emits a future-incompatibility report for lints (RFC 2834)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    extra_const_ub_checks: bool = (false, parse_bool, [TRACKED],
+        "validate every value written during const evaluation, reporting invalid values \
+        at the first write instead of at the end of evaluation (default: no)"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
@@ -1089,6 +1171,9 @@ mod parse {
         "generate human-readable, predictable names for codegen units (default: no)"),
     identify_regions: bool = (false, parse_bool, [UNTRACKED],
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
+    incremental_fixed_width_scalars: bool = (true, parse_bool, [TRACKED],
+        "store integer constants in the incremental cache with a fixed width of 17 bytes, \
+        which is faster to decode but takes more space (default: yes)"),
     incremental_ignore_spans: bool = (false, parse_bool, [UNTRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
@@ -1102,6 +1187,10 @@ mod parse {
         "a default MIR inlining threshold (default: 50)"),
     inline_mir_hint_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
         "inlining threshold for functions with inline hint (default: 100)"),
+    inline_mir_hot_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "MIR inlining threshold for hot call sites according to `-C profile-use` (default: 200)"),
+    inline_mir_cold_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "MIR inlining threshold for cold call sites according to `-C profile-use` (default: 10)"),
     inline_in_all_cgus: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "control whether `#[inline]` functions are in all CGUs"),
     input_stats: bool = (false, parse_bool, [UNTRACKED],
@@ -1126,8 +1215,9 @@ mod parse {
         "generate JSON tracing data file from LLVM data (default: no)"),
     ls: bool = (false, parse_bool, [UNTRACKED],
         "list the symbols defined by a library crate (default: no)"),
-    macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
-        "show macro backtraces (default: no)"),
+    macro_backtrace: MacroBacktrace = (MacroBacktrace::No, parse_macro_backtrace, [UNTRACKED],
+        "show macro backtraces, with the repeated frames of recursive macros grouped unless \
+        `full` is given (default: no)"),
     merge_functions: Option<MergeFunctions> = (None, parse_merge_functions, [TRACKED],
         "control the operation of the MergeFunctions LLVM pass, taking \
         the same values as the target option of the same name"),
@@ -1138,6 +1228,9 @@ mod parse {
         (default: no)"),
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
+    mir_passes: Option<Vec<String>> = (None, parse_opt_comma_list, [TRACKED],
+        "replace the MIR optimization passes with this comma-separated list of passes, which are \
+        validated after each pass (e.g. `ConstProp,SimplifyCfg-final`)"),
     mutable_noalias: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "emit noalias metadata for mutable references (default: yes for LLVM >= 12, otherwise no)"),
     new_llvm_pass_manager: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1150,6 +1243,8 @@ mod parse {
         "parse and expand the source, but run no analysis"),
     no_codegen: bool = (false, parse_no_flag, [TRACKED],
         "run all passes except codegen; no output"),
+    no_const_eval_metadata_cache: bool = (false, parse_no_flag, [UNTRACKED],
+        "evaluate the constants of other crates instead of loading their values from metadata"),
     no_generate_arange_section: bool = (false, parse_no_flag, [TRACKED],
         "omit DWARF address ranges that give faster lookups"),
     no_interleave_lints: bool = (false, parse_no_flag, [UNTRACKED],
@@ -1186,6 +1281,10 @@ mod parse {
         "use a more precise version of drop elaboration for matches on enums (default: yes). \
         This results in better codegen, but has caused miscompilations on some tier 2 platforms. \
         See #77382 and #74551."),
+    print_consts: ConstPrintStyle = (ConstPrintStyle::Decimal, parse_const_print_style,
+        [UNTRACKED],
+        "how to print integer constants in MIR dumps and diagnostics: `decimal` (default) or \
+        `hex` (the bits, zero-padded to the width of the type)"),
     print_fuel: Option<String> = (None, parse_opt_string, [TRACKED],
         "make rustc print the total optimization fuel used by a crate"),
     print_link_args: bool = (false, parse_bool, [UNTRACKED],
@@ -1198,8 +1297,21 @@ mod parse {
         "print layout information for each type encountered (default: no)"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
          "show backtraces for panics during proc-macro execution (default: no)"),
+    proc_macro_cache: bool = (false, parse_bool, [UNTRACKED],
+        "reuse the output of proc macros whose input didn't change since the last incremental \
+        compilation (default: no)"),
+    proc_macro_execution: ProcMacroExecution = (ProcMacroExecution::SameThread,
+        parse_proc_macro_execution, [UNTRACKED],
+        "where to run proc macros: `same-thread` or in an `external` process (Unix only) \
+        (default: same-thread)"),
+    proc_macro_memory_limit: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "limit the address space of the process running proc macros to this many MiB \
+        (only with `-Z proc-macro-execution=external` on Unix)"),
     profile: bool = (false, parse_bool, [TRACKED],
         "insert profiling code (default: no)"),
+    profile_const_eval: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the time and steps each const evaluation took to this file as JSON, and their \
+        call stacks in the folded format of flamegraph tools next to it"),
     profile_emit: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
         "file path to emit profiling data at runtime when using 'profile' \
         (default based on relative source path)"),
@@ -1260,6 +1372,12 @@ mod parse {
         "which mangling version to use for symbol names ('legacy' (default) or 'v0')"),
     teach: bool = (false, parse_bool, [TRACKED],
         "show extended diagnostic help (default: no)"),
+    terminal_url_template: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "the URL that locations in diagnostics link to with `-Z terminal-urls`, with `{path}`, \
+        `{line}` and `{column}` replaced (default: `file://{path}`)"),
+    terminal_urls: TerminalUrl = (TerminalUrl::No, parse_terminal_url, [UNTRACKED],
+        "add hyperlinks to error codes, locations and standard library paths in diagnostics \
+        (`auto` adds them if the terminal is known to support them) (default: no)"),
     terminal_width: Option<usize> = (None, parse_opt_number, [UNTRACKED],
         "set the current terminal width"),
     tune_cpu: Option<String> = (None, parse_opt_string, [TRACKED],
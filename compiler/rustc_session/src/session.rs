@@ -1,6 +1,8 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+pub use crate::code_stats::{
+    ConstEvalProfile, DataTypeKind, FieldInfo, SizeKind, VariantInfo, VariantTag,
+};
 use crate::config::{self, CrateType, OutputType, PrintRequest, SwitchWithOptPath};
 use crate::filesearch;
 use crate::lint::{self, LintId};
@@ -20,6 +22,7 @@
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{Diagnostic, DiagnosticBuilder, DiagnosticId, ErrorReported};
 use rustc_lint_defs::FutureBreakage;
 pub use rustc_span::crate_disambiguator::CrateDisambiguator;
@@ -1188,6 +1191,8 @@ fn default_emitter(
     emitter_dest: Option<Box<dyn Write + Send>>,
 ) -> Box<dyn Emitter + sync::Send> {
     let macro_backtrace = sopts.debugging_opts.macro_backtrace;
+    let fold_macro_backtrace = macro_backtrace != config::MacroBacktrace::Full;
+    let macro_backtrace = macro_backtrace.enabled();
     match (sopts.error_format, emitter_dest) {
         (config::ErrorOutputType::HumanReadable(kind), dst) => {
             let (short, color_config) = kind.unzip();
@@ -1216,6 +1221,12 @@ fn default_emitter(
                         macro_backtrace,
                     ),
                 };
+                let emitter = emitter
+                    .terminal_urls(
+                        sopts.debugging_opts.terminal_urls,
+                        sopts.debugging_opts.terminal_url_template.clone(),
+                    )
+                    .fold_macro_backtrace(fold_macro_backtrace);
                 Box::new(emitter.ui_testing(sopts.debugging_opts.ui_testing))
             }
         }
@@ -1228,6 +1239,7 @@ fn default_emitter(
                 sopts.debugging_opts.terminal_width,
                 macro_backtrace,
             )
+            .fold_macro_backtrace(fold_macro_backtrace)
             .ui_testing(sopts.debugging_opts.ui_testing),
         ),
         (config::ErrorOutputType::Json { pretty, json_rendered }, Some(dst)) => Box::new(
@@ -1240,8 +1252,11 @@ fn default_emitter(
                 sopts.debugging_opts.terminal_width,
                 macro_backtrace,
             )
+            .fold_macro_backtrace(fold_macro_backtrace)
             .ui_testing(sopts.debugging_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif, None) => Box::new(SarifEmitter::stderr(source_map)),
+        (config::ErrorOutputType::Sarif, Some(dst)) => Box::new(SarifEmitter::new(dst, source_map)),
     }
 }
 
@@ -1307,6 +1322,25 @@ pub fn build_session(
         emitter,
         sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
     );
+    if let Some(code) = &sopts.explain {
+        span_diagnostic.explain_with_args(code);
+    }
+    // Like the `-Z` flag, the environment variable is only honored by nightly compilers.
+    let dedup_server = sopts.debugging_opts.diagnostic_dedup_server.clone().or_else(|| {
+        if !sopts.unstable_features.is_nightly_build() {
+            return None;
+        }
+        env::var_os(rustc_errors::dedup_server::SERVER_ENV).map(PathBuf::from)
+    });
+    if let Some(path) = dedup_server {
+        if let Err(err) = span_diagnostic.connect_dedup_server(&path) {
+            span_diagnostic.warn(&format!(
+                "failed to connect to the diagnostic deduplication server at `{}`: {}",
+                path.display(),
+                err
+            ));
+        }
+    }
 
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.debugging_opts.self_profile
     {
@@ -1551,6 +1585,7 @@ pub fn early_error_no_abort(output: config::ErrorOutputType, msg: &str) {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, false))
         }
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic()),
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_fatal(msg).emit();
@@ -1570,6 +1605,7 @@ pub fn early_warn(output: config::ErrorOutputType, msg: &str) {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, false))
         }
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic()),
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_warn(msg).emit();
@@ -836,6 +836,19 @@ pub fn is_root(&self) -> bool {
     }
 }
 
+/// One macro invocation or compiler transformation in the chain that produced a span, see
+/// [`Span::expansion_trace`].
+#[derive(Clone, Debug, Encodable, Decodable, HashStable_Generic)]
+pub struct ExpansionStep {
+    pub kind: ExpnKind,
+    /// The span of the invocation, which may itself come from the next step of the chain.
+    pub call_site: Span,
+    /// The span of the definition of the macro, if it is known.
+    pub def_site: Span,
+    /// The `DefId` of the macro, if this step is a macro invocation.
+    pub macro_def_id: Option<DefId>,
+}
+
 /// Expansion kind.
 #[derive(Clone, Debug, PartialEq, Encodable, Decodable, HashStable_Generic)]
 pub enum ExpnKind {
@@ -38,7 +38,7 @@
 pub mod hygiene;
 pub use hygiene::SyntaxContext;
 use hygiene::Transparency;
-pub use hygiene::{DesugaringKind, ExpnData, ExpnId, ExpnKind, ForLoopLoc, MacroKind};
+pub use hygiene::{DesugaringKind, ExpansionStep, ExpnData, ExpnId, ExpnKind, ForLoopLoc, MacroKind};
 pub mod def_id;
 use def_id::{CrateNum, DefId, DefPathHash, LOCAL_CRATE};
 pub mod lev_distance;
@@ -647,6 +647,26 @@ pub fn macro_backtrace(mut self) -> impl Iterator<Item = ExpnData> {
         })
     }
 
+    /// Returns every macro invocation, desugaring and other compiler transformation that produced
+    /// this span, starting with the innermost one. Unlike [`Span::macro_backtrace`], this includes
+    /// recursive invocations.
+    pub fn expansion_trace(mut self) -> Vec<ExpansionStep> {
+        let mut trace = Vec::new();
+        loop {
+            let expn_data = self.ctxt().outer_expn_data();
+            if expn_data.is_root() {
+                return trace;
+            }
+            self = expn_data.call_site;
+            trace.push(ExpansionStep {
+                kind: expn_data.kind,
+                call_site: expn_data.call_site,
+                def_site: expn_data.def_site,
+                macro_def_id: expn_data.macro_def_id,
+            });
+        }
+    }
+
     /// Returns a `Span` that would enclose both `self` and `end`.
     ///
     /// ```text
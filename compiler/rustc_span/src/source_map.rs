@@ -102,6 +102,12 @@ pub trait FileLoader {
 
     /// Read the contents of an UTF-8 file into memory.
     fn read_file(&self, path: &Path) -> io::Result<String>;
+
+    /// Read the contents of a file that doesn't have to be UTF-8 into memory, for
+    /// `include_bytes!` and `include_str!`. Reads from the file system by default.
+    fn read_binary_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
 }
 
 /// A FileLoader that uses std::fs to load real files.
@@ -117,6 +123,60 @@ fn read_file(&self, path: &Path) -> io::Result<String> {
     }
 }
 
+/// A FileLoader that serves files from memory, so a crate can be compiled without having its
+/// files on disk, including the files of its modules and of `include_bytes!` and `include_str!`.
+///
+/// Files are looked up by the path the compiler computes for them, so they have to be added with
+/// paths relative to the same directory as the path of the crate root.
+#[derive(Default)]
+pub struct VirtualFileLoader {
+    files: FxHashMap<PathBuf, Vec<u8>>,
+}
+
+impl VirtualFileLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, replacing the previous contents if it already exists.
+    pub fn add_file(&mut self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) -> &mut Self {
+        self.files.insert(Self::normalize(path.as_ref()), contents.into());
+        self
+    }
+
+    /// Removes `.` components and repeated separators, which don't change the file a path refers
+    /// to but would make it look different.
+    fn normalize(path: &Path) -> PathBuf {
+        path.components().collect()
+    }
+
+    fn get(&self, path: &Path) -> io::Result<&[u8]> {
+        match self.files.get(&Self::normalize(path)) {
+            Some(contents) => Ok(contents),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no virtual file at `{}`", path.display()),
+            )),
+        }
+    }
+}
+
+impl FileLoader for VirtualFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.files.contains_key(&Self::normalize(path))
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.get(path)?.to_vec()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+        })
+    }
+
+    fn read_binary_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        Ok(self.get(path)?.to_vec())
+    }
+}
+
 // This is a `SourceFile` identifier that is used to correlate `SourceFile`s between
 // subsequent compilation sessions (which is something we need to do during
 // incremental compilation).
@@ -205,9 +265,7 @@ pub fn load_file(&self, path: &Path) -> io::Result<Lrc<SourceFile>> {
     /// Unlike `load_file`, guarantees that no normalization like BOM-removal
     /// takes place.
     pub fn load_binary_file(&self, path: &Path) -> io::Result<Vec<u8>> {
-        // Ideally, this should use `self.file_loader`, but it can't
-        // deal with binary files yet.
-        let bytes = fs::read(path)?;
+        let bytes = self.file_loader.read_binary_file(path)?;
 
         // We need to add file to the `SourceMap`, so that it is present
         // in dep-info. There's also an edge case that file might be both
@@ -312,3 +312,24 @@ fn span_substr(
         }
     }
 }
+
+#[test]
+fn virtual_file_loader() {
+    let mut loader = VirtualFileLoader::new();
+    loader.add_file("src/lib.rs", "mod foo;").add_file("src/./data.bin", vec![0xff, 0x00]);
+    let sm = SourceMap::with_file_loader_and_hash_kind(
+        Box::new(loader),
+        FilePathMapping::empty(),
+        SourceFileHashAlgorithm::Md5,
+    );
+
+    assert!(sm.file_exists(Path::new("src/lib.rs")));
+    assert!(!sm.file_exists(Path::new("src/foo.rs")));
+    let file = sm.load_file(Path::new("src//lib.rs")).unwrap();
+    assert_eq!(file.src.as_deref().map(|src| &src[..]), Some("mod foo;"));
+
+    assert_eq!(sm.load_binary_file(Path::new("src/data.bin")).unwrap(), vec![0xff, 0x00]);
+    assert!(sm.load_file(Path::new("src/data.bin")).is_err());
+    let err = sm.load_file(Path::new("src/foo.rs")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
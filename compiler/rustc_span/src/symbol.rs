@@ -401,6 +401,7 @@
         const_indexing,
         const_let,
         const_loop,
+        const_make_global,
         const_mut_refs,
         const_panic,
         const_precise_live_drops,
@@ -529,6 +530,7 @@
         expected,
         expf32,
         expf64,
+        explicit_tail_calls,
         export_name,
         expr,
         extended_key_value_attributes,
@@ -737,6 +739,8 @@
         mem_uninitialized,
         mem_zeroed,
         member_constraints,
+        memchr,
+        memcmp,
         memory,
         message,
         meta,
@@ -789,6 +793,7 @@
         new,
         new_unchecked,
         next,
+        niches,
         nll,
         no,
         no_builtins,
@@ -907,6 +912,7 @@
         proc_macro_expr,
         proc_macro_gen,
         proc_macro_hygiene,
+        proc_macro_impure,
         proc_macro_internals,
         proc_macro_mod,
         proc_macro_non_items,
@@ -1180,6 +1186,7 @@
         str_alloc,
         string_type,
         stringify,
+        strlen,
         struct_field_attributes,
         struct_inherit,
         struct_variant,
@@ -38,3 +38,33 @@ fn check(before: &str, after: &str, expected_positions: &[u32]) {
     check("\r\r\n", "\r\n", &[2]);
     check("hello\rworld", "hello\rworld", &[]);
 }
+
+#[test]
+fn test_expansion_trace() {
+    with_default_session_globals(|| {
+        let sm = Lrc::new(SourceMap::new(source_map::FilePathMapping::empty()));
+        sm.new_source_file(PathBuf::from("test.rs").into(), "fn main() { m!(); }".to_owned());
+        with_source_map(sm, || {
+            let bang = |name| ExpnKind::Macro {
+                kind: MacroKind::Bang,
+                name: Symbol::intern(name),
+                proc_macro: false,
+            };
+            let expand = |span: Span, name| {
+                let expn_data = ExpnData::default(bang(name), span, Edition::Edition2018, None);
+                span.fresh_expansion(expn_data)
+            };
+
+            let call_site = Span::with_root_ctxt(BytePos(12), BytePos(17));
+            assert!(call_site.expansion_trace().is_empty());
+
+            // Recursive invocations are part of the trace.
+            let inner = expand(expand(call_site, "m"), "m");
+            let trace = inner.expansion_trace();
+            assert_eq!(trace.len(), 2);
+            assert_eq!(trace[0].kind, bang("m"));
+            assert_eq!(trace[1].call_site, call_site);
+            assert_eq!(inner.macro_backtrace().count(), 1);
+        })
+    })
+}
@@ -383,6 +383,24 @@ pub fn truncate(self, value: u128) -> u128 {
         // Truncate (shift left to drop out leftover values, shift right to fill with zeroes).
         (value << shift) >> shift
     }
+
+    /// The minimum value of the signed integer type of this size, e.g. `i8::MIN` for one byte.
+    #[inline]
+    pub fn signed_int_min(&self) -> i128 {
+        self.sign_extend(1_u128 << (self.bits() - 1)) as i128
+    }
+
+    /// The maximum value of the signed integer type of this size, e.g. `i8::MAX` for one byte.
+    #[inline]
+    pub fn signed_int_max(&self) -> i128 {
+        i128::MAX >> (128 - self.bits())
+    }
+
+    /// The maximum value of the unsigned integer type of this size, e.g. `u8::MAX` for one byte.
+    #[inline]
+    pub fn unsigned_int_max(&self) -> u128 {
+        u128::MAX >> (128 - self.bits())
+    }
 }
 
 // Panicking addition, subtraction and multiplication for convenience.
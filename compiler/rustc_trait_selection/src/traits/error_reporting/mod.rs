@@ -14,18 +14,20 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder, ErrorReported};
 use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::Visitor;
 use rustc_hir::Node;
 use rustc_middle::mir::abstract_const::NotConstEvaluatable;
 use rustc_middle::ty::error::ExpectedFound;
 use rustc_middle::ty::fold::TypeFolder;
+use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
 use rustc_middle::ty::{
     self, fast_reject, AdtKind, SubtypePredicate, ToPolyTraitRef, ToPredicate, Ty, TyCtxt,
     TypeFoldable, WithConstness,
 };
 use rustc_session::DiagnosticMessageId;
-use rustc_span::symbol::{kw, sym};
+use rustc_span::symbol::{kw, sym, Symbol};
 use rustc_span::{ExpnKind, MultiSpan, Span, DUMMY_SP};
 use std::fmt;
 use std::iter;
@@ -311,6 +313,9 @@ fn report_selection_error(
                                 post_message,
                             ))
                         );
+                        err.set_arg("self_ty", trait_ref.skip_binder().self_ty());
+                        err.set_arg("trait", trait_ref.print_only_trait_path());
+                        err.set_arg("bound", trait_ref.without_const().to_predicate(tcx));
 
                         if is_try_conversion {
                             let none_error = self
@@ -758,6 +763,11 @@ fn report_selection_error(
                     // Note that with `feature(const_evaluatable_checked)` this case should not
                     // be reachable.
                     err.note("this may fail depending on what value the parameter takes");
+                    if let ty::PredicateKind::ConstEvaluatable(def, substs) =
+                        obligation.predicate.kind().skip_binder()
+                    {
+                        self.note_generic_params_of_const(&mut err, def, substs);
+                    }
                     err.emit();
                     return;
                 }
@@ -1093,6 +1103,15 @@ fn is_recursive_obligation(
         obligated_types: &mut Vec<&ty::TyS<'tcx>>,
         cause_code: &ObligationCauseCode<'tcx>,
     ) -> bool;
+
+    /// Names the generic parameters that keep the constant `def` from being evaluated, and
+    /// lists the generic arguments of it that are already known.
+    fn note_generic_params_of_const(
+        &self,
+        err: &mut DiagnosticBuilder<'tcx>,
+        def: ty::WithOptConstParam<DefId>,
+        substs: SubstsRef<'tcx>,
+    );
 }
 
 impl<'a, 'tcx> InferCtxtPrivExt<'tcx> for InferCtxt<'a, 'tcx> {
@@ -1861,6 +1880,123 @@ fn is_recursive_obligation(
         }
         false
     }
+
+    fn note_generic_params_of_const(
+        &self,
+        err: &mut DiagnosticBuilder<'tcx>,
+        def: ty::WithOptConstParam<DefId>,
+        substs: SubstsRef<'tcx>,
+    ) {
+        let tcx = self.tcx;
+        // Anonymous constants have all generic parameters of their parent in scope, so look at
+        // their body to find out which of them are actually used.
+        let used_params = def
+            .did
+            .as_local()
+            .filter(|_| tcx.def_kind(def.did) == DefKind::AnonConst)
+            .map(|did| {
+                let body_id = tcx.hir().body_owned_by(tcx.hir().local_def_id_to_hir_id(did));
+                let mut visitor = FindGenericParams { tcx, params: vec![] };
+                visitor.visit_body(tcx.hir().body(body_id));
+                visitor.params
+            });
+
+        let generics = tcx.generics_of(def.did);
+        let mut blocking = vec![];
+        let mut known = vec![];
+        for (index, arg) in substs.iter().enumerate() {
+            let param = generics.param_at(index, tcx);
+            if let ty::GenericParamDefKind::Lifetime = param.kind {
+                continue;
+            }
+            if !used_params.as_ref().map_or(true, |used| used.contains(&param.name)) {
+                continue;
+            }
+            if arg.has_param_types_or_consts() {
+                blocking.push(format!("`{}`", arg));
+            } else {
+                known.push(format!("`{} = {}`", param.name, arg));
+            }
+        }
+
+        match &blocking[..] {
+            // Nothing to point out if the arguments of the constant are all known.
+            [] => return,
+            [param] => {
+                err.note(&format!("the constant depends on the generic parameter {}", param));
+            }
+            [params @ .., last] => {
+                err.note(&format!(
+                    "the constant depends on the generic parameters {} and {}",
+                    params.join(", "),
+                    last,
+                ));
+            }
+        }
+        if !known.is_empty() {
+            err.note(&format!(
+                "the generic argument{} {} {} known",
+                pluralize!(known.len()),
+                known.join(", "),
+                if known.len() == 1 { "is" } else { "are" },
+            ));
+        }
+    }
+}
+
+/// Collects the names of the generic parameters used in a body, see
+/// `note_generic_params_of_const`.
+struct FindGenericParams<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    params: Vec<Symbol>,
+}
+
+impl FindGenericParams<'_> {
+    fn add(&mut self, name: Symbol) {
+        if !self.params.contains(&name) {
+            self.params.push(name);
+        }
+    }
+}
+
+impl<'v> Visitor<'v> for FindGenericParams<'_> {
+    type Map = rustc_hir::intravisit::ErasedMap<'v>;
+
+    fn nested_visit_map(&mut self) -> hir::intravisit::NestedVisitorMap<Self::Map> {
+        hir::intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_path(&mut self, path: &'v hir::Path<'v>, _id: hir::HirId) {
+        match path.res {
+            Res::Def(DefKind::TyParam | DefKind::ConstParam, _) => {
+                if let Some(segment) = path.segments.last() {
+                    self.add(segment.ident.name);
+                }
+            }
+            // In impls and type definitions, `Self` stands for a type using their parameters.
+            Res::SelfTy(_, Some((def_id, _))) => {
+                for arg in self.tcx.type_of(def_id).walk() {
+                    match arg.unpack() {
+                        GenericArgKind::Type(ty) => {
+                            if let ty::Param(param) = ty.kind() {
+                                self.add(param.name);
+                            }
+                        }
+                        GenericArgKind::Const(ct) => {
+                            if let ty::ConstKind::Param(param) = ct.val {
+                                self.add(param.name);
+                            }
+                        }
+                        GenericArgKind::Lifetime(_) => {}
+                    }
+                }
+            }
+            // In traits, `Self` is a generic parameter itself.
+            Res::SelfTy(Some(_), None) => self.add(kw::SelfUpper),
+            _ => {}
+        }
+        hir::intravisit::walk_path(self, path);
+    }
 }
 
 /// Look for type `param` in an ADT being used only through a reference to confirm that suggesting
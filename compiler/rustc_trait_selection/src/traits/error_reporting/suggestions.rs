@@ -2434,7 +2434,7 @@ fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
         // we only care about tail expressions when `in_block_tail` is `true`, which means that
         // they're in the return path of the function body.
         match ex.kind {
-            hir::ExprKind::Ret(Some(ex)) => {
+            hir::ExprKind::Ret(Some(ex)) | hir::ExprKind::Become(ex) => {
                 self.returns.push(ex);
             }
             hir::ExprKind::Block(block, _) if self.in_block_tail => {
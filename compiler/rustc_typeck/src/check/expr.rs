@@ -262,6 +262,7 @@ fn check_expr_kind(
                 }
             }
             ExprKind::Ret(ref expr_opt) => self.check_expr_return(expr_opt.as_deref(), expr),
+            ExprKind::Become(call) => self.check_expr_become(call, expr),
             ExprKind::Loop(body, _, source, _) => {
                 self.check_expr_loop(body, source, expected, expr)
             }
@@ -708,6 +709,25 @@ fn check_expr_return(
         self.tcx.types.never
     }
 
+    fn check_expr_become(
+        &self,
+        call: &'tcx hir::Expr<'tcx>,
+        expr: &'tcx hir::Expr<'tcx>,
+    ) -> Ty<'tcx> {
+        if let Some(ret_coercion) = &self.ret_coercion {
+            if self.ret_coercion_span.get().is_none() {
+                self.ret_coercion_span.set(Some(call.span));
+            }
+            // The result of a tail call is returned as is, so it cannot be coerced.
+            let ret_ty = ret_coercion.borrow().expected_ty();
+            let call_ty = self.check_expr_with_hint(call, ret_ty);
+            self.demand_eqtype(call.span, ret_ty, call_ty);
+        } else {
+            self.tcx.sess.emit_err(ReturnStmtOutsideOfFnBody { span: expr.span });
+        }
+        self.tcx.types.never
+    }
+
     pub(super) fn check_return_expr(&self, return_expr: &'tcx hir::Expr<'tcx>) {
         let ret_coercion = self.ret_coercion.as_ref().unwrap_or_else(|| {
             span_bug!(return_expr.span, "check_return_expr called outside fn body")
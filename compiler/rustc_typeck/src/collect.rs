@@ -908,16 +908,31 @@ fn convert_enum_variant_types(tcx: TyCtxt<'_>, def_id: DefId, variants: &[hir::V
             } else if let Some(discr) = repr_type.disr_incr(tcx, prev_discr) {
                 Some(discr)
             } else {
-                struct_span_err!(tcx.sess, variant.span, E0370, "enum discriminant overflowed")
-                    .span_label(
-                        variant.span,
-                        format!("overflowed on value after {}", prev_discr.unwrap()),
-                    )
-                    .note(&format!(
-                        "explicitly set `{} = {}` if that is desired outcome",
-                        variant.ident, wrapped_discr
-                    ))
-                    .emit();
+                let prev_discr = prev_discr.unwrap();
+                let (min, max) = Discr::bounds(tcx, prev_discr.ty);
+                tcx.struct_span_lint_hir(
+                    lint::builtin::ENUM_DISCRIMINANT_OVERFLOW,
+                    variant.id,
+                    variant.span,
+                    |lint| {
+                        lint.build("enum discriminant overflowed")
+                            .code(rustc_errors::error_code!(E0370))
+                            .span_label(
+                                variant.span,
+                                format!("overflowed on value after {}", prev_discr),
+                            )
+                            .note(&format!(
+                                "the implicit discriminant `{} + 1` does not fit into the type \
+                                 `{}` whose range is `{}..={}`",
+                                prev_discr, prev_discr.ty, min, max,
+                            ))
+                            .note(&format!(
+                                "explicitly set `{} = {}` if that is desired outcome",
+                                variant.ident, wrapped_discr
+                            ))
+                            .emit();
+                    },
+                );
                 None
             }
             .unwrap_or(wrapped_discr),
@@ -377,6 +377,10 @@ pub fn walk_expr(&mut self, expr: &hir::Expr<'_>) {
                 }
             }
 
+            hir::ExprKind::Become(ref call) => {
+                self.consume_expr(call);
+            }
+
             hir::ExprKind::Assign(ref lhs, ref rhs, _) => {
                 self.mutate_expr(lhs);
                 self.consume_expr(rhs);
@@ -358,6 +358,7 @@ fn cat_expr_adjusted_with<F>(
             | hir::ExprKind::AssignOp(..)
             | hir::ExprKind::Closure(..)
             | hir::ExprKind::Ret(..)
+            | hir::ExprKind::Become(..)
             | hir::ExprKind::Unary(..)
             | hir::ExprKind::Yield(..)
             | hir::ExprKind::MethodCall(..)
@@ -400,6 +400,19 @@ fn run_client<A: for<'a, 's> DecodeMut<'a, 's, ()>, R: Encode<()>>(
     b
 }
 
+impl<F: Copy> Client<F> {
+    fn run_remote(
+        &self,
+        input: Buffer<u8>,
+        mut dispatch: impl FnMut(Buffer<u8>) -> Buffer<u8>,
+        force_show_panics: bool,
+    ) -> Buffer<u8> {
+        let bridge =
+            Bridge { cached_buffer: input, dispatch: (&mut dispatch).into(), force_show_panics };
+        (self.run)(bridge, self.f)
+    }
+}
+
 impl Client<fn(crate::TokenStream) -> crate::TokenStream> {
     #[rustc_allow_const_fn_unstable(const_fn)]
     pub const fn expand1(f: fn(crate::TokenStream) -> crate::TokenStream) -> Self {
@@ -459,6 +472,23 @@ pub fn name(&self) -> &'static str {
         }
     }
 
+    /// Runs this macro for a server in another process, which sent `input` and answers the
+    /// requests passed to `dispatch`. The result is sent back to the server, which uses a
+    /// `server::Remote` strategy to talk to this process.
+    pub fn run_remote(
+        &self,
+        input: Buffer<u8>,
+        dispatch: impl FnMut(Buffer<u8>) -> Buffer<u8>,
+        force_show_panics: bool,
+    ) -> Buffer<u8> {
+        match self {
+            ProcMacro::CustomDerive { client, .. } | ProcMacro::Bang { client, .. } => {
+                client.run_remote(input, dispatch, force_show_panics)
+            }
+            ProcMacro::Attr { client, .. } => client.run_remote(input, dispatch, force_show_panics),
+        }
+    }
+
     #[rustc_allow_const_fn_unstable(const_fn)]
     pub const fn custom_derive(
         trait_name: &'static str,
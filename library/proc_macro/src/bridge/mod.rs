@@ -206,7 +206,7 @@ macro_rules! reverse_decode {
 #[forbid(unsafe_code)]
 pub mod server;
 
-use buffer::Buffer;
+pub use buffer::Buffer;
 pub use rpc::PanicMessage;
 use rpc::{Decode, DecodeMut, Encode, Reader, Writer};
 
@@ -268,6 +268,36 @@ enum State<T> {
     }
 }
 
+/// Runs the client with the function `self.0`, which gets the input, whether to always show
+/// panics, and the dispatcher for the requests of the client. This allows running the client where
+/// `run_client` can't be called, like in another process that looks up the same proc macro and
+/// runs it with `ProcMacro::run_remote`.
+pub struct Remote<F>(pub F);
+
+impl<F> ExecutionStrategy for Remote<F>
+where
+    F: Fn(Buffer<u8>, bool, &mut dyn FnMut(Buffer<u8>) -> Buffer<u8>) -> Buffer<u8>,
+{
+    fn run_bridge_and_client<D: Copy + Send + 'static>(
+        &self,
+        dispatcher: &mut impl DispatcherTrait,
+        input: Buffer<u8>,
+        _run_client: extern "C" fn(Bridge<'_>, D) -> Buffer<u8>,
+        _client_data: D,
+        force_show_panics: bool,
+    ) -> Buffer<u8> {
+        (self.0)(input, force_show_panics, &mut |b| dispatcher.dispatch(b))
+    }
+}
+
+/// The output of a client that panicked with `message`, for `Remote` strategies that lost the
+/// connection to their client.
+pub fn client_panicked(message: String) -> Buffer<u8> {
+    let mut b = Buffer::new();
+    Err::<(), _>(PanicMessage::String(message)).encode(&mut b, &mut ());
+    b
+}
+
 fn run_server<
     S: Server,
     I: Encode<HandleStore<MarkedTypes<S>>>,
@@ -307,6 +307,15 @@ fn to_src(&self, cx: &DocContext<'_>) -> String {
             s
         }
         _ => {
+            // Aggregates are shown the way they would be written in source.
+            if matches!(n.ty.kind(), ty::Adt(..) | ty::Tuple(_) | ty::Array(..)) {
+                let param_env = ty::ParamEnv::reveal_all();
+                if let Some(expr) =
+                    cx.tcx.lift(n).and_then(|n| n.try_to_user_expr(cx.tcx, param_env))
+                {
+                    return expr;
+                }
+            }
             let mut s = n.to_string();
             // array lengths are obviously usize
             if s.ends_with("_usize") {
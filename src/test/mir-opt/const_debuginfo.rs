@@ -1,4 +1,4 @@
-// compile-flags: -C overflow-checks=no -Zunsound-mir-opts
+// compile-flags: -C overflow-checks=no -Zunsound-mir-opts -g
 
 struct Point {
     x: u32,
@@ -0,0 +1,30 @@
+// compile-flags: -Z mir-opt-level=3
+// EMIT_MIR jump_threading.option_chain.JumpThreading.diff
+// EMIT_MIR jump_threading.result_chain.JumpThreading.diff
+
+fn option_chain(x: Option<u8>) -> u8 {
+    let y = match x {
+        Some(v) => Some(v),
+        None => None,
+    };
+    match y {
+        Some(v) => v,
+        None => 0,
+    }
+}
+
+fn result_chain(x: Result<u8, i32>) -> u8 {
+    let y = match x {
+        Ok(v) => Some(v),
+        Err(_) => None,
+    };
+    match y {
+        Some(v) => v,
+        None => 0,
+    }
+}
+
+fn main() {
+    option_chain(Some(1));
+    result_chain(Ok(1));
+}
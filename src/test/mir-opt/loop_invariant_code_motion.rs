@@ -0,0 +1,16 @@
+// compile-flags: -Z mir-opt-level=3
+// EMIT_MIR loop_invariant_code_motion.sum.LoopInvariantCodeMotion.diff
+
+fn sum(s: &[u32], n: usize) -> u32 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < n {
+        total += s[i];
+        i += 1;
+    }
+    total
+}
+
+fn main() {
+    sum(&[1, 2, 3], 3);
+}
@@ -0,0 +1,7 @@
+pub const fn div(a: u32, b: u32) -> u32 {
+    a / b
+}
+
+pub const fn div_by_zero(a: u32) -> u32 {
+    div(a, 0)
+}
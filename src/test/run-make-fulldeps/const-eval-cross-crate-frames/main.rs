@@ -0,0 +1,5 @@
+extern crate dep;
+
+static X: u32 = dep::div_by_zero(1);
+
+fn main() {}
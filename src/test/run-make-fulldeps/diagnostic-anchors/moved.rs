@@ -0,0 +1,7 @@
+#![crate_type = "lib"]
+
+pub fn f() {
+    let s = String::new();
+    drop(s);
+    drop(s);
+}
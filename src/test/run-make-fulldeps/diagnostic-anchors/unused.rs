@@ -0,0 +1,5 @@
+#![crate_type = "lib"]
+
+pub fn f() {
+    let x = 1;
+}
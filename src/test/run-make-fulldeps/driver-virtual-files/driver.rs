@@ -0,0 +1,60 @@
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+
+use rustc_driver::{Callbacks, RunCompiler, VirtualFileLoader};
+
+use std::path::PathBuf;
+
+struct NoCallbacks;
+
+impl Callbacks for NoCallbacks {}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        panic!("expected rustc path");
+    }
+
+    let tmpdir = PathBuf::from(&args[1]);
+    let mut sysroot = PathBuf::from(&args[2]);
+    sysroot.pop();
+    sysroot.pop();
+
+    let mut files = VirtualFileLoader::new();
+    files
+        .add_file(
+            "virtual/main.rs",
+            r#"
+                mod greeting;
+
+                fn main() {
+                    greeting::greet();
+                    print!("{}", include_str!("data/text.txt"));
+                    println!("{} bytes", include_bytes!("data/bytes.bin").len());
+                }
+            "#,
+        )
+        .add_file(
+            "virtual/greeting.rs",
+            r#"pub fn greet() { println!("hello from a virtual module"); }"#,
+        )
+        .add_file("virtual/data/text.txt", "included text\n")
+        .add_file("virtual/data/bytes.bin", vec![0xff, 0x00, 0x80]);
+
+    let mut rustc_args = vec![
+        "rustc".to_string(),
+        "virtual/main.rs".to_string(),
+        "-o".to_string(),
+        tmpdir.join("virtual-crate").display().to_string(),
+        "--sysroot".to_string(),
+        sysroot.display().to_string(),
+    ];
+    if let Ok(linker) = std::env::var("RUSTC_LINKER") {
+        rustc_args.push(format!("-Clinker={}", linker));
+    }
+    let mut callbacks = NoCallbacks;
+    let mut compiler = RunCompiler::new(&rustc_args, &mut callbacks);
+    compiler.set_file_loader(Some(Box::new(files)));
+    compiler.run().unwrap();
+}
@@ -0,0 +1,26 @@
+#!/usr/bin/env python
+
+# Checks the MIR dump of `add_one` written by `-Zdump-mir-format=json`.
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    body = json.load(f)
+
+assert body["version"] == 1
+assert body["def_path"] == "add_one"
+assert body["pass"] == "PreCodegen"
+assert body["disambiguator"] == "after"
+assert body["arg_count"] == 1
+assert body["return_ty"] == "u32"
+assert body["locals"][1]["ty"].endswith("Option<u32>")
+
+blocks = {block["block"]: block for block in body["blocks"]}
+assert blocks["bb0"]["terminator"]["kind"] == "SwitchInt"
+for block in blocks.values():
+    for successor in block["terminator"]["successors"]:
+        assert successor in blocks
+    for statement in block["statements"]:
+        assert statement["kind"] and statement["text"] and statement["span"]
+assert any(block["terminator"]["kind"] == "Return" for block in blocks.values())
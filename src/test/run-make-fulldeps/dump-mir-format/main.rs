@@ -0,0 +1,10 @@
+fn add_one(x: Option<u32>) -> u32 {
+    match x {
+        Some(x) => x + 1,
+        None => 0,
+    }
+}
+
+fn main() {
+    println!("{}", add_one(Some(1)));
+}
@@ -0,0 +1,90 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast as ast;
+use rustc_interface::interface;
+use rustc_interface::tool::{run_tool, AnalysisContext, Compilation, ToolCallbacks};
+use rustc_session::config::{Input, Options, OutputType, OutputTypes};
+use rustc_session::DiagnosticOutput;
+use rustc_span::source_map::FileName;
+
+use std::path::PathBuf;
+
+const SOURCE: &str = r#"
+fn callee(x: bool) -> u32 {
+    if x { 1 } else { 2 }
+}
+
+fn main() {
+    callee(true);
+}
+"#;
+
+struct PrintBodies;
+
+impl ToolCallbacks for PrintBodies {
+    fn after_expansion(&mut self, krate: &ast::Crate) -> Compilation {
+        let functions =
+            krate.items.iter().filter(|item| matches!(item.kind, ast::ItemKind::Fn(..))).count();
+        println!("expanded: {} functions", functions);
+        Compilation::Continue
+    }
+
+    fn after_analysis<'tcx>(&mut self, cx: AnalysisContext<'tcx>) -> Compilation {
+        let mut bodies: Vec<_> = cx
+            .body_owners()
+            .map(|def_id| {
+                let body = cx.optimized_mir(def_id.to_def_id());
+                (cx.def_path_str(def_id.to_def_id()), body.arg_count)
+            })
+            .collect();
+        bodies.sort();
+        for (name, arg_count) in bodies {
+            println!("{}: {} arguments", name, arg_count);
+        }
+        // Nothing has to be generated.
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        panic!("expected rustc path");
+    }
+
+    let tmpdir = PathBuf::from(&args[1]);
+    let mut sysroot = PathBuf::from(&args[2]);
+    sysroot.pop();
+    sysroot.pop();
+
+    let mut opts = Options::default();
+    opts.output_types = OutputTypes::new(&[(OutputType::Exe, None)]);
+    opts.maybe_sysroot = Some(sysroot);
+
+    let config = interface::Config {
+        opts,
+        crate_cfg: Default::default(),
+        input: Input::Str { name: FileName::anon_source_code(SOURCE), input: SOURCE.to_string() },
+        input_path: None,
+        output_file: Some(tmpdir.join("out")),
+        output_dir: None,
+        file_loader: None,
+        diagnostic_output: DiagnosticOutput::Default,
+        stderr: None,
+        lint_caps: Default::default(),
+        parse_sess_created: None,
+        register_lints: None,
+        override_queries: None,
+        make_codegen_backend: None,
+        registry: rustc_driver::diagnostics_registry(),
+    };
+
+    run_tool(config, &mut PrintBodies).unwrap();
+    assert!(!tmpdir.join("out").exists());
+}
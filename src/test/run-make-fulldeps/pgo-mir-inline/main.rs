@@ -0,0 +1,41 @@
+#[inline(never)]
+fn opaque(x: u32) -> u32 {
+    x.rotate_left(3)
+}
+
+// Too large to be inlined with `-Zinline-mir-hint-threshold=50`, unless the call site is hot.
+#[no_mangle]
+#[inline]
+pub fn hot_callee(x: u32) -> u32 {
+    opaque(opaque(opaque(x)))
+}
+
+// Small enough to be inlined, unless the call site is cold.
+#[no_mangle]
+#[inline]
+pub fn cold_callee(x: u32) -> u32 {
+    x ^ 11
+}
+
+#[inline(never)]
+fn hot_caller(x: u32) -> u32 {
+    hot_callee(x)
+}
+
+#[inline(never)]
+fn cold_caller(x: u32) -> u32 {
+    cold_callee(x)
+}
+
+fn main() {
+    let arg = std::env::args().skip(1).next().unwrap();
+    let mut acc = arg.len() as u32;
+    for i in 0..100_000 {
+        acc = hot_caller(acc.wrapping_add(i));
+    }
+    if arg == "!" {
+        // This branch is never taken at runtime
+        acc = cold_caller(acc);
+    }
+    println!("{}", acc);
+}
@@ -0,0 +1,14 @@
+extern crate macros;
+
+use macros::{impure, pure};
+
+fn main() {
+    let a = pure!(1);
+    let b = impure!(2);
+    #[cfg(not(changed))]
+    let c = pure!(3);
+    #[cfg(changed)]
+    let c = pure!(4);
+    assert_eq!(a + b, 3);
+    assert!(c == 3 || c == 4);
+}
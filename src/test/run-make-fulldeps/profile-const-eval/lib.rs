@@ -0,0 +1,6 @@
+const fn fib(n: u32) -> u32 {
+    if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+}
+
+pub const SLOW: u32 = fib(15);
+pub const FAST: u32 = 1 + 1;
@@ -0,0 +1,136 @@
+// run-pass
+// Test that `TyCtxt::enumerate_values` lists every value of `bool`, the integer types, `char` and
+// fieldless enums, including enums with negative discriminants, and respects its limit.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const SOURCE: &str = r#"
+pub enum Sign {
+    Minus = -1,
+    Zero = 0,
+    Plus = 1,
+}
+
+#[repr(i8)]
+pub enum Extremes {
+    Max = 127,
+    Min = -128,
+}
+
+pub enum WithFields {
+    A(u8),
+    B,
+}
+
+pub fn probe(_: bool, _: i8, _: u8, _: u16, _: char, _: Sign, _: Extremes, _: WithFields) {}
+"#;
+
+/// All valid `char`s.
+const CHAR_COUNT: u128 = 0x11_0000 - 0x800;
+
+/// The values of the parameter `index` of `probe` with at most `limit` values, sign-extended if
+/// `signed` is set.
+fn values(tcx: TyCtxt<'_>, index: usize, limit: u128, signed: bool) -> Option<Vec<i128>> {
+    let def_id = tcx
+        .body_owners()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| tcx.opt_item_name(def_id).map_or(false, |item| item.as_str() == "probe"))
+        .unwrap();
+    let ty = tcx.fn_sig(def_id).skip_binder().inputs()[index];
+    let values = tcx.enumerate_values(ty, limit)?.map(|int| {
+        let size = int.size();
+        let bits = int.assert_bits(size);
+        if signed { size.sign_extend(bits) as i128 } else { bits as i128 }
+    });
+    Some(values.collect())
+}
+
+#[derive(Default)]
+struct EnumerateValuesCallbacks {
+    bools: Option<Vec<i128>>,
+    too_few_bools: Option<Vec<i128>>,
+    i8s: Option<Vec<i128>>,
+    too_few_i8s: Option<Vec<i128>>,
+    u8s: Option<Vec<i128>>,
+    u16s: Option<Vec<i128>>,
+    chars: Option<Vec<i128>>,
+    too_few_chars: Option<Vec<i128>>,
+    signs: Option<Vec<i128>>,
+    extremes: Option<Vec<i128>>,
+    with_fields: Option<Vec<i128>>,
+}
+
+impl rustc_driver::Callbacks for EnumerateValuesCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        config.input = Input::Str {
+            name: FileName::Custom("enumerate-values".to_string()),
+            input: SOURCE.to_string(),
+        };
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            self.bools = values(tcx, 0, 2, false);
+            self.too_few_bools = values(tcx, 0, 1, false);
+            self.i8s = values(tcx, 1, 256, true);
+            self.too_few_i8s = values(tcx, 1, 255, true);
+            self.u8s = values(tcx, 2, 256, false);
+            self.u16s = values(tcx, 3, 256, false);
+            self.chars = values(tcx, 4, CHAR_COUNT, false);
+            self.too_few_chars = values(tcx, 4, CHAR_COUNT - 1, false);
+            self.signs = values(tcx, 5, 256, true);
+            self.extremes = values(tcx, 6, 256, true);
+            self.with_fields = values(tcx, 7, 256, false);
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = vec![
+        "enumerate-values".to_string(),
+        "enumerate-values.rs".to_string(),
+        "--crate-type=lib".to_string(),
+    ];
+    let mut callbacks = EnumerateValuesCallbacks::default();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut callbacks).run().ok();
+    })
+    .ok();
+
+    assert_eq!(callbacks.bools, Some(vec![0, 1]));
+    assert_eq!(callbacks.too_few_bools, None);
+    assert_eq!(callbacks.i8s, Some((-128..=127).collect()));
+    assert_eq!(callbacks.too_few_i8s, None);
+    assert_eq!(callbacks.u8s, Some((0..=255).collect()));
+    assert_eq!(callbacks.u16s, None);
+    let chars = callbacks.chars.unwrap();
+    assert_eq!(chars.len() as u128, CHAR_COUNT);
+    assert_eq!(chars.first(), Some(&0));
+    assert_eq!(chars.last(), Some(&0x10_FFFF));
+    assert_eq!(chars.iter().position(|&c| c == 0xE000), Some(0xD800));
+    assert_eq!(callbacks.too_few_chars, None);
+    assert_eq!(callbacks.signs, Some(vec![-1, 0, 1]));
+    assert_eq!(callbacks.extremes, Some(vec![127, -128]));
+    assert_eq!(callbacks.with_fields, None);
+}
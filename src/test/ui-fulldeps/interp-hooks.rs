@@ -0,0 +1,130 @@
+// run-pass
+// Test that a tool can drive the interpreter with its own `InterpHooks`.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_mir;
+extern crate rustc_session;
+extern crate rustc_span;
+extern crate rustc_target;
+
+use rustc_driver::Compilation;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_mir::interpret::{
+    HookedInterpCx, HookedMachine, InterpHooks, InterpResult, MemoryKind, OpTy, PlaceTy, Pointer,
+    Scalar, StackPopCleanup,
+};
+use rustc_session::config::Input;
+use rustc_span::{FileName, DUMMY_SP};
+use rustc_target::abi::{LayoutOf, Size};
+
+const SOURCE: &str = r#"
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn compute() -> u32 {
+    let a = [1, 2, 3];
+    double(a[1]) + a[2]
+}
+"#;
+
+#[derive(Default)]
+struct Tracer {
+    calls: Vec<String>,
+    bytes_written: u64,
+}
+
+impl<'mir, 'tcx> InterpHooks<'mir, 'tcx> for Tracer {
+    fn memory_written(&mut self, _ptr: Pointer, size: Size) -> InterpResult<'tcx> {
+        self.bytes_written += size.bytes();
+        Ok(())
+    }
+
+    fn call_fn(
+        ecx: &mut HookedInterpCx<'mir, 'tcx, Self>,
+        instance: ty::Instance<'tcx>,
+        _args: &[OpTy<'tcx>],
+        dest: Option<&PlaceTy<'tcx>>,
+    ) -> InterpResult<'tcx, bool> {
+        let name = ecx.tcx.item_name(instance.def_id()).to_string();
+        ecx.memory.extra.calls.push(name.clone());
+        if name != "double" {
+            return Ok(false);
+        }
+        // Replace the result of `double` instead of evaluating it.
+        ecx.write_scalar(Scalar::from_u32(100), dest.unwrap())?;
+        Ok(true)
+    }
+}
+
+fn eval_compute(tcx: TyCtxt<'_>) -> (u32, Tracer) {
+    let def_id = tcx
+        .body_owners()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| tcx.opt_item_name(def_id).map_or(false, |name| name.as_str() == "compute"))
+        .unwrap();
+    let instance = ty::Instance::mono(tcx, def_id);
+    let param_env = ty::ParamEnv::reveal_all();
+    let mut ecx = HookedMachine::new_interp_cx(tcx, DUMMY_SP, param_env, Tracer::default());
+
+    let body = ecx.load_mir(instance.def, None).unwrap();
+    let layout = ecx.layout_of(tcx.types.u32).unwrap();
+    let ret = ecx.allocate(layout, MemoryKind::Stack).unwrap();
+    let cleanup = StackPopCleanup::None { cleanup: false };
+    ecx.push_stack_frame(instance, body, Some(&ret.into()), cleanup).unwrap();
+    ecx.run().unwrap();
+
+    let result = ecx.read_scalar(&ret.into()).unwrap().to_u32().unwrap();
+    (result, ecx.memory.extra)
+}
+
+struct TracerCallbacks {
+    result: Option<(u32, Tracer)>,
+}
+
+impl rustc_driver::Callbacks for TracerCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        config.input = Input::Str {
+            name: FileName::Custom("interp-hooks".to_string()),
+            input: SOURCE.to_string(),
+        };
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            self.result = Some(eval_compute(tcx));
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = vec![
+        "interp-hooks".to_string(),
+        "interp-hooks.rs".to_string(),
+        "--crate-type=lib".to_string(),
+    ];
+    let mut callbacks = TracerCallbacks { result: None };
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut callbacks).run().ok();
+    })
+    .ok();
+
+    let (result, tracer) = callbacks.result.unwrap();
+    assert_eq!(result, 103);
+    assert_eq!(tracer.calls, ["double"]);
+    assert!(tracer.bytes_written > 0);
+}
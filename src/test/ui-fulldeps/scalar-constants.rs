@@ -0,0 +1,102 @@
+// run-pass
+// Test that the `scalar_constants` query finds the constants of functions, and that it can be
+// asked about constants and statics, which have no optimized MIR.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_interface::{interface, Queries};
+use rustc_middle::mir::ScalarConstantUsage;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const SOURCE: &str = r#"
+pub const LIMIT: u32 = 7;
+
+pub static TABLE: [u16; 2] = [300, 400];
+
+pub fn classify(x: u8) -> u32 {
+    match x {
+        3 => 10,
+        200 => 20,
+        _ => x as u32 + LIMIT,
+    }
+}
+"#;
+
+/// The `u128` values of the constants of the item named `name` that are used as `usage`.
+fn constants(tcx: TyCtxt<'_>, name: &str, usage: ScalarConstantUsage) -> Vec<u128> {
+    let def_id = tcx
+        .body_owners()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| tcx.opt_item_name(def_id).map_or(false, |item| item.as_str() == name))
+        .unwrap();
+    tcx.scalar_constants(def_id)
+        .iter()
+        .filter(|constant| constant.usage == usage)
+        .map(|constant| constant.value.assert_bits(constant.value.size()))
+        .collect()
+}
+
+#[derive(Default)]
+struct ScalarConstantsCallbacks {
+    classify_operands: Vec<u128>,
+    classify_targets: Vec<u128>,
+    const_constants: Vec<u128>,
+    static_constants: Vec<u128>,
+}
+
+impl rustc_driver::Callbacks for ScalarConstantsCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        config.input = Input::Str {
+            name: FileName::Custom("scalar-constants".to_string()),
+            input: SOURCE.to_string(),
+        };
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            use ScalarConstantUsage::*;
+            self.classify_operands = constants(tcx, "classify", Operand);
+            self.classify_targets = constants(tcx, "classify", SwitchTarget);
+            self.const_constants = constants(tcx, "LIMIT", Operand);
+            self.static_constants = constants(tcx, "TABLE", Operand);
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = vec![
+        "scalar-constants".to_string(),
+        "scalar-constants.rs".to_string(),
+        "--crate-type=lib".to_string(),
+    ];
+    let mut callbacks = ScalarConstantsCallbacks::default();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut callbacks).run().ok();
+    })
+    .ok();
+
+    assert!(callbacks.classify_operands.contains(&10));
+    assert!(callbacks.classify_operands.contains(&20));
+    assert!(callbacks.classify_targets.contains(&3));
+    assert!(callbacks.classify_targets.contains(&200));
+    assert!(callbacks.const_constants.is_empty());
+    assert!(callbacks.static_constants.is_empty());
+}
@@ -0,0 +1,118 @@
+// run-pass
+// Test that `const_to_valtree` represents raw pointers into statics by the static they point
+// into, and rejects function pointers and raw pointers that could compare equal to pointers
+// with a different valtree.
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_driver::Compilation;
+use rustc_interface::{interface, Queries};
+use rustc_middle::mir::interpret::GlobalId;
+use rustc_middle::ty::{self, Instance, TyCtxt};
+use rustc_session::config::Input;
+use rustc_span::FileName;
+
+const SOURCE: &str = r#"
+#![feature(const_ptr_offset)]
+
+pub struct Ptr(pub *const u8);
+unsafe impl Sync for Ptr {}
+
+pub static TABLE: [u8; 4] = [1, 2, 3, 4];
+
+pub static START: Ptr = Ptr(&TABLE as *const [u8; 4] as *const u8);
+pub static SECOND: Ptr = Ptr(&TABLE[1]);
+pub static END: Ptr = Ptr((&TABLE as *const [u8; 4]).wrapping_add(1) as *const u8);
+pub static ANONYMOUS: Ptr = Ptr(&7);
+pub static ADDRESS: Ptr = Ptr(16 as *const u8);
+
+pub fn function() {}
+
+pub static FUNCTION: fn() = function;
+"#;
+
+/// Describes the valtree of the static named `name`.
+fn valtree(tcx: TyCtxt<'_>, name: &str) -> String {
+    let def_id = tcx
+        .body_owners()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| tcx.opt_item_name(def_id).map_or(false, |item| item.as_str() == name))
+        .unwrap();
+    let param_env = ty::ParamEnv::reveal_all();
+    let gid = GlobalId { instance: Instance::mono(tcx, def_id), promoted: None };
+    let alloc = tcx.eval_to_allocation_raw(param_env.and(gid)).unwrap();
+    match tcx.const_to_valtree(param_env.and(alloc)) {
+        None => "none".to_string(),
+        Some(ty::ValTree::Branch([ty::ValTree::Pointer(def_id, offset)])) => {
+            format!("{} + {}", tcx.item_name(*def_id), offset.bytes())
+        }
+        Some(ty::ValTree::Branch([ty::ValTree::Leaf(int)])) => {
+            format!("{}", int.assert_bits(int.size()))
+        }
+        Some(valtree) => format!("{:?}", valtree),
+    }
+}
+
+#[derive(Default)]
+struct ValTreePointersCallbacks {
+    valtrees: Vec<(&'static str, String)>,
+}
+
+impl rustc_driver::Callbacks for ValTreePointersCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        config.input = Input::Str {
+            name: FileName::Custom("valtree-pointers".to_string()),
+            input: SOURCE.to_string(),
+        };
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            for &name in &["START", "SECOND", "END", "ANONYMOUS", "ADDRESS", "FUNCTION"] {
+                self.valtrees.push((name, valtree(tcx, name)));
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args = vec![
+        "valtree-pointers".to_string(),
+        "valtree-pointers.rs".to_string(),
+        "--crate-type=lib".to_string(),
+    ];
+    let mut callbacks = ValTreePointersCallbacks::default();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut callbacks).run().ok();
+    })
+    .ok();
+
+    assert_eq!(
+        callbacks.valtrees,
+        [
+            ("START", "TABLE + 0".to_string()),
+            ("SECOND", "TABLE + 1".to_string()),
+            // One past the end of `TABLE` may be the address of another item.
+            ("END", "none".to_string()),
+            ("ANONYMOUS", "none".to_string()),
+            ("ADDRESS", "16".to_string()),
+            // Function pointers to the same function may differ at runtime.
+            ("FUNCTION", "none".to_string()),
+        ]
+    );
+}
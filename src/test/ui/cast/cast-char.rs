@@ -6,5 +6,7 @@ fn main() {
     const XY: char = 129160 as char;
     //~^ ERROR only `u8` can be cast into `char`
     const ZYX: char = '\u{01F888}';
+    const SURROGATE: char = 0xD800 as char;
+    //~^ ERROR only `u8` can be cast into `char`
     println!("{}", XYZ);
 }
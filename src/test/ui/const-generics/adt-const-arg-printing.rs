@@ -0,0 +1,26 @@
+// Checks that struct- and enum-valued const arguments are printed the way they would be
+// written in source, without integer suffixes on their fields.
+#![feature(const_generics)]
+#![allow(incomplete_features, dead_code)]
+
+#[derive(PartialEq, Eq)]
+struct Dim {
+    w: u32,
+    h: u8,
+}
+
+#[derive(PartialEq, Eq)]
+enum Depth {
+    Bits(u8),
+    Float,
+}
+
+struct Image<const D: Dim>;
+struct Buffer<const D: Depth>;
+
+fn main() {
+    let _: Image<{ Dim { w: 640, h: 48 } }> = Image::<{ Dim { w: 640, h: 96 } }>;
+    //~^ ERROR mismatched types
+    let _: Buffer<{ Depth::Bits(8) }> = Buffer::<{ Depth::Bits(16) }>;
+    //~^ ERROR mismatched types
+}
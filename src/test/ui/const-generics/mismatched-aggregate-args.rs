@@ -0,0 +1,20 @@
+// Check that mismatched const arguments of aggregate types are printed the way they would be
+// written in source.
+#![feature(const_generics)]
+#![allow(incomplete_features)]
+
+#[derive(PartialEq, Eq)]
+struct Point {
+    x: u32,
+    y: i8,
+}
+
+struct P<const P: Point>;
+struct T<const T: (u8, char)>;
+
+fn main() {
+    let _: P<{ Point { x: 1, y: -1 } }> = P::<{ Point { x: 2, y: -1 } }>;
+    //~^ ERROR mismatched types
+    let _: T<{ (1, 'a') }> = T::<{ (2, 'a') }>;
+    //~^ ERROR mismatched types
+}
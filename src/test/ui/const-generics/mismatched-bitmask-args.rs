@@ -0,0 +1,11 @@
+// Check that mismatched const arguments which look like bitmasks get their differing bits
+// pointed out.
+
+struct Mask<const M: u32>;
+
+fn main() {
+    let _: Mask<0xff> = Mask::<0x1ff>;
+    //~^ ERROR mismatched types
+    let _: Mask<2> = Mask::<3>;
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,16 @@
+// Check that constants of integer newtypes that can't be zero are printed through their
+// constructor, as their field is private.
+#![feature(const_generics)]
+#![allow(incomplete_features)]
+
+use std::num::NonZeroU32;
+
+struct Wrap<const N: NonZeroU32>;
+
+const FIVE: NonZeroU32 = unsafe { NonZeroU32::new_unchecked(5) };
+const SIX: NonZeroU32 = unsafe { NonZeroU32::new_unchecked(6) };
+
+fn main() {
+    let _: Wrap<FIVE> = Wrap::<SIX>;
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,12 @@
+// Check that `-Z print-consts=hex` prints integer constants as zero-padded bits.
+// compile-flags: -Z print-consts=hex
+
+struct Mask<const M: u32>;
+struct Signed<const S: i8>;
+
+fn main() {
+    let _: Mask<0xff> = Mask::<0x1ff>;
+    //~^ ERROR mismatched types
+    let _: Signed<-1> = Signed::<1>;
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,23 @@
+pub const SUM: u64 = {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 1000 {
+        sum += i;
+        i += 1;
+    }
+    sum
+};
+
+pub const GREETING: &str = "hello";
+
+pub const PAIR: (u8, &[u16]) = (7, &[1, 2, 3]);
+
+pub struct Wrapper(pub u32);
+
+impl Wrapper {
+    pub const DOUBLED: Wrapper = Wrapper(21 * 2);
+}
+
+pub const fn generic_len<T>(slice: &[T]) -> usize {
+    slice.len()
+}
@@ -0,0 +1,9 @@
+// aux-build:cross_crate_frames.rs
+// Frames of `const fn`s from other crates are shown with their source if it is available.
+
+extern crate cross_crate_frames;
+
+static X: u32 = cross_crate_frames::div_by_zero(1);
+//~^ ERROR could not evaluate static initializer
+
+fn main() {}
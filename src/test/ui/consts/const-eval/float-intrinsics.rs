@@ -0,0 +1,40 @@
+// run-pass
+#![feature(core_intrinsics, const_float_intrinsics)]
+
+use std::intrinsics::*;
+
+const SQRT_2: f64 = unsafe { sqrtf64(2.0) };
+const SQRT_SMALL: f32 = unsafe { sqrtf32(1e-40) };
+const SQRT_NEG: f64 = unsafe { sqrtf64(-1.0) };
+const POWI: f64 = unsafe { powif64(1.5, 5) };
+const POWI_NEG: f32 = unsafe { powif32(2.0, -3) };
+const FMA: f64 = unsafe { fmaf64(0.1, 10.0, -1.0) };
+const FLOOR: f64 = unsafe { floorf64(-1.5) };
+const CEIL: f32 = unsafe { ceilf32(-1.5) };
+const TRUNC: f64 = unsafe { truncf64(-1.5) };
+const ROUND: f32 = unsafe { roundf32(2.5) };
+const RINT: f64 = unsafe { rintf64(2.5) };
+const NEARBYINT: f32 = unsafe { nearbyintf32(3.5) };
+const FABS: f64 = unsafe { fabsf64(-0.0) };
+const COPYSIGN: f32 = unsafe { copysignf32(1.0, -0.0) };
+const MINNUM: f64 = unsafe { minnumf64(f64::NAN, 1.0) };
+const MAXNUM: f32 = unsafe { maxnumf32(1.0, 2.0) };
+
+fn main() {
+    assert_eq!(SQRT_2, 2.0f64.sqrt());
+    assert_eq!(SQRT_SMALL, 1e-40f32.sqrt());
+    assert!(SQRT_NEG.is_nan());
+    assert_eq!(POWI, 7.59375);
+    assert_eq!(POWI_NEG, 0.125);
+    assert_eq!(FMA, 0.1f64.mul_add(10.0, -1.0));
+    assert_eq!(FLOOR, -2.0);
+    assert_eq!(CEIL, -1.0);
+    assert_eq!(TRUNC, -1.0);
+    assert_eq!(ROUND, 3.0);
+    assert_eq!(RINT, 2.0);
+    assert_eq!(NEARBYINT, 4.0);
+    assert_eq!(FABS.to_bits(), 0.0f64.to_bits());
+    assert_eq!(COPYSIGN, -1.0);
+    assert_eq!(MINNUM, 1.0);
+    assert_eq!(MAXNUM, 2.0);
+}
@@ -0,0 +1,10 @@
+// compile-flags: -Z const-eval-foreign-shims
+
+extern "C" {
+    fn strlen(s: *const u8) -> usize;
+}
+
+static LEN: usize = unsafe { strlen(b"hello".as_ptr()) };
+//~^ ERROR could not evaluate static initializer
+
+fn main() {}
@@ -0,0 +1,40 @@
+// run-pass
+// compile-flags: -Z const-eval-foreign-shims
+#![feature(const_ptr_offset, const_raw_ptr_deref)]
+
+extern "C" {
+    fn memchr(s: *const u8, c: i32, n: usize) -> *const u8;
+    fn memcmp(s1: *const u8, s2: *const u8, n: usize) -> i32;
+    fn strlen(s: *const u8) -> usize;
+}
+
+const fn compare(a: &[u8], b: &[u8]) -> i32 {
+    unsafe { memcmp(a.as_ptr(), b.as_ptr(), a.len()) }
+}
+
+const fn after(s: &[u8], c: u8) -> u8 {
+    unsafe { *memchr(s.as_ptr(), c as i32, s.len()).add(1) }
+}
+
+const fn c_str_len(s: &[u8]) -> usize {
+    unsafe { strlen(s.as_ptr()) }
+}
+
+const LESS: i32 = compare(b"abc", b"abd");
+const EQUAL: i32 = compare(b"abc", b"abc");
+const GREATER: i32 = compare(b"b", b"a");
+const AFTER: u8 = after(b"hello", b'e');
+const AFTER_WIDE: u8 = after(b"a\xffb", 0x1ff);
+const LEN: usize = c_str_len(b"hello\0world\0");
+const EMPTY: usize = c_str_len(b"\0");
+
+fn main() {
+    assert!(LESS < 0);
+    assert_eq!(EQUAL, 0);
+    assert!(GREATER > 0);
+    assert_eq!(AFTER, b'l');
+    assert_eq!(AFTER_WIDE, b'b');
+    assert_eq!(LEN, 5);
+    assert_eq!(EMPTY, 0);
+    assert_eq!(LEN, unsafe { strlen(b"hello\0world\0".as_ptr()) });
+}
@@ -0,0 +1,27 @@
+// Check that constant evaluation reports shifts by constants depending on generic parameters
+// that exceed the bit width of the shifted value at the shift itself.
+#![feature(const_fn_trait_bound)]
+
+const fn shl<const N: u32>(x: u32) -> u32 {
+    x << N //~ ERROR could not evaluate static initializer
+}
+
+trait Amount {
+    const AMOUNT: u32;
+}
+
+struct Forty;
+
+impl Amount for Forty {
+    const AMOUNT: u32 = 40;
+}
+
+const fn shr<T: Amount>(x: u32) -> u32 {
+    x >> T::AMOUNT //~ ERROR could not evaluate static initializer
+}
+
+pub static SMALL: u32 = shl::<4>(1);
+pub static SHL: u32 = shl::<40>(1);
+pub static SHR: u32 = shr::<Forty>(1);
+
+fn main() {}
@@ -0,0 +1,25 @@
+// run-pass
+// Heap allocations that were made global are part of the final value, even if they are only
+// reachable through a `*mut` pointer, and can still be read during the evaluation.
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+#![feature(const_raw_ptr_deref)]
+#![feature(const_mut_refs)]
+use std::intrinsics;
+
+const BAR: *mut i32 = unsafe {
+    let ptr = intrinsics::const_allocate(4, 4) as *mut i32;
+    *ptr = 42;
+    intrinsics::const_make_global(ptr as *mut u8) as *mut i32
+};
+
+const READ_BACK: i32 = unsafe {
+    let ptr = intrinsics::const_allocate(4, 4) as *mut i32;
+    *ptr = 7;
+    *(intrinsics::const_make_global(ptr as *mut u8) as *const i32)
+};
+
+fn main() {
+    assert_eq!(unsafe { *BAR }, 42);
+    assert_eq!(READ_BACK, 7);
+}
@@ -0,0 +1,33 @@
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+#![feature(const_raw_ptr_deref)]
+#![feature(const_mut_refs)]
+#![feature(const_ptr_offset)]
+use std::intrinsics;
+
+// Only the start of a heap allocation can be made global.
+const NOT_HEAP: () = unsafe {
+    let mut x = 0u8;
+    intrinsics::const_make_global(&mut x as *mut u8);
+    //~^ error: evaluation of constant value failed
+};
+
+const NOT_START: () = unsafe {
+    let ptr = intrinsics::const_allocate(4, 4);
+    intrinsics::const_make_global(ptr.add(1));
+    //~^ error: evaluation of constant value failed
+};
+
+// Heap allocations have to be made global explicitly to end up in the final value.
+const fn not_made_global() -> *const u8 {
+    unsafe {
+        let ptr = intrinsics::const_allocate(4, 4);
+        *ptr = 1;
+        ptr
+    }
+}
+
+const NOT_MADE_GLOBAL: *const u8 = not_made_global();
+//~^ error: untyped pointers are not allowed in constant
+
+fn main() {}
@@ -0,0 +1,61 @@
+// run-pass
+// Heap allocations that are only reachable through raw pointers become part of the constant once
+// they are made global.
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+#![feature(const_raw_ptr_deref)]
+#![feature(const_mut_refs)]
+#![feature(const_ptr_offset)]
+use std::intrinsics;
+use std::mem::{align_of, size_of};
+
+struct ConstVec<T> {
+    ptr: *const T,
+    len: usize,
+}
+
+unsafe impl<T: Sync> Sync for ConstVec<T> {}
+
+impl<T> ConstVec<T> {
+    fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+const fn alloc<T>(len: usize) -> *mut T {
+    unsafe { intrinsics::const_allocate(len * size_of::<T>(), align_of::<T>()) as *mut T }
+}
+
+const fn make_global<T>(ptr: *mut T) -> *const T {
+    unsafe { intrinsics::const_make_global(ptr as *mut u8) as *const T }
+}
+
+const fn squares(len: usize) -> ConstVec<u64> {
+    let ptr = alloc::<u64>(len);
+    let mut i = 0;
+    while i < len {
+        unsafe { *ptr.add(i) = (i * i) as u64 };
+        i += 1;
+    }
+    ConstVec { ptr: make_global(ptr), len }
+}
+
+const fn rows() -> ConstVec<ConstVec<u64>> {
+    let ptr = alloc::<ConstVec<u64>>(2);
+    unsafe {
+        *ptr = squares(2);
+        *ptr.add(1) = squares(3);
+    }
+    ConstVec { ptr: make_global(ptr), len: 2 }
+}
+
+const SQUARES: ConstVec<u64> = squares(4);
+const ROWS: ConstVec<ConstVec<u64>> = rows();
+static SQUARES_REF: &ConstVec<u64> = &SQUARES;
+
+fn main() {
+    assert_eq!(SQUARES.as_slice(), &[0, 1, 4, 9]);
+    let rows: Vec<&[u64]> = ROWS.as_slice().iter().map(ConstVec::as_slice).collect();
+    assert_eq!(rows, [&[0, 1][..], &[0, 1, 4][..]]);
+    assert_eq!(SQUARES_REF.as_slice(), &[0, 1, 4, 9]);
+}
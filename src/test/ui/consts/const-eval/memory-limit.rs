@@ -0,0 +1,22 @@
+// compile-flags: -Z const-eval-memory-limit=1000
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+use std::intrinsics::const_allocate;
+
+const fn alloc(size: usize) -> *mut u8 {
+    unsafe { const_allocate(size, 1) }
+    //~^ ERROR evaluation of `BIG` ran out of memory
+}
+
+const SMALL: () = {
+    alloc(400);
+    alloc(400);
+};
+
+const BIG: () = {
+    alloc(400);
+    alloc(400);
+    alloc(400);
+};
+
+fn main() {}
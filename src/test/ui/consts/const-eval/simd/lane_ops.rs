@@ -0,0 +1,60 @@
+// run-pass
+#![feature(repr_simd)]
+#![feature(platform_intrinsics)]
+#![feature(staged_api)]
+#![stable(feature = "foo", since = "1.33.7")]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)] #[derive(Copy, Clone)] struct u8x4(u8, u8, u8, u8);
+#[repr(simd)] #[derive(Copy, Clone)] struct i32x4(i32, i32, i32, i32);
+#[repr(simd)] #[derive(Copy, Clone)] struct f32x2(f32, f32);
+
+extern "platform-intrinsic" {
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_add<T>(x: T, y: T) -> T;
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_mul<T>(x: T, y: T) -> T;
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_shl<T>(x: T, y: T) -> T;
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_xor<T>(x: T, y: T) -> T;
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_neg<T>(x: T) -> T;
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_lt<T, U>(x: T, y: T) -> U;
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_select<M, T>(mask: M, a: T, b: T) -> T;
+    #[rustc_const_stable(feature = "foo", since = "1.3.37")]
+    fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32; 4]) -> U;
+}
+
+fn main() {
+    {
+        // Arithmetic wraps around.
+        const A: u8x4 = u8x4(1, 2, 250, 128);
+        const B: u8x4 = unsafe { simd_add(A, u8x4(1, 1, 10, 128)) };
+        const C: u8x4 = unsafe { simd_mul(A, u8x4(3, 3, 3, 2)) };
+        const D: u8x4 = unsafe { simd_shl(A, u8x4(1, 2, 0, 1)) };
+        const E: u8x4 = unsafe { simd_xor(A, u8x4(0xff, 0, 0xff, 0)) };
+        assert_eq!((B.0, B.1, B.2, B.3), (2, 3, 4, 0));
+        assert_eq!((C.0, C.1, C.2, C.3), (3, 6, 238, 0));
+        assert_eq!((D.0, D.1, D.2, D.3), (2, 8, 250, 0));
+        assert_eq!((E.0, E.1, E.2, E.3), (254, 2, 5, 128));
+    }
+    {
+        const A: i32x4 = i32x4(-1, 0, 1, i32::MIN);
+        const B: i32x4 = i32x4(0, 0, 0, 0);
+        const N: i32x4 = unsafe { simd_neg(A) };
+        const M: i32x4 = unsafe { simd_lt(A, B) };
+        const S: i32x4 = unsafe { simd_select(M, A, N) };
+        const SHUF: i32x4 = unsafe { simd_shuffle4(A, N, [3, 4, 0, 7]) };
+        assert_eq!((N.0, N.1, N.2, N.3), (1, 0, -1, i32::MIN));
+        assert_eq!((M.0, M.1, M.2, M.3), (-1, 0, 0, -1));
+        assert_eq!((S.0, S.1, S.2, S.3), (-1, 0, -1, i32::MIN));
+        assert_eq!((SHUF.0, SHUF.1, SHUF.2, SHUF.3), (i32::MIN, 1, -1, i32::MIN));
+    }
+    {
+        const A: f32x2 = unsafe { simd_add(f32x2(0.5, 1.0), f32x2(0.25, -1.0)) };
+        assert_eq!((A.0, A.1), (0.75, 0.0));
+    }
+}
@@ -0,0 +1,21 @@
+// check-pass
+#![feature(const_eval_limit)]
+#![const_eval_limit = "500"]
+
+// The limit on the enclosing module overrides the crate's limit.
+
+#[const_eval_limit = "1000000"]
+mod m {
+    pub const X: usize = {
+        let mut x = 0;
+        while x != 1000 {
+            x += 1;
+        }
+
+        x
+    };
+}
+
+fn main() {
+    assert_eq!(m::X, 1000);
+}
@@ -0,0 +1,17 @@
+// check-pass
+// compile-flags: -Zconst-eval-report-progress=2
+// Test that constant evaluation reports the call stack every two evaluated terminators.
+
+const fn leaf() -> u32 {
+    1
+}
+
+const fn middle() -> u32 {
+    leaf()
+}
+
+const X: u32 = middle();
+
+fn main() {
+    assert_eq!(X, 1);
+}
@@ -0,0 +1,23 @@
+// Checks that the values of another crate's constants are the same, whether they are loaded from
+// that crate's metadata or evaluated again.
+
+// run-pass
+// aux-build:cached_consts.rs
+// revisions: cached uncached
+//[uncached] compile-flags: -Zno-const-eval-metadata-cache
+
+extern crate cached_consts;
+
+use cached_consts::{Wrapper, GREETING, PAIR, SUM};
+
+const LEN: usize = cached_consts::generic_len(PAIR.1);
+
+fn main() {
+    let array = [0_u8; SUM as usize % 7];
+    assert_eq!(array.len(), 499500 % 7);
+    assert_eq!(SUM, 499500);
+    assert_eq!(GREETING, "hello");
+    assert_eq!(PAIR, (7, &[1, 2, 3][..]));
+    assert_eq!(LEN, 3);
+    assert_eq!(Wrapper::DOUBLED.0, 42);
+}
@@ -0,0 +1,26 @@
+// revisions: no_flag with_flag
+// [no_flag] check-pass
+// [with_flag] compile-flags: -Zextra-const-ub-checks
+
+use std::mem::transmute;
+use std::num::NonZeroU8;
+
+// None of these invalid values ever reach the final value of the static, so they are only
+// detected when every write is validated.
+
+static INVALID_BOOL: () = unsafe {
+    let _x: bool = transmute(3u8);
+    //[with_flag]~^ ERROR could not evaluate static initializer
+};
+
+static INVALID_CHAR: () = unsafe {
+    let _x: char = transmute(0xD800u32);
+    //[with_flag]~^ ERROR could not evaluate static initializer
+};
+
+static INVALID_NON_ZERO: () = unsafe {
+    let _x: NonZeroU8 = transmute(0u8);
+    //[with_flag]~^ ERROR could not evaluate static initializer
+};
+
+fn main() {}
@@ -0,0 +1,15 @@
+// run-pass
+// Check that the wrapped-around discriminant is used if `enum_discriminant_overflow` is allowed.
+
+#[allow(enum_discriminant_overflow)]
+#[repr(u8)]
+#[derive(Copy, Clone)]
+enum Wrapping {
+    Last = 255,
+    First,
+}
+
+fn main() {
+    assert_eq!(Wrapping::Last as u8, 255);
+    assert_eq!(Wrapping::First as u8, 0);
+}
@@ -0,0 +1,15 @@
+// Passing `--explain` along with a crate prints the explanation of the first error with that code
+// after the errors, instantiated with its types.
+// compile-flags: --explain E0277 --error-format human
+
+trait Foo {
+    fn bar(&self);
+}
+
+fn some_func<T: Foo>(foo: T) {
+    foo.bar();
+}
+
+fn main() {
+    some_func(5i32);
+}
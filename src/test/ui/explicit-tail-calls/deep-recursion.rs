@@ -0,0 +1,69 @@
+// run-pass
+// Test that `become` does not grow the stack, both at runtime and during const evaluation.
+
+#![allow(incomplete_features)]
+#![feature(explicit_tail_calls)]
+
+use std::cell::Cell;
+
+struct Counter<'a>(&'a Cell<u32>);
+
+impl Drop for Counter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn count_down(n: u64, acc: u64) -> u64 {
+    if n == 0 {
+        return acc;
+    }
+    become count_down(n - 1, acc + 1)
+}
+
+fn is_even(n: u64) -> bool {
+    if n == 0 { true } else { become is_odd(n - 1) }
+}
+
+fn is_odd(n: u64) -> bool {
+    if n == 0 { false } else { become is_even(n - 1) }
+}
+
+fn drops_before_call(counter: &Cell<u32>, n: u32) -> u32 {
+    let _guard = Counter(counter);
+    if n == 0 {
+        return counter.get();
+    }
+    // `_guard` is dropped before the callee starts running.
+    become drops_before_call(counter, n - 1)
+}
+
+fn big_args(values: [u64; 16], n: u64) -> [u64; 16] {
+    if n == 0 {
+        return values;
+    }
+    let mut values = values;
+    values[(n % 16) as usize] += 1;
+    become big_args(values, n - 1)
+}
+
+const fn const_count_down(n: u64, acc: u64) -> u64 {
+    if n == 0 {
+        return acc;
+    }
+    become const_count_down(n - 1, acc + 2)
+}
+
+const CONST_RESULT: u64 = const_count_down(1000, 0);
+
+fn main() {
+    assert_eq!(count_down(10_000_000, 0), 10_000_000);
+    assert!(is_even(1_000_000));
+    assert!(is_odd(1_000_001));
+    let counter = Cell::new(0);
+    assert_eq!(drops_before_call(&counter, 100_000), 100_000);
+    assert_eq!(counter.get(), 100_001);
+    let values = big_args([0; 16], 1_600_000);
+    assert!(values.iter().all(|&v| v == 100_000));
+    assert_eq!(CONST_RESULT, 2000);
+}
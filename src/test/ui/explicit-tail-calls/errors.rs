@@ -0,0 +1,36 @@
+#![allow(incomplete_features)]
+#![feature(explicit_tail_calls)]
+
+fn callee(x: u32) -> u32 {
+    x
+}
+
+fn other_signature(x: u64) -> u32 {
+    x as u32
+}
+
+fn not_a_call(x: u32) -> u32 {
+    become x + 1 //~ ERROR `become` requires a function call
+}
+
+fn mismatched(x: u32) -> u32 {
+    become other_signature(x as u64) //~ ERROR mismatched signatures
+}
+
+#[track_caller]
+fn track_caller_caller(x: u32) -> u32 {
+    become callee(x) //~ ERROR a function marked with `#[track_caller]` cannot perform a tail call
+}
+
+trait Count {
+    fn count(&self, n: u32) -> u32;
+}
+
+fn count_unsized<T: Count + ?Sized>(x: &T, n: u32) -> u32 {
+    become x.count(n) //~ ERROR methods of possibly unsized types cannot be tail called
+}
+
+fn main() {
+    let _ = |x: u32| -> u32 { become callee(x) };
+    //~^ ERROR `become` is not allowed in closures and generators
+}
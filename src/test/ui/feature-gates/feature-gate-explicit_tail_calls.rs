@@ -0,0 +1,9 @@
+fn f() -> u32 {
+    become g(); //~ ERROR `become` expression is experimental
+}
+
+fn g() -> u32 {
+    0
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro]
+#[proc_macro_impure] //~ ERROR the `#[proc_macro_impure]` attribute is an experimental feature
+pub fn m(input: TokenStream) -> TokenStream {
+    input
+}
@@ -0,0 +1,28 @@
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+// Tests the niches reported by the `layout_niches` query.
+
+use std::num::NonZeroU16;
+
+#[rustc_layout(niches)]
+#[repr(C)]
+struct Fields { //~ ERROR: niches
+    a: u8,
+    b: bool,
+    c: NonZeroU16,
+}
+
+#[rustc_layout(niches)]
+type OptionBool = Option<bool>; //~ ERROR: niches
+
+#[rustc_layout(niches)]
+type Char = char; //~ ERROR: niches
+
+#[rustc_layout(niches)]
+union Union { //~ ERROR: niches
+    a: bool,
+    b: u8,
+}
+
+fn main() {}
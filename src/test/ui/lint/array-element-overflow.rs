@@ -0,0 +1,17 @@
+// Identical overflows in the elements of an array literal are reported once.
+
+// build-fail
+
+fn black_box<T>(_: T) {
+    unimplemented!()
+}
+
+fn main() {
+    let a = [200u8 + 100, 200u8 + 100, 200u8 + 100, 200u8 + 100];
+    //~^ ERROR this arithmetic operation will overflow
+    let b = [i32::MAX + 1, 0, i32::MAX + 1, i32::MAX + 1, 7, -i32::MIN];
+    //~^ ERROR this arithmetic operation will overflow
+    //~| ERROR this arithmetic operation will overflow
+    black_box(a);
+    black_box(b);
+}
@@ -0,0 +1,32 @@
+// build-fail
+// Check that shifts by constants depending on generic parameters are checked
+// once the value of the constant is known.
+
+fn shl<const N: u32>(x: u32) -> u32 {
+    x << N //~ ERROR: this arithmetic operation will overflow
+}
+
+trait Amount {
+    const AMOUNT: u32;
+}
+
+struct Forty;
+
+impl Amount for Forty {
+    const AMOUNT: u32 = 40;
+}
+
+fn shr<T: Amount>(x: u64) -> u64 {
+    x >> T::AMOUNT
+}
+
+fn shr_narrow<T: Amount>(x: u32) -> u32 {
+    x >> T::AMOUNT //~ ERROR: this arithmetic operation will overflow
+}
+
+fn main() {
+    shl::<4>(1);
+    shl::<40>(1);
+    shr::<Forty>(1);
+    shr_narrow::<Forty>(1);
+}
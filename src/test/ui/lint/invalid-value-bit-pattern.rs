@@ -0,0 +1,34 @@
+// Check that `invalid_value` points out the invalid bit pattern and the valid range
+// for types whose validity is range-based.
+
+#![allow(deprecated)]
+#![deny(invalid_value)]
+
+use std::mem;
+use std::num::{NonZeroU16, NonZeroU8};
+
+#[repr(u8)]
+enum OneOrTwo {
+    One = 1,
+    Two = 2,
+}
+
+#[repr(i8)]
+enum AroundZero {
+    MinusOne = -1,
+    Zero = 0,
+}
+
+struct Wrap<T> { wrapped: T }
+
+fn main() {
+    unsafe {
+        let _val: NonZeroU8 = mem::zeroed(); //~ ERROR: does not permit zero-initialization
+        let _val: Wrap<NonZeroU16> = mem::zeroed(); //~ ERROR: does not permit zero-initialization
+        let _val: OneOrTwo = mem::zeroed(); //~ ERROR: does not permit zero-initialization
+
+        // These are fine.
+        let _val: Option<NonZeroU8> = mem::zeroed();
+        let _val: AroundZero = mem::zeroed();
+    }
+}
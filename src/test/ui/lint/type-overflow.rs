@@ -19,4 +19,6 @@ fn main() {
     let fail = 0x8FFF_FFFF_FFFF_FFFE; //~WARNING literal out of range for `i32`
 
     let fail = -0b1111_1111i8; //~WARNING literal out of range for `i8`
+
+    let fail = -0x180i8; //~WARNING literal out of range for `i8`
 }
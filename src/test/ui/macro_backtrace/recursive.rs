@@ -0,0 +1,13 @@
+// The repeated frames of the expansions of a recursive macro are grouped in macro backtraces.
+
+// compile-flags: -Z macro-backtrace
+
+macro_rules! count {
+    () => { error };
+    ($head:tt $($tail:tt)*) => { count!($($tail)*) };
+}
+//~^^^ ERROR cannot find value `error` in this scope
+
+fn main() {
+    count!(a b c d);
+}
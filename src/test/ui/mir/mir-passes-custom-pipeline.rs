@@ -0,0 +1,40 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=3
+// compile-flags: -Zmir-passes=SimplifyCfg-final,Inline,ConstProp,JumpThreading,ConstProp
+// Test that a custom MIR pass pipeline can reorder and repeat passes, including ones that run
+// before generator lowering by default.
+
+#![feature(generators, generator_trait)]
+
+use std::ops::{Generator, GeneratorState};
+use std::pin::Pin;
+
+#[inline]
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+fn sum(v: &[u32]) -> u32 {
+    let mut total = 0;
+    for x in v {
+        total += double(*x);
+    }
+    total
+}
+
+fn main() {
+    assert_eq!(sum(&[1, 2, 3]), 12);
+
+    let mut generator = || {
+        yield double(1);
+        double(2)
+    };
+    match Pin::new(&mut generator).resume(()) {
+        GeneratorState::Yielded(2) => {}
+        _ => panic!(),
+    }
+    match Pin::new(&mut generator).resume(()) {
+        GeneratorState::Complete(4) => {}
+        _ => panic!(),
+    }
+}
@@ -0,0 +1,5 @@
+// compile-flags: -Zmir-passes=ConstProp,NoSuchPass
+// normalize-stderr-test: "known passes are: .*" -> "known passes are: $$PASSES"
+
+// The names are checked even though this crate has no MIR to optimize.
+#![crate_type = "lib"]
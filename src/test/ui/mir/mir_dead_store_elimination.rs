@@ -0,0 +1,97 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=2 -Zvalidate-mir
+// Test that removing dead stores does not remove writes that are observed later, in particular
+// through references and raw pointers, the discriminant of an enum and other fields of a union.
+
+struct Pair {
+    a: u32,
+    b: u32,
+}
+
+fn overwritten(x: u32) -> u32 {
+    let mut y = x;
+    y = y + 1;
+    y = 7;
+    y
+}
+
+fn other_field(x: u32) -> u32 {
+    let mut p = Pair { a: x, b: 0 };
+    p.a = 3;
+    p.b = x * 2;
+    p.b
+}
+
+fn read_through_ref(x: u32) -> u32 {
+    let mut p = Pair { a: 0, b: 0 };
+    let r = &mut p;
+    r.a = x;
+    p.a + p.b
+}
+
+fn read_through_raw(x: u32) -> u32 {
+    let mut v = 0;
+    let ptr = &mut v as *mut u32;
+    v = x;
+    unsafe { *ptr }
+}
+
+fn in_loop(n: u32) -> u32 {
+    let mut last = 0;
+    let mut i = 0;
+    while i < n {
+        last = i;
+        i += 1;
+    }
+    last
+}
+
+fn whole_then_field(x: u32) -> (u32, u32) {
+    let mut t = (x, x);
+    t.0 = 1;
+    t
+}
+
+enum Either {
+    Left(u32),
+    Right(u32, u32),
+}
+
+fn variant_then_discriminant(x: u32) -> u32 {
+    // Builds the variant field by field, followed by a `SetDiscriminant` of the whole local.
+    let e = if x > 3 { Either::Right(x, x + 1) } else { Either::Left(x) };
+    match e {
+        Either::Left(a) => a,
+        Either::Right(a, b) => a + b,
+    }
+}
+
+fn niche_then_discriminant(x: bool) -> Option<(u32, bool)> {
+    let mut t = (1, x);
+    t.0 = 2;
+    Some(t)
+}
+
+union Bits {
+    int: u32,
+    bytes: [u8; 4],
+}
+
+fn other_union_field(x: u32) -> [u8; 4] {
+    let mut u = Bits { bytes: [0; 4] };
+    u.int = x;
+    unsafe { u.bytes }
+}
+
+fn main() {
+    assert_eq!(overwritten(1), 7);
+    assert_eq!(other_field(4), 8);
+    assert_eq!(read_through_ref(5), 5);
+    assert_eq!(read_through_raw(6), 6);
+    assert_eq!(in_loop(10), 9);
+    assert_eq!(whole_then_field(2), (1, 2));
+    assert_eq!(variant_then_discriminant(2), 2);
+    assert_eq!(variant_then_discriminant(4), 9);
+    assert_eq!(niche_then_discriminant(true), Some((2, true)));
+    assert_eq!(other_union_field(0x01020304), 0x01020304u32.to_ne_bytes());
+}
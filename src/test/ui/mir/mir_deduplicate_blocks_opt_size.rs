@@ -0,0 +1,51 @@
+// run-pass
+// compile-flags: -Copt-level=z -Zvalidate-mir
+// needs-unwind
+// Test that merging equal blocks when optimizing for size keeps the panic and drop paths that
+// are shared between branches working.
+
+use std::cell::Cell;
+
+struct Noisy<'a>(&'a Cell<u32>);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn pick(x: u32, drops: &Cell<u32>) -> u32 {
+    let a = Noisy(drops);
+    let b = Noisy(drops);
+    match x {
+        0 => 10,
+        1 => {
+            drop(a);
+            20
+        }
+        2 => {
+            drop(b);
+            20
+        }
+        _ => 30,
+    }
+}
+
+fn checked(v: &[u32], i: usize, j: usize) -> u32 {
+    if i < j { v[i] + v[j] } else { v[j] * v[i] }
+}
+
+fn main() {
+    let drops = Cell::new(0);
+    assert_eq!(pick(0, &drops), 10);
+    assert_eq!(drops.get(), 2);
+    assert_eq!(pick(1, &drops), 20);
+    assert_eq!(pick(2, &drops), 20);
+    assert_eq!(pick(3, &drops), 30);
+    assert_eq!(drops.get(), 8);
+
+    let v = [1, 2, 3];
+    assert_eq!(checked(&v, 0, 2), 4);
+    assert_eq!(checked(&v, 2, 1), 6);
+    assert!(std::panic::catch_unwind(|| checked(&v, 0, 5)).is_err());
+}
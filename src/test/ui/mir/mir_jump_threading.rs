@@ -0,0 +1,112 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=3 -Zvalidate-mir
+// Test that threading jumps over switches on enum discriminants keeps the results unchanged,
+// in particular when the discriminant changes in between.
+
+#[derive(Debug, PartialEq)]
+enum Signed {
+    Negative = -1,
+    Zero = 0,
+    Positive = 1,
+}
+
+fn option_chain(x: Option<u32>) -> Option<u32> {
+    x.map(|v| v + 1).and_then(|v| if v > 10 { None } else { Some(v * 2) }).filter(|v| v % 4 == 0)
+}
+
+fn result_chain(x: Result<u32, String>) -> Result<u32, usize> {
+    x.map(|v| v * 3).map_err(|e| e.len()).and_then(|v| if v == 0 { Err(0) } else { Ok(v) })
+}
+
+fn question_mark(x: Option<u8>, y: Option<u8>) -> Option<u16> {
+    let a = x?;
+    let b = y?;
+    Some(a as u16 + b as u16)
+}
+
+fn rematch(x: Option<u32>) -> u32 {
+    let y = match x {
+        Some(v) => Some(v),
+        None => None,
+    };
+    match y {
+        Some(v) => v,
+        None => 7,
+    }
+}
+
+fn changed_in_between(x: Option<u32>, replace: bool) -> u32 {
+    let mut y = x;
+    if replace {
+        y = None;
+    }
+    match y {
+        Some(v) => v,
+        None => 0,
+    }
+}
+
+fn changed_through_ref(x: Option<u32>) -> u32 {
+    let mut y = x;
+    let r = &mut y;
+    if let Some(v) = x {
+        *r = Some(v + 1);
+    } else {
+        *r = Some(100);
+    }
+    match y {
+        Some(v) => v,
+        None => 0,
+    }
+}
+
+fn signed(x: i32) -> Signed {
+    let s = if x < 0 {
+        Signed::Negative
+    } else if x == 0 {
+        Signed::Zero
+    } else {
+        Signed::Positive
+    };
+    match s {
+        Signed::Negative => Signed::Positive,
+        Signed::Zero => Signed::Zero,
+        Signed::Positive => Signed::Negative,
+    }
+}
+
+fn in_loop(values: &[Option<u32>]) -> u32 {
+    let mut last = None;
+    let mut sum = 0;
+    for &value in values {
+        if let Some(v) = last {
+            sum += v;
+        }
+        last = value;
+    }
+    sum + last.unwrap_or(1000)
+}
+
+fn main() {
+    assert_eq!(option_chain(Some(1)), Some(4));
+    assert_eq!(option_chain(Some(2)), None);
+    assert_eq!(option_chain(Some(20)), None);
+    assert_eq!(option_chain(None), None);
+    assert_eq!(result_chain(Ok(2)), Ok(6));
+    assert_eq!(result_chain(Ok(0)), Err(0));
+    assert_eq!(result_chain(Err("abc".to_string())), Err(3));
+    assert_eq!(question_mark(Some(1), Some(2)), Some(3));
+    assert_eq!(question_mark(None, Some(2)), None);
+    assert_eq!(question_mark(Some(1), None), None);
+    assert_eq!(rematch(Some(3)), 3);
+    assert_eq!(rematch(None), 7);
+    assert_eq!(changed_in_between(Some(5), false), 5);
+    assert_eq!(changed_in_between(Some(5), true), 0);
+    assert_eq!(changed_through_ref(Some(5)), 6);
+    assert_eq!(changed_through_ref(None), 100);
+    assert_eq!(signed(-5), Signed::Positive);
+    assert_eq!(signed(0), Signed::Zero);
+    assert_eq!(signed(5), Signed::Negative);
+    assert_eq!(in_loop(&[Some(1), None, Some(2), Some(3)]), 6);
+    assert_eq!(in_loop(&[Some(1), None]), 1001);
+}
@@ -0,0 +1,69 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=3 -Coverflow-checks=on
+// Test that hoisting loop invariants out of loops does not change what the loops compute.
+
+enum Shape {
+    Square(u32),
+    Rect(u32, u32),
+}
+
+fn sum_scaled(xs: &[u32], a: u32, b: u32) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < xs.len() {
+        sum += xs[i] * (a + b);
+        i += 1;
+    }
+    sum
+}
+
+fn count_squares(shapes: &[Shape], shape: Shape) -> usize {
+    let mut count = 0;
+    for _ in shapes {
+        if let Shape::Square(_) = shape {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn nested(xs: &[u32], n: u32) -> u32 {
+    let mut sum = 0;
+    for i in 0..n {
+        for x in xs {
+            sum += x + (n - 1) + i;
+        }
+    }
+    sum
+}
+
+fn maybe_divide(xs: &[u32], d: u32) -> u32 {
+    let mut sum = 0;
+    for &x in xs {
+        // The division must stay behind the check for zero.
+        if d != 0 {
+            sum += x / d;
+        }
+    }
+    sum
+}
+
+fn never_runs(xs: &[u32], a: u32) -> u32 {
+    let mut sum = 0;
+    for &x in xs {
+        // Would overflow if it was evaluated.
+        sum += x + (a + 1);
+    }
+    sum
+}
+
+fn main() {
+    assert_eq!(sum_scaled(&[1, 2, 3], 2, 3), 30);
+    assert_eq!(sum_scaled(&[], 2, 3), 0);
+    assert_eq!(count_squares(&[Shape::Rect(1, 2), Shape::Square(3)], Shape::Square(1)), 2);
+    assert_eq!(count_squares(&[Shape::Square(3)], Shape::Rect(1, 2)), 0);
+    assert_eq!(nested(&[1, 2], 3), 27);
+    assert_eq!(maybe_divide(&[4, 8], 0), 0);
+    assert_eq!(maybe_divide(&[4, 8], 2), 6);
+    assert_eq!(never_runs(&[], u32::MAX), 0);
+}
@@ -0,0 +1,78 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=2 -Zvalidate-mir
+// Test that splitting aggregates into their fields keeps the MIR valid and the results unchanged.
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Loud<'a>(&'a mut u32);
+
+impl Drop for Loud<'_> {
+    fn drop(&mut self) {
+        *self.0 += 1;
+    }
+}
+
+struct Wrapper<T> {
+    value: T,
+    count: usize,
+}
+
+fn swap_fields(a: i32, b: i32) -> i32 {
+    let p = Point { x: a, y: b };
+    let q = Point { x: p.y, y: p.x };
+    q.x * 10 + q.y
+}
+
+fn tuple_in_loop(n: u32) -> u32 {
+    let mut acc = (0, 1);
+    for _ in 0..n {
+        acc = (acc.1, acc.0 + acc.1);
+    }
+    acc.0
+}
+
+fn borrow_field(a: i32) -> i32 {
+    let mut p = Point { x: a, y: 0 };
+    let y = &mut p.y;
+    *y += 5;
+    p.x + p.y
+}
+
+fn whole_use(a: i32) -> Point {
+    let p = Point { x: a, y: a + 1 };
+    let copy = p;
+    Point { x: copy.y, y: p.x }
+}
+
+fn with_drop(counter: &mut u32) -> u32 {
+    {
+        let _loud = (Loud(counter), 1);
+    }
+    *counter
+}
+
+fn generic<T: Copy>(value: T) -> (T, usize) {
+    let w = Wrapper { value, count: 2 };
+    (w.value, w.count)
+}
+
+fn nested(a: u8) -> u8 {
+    let t = ((a, a + 1), Point { x: 1, y: 2 });
+    (t.0).1 + t.1.y as u8
+}
+
+fn main() {
+    assert_eq!(swap_fields(1, 2), 21);
+    assert_eq!(tuple_in_loop(10), 55);
+    assert_eq!(borrow_field(3), 8);
+    let p = whole_use(4);
+    assert_eq!((p.x, p.y), (5, 4));
+    let mut counter = 0;
+    assert_eq!(with_drop(&mut counter), 1);
+    assert_eq!(generic('a'), ('a', 2));
+    assert_eq!(nested(3), 6);
+}
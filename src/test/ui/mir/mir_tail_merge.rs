@@ -0,0 +1,73 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=3 -Zvalidate-mir
+// Test that merging the common tails of match arms keeps the code that differs between the arms.
+
+#[inline(never)]
+fn consume(x: u32) -> u32 {
+    x * 10
+}
+
+fn arms(x: u8) -> u32 {
+    match x {
+        0 => consume(1),
+        1 => consume(2),
+        2 => consume(3),
+        _ => consume(4),
+    }
+}
+
+fn arms_with_temporaries(x: u8, s: &str) -> usize {
+    match x {
+        0 => String::from(s).len(),
+        1 => {
+            let t = String::from(s) + "!";
+            t.len()
+        }
+        _ => String::from(s).len(),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    Start,
+    Digit,
+    Letter,
+    Done,
+}
+
+fn step(state: State, c: char) -> State {
+    match (state, c) {
+        (State::Done, _) => State::Done,
+        (_, '0'..='9') => State::Digit,
+        (_, 'a'..='z') => State::Letter,
+        (State::Start, _) => State::Done,
+        (State::Digit, _) => State::Done,
+        (State::Letter, _) => State::Done,
+    }
+}
+
+fn count(input: &str) -> (u32, u32) {
+    let mut state = State::Start;
+    let (mut digits, mut letters) = (0, 0);
+    for c in input.chars() {
+        state = step(state, c);
+        match state {
+            State::Digit => digits += 1,
+            State::Letter => letters += 1,
+            State::Start | State::Done => break,
+        }
+    }
+    (digits, letters)
+}
+
+fn main() {
+    assert_eq!(arms(0), 10);
+    assert_eq!(arms(1), 20);
+    assert_eq!(arms(2), 30);
+    assert_eq!(arms(200), 40);
+    assert_eq!(arms_with_temporaries(0, "ab"), 2);
+    assert_eq!(arms_with_temporaries(1, "ab"), 3);
+    assert_eq!(arms_with_temporaries(5, "abc"), 3);
+    assert_eq!(count("ab12c-de"), (2, 3));
+    assert_eq!(count("-"), (0, 0));
+}
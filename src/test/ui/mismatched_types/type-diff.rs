@@ -0,0 +1,18 @@
+// Mismatched types that are too long to compare on two lines are compared as a tree of their
+// differences.
+
+use std::collections::{BTreeMap, HashMap};
+
+struct Wrapper<A, B>(A, B);
+
+type Entry = (String, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, bool, char);
+type Hashed = Wrapper<Vec<(String, Option<Box<HashMap<String, Vec<Entry>>>>)>, u8>;
+type Sorted = Wrapper<Vec<(String, Option<Box<BTreeMap<String, Vec<Entry>>>>)>, u8>;
+
+fn hashed() -> Hashed {
+    Wrapper(Vec::new(), 0)
+}
+
+fn main() {
+    let _: Sorted = hashed(); //~ ERROR mismatched types
+}
@@ -0,0 +1,68 @@
+// Matching on a fieldless enum cast to an integer only has to cover the discriminants of the
+// enum.
+
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    Mid = 5,
+    High = 200,
+}
+
+#[repr(i8)]
+enum Sign {
+    Neg = -3,
+    Zero = 0,
+    Pos = 3,
+}
+
+fn exhaustive(level: Level) -> &'static str {
+    match level as u8 {
+        1 => "low",
+        5 => "mid",
+        200 => "high",
+    }
+}
+
+fn exhaustive_ranges(level: Level) -> bool {
+    match level as u8 {
+        0..=10 => false,
+        100..=u8::MAX => true,
+    }
+}
+
+fn missing(level: Level) {
+    match level as u8 {
+    //~^ ERROR non-exhaustive patterns: `200_u8` not covered
+        1 | 5 => {}
+    }
+}
+
+fn missing_signed(sign: Sign) {
+    match sign as i8 {
+    //~^ ERROR non-exhaustive patterns: `-3_i8` and `3_i8` not covered
+        0 => {}
+    }
+}
+
+fn missing_truncated(sign: Sign) {
+    match sign as u8 {
+    //~^ ERROR non-exhaustive patterns: `0_u8` and `253_u8` not covered
+        3 => {}
+    }
+}
+
+#[repr(i16)]
+enum Unordered {
+    A = 9,
+    B = -2,
+    C = 4,
+}
+
+fn missing_unordered(value: Unordered) {
+    match value as i16 {
+    //~^ ERROR non-exhaustive patterns: `-2_i16`, `4_i16` and `9_i16` not covered
+        0 => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// The witnesses of non-exhaustive integer matches are the minimal set of missing ranges.
+
+fn main() {
+    let x: u8 = 0;
+    match x {
+    //~^ ERROR non-exhaustive patterns: `3_u8..=9_u8` and `200_u8..=u8::MAX` not covered
+        0 | 1 | 2 => {}
+        n @ 10..=99 => {}
+        100..=199 => {}
+    }
+
+    let y: i16 = 0;
+    match y {
+    //~^ ERROR `i16::MIN..=-1_i16`, `5_i16..=9_i16` and `i16::MAX` not covered
+        0..=4 => {}
+        5..=9 if true => {}
+        10..=20 | 15..=32766 => {}
+    }
+}
@@ -0,0 +1,23 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+use proc_macro::TokenStream;
+
+#[proc_macro]
+pub fn abort(_: TokenStream) -> TokenStream {
+    std::process::abort()
+}
+
+#[proc_macro]
+pub fn allocate_too_much(_: TokenStream) -> TokenStream {
+    let buffer = Vec::<u8>::with_capacity(1 << 36);
+    format!("const LEN: usize = {};", buffer.len()).parse().unwrap()
+}
+
+#[proc_macro]
+pub fn answer(_: TokenStream) -> TokenStream {
+    "fn answer() -> u32 { 42 }".parse().unwrap()
+}
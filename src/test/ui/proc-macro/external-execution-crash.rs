@@ -0,0 +1,20 @@
+// aux-build:process-crash.rs
+// compile-flags: -Z proc-macro-execution=external -Z proc-macro-memory-limit=4096
+// only-linux
+// only-64bit
+// normalize-stderr-test "signal: 6 \(core dumped\)" -> "signal: 6"
+
+// A proc macro that crashes its process or runs out of memory only fails its own expansion, the
+// next proc macro runs in a new process.
+
+extern crate process_crash;
+
+use process_crash::{abort, allocate_too_much, answer};
+
+abort!(); //~ ERROR proc macro panicked
+allocate_too_much!(); //~ ERROR proc macro panicked
+answer!();
+
+fn main() {
+    let _ = answer();
+}
@@ -0,0 +1,14 @@
+// aux-build:test-macros.rs
+// compile-flags: -Z proc-macro-execution=external
+
+// The message of a proc macro that panicked in a separate process is reported like the one of a
+// proc macro that panicked in the compiler.
+
+#[macro_use]
+extern crate test_macros;
+
+#[derive(Panic)]
+//~^ ERROR: proc-macro derive panicked
+struct Foo;
+
+fn main() {}
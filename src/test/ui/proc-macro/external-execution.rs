@@ -0,0 +1,21 @@
+// check-pass
+// aux-build:test-macros.rs
+// compile-flags: -Z proc-macro-execution=external
+
+// Proc macros running in a separate process expand like the ones running in the compiler.
+
+#[macro_use]
+extern crate test_macros;
+
+#[identity_attr]
+#[derive(Identity, Empty)]
+struct S {
+    #[empty_helper]
+    field: u8,
+}
+
+identity!(fn f() -> u8 { 1 });
+
+fn main() {
+    let _ = S { field: f() }.field;
+}
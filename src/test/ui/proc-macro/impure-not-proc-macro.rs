@@ -0,0 +1,6 @@
+#![feature(proc_macro_impure)]
+
+#[proc_macro_impure] //~ ERROR attribute should be applied to a proc macro
+fn f() {}
+
+fn main() {}
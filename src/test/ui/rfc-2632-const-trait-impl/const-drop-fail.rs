@@ -0,0 +1,24 @@
+// Values with a destructor that is not from an `impl const Drop` block still cannot be dropped
+// at compile time, even next to values that can.
+
+#![allow(incomplete_features)]
+#![feature(const_trait_impl)]
+
+struct ConstDrop;
+
+impl const Drop for ConstDrop {
+    fn drop(&mut self) {}
+}
+
+struct NonConstDrop;
+
+impl Drop for NonConstDrop {
+    fn drop(&mut self) {}
+}
+
+const fn drop_pair(_pair: (ConstDrop, NonConstDrop)) {}
+//~^ ERROR destructors cannot be evaluated at compile-time
+
+const fn drop_const(_value: [ConstDrop; 2]) {}
+
+fn main() {}
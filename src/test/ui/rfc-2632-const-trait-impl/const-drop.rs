@@ -0,0 +1,63 @@
+// run-pass
+// Values whose destructors come from `impl const Drop` blocks can be dropped at compile time,
+// also inside arrays, tuples and enums.
+
+#![allow(incomplete_features)]
+#![feature(const_trait_impl, const_mut_refs)]
+
+struct Counter<'a>(&'a mut u32);
+
+impl const Drop for Counter<'_> {
+    fn drop(&mut self) {
+        *self.0 += 1;
+    }
+}
+
+enum Either<'a> {
+    Left(Counter<'a>),
+    Right(u8, (Counter<'a>, Counter<'a>)),
+}
+
+const fn drop_one() -> u32 {
+    let mut n = 0;
+    {
+        let _c = Counter(&mut n);
+    }
+    n
+}
+
+const fn drop_array() -> [u32; 3] {
+    let mut counts = [0; 3];
+    {
+        let [a, b, c] = &mut counts;
+        let _counters = [Counter(a), Counter(b), Counter(c)];
+    }
+    counts
+}
+
+const fn drop_enum(right: bool) -> [u32; 2] {
+    let mut counts = [0; 2];
+    {
+        let [a, b] = &mut counts;
+        let _e = if right {
+            Either::Right(7, (Counter(a), Counter(b)))
+        } else {
+            Either::Left(Counter(a))
+        };
+    }
+    counts
+}
+
+const ONE: u32 = drop_one();
+const ARRAY: [u32; 3] = drop_array();
+const LEFT: [u32; 2] = drop_enum(false);
+const RIGHT: [u32; 2] = drop_enum(true);
+
+fn main() {
+    assert_eq!(ONE, 1);
+    assert_eq!(ARRAY, [1, 1, 1]);
+    assert_eq!(LEFT, [1, 0]);
+    assert_eq!(RIGHT, [1, 1]);
+    assert_eq!(drop_array(), ARRAY);
+    assert_eq!(drop_enum(true), RIGHT);
+}
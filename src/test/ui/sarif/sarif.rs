@@ -0,0 +1,7 @@
+// compile-flags: --error-format=sarif -Z unstable-options
+
+#![deny(unused_variables)]
+
+fn main() {
+    let x = 1; //~ ERROR unused variable: `x`
+}
@@ -0,0 +1,8 @@
+// Error codes link to the error index and locations to their files with `-Z terminal-urls=yes`.
+
+// compile-flags: --error-format human -Z terminal-urls=yes
+// ignore-windows
+
+fn main() {
+    let _: i32 = ""; //~ ERROR mismatched types
+}
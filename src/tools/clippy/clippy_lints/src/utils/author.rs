@@ -483,6 +483,12 @@ fn visit_expr(&mut self, expr: &Expr<'_>) {
                     println!("Ret(None) = {};", current);
                 }
             },
+            ExprKind::Become(value) => {
+                let value_pat = self.next("value");
+                println!("Become(ref {}) = {};", value_pat, current);
+                self.current = value_pat;
+                self.visit_expr(value);
+            },
             ExprKind::InlineAsm(_) => {
                 println!("InlineAsm(_) = {};", current);
                 println!("    // unimplemented: `ExprKind::InlineAsm` is not further destructured at the moment");
@@ -74,6 +74,7 @@ fn identify_some_pure_patterns(expr: &Expr<'_>) -> bool {
         | ExprKind::Break(..)
         | ExprKind::Continue(..)
         | ExprKind::Ret(..)
+        | ExprKind::Become(..)
         | ExprKind::InlineAsm(..)
         | ExprKind::LlvmInlineAsm(..)
         | ExprKind::Repeat(..)
@@ -252,6 +252,7 @@ pub fn eq_expr(&mut self, left: &Expr<'_>, right: &Expr<'_>) -> bool {
                 self.eq_expr(le, re) && self.eq_body(ll_id.body, rl_id.body)
             },
             (&ExprKind::Ret(ref l), &ExprKind::Ret(ref r)) => both(l, r, |l, r| self.eq_expr(l, r)),
+            (&ExprKind::Become(l), &ExprKind::Become(r)) => self.eq_expr(l, r),
             (&ExprKind::Path(ref l), &ExprKind::Path(ref r)) => self.eq_qpath(l, r),
             (&ExprKind::Struct(l_path, lf, ref lo), &ExprKind::Struct(r_path, rf, ref ro)) => {
                 self.eq_qpath(l_path, r_path)
@@ -710,6 +711,9 @@ pub fn hash_expr(&mut self, e: &Expr<'_>) {
                     self.hash_expr(e);
                 }
             },
+            ExprKind::Become(e) => {
+                self.hash_expr(e);
+            },
             ExprKind::Path(ref qpath) => {
                 self.hash_qpath(qpath);
             },
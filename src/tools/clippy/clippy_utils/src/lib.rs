@@ -567,6 +567,7 @@ pub fn can_move_expr_to_closure_no_visit(cx: &LateContext<'tcx>, expr: &'tcx Exp
         ExprKind::Break(..)
         | ExprKind::Continue(_)
         | ExprKind::Ret(_)
+        | ExprKind::Become(_)
         | ExprKind::Yield(..)
         | ExprKind::InlineAsm(_)
         | ExprKind::LlvmInlineAsm(_) => false,
@@ -309,6 +309,8 @@ fn build_diagnostic(level: DiagnosticLevel, span: Option<MultiSpan>) -> Diagnost
                 message: vec![],
                 children: vec![],
                 suggestions: vec![],
+                args: vec![],
+                anchor: None,
                 span: span.unwrap_or_else(MultiSpan::new),
                 sort_span: DUMMY_SP,
             }